@@ -0,0 +1,167 @@
+use chrono::DateTime;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::{parse_amount, Transaction, TransactionType};
+
+// Wording issuers use in purchase alerts; each pattern captures the amount then the merchant
+const ALERT_PATTERNS: &[&str] = &[
+    r"(?i)you made an? \$([\d,]+\.\d{2}) (?:purchase|transaction|payment) (?:at|with|to) (.+?)(?:\s+on\s|[.\n]|$)",
+    r"(?i)(?:a )?charge of \$([\d,]+\.\d{2}) (?:at|to|from) (.+?)(?:\s+(?:was|has been)\s|[.\n]|$)",
+    r"(?i)transaction of \$([\d,]+\.\d{2}) (?:at|with) (.+?)(?:\s+(?:was|has been|on)\s|[.\n]|$)",
+    r"(?i)\$([\d,]+\.\d{2}) (?:purchase|transaction|charge) (?:at|with) (.+?)(?:\s+on\s|[.\n]|$)",
+];
+
+// An exported .mbox file, a single .eml, or a folder of .eml files
+pub fn is_alert_source(path: &Path) -> bool {
+    path.is_dir() || matches!(
+        path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref(),
+        Some("mbox") | Some("eml")
+    )
+}
+
+fn read_messages(path: &Path) -> Result<Vec<String>, String> {
+    if path.is_dir() {
+        let mut messages = Vec::new();
+        let entries = fs::read_dir(path).map_err(|e| format!("Could not read alert folder: {}", e))?;
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            if entry_path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("eml")) {
+                messages.push(fs::read_to_string(&entry_path)
+                    .map_err(|e| format!("Could not read {}: {}", entry_path.display(), e))?);
+            }
+        }
+        return Ok(messages);
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| format!("Could not read alerts: {}", e))?;
+    if path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("mbox")) {
+        // mbox separates messages with a line starting "From "
+        let mut messages = Vec::new();
+        let mut current = String::new();
+        for line in content.lines() {
+            if line.starts_with("From ") && !current.is_empty() {
+                messages.push(std::mem::take(&mut current));
+            }
+            current.push_str(line);
+            current.push('\n');
+        }
+        if !current.is_empty() {
+            messages.push(current);
+        }
+        Ok(messages)
+    } else {
+        Ok(vec![content])
+    }
+}
+
+fn header<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+    headers.lines()
+        .find_map(|line| {
+            let (field, value) = line.split_once(':')?;
+            field.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+}
+
+// Just enough quoted-printable decoding for alert text: soft line breaks and =XX escapes
+fn decode_quoted_printable(body: &str) -> String {
+    let joined = body.replace("=\r\n", "").replace("=\n", "");
+    let bytes = joined.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'=' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn clean_merchant(raw: &str) -> String {
+    raw.trim()
+        .trim_end_matches(['.', ',', '!'])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_message(message: &str, patterns: &[Regex], tags: &Regex) -> Option<(Option<String>, Transaction)> {
+    let (headers, body) = message.split_once("\n\n")
+        .or_else(|| message.split_once("\r\n\r\n"))
+        .unwrap_or((message, ""));
+
+    let subject = header(headers, "Subject").unwrap_or("");
+    let text = format!("{}\n{}", subject, tags.replace_all(&decode_quoted_printable(body), " "));
+
+    let captures = patterns.iter().find_map(|pattern| pattern.captures(&text))?;
+    let amount = parse_amount(&captures[1]).ok()?;
+    let merchant = clean_merchant(&captures[2]);
+    if amount <= 0.0 || merchant.is_empty() {
+        return None;
+    }
+
+    let sent = DateTime::parse_from_rfc2822(header(headers, "Date")?).ok()?;
+
+    Some((
+        header(headers, "Message-ID").map(str::to_string),
+        Transaction {
+            date: sent.format("%Y-%m-%d").to_string(),
+            description: merchant,
+            amount,
+            category: None,
+            currency: None,
+            country: None,
+            time: Some(sent.format("%H:%M").to_string()),
+            mcc: None,
+            rewards: None,
+            order_id: None,
+            payment_id: None,
+            section: None,
+            transaction_type: TransactionType::Debit,
+            note: None,
+            receipt_path: None,
+            tags: Vec::new(),
+            shared: None,
+            merchant_info: None,
+            manual: false,
+            rule_category: None,
+            import_key: None,
+        },
+    ))
+}
+
+// Rebuilds transactions from purchase alert emails. Messages that aren't purchase alerts
+// are ignored, and the same alert exported twice is only counted once.
+pub fn parse_alerts(path: &Path) -> Result<Vec<Transaction>, String> {
+    let patterns: Vec<Regex> = ALERT_PATTERNS.iter()
+        .map(|pattern| Regex::new(pattern).map_err(|e| format!("Invalid alert pattern: {}", e)))
+        .collect::<Result<_, _>>()?;
+    let tags = Regex::new(r"<[^>]+>").map_err(|e| format!("Invalid alert pattern: {}", e))?;
+
+    let mut seen = HashSet::new();
+    let mut transactions = Vec::new();
+
+    for message in read_messages(path)? {
+        let Some((message_id, tx)) = parse_message(&message, &patterns, &tags) else {
+            continue;
+        };
+        let key = message_id.unwrap_or_else(|| format!("{}|{:?}|{}|{:.2}", tx.date, tx.time, tx.description, tx.amount));
+        if seen.insert(key) {
+            transactions.push(tx);
+        }
+    }
+
+    transactions.sort_by(|a, b| (&a.date, &a.time).cmp(&(&b.date, &b.time)));
+    Ok(transactions)
+}
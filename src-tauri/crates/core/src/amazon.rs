@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::{categorize_description, find_column, optional_field, parse_amount, parse_date, text, Transaction};
+
+// Amazon charges the card when an order ships, which can be several days after it was placed
+const MAX_SHIPPING_DELAY_DAYS: i64 = 7;
+const AMOUNT_TOLERANCE: f64 = 0.01;
+const MAX_ITEMS_IN_DESCRIPTION: usize = 2;
+const MAX_TITLE_LENGTH: usize = 40;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AmazonOrder {
+    pub order_id: String,
+    pub order_date: String,
+    pub items: Vec<AmazonItem>,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AmazonItem {
+    pub title: String,
+    pub category: Option<String>,
+    pub amount: f64,
+}
+
+pub fn is_amazon_charge(description: &str) -> bool {
+    let desc_lower = description.to_lowercase();
+    desc_lower.contains("amzn") || desc_lower.contains("amazon")
+}
+
+// Amazon's order-history report has one row per item; rows are grouped back into orders
+pub fn parse_order_history(content: &str) -> Result<Vec<AmazonOrder>, String> {
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
+    let headers = rdr.headers()
+        .map_err(|e| format!("Could not read order history headers: {}", e))?
+        .clone();
+
+    let order_id_col = find_column(&headers, &["order id"])
+        .ok_or("Order history has no Order ID column")?;
+    let date_col = find_column(&headers, &["order date"])
+        .ok_or("Order history has no Order Date column")?;
+    let total_col = find_column(&headers, &["item total", "total owed", "total charged"])
+        .ok_or("Order history has no item total column")?;
+    let title_col = find_column(&headers, &["title", "product name"]);
+    let category_col = find_column(&headers, &["category"]);
+
+    let mut orders: BTreeMap<String, AmazonOrder> = BTreeMap::new();
+
+    for result in rdr.records() {
+        let record = result.map_err(|e| format!("Could not read order history: {}", e))?;
+
+        let Some(order_id) = optional_field(&record, Some(order_id_col)) else {
+            continue;
+        };
+        let amount = record.get(total_col)
+            .and_then(|value| parse_amount(value).ok())
+            .unwrap_or(0.0)
+            .abs();
+
+        let order = orders.entry(order_id.clone()).or_insert_with(|| AmazonOrder {
+            order_id,
+            order_date: optional_field(&record, Some(date_col)).unwrap_or_default(),
+            items: Vec::new(),
+            total: 0.0,
+        });
+        order.total += amount;
+        order.items.push(AmazonItem {
+            title: optional_field(&record, title_col).unwrap_or_else(|| "Unknown item".to_string()),
+            category: optional_field(&record, category_col),
+            amount,
+        });
+    }
+
+    Ok(orders.into_values().collect())
+}
+
+// Amazon's own categories, mapped onto the ones the analysis uses
+fn map_category(order: &AmazonOrder) -> Option<String> {
+    let main_item = order.items.iter().max_by(|a, b| a.amount.total_cmp(&b.amount))?;
+
+    if let Some(category) = &main_item.category {
+        let category_lower = category.to_lowercase();
+        let mapped = if category_lower.contains("grocery") || category_lower.contains("food") {
+            "Food & Dining"
+        } else if category_lower.contains("health") || category_lower.contains("beauty") ||
+                  category_lower.contains("personal care") || category_lower.contains("medical") {
+            "Healthcare"
+        } else if category_lower.contains("movie") || category_lower.contains("music") ||
+                  category_lower.contains("video") || category_lower.contains("game") ||
+                  category_lower.contains("book") || category_lower.contains("kindle") {
+            "Entertainment"
+        } else {
+            "Shopping"
+        };
+        return Some(mapped.to_string());
+    }
+
+    match categorize_description(&main_item.title).as_str() {
+        "Other" => Some("Shopping".to_string()),
+        category => Some(category.to_string()),
+    }
+}
+
+fn describe_items(order: &AmazonOrder) -> String {
+    let mut titles: Vec<String> = order.items.iter()
+        .take(MAX_ITEMS_IN_DESCRIPTION)
+        .map(|item| text::truncate(&item.title, MAX_TITLE_LENGTH))
+        .collect();
+
+    if order.items.len() > MAX_ITEMS_IN_DESCRIPTION {
+        titles.push(format!("+{} more", order.items.len() - MAX_ITEMS_IN_DESCRIPTION));
+    }
+    titles.join(", ")
+}
+
+// Pairs each unenriched Amazon charge with the unclaimed order of the same total placed closest before it.
+// Returns how many charges were enriched.
+pub fn enrich_transactions(transactions: &mut [Transaction], orders: &[AmazonOrder]) -> usize {
+    // Orders already linked by an earlier import can't be claimed twice
+    let mut claimed: Vec<bool> = orders.iter()
+        .map(|order| transactions.iter().any(|t| t.order_id.as_deref() == Some(order.order_id.as_str())))
+        .collect();
+    let mut matched = 0;
+
+    for tx in transactions.iter_mut().filter(|t| t.order_id.is_none() && is_amazon_charge(&t.description)) {
+        let Some(charge_date) = parse_date(&tx.date) else {
+            continue;
+        };
+
+        let best = orders.iter().enumerate()
+            .filter(|(index, order)| !claimed[*index] && (order.total - tx.amount).abs() < AMOUNT_TOLERANCE)
+            .filter_map(|(index, order)| {
+                let delay = (charge_date - parse_date(&order.order_date)?).num_days();
+                (0..=MAX_SHIPPING_DELAY_DAYS).contains(&delay).then_some((index, delay))
+            })
+            .min_by_key(|(_, delay)| *delay);
+
+        if let Some((index, _)) = best {
+            claimed[index] = true;
+            matched += 1;
+            // The original text stays first so merchant grouping still sees "AMZN MKTP"
+            tx.description = format!("{} - {}", tx.description, describe_items(&orders[index]));
+            tx.category = map_category(&orders[index]);
+            tx.order_id = Some(orders[index].order_id.clone());
+        }
+    }
+
+    matched
+}
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::history::StoredStatement;
+use crate::interner::CompactHistory;
+use crate::settings::Settings;
+use crate::{business, carbon, cash_advance, food, fuel, generate_insights, growth, heatmap, income, micro_spending, pareto, parse_date, round_up, subscriptions, travel, trips};
+use crate::{AnalysisResult, Transaction};
+
+// What every analyzer gets to look at: the statement being analyzed and the ones before it
+pub struct AnalyzerInput<'a> {
+    pub transactions: &'a [Transaction],
+    pub history: &'a [&'a StoredStatement],
+    pub settings: &'a Settings,
+    pub file_path: &'a str,
+}
+
+// One step of the analysis. Analyzers run in registration order after the category and
+// merchant totals are filled in; each adds its own section or insights to the result, and can
+// read what earlier analyzers added.
+pub trait Analyzer: Send + Sync {
+    // Registering another analyzer under the same id replaces this one
+    fn id(&self) -> &str;
+    fn analyze(&self, input: &AnalyzerInput, analysis: &mut AnalysisResult);
+}
+
+// A section from an analyzer the result has no field of its own for, shown as a simple table
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AnalyzerSection {
+    // The id of the analyzer that added it
+    pub analyzer: String,
+    pub title: String,
+    // Unit of the row values, e.g. "kg CO2e"; None for amounts in the statement currency
+    #[serde(default)]
+    pub unit: Option<String>,
+    pub rows: Vec<SectionRow>,
+    #[serde(default)]
+    pub total: Option<f64>,
+    // The total a month earlier, when there's one to compare against
+    #[serde(default)]
+    pub previous_total: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SectionRow {
+    pub label: String,
+    pub value: f64,
+    #[serde(default)]
+    pub previous: Option<f64>,
+}
+
+// The analyses that ship with the app, registered like any other
+struct Builtin {
+    id: &'static str,
+    run: fn(&AnalyzerInput, &mut AnalysisResult),
+}
+
+impl Analyzer for Builtin {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn analyze(&self, input: &AnalyzerInput, analysis: &mut AnalysisResult) {
+        (self.run)(input, analysis)
+    }
+}
+
+fn builtins() -> Vec<Arc<dyn Analyzer>> {
+    let builtins: [Builtin; 16] = [
+        // Small habitual purchases below the configured threshold
+        Builtin { id: "micro_spending", run: |input, analysis| {
+            analysis.micro_spending = micro_spending::micro_spending_report(input.transactions, input.settings.micro_spend_threshold);
+        }},
+        // Category, merchant and habit insights, which read the micro-spending report
+        Builtin { id: "spending_insights", run: |input, analysis| {
+            let insights = generate_insights(input.transactions, &analysis.spending_categories, analysis.micro_spending.as_ref(), input.file_path);
+            analysis.insights.extend(insights);
+        }},
+        // Flagged separately so the UI can show them above everything else
+        Builtin { id: "cash_advance", run: |input, analysis| {
+            analysis.cash_advance = cash_advance::detect_cash_advances(input.transactions);
+            if let Some(warning) = &analysis.cash_advance {
+                analysis.insights.push(cash_advance::cash_advance_insight(warning));
+            }
+        }},
+        // Foreign spending and FX fees
+        Builtin { id: "travel", run: |input, analysis| {
+            analysis.travel = travel::summarize_travel(input.transactions);
+            if let Some(summary) = &analysis.travel {
+                analysis.insights.extend(travel::travel_insights(summary));
+            }
+        }},
+        // Runs of away-from-home charges, with what each trip cost
+        Builtin { id: "trips", run: |input, analysis| {
+            analysis.trips = trips::detect_trips(input.transactions, input.settings.home_state.as_deref());
+        }},
+        // Recurring charges across the history, the price rises and paid trials that landed in
+        // this statement, and services paid for twice over
+        Builtin { id: "subscriptions", run: |input, analysis| {
+            let dates: Vec<_> = input.transactions.iter().filter_map(|t| parse_date(&t.date)).collect();
+            let (Some(&since), Some(&as_of)) = (dates.iter().min(), dates.iter().max()) else {
+                return;
+            };
+            let mut history = CompactHistory::from_statements(input.history.iter().copied());
+            history.extend(input.transactions);
+            analysis.subscriptions = subscriptions::detect_subscriptions(&history, as_of);
+            analysis.insights.extend(subscriptions::price_hike_insights(&analysis.subscriptions, since));
+            analysis.trial_conversions = subscriptions::detect_trial_conversions(&history, since);
+            analysis.insights.extend(analysis.trial_conversions.iter().map(subscriptions::trial_insight));
+            analysis.service_overlaps = subscriptions::service_overlaps(&analysis.subscriptions);
+            analysis.insights.extend(analysis.service_overlaps.iter().map(subscriptions::overlap_insight));
+        }},
+        // Groceries against eating out, and whether the eating-out share is climbing
+        Builtin { id: "food", run: |input, analysis| {
+            analysis.food = food::food_report(input.transactions, input.history);
+            if let Some(insight) = analysis.food.as_ref().and_then(food::dining_insight) {
+                analysis.insights.push(insight);
+            }
+        }},
+        // Fuel spend per fill-up and against the usual month
+        Builtin { id: "vehicle", run: |input, analysis| {
+            analysis.vehicle = fuel::vehicle_report(input.transactions, input.history);
+            if let Some(insight) = analysis.vehicle.as_ref().and_then(fuel::fuel_insight) {
+                analysis.insights.push(insight);
+            }
+        }},
+        // Merchants that grew compared to previous statements
+        Builtin { id: "merchant_growth", run: |input, analysis| {
+            analysis.merchant_growth = growth::compare_with_history(input.transactions, input.history);
+            if let Some(report) = &analysis.merchant_growth {
+                analysis.insights.extend(growth::growth_insights(report));
+            }
+        }},
+        // When during the week (and day, if timestamps exist) money goes out
+        Builtin { id: "heatmap", run: |input, analysis| {
+            analysis.heatmap = heatmap::build_heatmap(input.transactions);
+        }},
+        // How concentrated spending is across merchants
+        Builtin { id: "merchant_concentration", run: |input, analysis| {
+            analysis.merchant_concentration = pareto::analyze_concentration(input.transactions);
+            if let Some(concentration) = &analysis.merchant_concentration {
+                analysis.insights.push(pareto::pareto_insight(concentration));
+            }
+        }},
+        // What rounding every purchase into savings would have put aside
+        Builtin { id: "round_up", run: |input, analysis| {
+            analysis.round_up = round_up::simulate_round_up(input.transactions, input.settings.round_up_increment);
+            if let Some(simulation) = &analysis.round_up {
+                analysis.insights.push(round_up::round_up_insight(simulation));
+            }
+        }},
+        // Spending as a share of income, split into needs, wants and savings
+        Builtin { id: "income", run: |input, analysis| {
+            analysis.income = income::income_report(input.transactions, input.settings.monthly_income, &input.settings.category_classes);
+            if let Some(report) = &analysis.income {
+                analysis.insights.push(income::income_insight(report));
+            }
+        }},
+        // Business expenses by tag or by the rules in settings
+        Builtin { id: "business", run: |input, analysis| {
+            analysis.business = business::split(input.transactions, &input.settings.business_rules);
+        }},
+        // Cashback the issuer reported alongside each purchase
+        Builtin { id: "rewards", run: |input, analysis| {
+            analysis.rewards_earned = input.transactions.iter()
+                .filter_map(|t| t.rewards)
+                .reduce(|total, reward| total + reward);
+        }},
+        // Estimated emissions per category, only for users who turn it on
+        Builtin { id: carbon::ANALYZER_ID, run: |input, analysis| {
+            if !input.settings.carbon_footprint {
+                return;
+            }
+            if let Some(section) = carbon::footprint_section(input) {
+                analysis.insights.extend(carbon::footprint_insight(&section));
+                analysis.sections.push(section);
+            }
+        }},
+    ];
+    builtins.into_iter().map(|builtin| Arc::new(builtin) as Arc<dyn Analyzer>).collect()
+}
+
+fn registry() -> &'static RwLock<Vec<Arc<dyn Analyzer>>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Arc<dyn Analyzer>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(builtins()))
+}
+
+// Adds an analyzer to every analysis from now on, or swaps out the one with the same id in place
+pub fn register(analyzer: Arc<dyn Analyzer>) {
+    let Ok(mut analyzers) = registry().write() else { return };
+    match analyzers.iter_mut().find(|existing| existing.id() == analyzer.id()) {
+        Some(existing) => *existing = analyzer,
+        None => analyzers.push(analyzer),
+    }
+}
+
+pub fn unregister(id: &str) {
+    if let Ok(mut analyzers) = registry().write() {
+        analyzers.retain(|analyzer| analyzer.id() != id);
+    }
+}
+
+// A snapshot, so the lock isn't held while analyzers run
+pub fn registered() -> Vec<Arc<dyn Analyzer>> {
+    registry().read().map(|analyzers| analyzers.clone()).unwrap_or_default()
+}
+
+pub fn run_all(input: &AnalyzerInput, analysis: &mut AnalysisResult) {
+    for analyzer in registered() {
+        analyzer.analyze(input, analysis);
+    }
+}
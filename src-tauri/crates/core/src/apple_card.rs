@@ -0,0 +1,110 @@
+use crate::{optional_field, parse_amount, Transaction, TransactionType};
+
+// Apple Card's export has both a transaction and a clearing date, plus its own category column
+pub fn is_apple_card_export(content: &str) -> bool {
+    let header = content.lines().next().unwrap_or("").to_lowercase();
+    header.contains("clearing date") && header.contains("merchant") && header.contains("purchased by")
+}
+
+fn column(headers: &csv::StringRecord, name: &str) -> Option<usize> {
+    headers.iter().position(|header| header.trim().eq_ignore_ascii_case(name))
+}
+
+// Apple Card categories mapped onto the ones the analysis uses; anything else is left to the keyword rules
+fn map_category(category: &str) -> Option<String> {
+    let category_lower = category.to_lowercase();
+
+    let mapped = if category_lower.contains("restaurant") || category_lower.contains("grocer") ||
+       category_lower.contains("food") {
+        "Food & Dining"
+    } else if category_lower.contains("gas") || category_lower.contains("transportation") ||
+              category_lower.contains("parking") {
+        "Gas & Transportation"
+    } else if category_lower.contains("shopping") || category_lower.contains("merchandise") {
+        "Shopping"
+    } else if category_lower.contains("entertainment") {
+        "Entertainment"
+    } else if category_lower.contains("medical") || category_lower.contains("health") {
+        "Healthcare"
+    } else if category_lower.contains("cash advance") {
+        "Cash Advance"
+    } else {
+        return None;
+    };
+    Some(mapped.to_string())
+}
+
+pub fn parse_apple_card(content: &str) -> Result<Vec<Transaction>, Box<dyn std::error::Error>> {
+    let mut transactions = Vec::new();
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
+    let headers = rdr.headers()?.clone();
+
+    let date_col = column(&headers, "Transaction Date").ok_or("Apple Card export has no Transaction Date column")?;
+    let description_col = column(&headers, "Description").ok_or("Apple Card export has no Description column")?;
+    let amount_col = headers.iter().position(|h| h.trim().to_lowercase().starts_with("amount"))
+        .ok_or("Apple Card export has no Amount column")?;
+    let merchant_col = column(&headers, "Merchant");
+    let category_col = column(&headers, "Category");
+    let type_col = column(&headers, "Type");
+    // Daily Cash comes either as a dollar amount, a percentage, or both
+    let daily_cash_col = column(&headers, "Daily Cash");
+    let daily_cash_rate_col = headers.iter().position(|h| {
+        let header = h.trim().to_lowercase();
+        header.starts_with("daily cash") && header.contains('%')
+    });
+
+    for result in rdr.records() {
+        let record = result?;
+
+        // Payments and Daily Cash adjustments aren't spending; refunds would need signed amounts
+        let kind = optional_field(&record, type_col).unwrap_or_default().to_lowercase();
+        if kind == "payment" || kind == "credit" || kind.starts_with("daily cash") {
+            continue;
+        }
+
+        let amount = parse_amount(record.get(amount_col).unwrap_or("0"))?;
+        if amount <= 0.0 {
+            continue;
+        }
+
+        // Prefer the cleaned-up merchant name; the raw description keeps the card network's noise
+        let description = optional_field(&record, merchant_col)
+            .or_else(|| optional_field(&record, Some(description_col)))
+            .unwrap_or_default();
+
+        let rewards = optional_field(&record, daily_cash_col)
+            .and_then(|value| parse_amount(&value).ok())
+            .or_else(|| {
+                let rate = optional_field(&record, daily_cash_rate_col)?;
+                let rate = rate.trim_end_matches('%').trim().parse::<f64>().ok()?;
+                Some((amount * rate).round() / 100.0)
+            })
+            .map(f64::abs);
+
+        transactions.push(Transaction {
+            date: record.get(date_col).unwrap_or("").to_string(),
+            description,
+            amount,
+            category: optional_field(&record, category_col).and_then(|c| map_category(&c)),
+            currency: None,
+            country: None,
+            time: None,
+            mcc: None,
+            rewards,
+            order_id: None,
+            payment_id: None,
+            section: None,
+            transaction_type: TransactionType::Debit,
+            note: None,
+            receipt_path: None,
+            tags: Vec::new(),
+            shared: None,
+            merchant_info: None,
+            manual: false,
+            rule_category: None,
+            import_key: None,
+        });
+    }
+
+    Ok(transactions)
+}
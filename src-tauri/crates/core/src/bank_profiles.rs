@@ -0,0 +1,434 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::parse_mode::{ParseMode, RowIssues};
+use crate::sign_convention::{self, SignConvention, SignDetection};
+use crate::{find_header_row, i18n, optional_field, parse_amount, parse_date, ParsedRows, Transaction, TransactionType, MAX_PREAMBLE_LINES};
+
+// Enough rows to guess from without dumping the file back to the UI
+const SAMPLE_ROWS: usize = 5;
+// Rows looked at when working out column roles from values alone
+const INFER_ROWS: usize = 50;
+// Share of a column's values that have to read as a date or amount for it to count as one
+const CONFIDENT_SHARE: f64 = 0.9;
+// A second text column scoring within this of the best is as likely to be the description
+const DESCRIPTION_MARGIN: f64 = 0.05;
+// Share of rows whose balance moves by exactly the amount, for a column to be a running balance
+const BALANCE_SHARE: f64 = 0.8;
+// Formats tried when guessing how a column writes its dates, day-first ones included
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%m/%d/%Y", "%d/%m/%Y", "%m/%d/%y", "%d/%m/%y", "%d.%m.%Y", "%d-%m-%Y", "%Y/%m/%d", "%b %d, %Y", "%d %b %Y"];
+
+// How to read one bank's CSV export, for banks the built-in parsers don't recognize
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BankProfile {
+    pub name: String,
+    // Lines of account details above the header row
+    #[serde(default)]
+    pub skip_lines: usize,
+    // Header cells, all of which must be present for the profile to apply to a file
+    pub header_signature: Vec<String>,
+    // Columns are named by their header text
+    pub date_column: String,
+    pub description_column: String,
+    #[serde(default)]
+    pub amount_column: Option<String>,
+    // Or money out and in as separate columns
+    #[serde(default)]
+    pub debit_column: Option<String>,
+    #[serde(default)]
+    pub credit_column: Option<String>,
+    // chrono format; None tries the usual formats
+    #[serde(default)]
+    pub date_format: Option<String>,
+    // For a single amount column; None infers it per file
+    #[serde(default)]
+    pub sign_convention: Option<SignConvention>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnRole {
+    Date,
+    Description,
+    Amount,
+    Debit,
+    Credit,
+    Ignore,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct DetectedColumn {
+    pub index: usize,
+    pub header: String,
+    pub samples: Vec<String>,
+    pub role: ColumnRole,
+}
+
+// Columns picked out by their values, for exports whose header names nothing useful
+// ("Field1, Field2") or that have no header at all
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredColumns {
+    pub date: usize,
+    pub description: usize,
+    pub amount: Option<usize>,
+    pub debit: Option<usize>,
+    pub credit: Option<usize>,
+}
+
+// The values could be read more than one way, so the user has to say which column is which
+#[derive(Debug)]
+pub struct AmbiguousColumns {
+    pub role: ColumnRole,
+}
+
+impl std::fmt::Display for AmbiguousColumns {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Could not tell which column is the {:?} column", self.role)
+    }
+}
+
+impl std::error::Error for AmbiguousColumns {}
+
+impl AmbiguousColumns {
+    pub fn message(&self, locale: &str) -> String {
+        let key = match self.role {
+            ColumnRole::Date => "column.date",
+            ColumnRole::Description => "column.description",
+            ColumnRole::Amount => "column.amount",
+            ColumnRole::Debit | ColumnRole::Credit => "column.debit_credit",
+            ColumnRole::Ignore => "column.other",
+        };
+        i18n::text(locale, "error.columns_ambiguous", &[("column", i18n::text(locale, key, &[]))])
+    }
+}
+
+// A starting point for a profile, for the user to correct and save
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ProfileSuggestion {
+    pub columns: Vec<DetectedColumn>,
+    pub template: BankProfile,
+}
+
+fn normalize(cell: &str) -> String {
+    cell.trim().trim_matches('"').trim().to_lowercase()
+}
+
+fn header_cells(content: &str, row: usize) -> Vec<String> {
+    content.lines().nth(row).map_or_else(Vec::new, |line| line.split(',').map(normalize).collect())
+}
+
+// The saved profile whose header signature matches this file
+pub fn matching<'a>(profiles: &'a [BankProfile], content: &str) -> Option<&'a BankProfile> {
+    profiles.iter().find(|profile| {
+        let cells = header_cells(content, profile.skip_lines);
+        !profile.header_signature.is_empty() &&
+            profile.header_signature.iter().all(|wanted| cells.contains(&normalize(wanted)))
+    })
+}
+
+fn parse_profile_date(raw: &str, format: Option<&str>) -> String {
+    let parsed = match format {
+        Some(format) => NaiveDate::parse_from_str(raw.trim(), format).ok(),
+        None => parse_date(raw),
+    };
+    parsed.map_or_else(|| raw.trim().to_string(), |date| date.format("%Y-%m-%d").to_string())
+}
+
+pub fn parse_with_profile(content: &str, profile: &BankProfile, sign_override: Option<SignConvention>, mode: ParseMode) -> Result<ParsedRows, Box<dyn std::error::Error>> {
+    let body = content.lines().skip(profile.skip_lines).collect::<Vec<_>>().join("\n");
+    let mut rdr = csv::Reader::from_reader(body.as_bytes());
+    let headers: Vec<String> = rdr.headers()?.iter().map(normalize).collect();
+
+    let column = |name: &str| -> Result<usize, Box<dyn std::error::Error>> {
+        headers.iter().position(|h| *h == normalize(name))
+            .ok_or_else(|| format!("Column \"{}\" from profile {} not found", name, profile.name).into())
+    };
+    let optional_column = |name: &Option<String>| name.as_deref().map(column).transpose();
+    let date_col = column(&profile.date_column)?;
+    let description_col = column(&profile.description_column)?;
+    let amount_col = optional_column(&profile.amount_column)?;
+    let debit_col = optional_column(&profile.debit_column)?;
+    let credit_col = optional_column(&profile.credit_column)?;
+    let split = debit_col.is_some() && credit_col.is_some();
+
+    let mut transactions = Vec::new();
+    let mut issues = RowIssues::new(mode);
+    for (index, result) in rdr.records().enumerate() {
+        let row = index + 1;
+        let record = result?;
+        let amount = if split {
+            let debit = optional_field(&record, debit_col).map(|d| parse_amount(&d)).transpose();
+            let credit = optional_field(&record, credit_col).map(|c| parse_amount(&c)).transpose();
+            debit.and_then(|debit| Ok(debit.unwrap_or(0.0).abs() - credit?.unwrap_or(0.0).abs()))
+        } else {
+            match optional_field(&record, amount_col) {
+                Some(amount) => parse_amount(&amount),
+                None => continue,
+            }
+        };
+        let amount = match amount {
+            Ok(amount) => amount,
+            Err(e) => {
+                issues.report(row, format!("amount could not be read ({})", e))?;
+                continue;
+            }
+        };
+        let Some(description) = optional_field(&record, Some(description_col)) else {
+            continue;
+        };
+        if amount == 0.0 {
+            continue;
+        }
+
+        let date = parse_profile_date(record.get(date_col).unwrap_or(""), profile.date_format.as_deref());
+        if parse_date(&date).is_none() {
+            issues.report(row, format!("date \"{}\" could not be read", date))?;
+            continue;
+        }
+        let mut tx = Transaction::new(date, description, if split { amount.abs() } else { amount });
+        if split && amount < 0.0 {
+            tx.transaction_type = TransactionType::Credit;
+        }
+        transactions.push(tx);
+    }
+
+    if split {
+        return Ok((transactions, None, issues.warnings));
+    }
+    let detection = match sign_override.or(profile.sign_convention) {
+        Some(convention) => SignDetection::overridden(convention),
+        None => sign_convention::detect(&transactions.iter().collect::<Vec<_>>()),
+    };
+    for tx in &mut transactions {
+        sign_convention::apply(tx, detection.convention);
+    }
+    Ok((transactions, Some(detection), issues.warnings))
+}
+
+// Role from the header text, else from what the sample values look like
+fn guess_role(header: &str, samples: &[String]) -> ColumnRole {
+    let named = |names: &[&str]| names.iter().any(|name| header.contains(name));
+    if named(&["date", "posted"]) {
+        ColumnRole::Date
+    } else if named(&["debit", "withdrawal", "money out", "paid out"]) {
+        ColumnRole::Debit
+    } else if named(&["credit", "deposit", "money in", "paid in"]) {
+        ColumnRole::Credit
+    } else if named(&["amount", "value", "sum"]) {
+        ColumnRole::Amount
+    } else if named(&["description", "merchant", "payee", "details", "narration", "memo", "reference"]) {
+        ColumnRole::Description
+    } else if !samples.is_empty() && samples.iter().all(|s| parse_date(s).is_some()) {
+        ColumnRole::Date
+    } else if !samples.is_empty() && samples.iter().all(|s| parse_amount(s).is_ok()) {
+        ColumnRole::Amount
+    } else {
+        ColumnRole::Ignore
+    }
+}
+
+fn share(values: &[&str], test: impl Fn(&str) -> bool) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().filter(|v| test(v)).count() as f64 / values.len() as f64
+}
+
+// Amounts carry cents or a currency sign, which keeps reference numbers and IDs out
+fn is_money(value: &str) -> bool {
+    parse_date(value).is_none() && parse_amount(value).is_ok()
+        && (value.contains('.') || value.contains(',') || value.contains('$'))
+}
+
+// How varied a column's text is, 0-1: the entropy of its values against the most it could be.
+// Descriptions differ row to row; currency codes, card numbers and statuses repeat. Reference
+// codes vary too, but are one word where merchant names are several.
+fn text_score(values: &[&str]) -> f64 {
+    let with_letters: Vec<&str> = values.iter().copied().filter(|v| v.chars().any(char::is_alphabetic)).collect();
+    if with_letters.len() < 2 {
+        return 0.0;
+    }
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for value in &with_letters {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    let n = with_letters.len() as f64;
+    let entropy: f64 = counts.values().map(|&c| { let p = c as f64 / n; -p * p.log2() }).sum();
+    let wordy = share(values, |v| v.contains(' '));
+    share(values, |v| v.chars().any(char::is_alphabetic)) * (0.5 + 0.5 * wordy) * entropy / n.log2()
+}
+
+// Whether `balance` moves by `amount` from each row to the next, as a running balance does
+fn is_running_balance(rows: &[csv::StringRecord], balance: usize, amount: usize) -> bool {
+    let pairs: Vec<(f64, f64)> = rows.iter()
+        .filter_map(|row| Some((parse_amount(row.get(balance)?).ok()?, parse_amount(row.get(amount)?).ok()?)))
+        .collect();
+    if pairs.len() < 3 {
+        return false;
+    }
+    let moves = pairs.windows(2)
+        .filter(|w| ((w[1].0 - w[0].0).abs() - w[1].1.abs()).abs() < 0.01 || ((w[1].0 - w[0].0).abs() - w[0].1.abs()).abs() < 0.01)
+        .count();
+    moves as f64 / (pairs.len() - 1) as f64 >= BALANCE_SHARE
+}
+
+// Works out date, description and amount columns from what the values look like. None when
+// nothing reads as a date and an amount, so the caller can fall back on column order; an
+// error when there's more than one fair reading.
+pub fn infer_columns(rows: &[csv::StringRecord]) -> Result<Option<InferredColumns>, AmbiguousColumns> {
+    let rows = &rows[..rows.len().min(INFER_ROWS)];
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let values: Vec<Vec<&str>> = (0..width)
+        .map(|col| rows.iter().filter_map(|row| row.get(col)).map(str::trim).filter(|v| !v.is_empty()).collect())
+        .collect();
+    let filled = |col: usize| values[col].len() as f64 / rows.len().max(1) as f64;
+
+    // The first date column; a second is usually the posting date
+    let Some(date) = (0..width).find(|&col| filled(col) >= CONFIDENT_SHARE && share(&values[col], |v| parse_date(v).is_some()) >= CONFIDENT_SHARE) else {
+        return Ok(None);
+    };
+    let mut money: Vec<usize> = (0..width)
+        .filter(|&col| col != date && !values[col].is_empty() && share(&values[col], is_money) >= CONFIDENT_SHARE)
+        .collect();
+    // A running balance beside the amount isn't a second amount
+    if money.len() == 2 {
+        let (a, b) = (money[0], money[1]);
+        if is_running_balance(rows, b, a) {
+            money.retain(|&col| col != b);
+        } else if is_running_balance(rows, a, b) {
+            money.retain(|&col| col != a);
+        }
+    }
+
+    let (amount, debit, credit) = match money.as_slice() {
+        [] => return Ok(None),
+        [amount] => (Some(*amount), None, None),
+        // Money out and in, each row filling one side. Cards see far more purchases than
+        // payments, so the fuller column is money out.
+        [a, b] if rows.iter().all(|row| {
+            let set = |col: usize| row.get(col).is_some_and(|v| !v.trim().is_empty());
+            set(*a) != set(*b)
+        }) => match values[*a].len().cmp(&values[*b].len()) {
+            std::cmp::Ordering::Greater => (None, Some(*a), Some(*b)),
+            std::cmp::Ordering::Less => (None, Some(*b), Some(*a)),
+            std::cmp::Ordering::Equal => return Err(AmbiguousColumns { role: ColumnRole::Debit }),
+        },
+        _ => return Err(AmbiguousColumns { role: ColumnRole::Amount }),
+    };
+
+    let mut text: Vec<(usize, f64)> = (0..width)
+        .filter(|col| ![Some(date), amount, debit, credit].contains(&Some(*col)))
+        .map(|col| (col, text_score(&values[col])))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    text.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let description = match text.as_slice() {
+        [] => return Err(AmbiguousColumns { role: ColumnRole::Description }),
+        // Two columns as varied as each other could either be the merchant
+        [first, second, ..] if first.1 - second.1 < DESCRIPTION_MARGIN => {
+            return Err(AmbiguousColumns { role: ColumnRole::Description });
+        }
+        [first, ..] => first.0,
+    };
+
+    Ok(Some(InferredColumns { date, description, amount, debit, credit }))
+}
+
+// The first format that reads every sample
+fn guess_date_format(samples: &[String]) -> Option<String> {
+    DATE_FORMATS.iter()
+        .find(|format| !samples.is_empty() && samples.iter().all(|s| NaiveDate::parse_from_str(s.trim(), format).is_ok()))
+        .map(|format| format.to_string())
+}
+
+// Unknown banks often name their columns in words find_header_row doesn't know, so failing
+// that, the header is the first line of several cells with no dates or amounts in it
+fn guess_header_row(content: &str) -> Option<usize> {
+    find_header_row(content).or_else(|| content.lines().take(MAX_PREAMBLE_LINES).position(|line| {
+        let cells: Vec<String> = line.split(',').map(normalize).collect();
+        cells.len() >= 3 && cells.iter().all(|cell| !cell.is_empty() && parse_amount(cell).is_err() && parse_date(cell).is_none())
+    }))
+}
+
+pub fn suggest_profile(content: &str, name: &str) -> Option<ProfileSuggestion> {
+    let header_row = guess_header_row(content)?;
+    let body = content.lines().skip(header_row).collect::<Vec<_>>().join("\n");
+    let mut rdr = csv::Reader::from_reader(body.as_bytes());
+    let headers: Vec<String> = rdr.headers().ok()?.iter().map(|h| h.trim().to_string()).collect();
+    let rows: Vec<csv::StringRecord> = rdr.records().filter_map(Result::ok).take(SAMPLE_ROWS).collect();
+
+    let mut columns: Vec<DetectedColumn> = headers.iter().enumerate().map(|(index, header)| {
+        let samples: Vec<String> = rows.iter().filter_map(|row| optional_field(row, Some(index))).collect();
+        DetectedColumn { index, header: header.clone(), role: guess_role(&normalize(header), &samples), samples }
+    }).collect();
+
+    // Only the first column of each role keeps it; extra date or amount columns are usually
+    // posting dates and running balances
+    let mut seen = Vec::new();
+    for column in &mut columns {
+        if column.role != ColumnRole::Ignore && seen.contains(&column.role) {
+            column.role = ColumnRole::Ignore;
+        }
+        seen.push(column.role);
+    }
+
+    // Headers like "Field1" say nothing, so the values decide
+    let named = |role: ColumnRole| columns.iter().any(|c| c.role == role);
+    if !named(ColumnRole::Date) || !(named(ColumnRole::Amount) || named(ColumnRole::Debit)) {
+        let all_rows: Vec<csv::StringRecord> = rdr.records().filter_map(Result::ok).take(INFER_ROWS).collect();
+        if let Ok(Some(inferred)) = infer_columns(&[rows.clone(), all_rows].concat()) {
+            for column in &mut columns {
+                column.role = match Some(column.index) {
+                    i if i == Some(inferred.date) => ColumnRole::Date,
+                    i if i == Some(inferred.description) => ColumnRole::Description,
+                    i if i == inferred.amount => ColumnRole::Amount,
+                    i if i == inferred.debit => ColumnRole::Debit,
+                    i if i == inferred.credit => ColumnRole::Credit,
+                    _ => ColumnRole::Ignore,
+                };
+            }
+        }
+    }
+
+    let header_of = |role: ColumnRole| columns.iter().find(|c| c.role == role).map(|c| c.header.clone());
+    let date_samples = columns.iter().find(|c| c.role == ColumnRole::Date).map(|c| c.samples.clone()).unwrap_or_default();
+    let template = BankProfile {
+        name: name.to_string(),
+        skip_lines: header_row,
+        header_signature: headers.clone(),
+        date_column: header_of(ColumnRole::Date).unwrap_or_default(),
+        description_column: header_of(ColumnRole::Description).unwrap_or_default(),
+        amount_column: header_of(ColumnRole::Amount),
+        debit_column: header_of(ColumnRole::Debit),
+        credit_column: header_of(ColumnRole::Credit),
+        date_format: guess_date_format(&date_samples),
+        sign_convention: None,
+    };
+    Some(ProfileSuggestion { columns, template })
+}
+
+pub fn validate(profile: &BankProfile, locale: &str) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err(i18n::error(locale, "error.profile_name_empty"));
+    }
+    let has_amount = profile.amount_column.is_some() || (profile.debit_column.is_some() && profile.credit_column.is_some());
+    if profile.date_column.trim().is_empty() || profile.description_column.trim().is_empty() || !has_amount {
+        return Err(i18n::error(locale, "error.profile_columns_missing"));
+    }
+    Ok(())
+}
+
+// Reads a sample file with the profile, failing if the profile doesn't fit it or yields nothing
+pub fn check_against_sample(profile: &BankProfile, content: &str, locale: &str) -> Result<Vec<Transaction>, String> {
+    if matching(std::slice::from_ref(profile), content).is_none() {
+        return Err(i18n::text(locale, "error.profile_sample_mismatch", &[("name", profile.name.clone())]));
+    }
+    let failed = |reason: String| i18n::text(locale, "error.profile_sample_failed", &[("reason", reason)]);
+    // Strict, so a bad row is reported rather than quietly left out of the check
+    let (transactions, _, _) = parse_with_profile(content, profile, None, ParseMode::Strict).map_err(|e| failed(e.to_string()))?;
+    if transactions.is_empty() {
+        return Err(failed("no transactions".to_string()));
+    }
+    Ok(transactions)
+}
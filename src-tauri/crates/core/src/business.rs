@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::{text, transaction_category, CategoryTotal, Transaction};
+
+// Tags that mark a charge as a business expense, or keep it personal whatever the rules say
+pub const BUSINESS_TAG: &str = "business";
+pub const PERSONAL_TAG: &str = "personal";
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct BusinessSplit {
+    pub business_total: f64,
+    pub personal_total: f64,
+    pub business_count: usize,
+    // Business spending only; percentages are of the business total
+    pub categories: Vec<CategoryTotal>,
+}
+
+// Tags win; otherwise a rule matching anywhere in the description makes it business
+pub fn is_business(transaction: &Transaction, rules: &[String]) -> bool {
+    if transaction.tags.iter().any(|tag| tag == PERSONAL_TAG) {
+        return false;
+    }
+    if transaction.tags.iter().any(|tag| tag == BUSINESS_TAG) {
+        return true;
+    }
+    let description = text::fold(&transaction.description);
+    rules.iter().any(|rule| description.contains(&text::fold(rule)))
+}
+
+// None when nothing in the statement is a business expense
+pub fn split(transactions: &[Transaction], rules: &[String]) -> Option<BusinessSplit> {
+    let (business, personal): (Vec<&Transaction>, Vec<&Transaction>) = transactions.iter()
+        .partition(|t| is_business(t, rules));
+    if business.is_empty() {
+        return None;
+    }
+
+    let business_total: f64 = business.iter().map(|t| t.amount).sum();
+    let mut by_category: BTreeMap<String, f64> = BTreeMap::new();
+    for transaction in &business {
+        *by_category.entry(transaction_category(transaction)).or_insert(0.0) += transaction.amount;
+    }
+    let mut categories: Vec<CategoryTotal> = by_category.into_iter()
+        .map(|(category, total)| {
+            let percentage = if business_total > 0.0 { total / business_total * 100.0 } else { 0.0 };
+            CategoryTotal::new(category, total, percentage)
+        })
+        .collect();
+    categories.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    Some(BusinessSplit {
+        business_total,
+        personal_total: personal.iter().map(|t| t.amount).sum(),
+        business_count: business.len(),
+        categories,
+    })
+}
@@ -0,0 +1,109 @@
+use crate::{parse_amount, Transaction};
+
+// ISO 20022 bank-to-customer statement (camt.053), as exported by most European banks
+pub fn is_camt053(content: &str) -> bool {
+    content.contains("camt.053") && content.contains("<Ntry")
+}
+
+// The inner text of every <tag>…</tag> in `xml`. camt entries don't nest an element inside
+// one of the same name, so the first closing tag is the matching one.
+fn elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut found = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after_name = &rest[start + open.len()..];
+        // Skip longer names sharing the prefix, e.g. <NtryDtls> when looking for <Ntry>
+        if !after_name.starts_with('>') && !after_name.starts_with(' ') {
+            rest = after_name;
+            continue;
+        }
+        let Some(content_start) = after_name.find('>') else {
+            break;
+        };
+        let content = &after_name[content_start + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        found.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+
+    found
+}
+
+fn first<'a>(xml: &'a str, path: &[&str]) -> Option<&'a str> {
+    path.iter().try_fold(xml, |current, tag| elements(current, tag).into_iter().next())
+}
+
+fn text(xml: &str, path: &[&str]) -> Option<String> {
+    first(xml, path)
+        .map(|value| value.trim()
+            .replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"")
+            .replace("&apos;", "'").replace("&amp;", "&"))
+        .filter(|value| !value.is_empty())
+}
+
+fn attribute(xml: &str, tag: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{} ", tag))?;
+    let element = &xml[start..start + xml[start..].find('>')?];
+    let marker = format!("{}=\"", name);
+    let value_start = element.find(&marker)? + marker.len();
+    let value_end = element[value_start..].find('"')?;
+    Some(element[value_start..value_start + value_end].to_string())
+}
+
+// Debit entries become transactions; credits are income or refunds, which the analysis doesn't track.
+// Reversals are skipped in both directions since they only undo an earlier booking.
+pub fn parse_camt053(content: &str) -> Result<Vec<Transaction>, String> {
+    let mut transactions = Vec::new();
+
+    for statement in elements(content, "Stmt") {
+        let account_currency = text(statement, &["Acct", "Ccy"]);
+
+        for entry in elements(statement, "Ntry") {
+            let status = text(entry, &["Sts"]).unwrap_or_default();
+            if status.contains("PDNG") || status.contains("INFO") {
+                continue;
+            }
+            if text(entry, &["CdtDbtInd"]).as_deref() != Some("DBIT") ||
+               text(entry, &["RvslInd"]).as_deref() == Some("true") {
+                continue;
+            }
+
+            let amount = text(entry, &["Amt"])
+                .and_then(|value| parse_amount(&value).ok())
+                .ok_or("camt.053 entry has no amount")?;
+            let date = text(entry, &["BookgDt", "Dt"])
+                .or_else(|| text(entry, &["BookgDt", "DtTm"]))
+                .or_else(|| text(entry, &["ValDt", "Dt"]))
+                .unwrap_or_default();
+
+            // The payee names the merchant best; remittance text and bank notes are fallbacks
+            let description = text(entry, &["RltdPties", "Cdtr", "Nm"])
+                .or_else(|| text(entry, &["RltdPties", "Cdtr", "Pty", "Nm"]))
+                .or_else(|| text(entry, &["RmtInf", "Ustrd"]))
+                .or_else(|| text(entry, &["AddtlNtryInf"]))
+                .unwrap_or_else(|| "Unknown".to_string());
+
+            // Only flag a currency when it differs from the account's own
+            let currency = attribute(entry, "Amt", "Ccy")
+                .filter(|currency| account_currency.as_deref() != Some(currency.as_str()));
+
+            let (date, time) = match date.split_once('T') {
+                Some((date, time)) => (date.to_string(), Some(time.chars().take(5).collect())),
+                None => (date, None),
+            };
+
+            transactions.push(Transaction {
+                currency,
+                time,
+                ..Transaction::new(date, description, amount)
+            });
+        }
+    }
+
+    Ok(transactions)
+}
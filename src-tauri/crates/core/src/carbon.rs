@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::analyzers::{AnalyzerInput, AnalyzerSection, SectionRow};
+use crate::history::StoredStatement;
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{i18n, parse_date, periods, transaction_category, Transaction};
+
+pub const ANALYZER_ID: &str = "carbon_footprint";
+const UNIT: &str = "kg CO2e";
+
+// Spend-based factors in kg CO2e per dollar, rounded from US input-output (EEIO) averages.
+// Fuel dominates its category, which is why it's so much higher than the rest; fees and
+// borrowing buy nothing physical.
+const DEFAULT_FACTORS: &[(&str, f64)] = &[
+    ("Food & Dining", 0.5),
+    ("Gas & Transportation", 1.6),
+    ("Shopping", 0.4),
+    ("Entertainment", 0.2),
+    ("Healthcare", 0.2),
+    ("Fees", 0.0),
+    ("Cash Advance", 0.0),
+    ("Other", 0.3),
+];
+// For categories set by hand that neither table lists
+const FALLBACK_FACTOR: f64 = 0.3;
+// Month-over-month swings smaller than this are noise from a few purchases moving around
+const NOTABLE_CHANGE_PERCENT: f64 = 10.0;
+
+// The factor from settings if there is one, otherwise the built-in one
+fn emission_factor(category: &str, overrides: &BTreeMap<String, f64>) -> f64 {
+    overrides.get(category).copied()
+        .or_else(|| DEFAULT_FACTORS.iter().find(|(name, _)| *name == category).map(|(_, factor)| *factor))
+        .unwrap_or(FALLBACK_FACTOR)
+}
+
+fn footprint_by_category<'a>(transactions: impl Iterator<Item = &'a Transaction>, overrides: &BTreeMap<String, f64>) -> HashMap<String, f64> {
+    let mut by_category: HashMap<String, f64> = HashMap::new();
+    for transaction in transactions {
+        let category = transaction_category(transaction);
+        let kg = transaction.amount * emission_factor(&category, overrides);
+        *by_category.entry(category).or_insert(0.0) += kg;
+    }
+    by_category
+}
+
+// The month before the latest one this statement reaches into, as earlier imports recorded it
+fn previous_month_footprint(transactions: &[Transaction], history: &[&StoredStatement], overrides: &BTreeMap<String, f64>) -> Option<HashMap<String, f64>> {
+    let month_of = |t: &Transaction| parse_date(&t.date).map(periods::period_month);
+    let latest: NaiveDate = transactions.iter().filter_map(month_of).max()?;
+    let previous = periods::previous_month(latest);
+
+    let mut in_previous = history.iter()
+        .flat_map(|statement| statement.transactions.iter())
+        .filter(|t| month_of(t) == Some(previous))
+        .peekable();
+    in_previous.peek()?;
+    Some(footprint_by_category(in_previous, overrides))
+}
+
+pub fn footprint_section(input: &AnalyzerInput) -> Option<AnalyzerSection> {
+    let overrides = &input.settings.emission_factors;
+    let current = footprint_by_category(input.transactions.iter(), overrides);
+    if current.is_empty() {
+        return None;
+    }
+    let previous = previous_month_footprint(input.transactions, input.history, overrides);
+
+    let mut rows: Vec<SectionRow> = current.iter()
+        .map(|(category, kg)| SectionRow {
+            label: category.clone(),
+            value: *kg,
+            previous: previous.as_ref().map(|previous| previous.get(category).copied().unwrap_or(0.0)),
+        })
+        .collect();
+    rows.sort_by(|a, b| b.value.total_cmp(&a.value));
+
+    Some(AnalyzerSection {
+        analyzer: ANALYZER_ID.to_string(),
+        title: i18n::text(&input.settings.locale, "carbon.title", &[]),
+        unit: Some(UNIT.to_string()),
+        total: Some(current.values().sum()),
+        previous_total: previous.map(|previous| previous.values().sum()),
+        rows,
+    })
+}
+
+pub fn footprint_insight(section: &AnalyzerSection) -> Option<Insight> {
+    let total = section.total?;
+    let top = section.rows.first()?;
+
+    let change_percent = section.previous_total
+        .filter(|previous| *previous > 0.0)
+        .map(|previous| (total - previous) / previous * 100.0)
+        .filter(|change| change.abs() >= NOTABLE_CHANGE_PERCENT);
+    let insight = match change_percent {
+        Some(change) if change > 0.0 => Insight::new(
+            InsightKind::Carbon,
+            Severity::Notice,
+            "carbon.footprint_up",
+            format!("This statement's spending comes to about {:.0} kg CO2e, {:.0}% more than last month. {} is the largest share", total, change, top.label),
+        ),
+        Some(change) => Insight::new(
+            InsightKind::Carbon,
+            Severity::Info,
+            "carbon.footprint_down",
+            format!("This statement's spending comes to about {:.0} kg CO2e, {:.0}% less than last month. {} is the largest share", total, -change, top.label),
+        ),
+        None => Insight::new(
+            InsightKind::Carbon,
+            Severity::Info,
+            "carbon.footprint",
+            format!("This statement's spending comes to about {:.0} kg CO2e. {} is the largest share", total, top.label),
+        ),
+    };
+    Some(insight
+        .with_category(&top.label)
+        .with_param("total", format!("{:.0}", total))
+        .with_param("change", format!("{:.0}", change_percent.unwrap_or(0.0).abs()))
+        .with_param("category", &top.label))
+}
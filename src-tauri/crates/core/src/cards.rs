@@ -0,0 +1,300 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::history::StoredStatement;
+use crate::{i18n, is_fee_or_interest, parse_date, text, transaction_category, SectionKind, Transaction};
+
+// How often a benefit's credit renews. Issuers reset them on calendar boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BenefitCadence {
+    Monthly,
+    Quarterly,
+    SemiAnnual,
+    Annual,
+}
+
+impl BenefitCadence {
+    fn periods_per_year(self) -> u32 {
+        match self {
+            BenefitCadence::Monthly => 12,
+            BenefitCadence::Quarterly => 4,
+            BenefitCadence::SemiAnnual => 2,
+            BenefitCadence::Annual => 1,
+        }
+    }
+
+    // 0-based period of the year a month (1-12) falls in
+    fn period_of(self, month: u32) -> u32 {
+        (month - 1) * self.periods_per_year() / 12
+    }
+
+    fn label(self, year: i32, period: u32) -> String {
+        match self {
+            BenefitCadence::Monthly => format!("{}-{:02}", year, period + 1),
+            BenefitCadence::Quarterly => format!("{}-Q{}", year, period + 1),
+            BenefitCadence::SemiAnnual => format!("{}-H{}", year, period + 1),
+            BenefitCadence::Annual => year.to_string(),
+        }
+    }
+}
+
+// A credit that comes with the card, e.g. "$10/mo dining credit". It counts as used in a
+// period by as much as was spent on qualifying purchases, up to the credit.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CardBenefit {
+    pub name: String,
+    // Per period
+    pub amount: f64,
+    pub cadence: BenefitCadence,
+    // Words in the description of a qualifying purchase, e.g. "grubhub"; any one matches
+    #[serde(default)]
+    pub merchants: Vec<String>,
+    // Or a whole category, e.g. "Food & Dining"
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+impl CardBenefit {
+    pub fn qualifies(&self, transaction: &Transaction) -> bool {
+        let description = text::fold(&transaction.description);
+        self.merchants.iter().any(|merchant| !merchant.trim().is_empty() && description.contains(&text::fold(merchant)))
+            || self.category.as_deref().is_some_and(|category| transaction_category(transaction).eq_ignore_ascii_case(category))
+    }
+}
+
+// A card the user holds, told apart from their others by what its statements print
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Card {
+    pub id: u64,
+    pub name: String,
+    // Matched against the statement summary; a card with neither takes every statement, which
+    // suits someone with one card
+    #[serde(default)]
+    pub issuer: Option<String>,
+    #[serde(default)]
+    pub last4: Option<String>,
+    #[serde(default)]
+    pub annual_fee: f64,
+    #[serde(default)]
+    pub benefits: Vec<CardBenefit>,
+    // Percent back on purchases, to estimate rewards from statements that don't report them
+    #[serde(default)]
+    pub reward_rate: Option<f64>,
+}
+
+impl Card {
+    pub fn validate(&self, locale: &str) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(i18n::error(locale, "error.card_name_empty"));
+        }
+        if !self.annual_fee.is_finite() || self.annual_fee < 0.0 {
+            return Err(i18n::error(locale, "error.card_fee_invalid"));
+        }
+        if self.reward_rate.is_some_and(|rate| !rate.is_finite() || !(0.0..=100.0).contains(&rate)) {
+            return Err(i18n::error(locale, "error.reward_rate_invalid"));
+        }
+        for benefit in &self.benefits {
+            if benefit.name.trim().is_empty() || !benefit.amount.is_finite() || benefit.amount <= 0.0 {
+                return Err(i18n::text(locale, "error.benefit_invalid", &[("name", benefit.name.clone())]));
+            }
+            if benefit.merchants.iter().all(|m| m.trim().is_empty()) && benefit.category.as_deref().is_none_or(|c| c.trim().is_empty()) {
+                return Err(i18n::text(locale, "error.benefit_unmatched", &[("name", benefit.name.clone())]));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn owns(&self, statement: &StoredStatement) -> bool {
+        let meta = statement.meta.as_ref();
+        let issuer_matches = self.issuer.as_deref().is_none_or(|issuer| {
+            meta.and_then(|m| m.issuer.as_deref()).is_some_and(|printed| printed.to_lowercase().contains(&issuer.trim().to_lowercase()))
+        });
+        let last4_matches = self.last4.as_deref().is_none_or(|last4| {
+            meta.and_then(|m| m.card_last4.as_deref()) == Some(last4.trim())
+        });
+        issuer_matches && last4_matches
+    }
+}
+
+// The card's purchases from its own statements
+pub fn card_transactions<'a>(card: &Card, statements: &'a [StoredStatement]) -> Vec<&'a Transaction> {
+    statements.iter().filter(|s| card.owns(s)).flat_map(|s| &s.transactions).collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct BenefitPeriod {
+    // "2024-03", "2024-Q1", "2024-H1" or "2024"
+    pub period: String,
+    pub available: f64,
+    pub used: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct BenefitUsage {
+    pub name: String,
+    pub cadence: BenefitCadence,
+    // Only periods the card's statements cover
+    pub periods: Vec<BenefitPeriod>,
+    pub available: f64,
+    pub captured: f64,
+    // Periods where some of the credit went unused
+    pub unused_periods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct BenefitReport {
+    pub card_id: u64,
+    pub card_name: String,
+    pub year: i32,
+    pub benefits: Vec<BenefitUsage>,
+    pub available: f64,
+    pub captured: f64,
+    pub annual_fee: f64,
+    // Benefit value captured less the annual fee
+    pub net_of_fee: f64,
+}
+
+fn usage(benefit: &CardBenefit, transactions: &[&Transaction], year: i32) -> BenefitUsage {
+    // Periods the card's statements reach into, so months not imported yet don't count as unused
+    let mut covered: Vec<u32> = transactions.iter()
+        .filter_map(|t| parse_date(&t.date))
+        .filter(|date| date.year() == year)
+        .map(|date| benefit.cadence.period_of(date.month()))
+        .collect();
+    covered.sort();
+    covered.dedup();
+
+    let periods: Vec<BenefitPeriod> = covered.into_iter().map(|period| {
+        let spent: f64 = transactions.iter()
+            .filter(|t| parse_date(&t.date).is_some_and(|date| date.year() == year && benefit.cadence.period_of(date.month()) == period))
+            .filter(|t| benefit.qualifies(t))
+            .map(|t| t.amount)
+            .sum();
+        BenefitPeriod {
+            period: benefit.cadence.label(year, period),
+            available: benefit.amount,
+            used: spent.clamp(0.0, benefit.amount),
+        }
+    }).collect();
+
+    BenefitUsage {
+        name: benefit.name.clone(),
+        cadence: benefit.cadence,
+        available: periods.iter().map(|p| p.available).sum(),
+        captured: periods.iter().map(|p| p.used).sum(),
+        unused_periods: periods.iter().filter(|p| p.used < p.available - 0.005).map(|p| p.period.clone()).collect(),
+        periods,
+    }
+}
+
+// How much of each benefit was used in `year`, against what the card costs
+pub fn benefit_report(card: &Card, statements: &[StoredStatement], year: i32) -> BenefitReport {
+    let transactions = card_transactions(card, statements);
+    let benefits: Vec<BenefitUsage> = card.benefits.iter().map(|benefit| usage(benefit, &transactions, year)).collect();
+    let captured = benefits.iter().map(|b| b.captured).sum();
+    BenefitReport {
+        card_id: card.id,
+        card_name: card.name.clone(),
+        year,
+        available: benefits.iter().map(|b| b.available).sum(),
+        captured,
+        annual_fee: card.annual_fee,
+        net_of_fee: captured - card.annual_fee,
+        benefits,
+    }
+}
+
+// The latest year the card has purchases in
+pub fn latest_year(card: &Card, statements: &[StoredStatement]) -> Option<i32> {
+    card_transactions(card, statements).iter().filter_map(|t| parse_date(&t.date)).map(|date| date.year()).max()
+}
+
+// Descriptions issuers print on the yearly charge for the card itself
+const ANNUAL_FEE_WORDS: &[&str] = &["annual fee", "membership fee", "annual membership"];
+
+fn is_interest(transaction: &Transaction) -> bool {
+    transaction.section == Some(SectionKind::Interest) || transaction.description.to_lowercase().contains("interest")
+}
+
+fn is_annual_fee(transaction: &Transaction) -> bool {
+    let description = transaction.description.to_lowercase();
+    ANNUAL_FEE_WORDS.iter().any(|word| description.contains(word))
+}
+
+// What one card gave and cost over a year
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CardBreakEven {
+    pub card_id: u64,
+    pub card_name: String,
+    // As the issuer reported them, else estimated from the card's reward rate
+    pub rewards: f64,
+    pub rewards_estimated: bool,
+    pub benefits: f64,
+    // From the statements when charged there, else the fee the card was set up with
+    pub annual_fee: f64,
+    pub other_fees: f64,
+    pub interest: f64,
+    // Rewards and benefits less fees and interest; positive when the card paid for itself
+    pub net: f64,
+}
+
+// Every card together, for someone with more than one
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct PortfolioBreakEven {
+    pub year: i32,
+    pub cards: Vec<CardBreakEven>,
+    pub rewards: f64,
+    pub benefits: f64,
+    pub fees: f64,
+    pub interest: f64,
+    pub net: f64,
+}
+
+pub fn break_even(card: &Card, statements: &[StoredStatement], year: i32) -> CardBreakEven {
+    let transactions: Vec<&Transaction> = card_transactions(card, statements).into_iter()
+        .filter(|t| parse_date(&t.date).is_some_and(|date| date.year() == year))
+        .collect();
+    let (charges, purchases): (Vec<&Transaction>, Vec<&Transaction>) = transactions.iter()
+        .partition(|t| is_fee_or_interest(t) || is_interest(t));
+    let (interest, fees): (Vec<&Transaction>, Vec<&Transaction>) = charges.into_iter().partition(|t| is_interest(t));
+    let interest: f64 = interest.iter().map(|t| t.amount).sum();
+    let charged_fee: f64 = fees.iter().filter(|t| is_annual_fee(t)).map(|t| t.amount).sum();
+    let other_fees: f64 = fees.iter().filter(|t| !is_annual_fee(t)).map(|t| t.amount).sum();
+    let annual_fee = if charged_fee > 0.0 { charged_fee } else { card.annual_fee };
+
+    let reported: f64 = transactions.iter().filter_map(|t| t.rewards).sum();
+    let rewards_estimated = reported == 0.0 && card.reward_rate.is_some();
+    let rewards = if rewards_estimated {
+        purchases.iter().map(|t| t.amount).sum::<f64>() * card.reward_rate.unwrap_or(0.0) / 100.0
+    } else {
+        reported
+    };
+    let benefits = benefit_report(card, statements, year).captured;
+
+    CardBreakEven {
+        card_id: card.id,
+        card_name: card.name.clone(),
+        rewards,
+        rewards_estimated,
+        benefits,
+        annual_fee,
+        other_fees,
+        interest,
+        net: rewards + benefits - annual_fee - other_fees - interest,
+    }
+}
+
+pub fn portfolio_break_even(cards: &[Card], statements: &[StoredStatement], year: i32) -> PortfolioBreakEven {
+    let cards: Vec<CardBreakEven> = cards.iter().map(|card| break_even(card, statements, year)).collect();
+    PortfolioBreakEven {
+        year,
+        rewards: cards.iter().map(|c| c.rewards).sum(),
+        benefits: cards.iter().map(|c| c.benefits).sum(),
+        fees: cards.iter().map(|c| c.annual_fee + c.other_fees).sum(),
+        interest: cards.iter().map(|c| c.interest).sum(),
+        net: cards.iter().map(|c| c.net).sum(),
+        cards,
+    }
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{parse_date, Transaction};
+
+// Cash advances skip the grace period and are billed at a higher APR than purchases
+const CASH_ADVANCE_APR: f64 = 29.99;
+// Typical issuer fee when the statement doesn't list it separately: 5%, minimum $10
+const DEFAULT_FEE_RATE: f64 = 0.05;
+const DEFAULT_MIN_FEE: f64 = 10.0;
+// Days between statement close and payment due date
+const DAYS_UNTIL_DUE: i64 = 25;
+// Fallback when the transaction date can't be parsed
+const DEFAULT_DAYS_OUTSTANDING: i64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CashAdvanceWarning {
+    pub severity: Severity,
+    pub message: String,
+    pub advances: Vec<Transaction>,
+    pub total_advanced: f64,
+    pub total_fees: f64,
+    pub fees_estimated: bool,
+    pub estimated_interest: f64,
+    pub effective_cost: f64,
+    pub effective_apr: f64,
+}
+
+pub fn is_cash_advance(description: &str) -> bool {
+    let desc_lower = description.to_lowercase();
+
+    if desc_lower.contains("fee") {
+        return false;
+    }
+
+    desc_lower.contains("cash advance") || desc_lower.contains("cash adv") ||
+    desc_lower.contains("atm withdrawal") || desc_lower.contains("cash withdrawal") ||
+    desc_lower.contains("casino") || desc_lower.contains("western union") ||
+    desc_lower.contains("moneygram") || desc_lower.contains("money order")
+}
+
+pub fn is_cash_advance_fee(description: &str) -> bool {
+    let desc_lower = description.to_lowercase();
+
+    desc_lower.contains("fee") &&
+    (desc_lower.contains("cash adv") || desc_lower.contains("atm"))
+}
+
+pub fn detect_cash_advances(transactions: &[Transaction]) -> Option<CashAdvanceWarning> {
+    let advances: Vec<Transaction> = transactions.iter()
+        .filter(|t| is_cash_advance(&t.description))
+        .cloned()
+        .collect();
+
+    let listed_fees: f64 = transactions.iter()
+        .filter(|t| is_cash_advance_fee(&t.description))
+        .map(|t| t.amount)
+        .sum();
+
+    if advances.is_empty() && listed_fees == 0.0 {
+        return None;
+    }
+
+    let total_advanced: f64 = advances.iter().map(|t| t.amount).sum();
+
+    // Not every issuer itemizes the fee, so fall back to the usual fee schedule
+    let fees_estimated = listed_fees == 0.0;
+    let total_fees = if fees_estimated {
+        advances.iter()
+            .map(|t| (t.amount * DEFAULT_FEE_RATE).max(DEFAULT_MIN_FEE))
+            .sum()
+    } else {
+        listed_fees
+    };
+
+    // Interest starts the day the cash is taken and runs at least until the payment due date
+    let statement_end = transactions.iter().filter_map(|t| parse_date(&t.date)).max();
+    let days_outstanding: Vec<i64> = advances.iter()
+        .map(|t| match (parse_date(&t.date), statement_end) {
+            (Some(date), Some(end)) => ((end - date).num_days() + DAYS_UNTIL_DUE).max(1),
+            _ => DEFAULT_DAYS_OUTSTANDING,
+        })
+        .collect();
+
+    let estimated_interest: f64 = advances.iter()
+        .zip(&days_outstanding)
+        .map(|(t, days)| t.amount * (CASH_ADVANCE_APR / 100.0) / 365.0 * *days as f64)
+        .sum();
+
+    let effective_cost = total_fees + estimated_interest;
+
+    let effective_apr = if total_advanced > 0.0 {
+        let weighted_days: f64 = advances.iter()
+            .zip(&days_outstanding)
+            .map(|(t, days)| t.amount * *days as f64)
+            .sum::<f64>() / total_advanced;
+        (effective_cost / total_advanced) * (365.0 / weighted_days) * 100.0
+    } else {
+        0.0
+    };
+
+    let message = if advances.is_empty() {
+        format!("You paid ${:.2} in cash advance fees this period", total_fees)
+    } else {
+        format!("Cash advances of ${:.2} will cost about ${:.2} in fees and interest — an effective APR of {:.1}%. Interest accrues immediately with no grace period, so pay these off first",
+                total_advanced, effective_cost, effective_apr)
+    };
+
+    Some(CashAdvanceWarning {
+        severity: Severity::Critical,
+        message,
+        advances,
+        total_advanced,
+        total_fees,
+        fees_estimated,
+        estimated_interest,
+        effective_cost,
+        effective_apr,
+    })
+}
+
+pub fn cash_advance_insight(warning: &CashAdvanceWarning) -> Insight {
+    let mut insight = Insight::new(
+        InsightKind::CashAdvance,
+        Severity::Critical,
+        if warning.advances.is_empty() { "cash_advance.fees_only" } else { "cash_advance.cost" },
+        warning.message.clone(),
+    )
+    .with_amount(warning.effective_cost)
+    .with_category("Cash Advance")
+    .with_param("advanced", format!("{:.2}", warning.total_advanced))
+    .with_param("fees", format!("{:.2}", warning.total_fees))
+    .with_param("cost", format!("{:.2}", warning.effective_cost))
+    .with_param("apr", format!("{:.1}", warning.effective_apr));
+    for advance in &warning.advances {
+        insight = insight.with_merchant(&advance.description);
+    }
+    insight
+}
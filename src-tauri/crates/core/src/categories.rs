@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::{transaction_category, Transaction};
+
+// Where a category falls in a 50/30/20 budget
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CategoryClass {
+    Need,
+    Want,
+    // Fees and borrowing: money that goes to the debt rather than to goods
+    SavingsDebt,
+}
+
+// Display metadata for the categories the analysis assigns, so every view colors and labels a
+// category the same way. Ids are stable across renames of the display name.
+struct CategoryConfig {
+    name: &'static str,
+    id: &'static str,
+    color: &'static str,
+    icon: &'static str,
+    class: CategoryClass,
+}
+
+const CATEGORIES: &[CategoryConfig] = &[
+    CategoryConfig { name: "Food & Dining", id: "food_dining", color: "#f57c00", icon: "🍽️", class: CategoryClass::Want },
+    CategoryConfig { name: "Gas & Transportation", id: "gas_transportation", color: "#1976d2", icon: "⛽", class: CategoryClass::Need },
+    CategoryConfig { name: "Shopping", id: "shopping", color: "#7b1fa2", icon: "🛍️", class: CategoryClass::Want },
+    CategoryConfig { name: "Entertainment", id: "entertainment", color: "#c2185b", icon: "🎬", class: CategoryClass::Want },
+    CategoryConfig { name: "Healthcare", id: "healthcare", color: "#388e3c", icon: "🩺", class: CategoryClass::Need },
+    CategoryConfig { name: "Fees", id: "fees", color: "#d32f2f", icon: "💸", class: CategoryClass::SavingsDebt },
+    CategoryConfig { name: "Cash Advance", id: "cash_advance", color: "#5d4037", icon: "🏧", class: CategoryClass::SavingsDebt },
+    CategoryConfig { name: "Other", id: "other", color: "#757575", icon: "📦", class: CategoryClass::Want },
+];
+
+// Colors for categories set by hand, picked by name so they don't change between runs
+const CUSTOM_PALETTE: &[&str] = &["#00897b", "#5e35b1", "#6d4c41", "#3949ab", "#c0ca33", "#e53935", "#8e24aa", "#00acc1"];
+const CUSTOM_ICON: &str = "🏷️";
+// Hand-made categories are usually finer splits of discretionary spending
+const CUSTOM_CLASS: CategoryClass = CategoryClass::Want;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryStyle {
+    pub id: String,
+    pub color: String,
+    pub icon: String,
+}
+
+// FNV-1a, written out because std's hasher isn't guaranteed stable across releases
+fn stable_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_end_matches('_').to_string()
+}
+
+pub fn style(name: &str) -> CategoryStyle {
+    if let Some(config) = CATEGORIES.iter().find(|c| c.name.eq_ignore_ascii_case(name)) {
+        return CategoryStyle {
+            id: config.id.to_string(),
+            color: config.color.to_string(),
+            icon: config.icon.to_string(),
+        };
+    }
+
+    let color = CUSTOM_PALETTE[(stable_hash(&name.to_lowercase()) % CUSTOM_PALETTE.len() as u64) as usize];
+    CategoryStyle {
+        id: format!("custom_{}", slug(name)),
+        color: color.to_string(),
+        icon: CUSTOM_ICON.to_string(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ClassTotal {
+    pub class: CategoryClass,
+    pub total: f64,
+    pub percentage: f64,
+}
+
+// The user's per-category choice from settings, else the category config's default
+pub fn class(name: &str, overrides: &BTreeMap<String, CategoryClass>) -> CategoryClass {
+    if let Some((_, class)) = overrides.iter().find(|(category, _)| category.eq_ignore_ascii_case(name)) {
+        return *class;
+    }
+    CATEGORIES.iter()
+        .find(|c| c.name.eq_ignore_ascii_case(name))
+        .map_or(CUSTOM_CLASS, |c| c.class)
+}
+
+// Spending per class, in need / want / savings-debt order, skipping empty classes
+pub fn class_totals(transactions: &[Transaction], overrides: &BTreeMap<String, CategoryClass>) -> Vec<ClassTotal> {
+    let total: f64 = transactions.iter().map(|t| t.amount).sum();
+    let mut totals: Vec<ClassTotal> = [CategoryClass::Need, CategoryClass::Want, CategoryClass::SavingsDebt]
+        .into_iter()
+        .map(|class| ClassTotal { class, total: 0.0, percentage: 0.0 })
+        .collect();
+
+    for tx in transactions {
+        let class = class(&transaction_category(tx), overrides);
+        if let Some(entry) = totals.iter_mut().find(|t| t.class == class) {
+            entry.total += tx.amount;
+        }
+    }
+
+    totals.retain(|t| t.total > 0.0);
+    for entry in &mut totals {
+        entry.percentage = entry.total / total * 100.0;
+    }
+    totals
+}
@@ -0,0 +1,400 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::Path;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{extract_merchant_name, transaction_category, Transaction};
+
+// A user-defined insight, e.g.
+// { "name": "Coffee habit", "filter": "merchant contains 'STARBUCKS' and amount < 20",
+//   "aggregation": "sum", "threshold": 40, "message": "Coffee cost you ${value} over {count} visits" }
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CustomInsightDefinition {
+    pub name: String,
+    pub filter: String,
+    #[serde(default)]
+    pub aggregation: Aggregation,
+    pub threshold: f64,
+    #[serde(default)]
+    pub trigger: Trigger,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    #[default]
+    Sum,
+    Count,
+    Average,
+    Max,
+    Min,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    #[default]
+    Above,
+    Below,
+}
+
+fn default_severity() -> Severity {
+    Severity::Notice
+}
+
+// Filter expressions: comparisons joined by and/or/not with parentheses.
+// Fields: description, merchant, category, amount, date, country, currency.
+// Operators: contains, starts_with, ends_with, matches (regex), ==, !=, <, <=, >, >=.
+#[derive(Debug)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Field, Op, Value),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Description,
+    Merchant,
+    Category,
+    Amount,
+    Date,
+    Country,
+    Currency,
+}
+
+#[derive(Debug)]
+enum Op {
+    Contains,
+    StartsWith,
+    EndsWith,
+    Matches(Regex),
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug)]
+enum Value {
+    Text(String),
+    Number(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Number(f64),
+    Symbol(String),
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = input.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if c == '\'' || c == '"' {
+            let end = chars[i + 1..].iter().position(|&ch| ch == c)
+                .ok_or_else(|| format!("Unterminated string starting at position {}", i))?;
+            tokens.push(Token::Text(chars[i + 1..i + 1 + end].iter().collect()));
+            i += end + 2;
+        } else if "<>=!".contains(c) {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if ["<=", ">=", "==", "!="].contains(&two.as_str()) {
+                tokens.push(Token::Symbol(two));
+                i += 2;
+            } else if c == '<' || c == '>' {
+                tokens.push(Token::Symbol(c.to_string()));
+                i += 1;
+            } else {
+                return Err(format!("Unexpected '{}' at position {}", c, i));
+            }
+        } else if c.is_ascii_digit() || c == '-' || c == '.' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text.parse::<f64>().map_err(|_| format!("Invalid number '{}'", text))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect::<String>().to_lowercase()));
+        } else {
+            return Err(format!("Unexpected '{}' at position {}", c, i));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w == word)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_and()?;
+        while self.is_word("or") {
+            self.next();
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_not()?;
+        while self.is_word("and") {
+            self.next();
+            left = Expr::And(Box::new(left), Box::new(self.parse_not()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        if self.is_word("not") {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Open) {
+            self.next();
+            let expr = self.parse_or()?;
+            if self.next() != Some(Token::Close) {
+                return Err("Missing closing parenthesis".to_string());
+            }
+            return Ok(expr);
+        }
+
+        let field = match self.next() {
+            Some(Token::Word(word)) => match word.as_str() {
+                "description" => Field::Description,
+                "merchant" => Field::Merchant,
+                "category" => Field::Category,
+                "amount" => Field::Amount,
+                "date" => Field::Date,
+                "country" => Field::Country,
+                "currency" => Field::Currency,
+                other => return Err(format!("Unknown field '{}'", other)),
+            },
+            other => return Err(format!("Expected a field name, found {:?}", other)),
+        };
+
+        let op = self.next().ok_or("Expected an operator")?;
+        let value = match self.next() {
+            Some(Token::Text(text)) => Value::Text(text),
+            Some(Token::Number(number)) => Value::Number(number),
+            other => return Err(format!("Expected a quoted string or number, found {:?}", other)),
+        };
+
+        let op = match op {
+            Token::Word(word) => match word.as_str() {
+                "contains" => Op::Contains,
+                "starts_with" => Op::StartsWith,
+                "ends_with" => Op::EndsWith,
+                "matches" => match &value {
+                    Value::Text(pattern) => Op::Matches(Regex::new(&format!("(?i){}", pattern))
+                        .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?),
+                    Value::Number(_) => return Err("matches needs a quoted pattern".to_string()),
+                },
+                other => return Err(format!("Unknown operator '{}'", other)),
+            },
+            Token::Symbol(symbol) => match symbol.as_str() {
+                "==" => Op::Eq,
+                "!=" => Op::Ne,
+                "<" => Op::Lt,
+                "<=" => Op::Le,
+                ">" => Op::Gt,
+                ">=" => Op::Ge,
+                other => return Err(format!("Unknown operator '{}'", other)),
+            },
+            other => return Err(format!("Expected an operator, found {:?}", other)),
+        };
+
+        Ok(Expr::Compare(field, op, value))
+    }
+}
+
+fn parse_filter(filter: &str) -> Result<Expr, String> {
+    let mut parser = Parser { tokens: tokenize(filter)?, position: 0 };
+    let expr = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected {:?} after end of expression", token));
+    }
+    Ok(expr)
+}
+
+fn field_text(tx: &Transaction, field: Field) -> String {
+    match field {
+        Field::Description => tx.description.clone(),
+        Field::Merchant => extract_merchant_name(&tx.description),
+        Field::Category => transaction_category(tx),
+        Field::Amount => tx.amount.to_string(),
+        Field::Date => tx.date.clone(),
+        Field::Country => tx.country.clone().unwrap_or_default(),
+        Field::Currency => tx.currency.clone().unwrap_or_default(),
+    }
+}
+
+fn matches(expr: &Expr, tx: &Transaction) -> bool {
+    match expr {
+        Expr::And(left, right) => matches(left, tx) && matches(right, tx),
+        Expr::Or(left, right) => matches(left, tx) || matches(right, tx),
+        Expr::Not(inner) => !matches(inner, tx),
+        Expr::Compare(Field::Amount, op, Value::Number(number)) => match op {
+            Op::Eq => (tx.amount - number).abs() < 0.005,
+            Op::Ne => (tx.amount - number).abs() >= 0.005,
+            Op::Lt => tx.amount < *number,
+            Op::Le => tx.amount <= *number,
+            Op::Gt => tx.amount > *number,
+            Op::Ge => tx.amount >= *number,
+            _ => false,
+        },
+        Expr::Compare(field, op, value) => {
+            let actual = field_text(tx, *field).to_lowercase();
+            let expected = match value {
+                Value::Text(text) => text.to_lowercase(),
+                Value::Number(number) => number.to_string(),
+            };
+            match op {
+                Op::Contains => actual.contains(&expected),
+                Op::StartsWith => actual.starts_with(&expected),
+                Op::EndsWith => actual.ends_with(&expected),
+                Op::Matches(regex) => regex.is_match(&actual),
+                Op::Eq => actual == expected,
+                Op::Ne => actual != expected,
+                Op::Lt => actual < expected,
+                Op::Le => actual <= expected,
+                Op::Gt => actual > expected,
+                Op::Ge => actual >= expected,
+            }
+        }
+    }
+}
+
+fn aggregate(amounts: &[f64], aggregation: Aggregation) -> f64 {
+    match aggregation {
+        Aggregation::Sum => amounts.iter().sum(),
+        Aggregation::Count => amounts.len() as f64,
+        Aggregation::Average if amounts.is_empty() => 0.0,
+        Aggregation::Average => amounts.iter().sum::<f64>() / amounts.len() as f64,
+        Aggregation::Max => amounts.iter().copied().fold(0.0, f64::max),
+        Aggregation::Min => amounts.iter().copied().reduce(f64::min).unwrap_or(0.0),
+    }
+}
+
+fn evaluate(definition: &CustomInsightDefinition, expr: &Expr, transactions: &[Transaction]) -> Option<Insight> {
+    let matching: Vec<&Transaction> = transactions.iter().filter(|t| matches(expr, t)).collect();
+    let amounts: Vec<f64> = matching.iter().map(|t| t.amount).collect();
+    let value = aggregate(&amounts, definition.aggregation);
+
+    let triggered = match definition.trigger {
+        Trigger::Above => value > definition.threshold,
+        Trigger::Below => value < definition.threshold,
+    };
+    if !triggered {
+        return None;
+    }
+
+    let total: f64 = amounts.iter().sum();
+    let value_text = match definition.aggregation {
+        Aggregation::Count => format!("{}", value),
+        _ => format!("{:.2}", value),
+    };
+    let message = definition.message
+        .replace("{name}", &definition.name)
+        .replace("{value}", &value_text)
+        .replace("{count}", &matching.len().to_string())
+        .replace("{total}", &format!("{:.2}", total))
+        .replace("{threshold}", &definition.threshold.to_string());
+
+    let mut insight = Insight::new(InsightKind::Custom, definition.severity, "custom", message)
+        .with_amount(value)
+        .with_param("name", &definition.name)
+        .with_param("value", &value_text)
+        .with_param("count", matching.len())
+        .with_param("total", format!("{:.2}", total));
+    let mut merchants: Vec<String> = matching.iter().map(|t| extract_merchant_name(&t.description)).collect();
+    merchants.sort();
+    merchants.dedup();
+    for merchant in &merchants {
+        insight = insight.with_merchant(merchant);
+    }
+    Some(insight)
+}
+
+// Evaluates every definition; ones with a broken filter are skipped rather than failing the analysis
+pub fn evaluate_all(definitions: &[CustomInsightDefinition], transactions: &[Transaction]) -> Vec<Insight> {
+    definitions.iter().filter_map(|definition| {
+        match parse_filter(&definition.filter) {
+            Ok(expr) => evaluate(definition, &expr, transactions),
+            Err(e) => {
+                log!("Skipping custom insight '{}': {}", definition.name, e);
+                None
+            }
+        }
+    }).collect()
+}
+
+pub fn load_definitions(path: &Path) -> Result<Vec<CustomInsightDefinition>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Could not read custom insights: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Custom insights file is invalid: {}", e))
+}
+
+// Rejects the whole set if any filter doesn't parse, naming the definition it's in
+pub fn validate(definitions: &[CustomInsightDefinition]) -> Result<(), String> {
+    for definition in definitions {
+        parse_filter(&definition.filter)
+            .map_err(|e| format!("Invalid filter in '{}': {}", definition.name, e))?;
+    }
+    Ok(())
+}
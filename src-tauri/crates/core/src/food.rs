@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::history::StoredStatement;
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{month_key, transaction_category, Transaction};
+
+// Merchant category codes, which issuers that export them get right far more often than
+// keywords do
+const GROCERY_MCCS: &[&str] = &["5411", "5422", "5441", "5451", "5462", "5499"];
+const DINING_MCCS: &[&str] = &["5811", "5812", "5813", "5814"];
+
+const GROCERY_KEYWORDS: &[&str] = &[
+    "grocery", "groceries", "supermarket", "kroger", "safeway", "whole foods", "trader joe",
+    "aldi", "lidl", "publix", "wegmans", "h-e-b", "heb ", "sprouts", "food lion", "albertsons",
+    "stop & shop", "meijer", "hy-vee", "winco", "instacart", "fresh market", "food 4 less",
+];
+const DINING_KEYWORDS: &[&str] = &[
+    "restaurant", "cafe", "coffee", "starbucks", "mcdonald", "pizza", "burger", "grill", "diner",
+    "bistro", "taco", "sushi", "chipotle", "kitchen", "bar & ", "pub ", "tavern", "doordash",
+    "uber eats", "ubereats", "grubhub", "postmates", "deliveroo", "wendy", "chick-fil-a",
+    "dunkin", "panera", "domino",
+];
+// Percentage points the dining-out share has to climb before it's flagged
+const SHARE_RISE_POINTS: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodKind {
+    Groceries,
+    Dining,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct FoodMonth {
+    // "2024-01"
+    pub month: String,
+    pub groceries: f64,
+    pub dining: f64,
+    pub dining_share: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct FoodReport {
+    pub groceries: f64,
+    pub dining: f64,
+    // Percent of food spending that went to eating out
+    pub dining_share: f64,
+    // The same share across earlier statements
+    pub previous_dining_share: Option<f64>,
+    // This statement and the ones before it, oldest first
+    pub months: Vec<FoodMonth>,
+}
+
+fn contains_any(description: &str, keywords: &[&str]) -> bool {
+    let padded = format!("{} ", description.to_lowercase());
+    keywords.iter().any(|keyword| padded.contains(keyword))
+}
+
+pub fn mcc_kind(transaction: &Transaction) -> Option<FoodKind> {
+    let mcc = transaction.mcc.as_deref()?.trim();
+    if GROCERY_MCCS.contains(&mcc) {
+        Some(FoodKind::Groceries)
+    } else if DINING_MCCS.contains(&mcc) {
+        Some(FoodKind::Dining)
+    } else {
+        None
+    }
+}
+
+pub fn description_kind(description: &str) -> Option<FoodKind> {
+    if contains_any(description, GROCERY_KEYWORDS) {
+        Some(FoodKind::Groceries)
+    } else if contains_any(description, DINING_KEYWORDS) {
+        Some(FoodKind::Dining)
+    } else {
+        None
+    }
+}
+
+// Only for Food & Dining charges. Ones no rule places count as eating out, which is all the
+// category held before groceries were split out.
+pub fn food_kind(transaction: &Transaction) -> Option<FoodKind> {
+    if transaction_category(transaction) != "Food & Dining" {
+        return None;
+    }
+    Some(mcc_kind(transaction)
+        .or_else(|| description_kind(&transaction.description))
+        .unwrap_or(FoodKind::Dining))
+}
+
+fn share(groceries: f64, dining: f64) -> f64 {
+    if groceries + dining > 0.0 { dining / (groceries + dining) * 100.0 } else { 0.0 }
+}
+
+fn totals<'a>(transactions: impl Iterator<Item = &'a Transaction>) -> BTreeMap<String, (f64, f64)> {
+    let mut months: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+    for tx in transactions {
+        let (Some(kind), Some(month)) = (food_kind(tx), month_key(&tx.date)) else {
+            continue;
+        };
+        let entry = months.entry(month).or_insert((0.0, 0.0));
+        match kind {
+            FoodKind::Groceries => entry.0 += tx.amount,
+            FoodKind::Dining => entry.1 += tx.amount,
+        }
+    }
+    months
+}
+
+pub fn food_report(transactions: &[Transaction], history: &[&StoredStatement]) -> Option<FoodReport> {
+    let (mut groceries, mut dining) = (0.0, 0.0);
+    for tx in transactions {
+        match food_kind(tx) {
+            Some(FoodKind::Groceries) => groceries += tx.amount,
+            Some(FoodKind::Dining) => dining += tx.amount,
+            None => {}
+        }
+    }
+    if groceries + dining <= 0.0 {
+        return None;
+    }
+
+    let mut months = totals(history.iter().flat_map(|s| s.transactions.iter()));
+    let (previous_groceries, previous_dining) = months.values()
+        .fold((0.0, 0.0), |(g, d), (groceries, dining)| (g + groceries, d + dining));
+    let previous_dining_share = (previous_groceries + previous_dining > 0.0)
+        .then(|| share(previous_groceries, previous_dining));
+
+    for (month, (month_groceries, month_dining)) in totals(transactions.iter()) {
+        let entry = months.entry(month).or_insert((0.0, 0.0));
+        entry.0 += month_groceries;
+        entry.1 += month_dining;
+    }
+
+    Some(FoodReport {
+        groceries,
+        dining,
+        dining_share: share(groceries, dining),
+        previous_dining_share,
+        months: months.into_iter()
+            .map(|(month, (groceries, dining))| FoodMonth { month, groceries, dining, dining_share: share(groceries, dining) })
+            .collect(),
+    })
+}
+
+pub fn dining_insight(report: &FoodReport) -> Option<Insight> {
+    let previous = report.previous_dining_share?;
+    let rise = report.dining_share - previous;
+    if rise < SHARE_RISE_POINTS {
+        return None;
+    }
+
+    Some(Insight::new(
+        InsightKind::DiningOut,
+        Severity::Notice,
+        "food.dining_share_up",
+        format!("Eating out took {:.0}% of your food spending, up from {:.0}% before. That's ${:.2} at restaurants against ${:.2} on groceries",
+                report.dining_share, previous, report.dining, report.groceries),
+    )
+    .with_amount(report.dining)
+    .with_category("Food & Dining")
+    .with_param("share", format!("{:.0}", report.dining_share))
+    .with_param("previous", format!("{:.0}", previous))
+    .with_param("dining", format!("{:.2}", report.dining))
+    .with_param("groceries", format!("{:.2}", report.groceries)))
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::history::StoredStatement;
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{month_key, monthly_factor, Transaction};
+
+// Station brands, matched as whole words so "BP" doesn't match inside another name
+const STATION_BRANDS: &[&str] = &[
+    "shell", "chevron", "exxon", "exxonmobil", "mobil", "bp", "sunoco", "valero", "citgo",
+    "marathon", "speedway", "arco", "texaco", "conoco", "phillips", "sinclair", "quiktrip",
+    "racetrac", "wawa", "sheetz", "casey's", "holiday", "kwik",
+];
+const FUEL_WORDS: &[&str] = &["fuel", "gas", "gasoline", "petrol", "diesel"];
+// Utility bills that say "gas" but aren't a fill-up
+const UTILITY_WORDS: &[&str] = &["electric", "utility", "utilities", "natural", "energy", "power"];
+// How far the monthly run rate can move before it's worth mentioning
+const DRIFT_THRESHOLD_PERCENT: f64 = 15.0;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct FuelMonth {
+    // "2024-01"
+    pub month: String,
+    pub total: f64,
+    pub fill_ups: usize,
+    pub average_fill_up: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct VehicleReport {
+    pub fill_ups: usize,
+    pub total: f64,
+    pub average_fill_up: f64,
+    // This statement scaled to an average month
+    pub monthly_fuel: f64,
+    pub fill_ups_per_month: f64,
+    // Averages over the months before this statement, once there are any
+    pub baseline_monthly_fuel: Option<f64>,
+    pub baseline_fill_ups_per_month: Option<f64>,
+    pub baseline_average_fill_up: Option<f64>,
+    // monthly_fuel less the baseline; positive when fuel is costing more than usual
+    pub budget_drift: Option<f64>,
+    // Every month with fuel, this statement included, oldest first
+    pub months: Vec<FuelMonth>,
+}
+
+pub fn is_fuel(transaction: &Transaction) -> bool {
+    let description = transaction.description.to_lowercase();
+    let words: Vec<&str> = description.split(|c: char| !c.is_alphanumeric() && c != '\'').collect();
+    if UTILITY_WORDS.iter().any(|word| words.contains(word)) {
+        return false;
+    }
+    STATION_BRANDS.iter().chain(FUEL_WORDS).any(|word| words.contains(word))
+}
+
+fn by_month<'a>(transactions: impl Iterator<Item = &'a Transaction>) -> BTreeMap<String, (f64, usize)> {
+    let mut months: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+    for tx in transactions.filter(|t| is_fuel(t)) {
+        if let Some(month) = month_key(&tx.date) {
+            let entry = months.entry(month).or_insert((0.0, 0));
+            entry.0 += tx.amount;
+            entry.1 += 1;
+        }
+    }
+    months
+}
+
+pub fn vehicle_report(transactions: &[Transaction], history: &[&StoredStatement]) -> Option<VehicleReport> {
+    let fuel: Vec<&Transaction> = transactions.iter().filter(|t| is_fuel(t)).collect();
+    if fuel.is_empty() {
+        return None;
+    }
+    let total: f64 = fuel.iter().map(|t| t.amount).sum();
+    let factor = monthly_factor(transactions);
+
+    // Months the history covers at all, so a month without fuel counts as zero, not as missing
+    let earlier: Vec<&Transaction> = history.iter().flat_map(|s| s.transactions.iter()).collect();
+    let covered: BTreeSet<String> = earlier.iter().filter_map(|t| month_key(&t.date)).collect();
+    let earlier_fuel = by_month(earlier.iter().copied());
+    let baseline = (!covered.is_empty()).then(|| {
+        let months = covered.len() as f64;
+        let spend: f64 = earlier_fuel.values().map(|(total, _)| total).sum();
+        let fill_ups: usize = earlier_fuel.values().map(|(_, count)| count).sum();
+        (spend / months, fill_ups as f64 / months, (fill_ups > 0).then(|| spend / fill_ups as f64))
+    });
+
+    let monthly_fuel = total * factor;
+    let mut months = earlier_fuel;
+    for (month, (month_total, count)) in by_month(transactions.iter()) {
+        let entry = months.entry(month).or_insert((0.0, 0));
+        entry.0 += month_total;
+        entry.1 += count;
+    }
+
+    Some(VehicleReport {
+        fill_ups: fuel.len(),
+        total,
+        average_fill_up: total / fuel.len() as f64,
+        monthly_fuel,
+        fill_ups_per_month: fuel.len() as f64 * factor,
+        baseline_monthly_fuel: baseline.map(|(spend, _, _)| spend),
+        baseline_fill_ups_per_month: baseline.map(|(_, fill_ups, _)| fill_ups),
+        baseline_average_fill_up: baseline.and_then(|(_, _, average)| average),
+        budget_drift: baseline.map(|(spend, _, _)| monthly_fuel - spend),
+        months: months.into_iter()
+            .map(|(month, (total, fill_ups))| FuelMonth { month, total, fill_ups, average_fill_up: total / fill_ups as f64 })
+            .collect(),
+    })
+}
+
+// Only when fuel is running well above its usual level; a cheaper month needs no warning
+pub fn fuel_insight(report: &VehicleReport) -> Option<Insight> {
+    let baseline = report.baseline_monthly_fuel.filter(|baseline| *baseline > 0.0)?;
+    let drift = report.budget_drift?;
+    if drift / baseline * 100.0 < DRIFT_THRESHOLD_PERCENT {
+        return None;
+    }
+    let usual_fill_ups = report.baseline_fill_ups_per_month.unwrap_or(0.0);
+
+    Some(Insight::new(
+        InsightKind::Fuel,
+        Severity::Notice,
+        "fuel.drift_up",
+        format!("Fuel is running ${:.0} a month, ${:.0} above your usual ${:.0}. You're filling up {:.1} times a month against {:.1} before",
+                report.monthly_fuel, drift, baseline, report.fill_ups_per_month, usual_fill_ups),
+    )
+    .with_amount(drift)
+    .with_param("monthly", format!("{:.0}", report.monthly_fuel))
+    .with_param("drift", format!("{:.0}", drift))
+    .with_param("baseline", format!("{:.0}", baseline))
+    .with_param("fill_ups", format!("{:.1}", report.fill_ups_per_month))
+    .with_param("usual_fill_ups", format!("{:.1}", usual_fill_ups)))
+}
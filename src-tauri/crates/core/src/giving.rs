@@ -0,0 +1,45 @@
+use crate::{extract_merchant_name, Transaction};
+
+// Tags a user can put on a charge to count it as a donation, or to keep a false match out
+pub const DONATION_TAG: &str = "donation";
+pub const NOT_DONATION_TAG: &str = "not-donation";
+
+// Words that only show up in descriptions of gifts
+const KEYWORDS: &[&str] = &["donation", "donate", "charity", "charitable", "nonprofit", "non-profit", "tithe", "fundraiser"];
+
+// Large nonprofits whose charges carry their name but none of the keywords
+const NONPROFITS: &[&str] = &[
+    "red cross", "unicef", "st jude", "salvation army", "habitat for humanity", "doctors without borders",
+    "msf usa", "wikimedia", "world wildlife", "wwf", "feeding america", "save the children", "oxfam",
+    "american cancer society", "heifer international", "direct relief", "charity water", "kiva",
+    "givedirectly", "amnesty international", "planned parenthood", "aclu", "npr", "pbs",
+];
+
+// The user's tags decide first; otherwise the description has to name a gift or a known nonprofit
+pub fn is_donation(transaction: &Transaction) -> bool {
+    if transaction.tags.iter().any(|tag| tag == NOT_DONATION_TAG) {
+        return false;
+    }
+    if transaction.tags.iter().any(|tag| tag == DONATION_TAG) {
+        return true;
+    }
+
+    let description = transaction.description.to_lowercase();
+    let words: Vec<&str> = description.split(|c: char| !c.is_alphanumeric() && c != '-').collect();
+    KEYWORDS.iter().any(|keyword| words.contains(keyword))
+        || NONPROFITS.iter().any(|name| contains_phrase(&description, name))
+}
+
+// On word boundaries, so "pbs" doesn't match inside another word
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    text.match_indices(phrase).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + phrase.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+// The organization a gift went to, as it appears on the statement
+pub fn recipient(transaction: &Transaction) -> String {
+    extract_merchant_name(&transaction.description)
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::i18n;
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{transaction_category, monthly_factor, Transaction};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GoalKind {
+    // "Reduce dining by $150/mo" - measured against the average when the goal was set
+    ReduceCategory { category: String, amount: f64 },
+    // "Keep dining under $300/mo"
+    CategoryUnder { category: String, limit: f64 },
+    // "Keep total under $2,500/mo"
+    TotalUnder { limit: f64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Goal {
+    pub id: u64,
+    pub name: String,
+    pub kind: GoalKind,
+    pub baseline: Option<f64>,
+    pub created_at: String,
+    #[serde(default)]
+    pub progress: Vec<GoalProgress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GoalProgress {
+    pub statement_id: u64,
+    pub evaluated_at: String,
+    pub actual: f64,
+    pub target: f64,
+    pub met: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct GoalStatus {
+    pub goal_id: u64,
+    pub name: String,
+    pub target: f64,
+    pub actual: f64,
+    pub met: bool,
+    pub remaining: f64,
+    pub trend: String,
+}
+
+pub fn category_spend(transactions: &[Transaction], category: &str) -> f64 {
+    transactions.iter()
+        .filter(|t| transaction_category(t).eq_ignore_ascii_case(category))
+        .map(|t| t.amount)
+        .sum()
+}
+
+// Monthly spend the goal measures
+fn measure(kind: &GoalKind, transactions: &[Transaction]) -> f64 {
+    let spend = match kind {
+        GoalKind::ReduceCategory { category, .. } | GoalKind::CategoryUnder { category, .. } => {
+            category_spend(transactions, category)
+        }
+        GoalKind::TotalUnder { .. } => transactions.iter().map(|t| t.amount).sum(),
+    };
+    spend * monthly_factor(transactions)
+}
+
+fn target(goal: &Goal) -> f64 {
+    match &goal.kind {
+        GoalKind::ReduceCategory { amount, .. } => (goal.baseline.unwrap_or(0.0) - amount).max(0.0),
+        GoalKind::CategoryUnder { limit, .. } | GoalKind::TotalUnder { limit } => *limit,
+    }
+}
+
+fn trend(progress: &[GoalProgress]) -> String {
+    match progress {
+        [.., previous, latest] if latest.actual < previous.actual => "improving",
+        [.., previous, latest] if latest.actual > previous.actual => "worsening",
+        [_, _, ..] => "steady",
+        _ => "new",
+    }.to_string()
+}
+
+// Records progress against every goal for a newly imported statement
+pub fn evaluate_goals(goals: &mut [Goal], statement_id: u64, transactions: &[Transaction]) -> Vec<GoalStatus> {
+    let evaluated_at = chrono::Utc::now().to_rfc3339();
+
+    goals.iter_mut().map(|goal| {
+        let actual = measure(&goal.kind, transactions);
+        let target = target(goal);
+        let met = actual <= target;
+
+        // Re-importing a statement replaces its earlier measurement
+        goal.progress.retain(|p| p.statement_id != statement_id);
+        goal.progress.push(GoalProgress {
+            statement_id,
+            evaluated_at: evaluated_at.clone(),
+            actual,
+            target,
+            met,
+        });
+
+        GoalStatus {
+            goal_id: goal.id,
+            name: goal.name.clone(),
+            target,
+            actual,
+            met,
+            remaining: target - actual,
+            trend: trend(&goal.progress),
+        }
+    }).collect()
+}
+
+// Where each goal stood as of its most recent measurement
+pub fn latest_statuses(goals: &[Goal]) -> Vec<GoalStatus> {
+    goals.iter().filter_map(|goal| {
+        let latest = goal.progress.last()?;
+        Some(GoalStatus {
+            goal_id: goal.id,
+            name: goal.name.clone(),
+            target: latest.target,
+            actual: latest.actual,
+            met: latest.met,
+            remaining: latest.target - latest.actual,
+            trend: trend(&goal.progress),
+        })
+    }).collect()
+}
+
+// Latest status of the goals that target one category
+pub fn category_statuses(goals: &[Goal], category: &str) -> Vec<GoalStatus> {
+    let targeting: Vec<Goal> = goals.iter()
+        .filter(|goal| match &goal.kind {
+            GoalKind::ReduceCategory { category: c, .. } | GoalKind::CategoryUnder { category: c, .. } => c.eq_ignore_ascii_case(category),
+            GoalKind::TotalUnder { .. } => false,
+        })
+        .cloned()
+        .collect();
+    latest_statuses(&targeting)
+}
+
+pub fn goal_insights(statuses: &[GoalStatus]) -> Vec<Insight> {
+    statuses.iter().map(|status| {
+        let insight = if status.met {
+            Insight::new(
+                InsightKind::Goal,
+                Severity::Info,
+                "goal.on_track",
+                format!("Goal \"{}\" is on track: ${:.2} of ${:.2} this month", status.name, status.actual, status.target),
+            )
+        } else {
+            Insight::new(
+                InsightKind::Goal,
+                Severity::Warning,
+                "goal.off_track",
+                format!("Goal \"{}\" is off track: ${:.2} over the ${:.2} target ({})",
+                        status.name, -status.remaining, status.target, status.trend),
+            )
+            .with_param("over", format!("{:.2}", -status.remaining))
+        };
+        insight
+            .with_amount(status.actual)
+            .with_param("name", &status.name)
+            .with_param("actual", format!("{:.2}", status.actual))
+            .with_param("target", format!("{:.2}", status.target))
+            .with_param("trend", &status.trend)
+    }).collect()
+}
+
+pub fn validate(kind: &GoalKind, locale: &str) -> Result<(), String> {
+    let value = match kind {
+        GoalKind::ReduceCategory { amount, .. } => *amount,
+        GoalKind::CategoryUnder { limit, .. } | GoalKind::TotalUnder { limit } => *limit,
+    };
+    if !value.is_finite() || value <= 0.0 {
+        return Err(i18n::error(locale, "error.goal_amount_invalid"));
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::interner::{CompactHistory, Symbol};
+use crate::history::StoredStatement;
+use crate::{merchant_totals, MerchantTotal, Transaction};
+
+// New merchants below this are usually one-off coffee runs, not worth surfacing
+const NEW_MERCHANT_THRESHOLD: f64 = 50.0;
+// Percent growth off a tiny base ($2 -> $10 = +400%) is noise
+const MIN_BASELINE_FOR_PERCENT: f64 = 20.0;
+const MAX_RESULTS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantGrowthReport {
+    pub statements_compared: usize,
+    pub by_amount: Vec<MerchantGrowth>,
+    pub by_percent: Vec<MerchantGrowth>,
+    pub new_merchants: Vec<MerchantTotal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MerchantGrowth {
+    pub merchant: String,
+    pub previous_average: f64,
+    pub current: f64,
+    pub change: f64,
+    pub change_percent: f64,
+}
+
+pub fn compare_with_history(transactions: &[Transaction], history: &[&StoredStatement]) -> Option<MerchantGrowthReport> {
+    if history.is_empty() {
+        return None;
+    }
+
+    let current = merchant_totals(transactions);
+    // History can run to years of statements, so it's totalled by interned merchant
+    let compact = CompactHistory::from_statements(history.iter().copied());
+    let mut previous: HashMap<Symbol, f64> = HashMap::new();
+    for tx in &compact.transactions {
+        *previous.entry(tx.merchant).or_insert(0.0) += tx.amount;
+    }
+    let statement_count = history.len() as f64;
+
+    let mut growth: Vec<MerchantGrowth> = Vec::new();
+    let mut new_merchants: Vec<MerchantTotal> = Vec::new();
+
+    for merchant_total in current {
+        match compact.merchants.get(&merchant_total.merchant).and_then(|symbol| previous.get(&symbol)) {
+            Some(previous_total) => {
+                // Average per statement, so one big month in the past doesn't dominate
+                let previous_average = previous_total / statement_count;
+                let change = merchant_total.total - previous_average;
+                if change <= 0.0 {
+                    continue;
+                }
+                let change_percent = if previous_average > 0.0 {
+                    (change / previous_average) * 100.0
+                } else {
+                    0.0
+                };
+                growth.push(MerchantGrowth {
+                    merchant: merchant_total.merchant,
+                    previous_average,
+                    current: merchant_total.total,
+                    change,
+                    change_percent,
+                });
+            }
+            None if merchant_total.total >= NEW_MERCHANT_THRESHOLD => {
+                new_merchants.push(merchant_total);
+            }
+            None => {}
+        }
+    }
+
+    let mut by_amount = growth.clone();
+    by_amount.sort_by(|a, b| b.change.total_cmp(&a.change));
+    by_amount.truncate(MAX_RESULTS);
+
+    let mut by_percent: Vec<MerchantGrowth> = growth.into_iter()
+        .filter(|g| g.previous_average >= MIN_BASELINE_FOR_PERCENT)
+        .collect();
+    by_percent.sort_by(|a, b| b.change_percent.total_cmp(&a.change_percent));
+    by_percent.truncate(MAX_RESULTS);
+
+    new_merchants.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    Some(MerchantGrowthReport {
+        statements_compared: history.len(),
+        by_amount,
+        by_percent,
+        new_merchants,
+    })
+}
+
+pub fn growth_insights(report: &MerchantGrowthReport) -> Vec<Insight> {
+    let mut insights = Vec::new();
+
+    if let Some(top) = report.by_amount.first() {
+        insights.push(Insight::new(
+            InsightKind::MerchantGrowth,
+            Severity::Notice,
+            "growth.top_merchant",
+            format!("Spending at {} grew the most: ${:.2} vs. your usual ${:.2} (+${:.2})",
+                    top.merchant, top.current, top.previous_average, top.change),
+        )
+        .with_amount(top.change)
+        .with_merchant(&top.merchant)
+        .with_param("merchant", &top.merchant)
+        .with_param("current", format!("{:.2}", top.current))
+        .with_param("previous", format!("{:.2}", top.previous_average))
+        .with_param("change", format!("{:.2}", top.change)));
+    }
+
+    if !report.new_merchants.is_empty() {
+        let count = report.new_merchants.len();
+        let total: f64 = report.new_merchants.iter().map(|m| m.total).sum();
+        let mut insight = Insight::new(
+            InsightKind::NewMerchants,
+            Severity::Info,
+            "growth.new_merchants",
+            format!("{} new {} this statement account for ${:.2}",
+                    count,
+                    if count == 1 { "merchant" } else { "merchants" },
+                    total),
+        )
+        .with_amount(total)
+        .with_param("count", count)
+        .with_param("total", format!("{:.2}", total));
+        for merchant in &report.new_merchants {
+            insight = insight.with_merchant(&merchant.merchant);
+        }
+        insights.push(insight);
+    }
+
+    insights
+}
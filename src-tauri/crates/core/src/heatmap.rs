@@ -0,0 +1,72 @@
+use chrono::{Datelike, Timelike};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{parse_date, parse_timestamp, Transaction};
+
+const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SpendingHeatmap {
+    pub days: Vec<String>,
+    pub day_counts: Vec<u32>,
+    pub day_amounts: Vec<f64>,
+    // Only present when the export carries times of day
+    pub hourly: Option<HourlyMatrix>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct HourlyMatrix {
+    // Indexed [day][hour], Monday first
+    pub counts: Vec<Vec<u32>>,
+    pub amounts: Vec<Vec<f64>>,
+    pub timed_transactions: usize,
+}
+
+pub fn build_heatmap(transactions: &[Transaction]) -> Option<SpendingHeatmap> {
+    let mut day_counts = vec![0u32; 7];
+    let mut day_amounts = vec![0.0; 7];
+    let mut hour_counts = vec![vec![0u32; 24]; 7];
+    let mut hour_amounts = vec![vec![0.0; 24]; 7];
+    let mut dated = 0;
+    let mut timed = 0;
+
+    for tx in transactions {
+        if let Some(timestamp) = parse_timestamp(&tx.date, tx.time.as_deref()) {
+            let day = timestamp.weekday().num_days_from_monday() as usize;
+            let hour = timestamp.hour() as usize;
+            day_counts[day] += 1;
+            day_amounts[day] += tx.amount;
+            hour_counts[day][hour] += 1;
+            hour_amounts[day][hour] += tx.amount;
+            dated += 1;
+            timed += 1;
+        } else if let Some(date) = parse_date(&tx.date) {
+            let day = date.weekday().num_days_from_monday() as usize;
+            day_counts[day] += 1;
+            day_amounts[day] += tx.amount;
+            dated += 1;
+        }
+    }
+
+    if dated == 0 {
+        return None;
+    }
+
+    let hourly = if timed > 0 {
+        Some(HourlyMatrix {
+            counts: hour_counts,
+            amounts: hour_amounts,
+            timed_transactions: timed,
+        })
+    } else {
+        None
+    };
+
+    Some(SpendingHeatmap {
+        days: DAYS.iter().map(|d| d.to_string()).collect(),
+        day_counts,
+        day_amounts,
+        hourly,
+    })
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::statement_meta::StatementMeta;
+use crate::Transaction;
+
+// An imported statement as kept in the history. Analytics that compare against earlier
+// statements take these; credit-analyzer-store is what persists them.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StoredStatement {
+    pub id: u64,
+    pub source_path: String,
+    pub imported_at: String,
+    pub transactions: Vec<Transaction>,
+    #[serde(default)]
+    pub meta: Option<StatementMeta>,
+    // The same on every synced device, where `id` isn't; empty until sync assigns one
+    #[serde(default)]
+    pub sync_id: String,
+}
@@ -0,0 +1,75 @@
+use scraper::{ElementRef, Html, Selector};
+
+// A table needs a date and an amount column to be the transactions rather than a summary box
+const DATE_WORDS: &[&str] = &["date", "posted"];
+const AMOUNT_WORDS: &[&str] = &["amount", "debit", "credit", "charge"];
+
+// Saved "print page" statements. The extension is the usual tell; a file saved without one is
+// recognised by its markup.
+pub fn is_html(file_name: &str, content: &str) -> bool {
+    let name = file_name.to_lowercase();
+    if name.ends_with(".html") || name.ends_with(".htm") {
+        return true;
+    }
+    let start = content.trim_start().get(..100).unwrap_or(content.trim_start()).to_lowercase();
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+fn selector(css: &str) -> Result<Selector, String> {
+    Selector::parse(css).map_err(|e| format!("Invalid selector {}: {}", css, e))
+}
+
+// A cell's text with the whitespace of the markup collapsed
+fn cell_text(cell: ElementRef) -> String {
+    cell.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn rows(table: ElementRef, row_selector: &Selector, cell_selector: &Selector) -> Vec<Vec<String>> {
+    table.select(row_selector)
+        .map(|row| row.select(cell_selector).map(cell_text).collect::<Vec<String>>())
+        .filter(|cells| cells.iter().any(|cell| !cell.is_empty()))
+        .collect()
+}
+
+fn is_transaction_header(cells: &[String]) -> bool {
+    let lower: Vec<String> = cells.iter().map(|cell| cell.to_lowercase()).collect();
+    let names = |words: &[&str]| lower.iter().any(|cell| words.iter().any(|word| cell.contains(word)));
+    names(DATE_WORDS) && names(AMOUNT_WORDS)
+}
+
+// Every transaction table in the page as one CSV, header first. Statements that split
+// transactions across several tables (one per card member, or per page) repeat the header, so
+// only the first copy is kept.
+pub fn transactions_csv(content: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let document = Html::parse_document(content);
+    let (table_selector, row_selector, cell_selector) = (selector("table")?, selector("tr")?, selector("th, td")?);
+    let mut header: Option<Vec<String>> = None;
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+
+    for table in document.select(&table_selector) {
+        let rows = rows(table, &row_selector, &cell_selector);
+        let Some(start) = rows.iter().position(|cells| is_transaction_header(cells)) else {
+            continue;
+        };
+        if header.is_none() {
+            writer.write_record(&rows[start])?;
+            header = Some(rows[start].clone());
+        }
+        for cells in &rows[start + 1..] {
+            if Some(cells) != header.as_ref() {
+                writer.write_record(cells)?;
+            }
+        }
+    }
+
+    if header.is_none() {
+        return Err("No transaction table found in this page".into());
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+// The page's visible text, for reading the due date and balances around the tables
+pub fn visible_text(content: &str) -> String {
+    let document = Html::parse_document(content);
+    document.root_element().text().collect::<Vec<_>>().join("\n")
+}
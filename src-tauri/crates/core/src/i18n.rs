@@ -0,0 +1,607 @@
+use crate::insights::Insight;
+
+pub const DEFAULT_LOCALE: &str = "en";
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "es", "hi", "de"];
+
+// Insight messages are rendered in English where they're generated (so plurals read
+// naturally), so English only needs entries for errors and single words.
+const EN: &[(&str, &str)] = &[
+    ("trend.improving", "improving"),
+    ("trend.worsening", "worsening"),
+    ("trend.steady", "steady"),
+    ("trend.new", "new"),
+    ("service.music_streaming", "music streaming"),
+    ("service.video_streaming", "video streaming"),
+    ("service.cloud_storage", "cloud storage"),
+    ("service.news", "news"),
+    ("service.fitness", "fitness"),
+    ("service.vpn", "VPN"),
+    ("service.password_manager", "password manager"),
+    ("service.gaming", "gaming"),
+    ("error.file_not_found", "File not found"),
+    ("error.parse_failed", "Could not read this file: {reason}"),
+    ("error.no_transactions", "No transactions found in this file"),
+    ("error.statement_not_found", "Statement {id} not found"),
+    ("error.transaction_not_found", "Transaction {index} not found in statement {id}"),
+    ("error.trash_not_found", "Deleted transaction {id} not found; deleted transactions are kept for 30 days"),
+    ("error.entry_date_invalid", "Could not read the date {date}; use a date like 2024-01-31"),
+    ("error.entry_description_empty", "Description can't be empty"),
+    ("error.entry_amount_invalid", "Amount must be a positive number"),
+    ("error.ocr_unavailable", "Reading receipt images needs Tesseract OCR installed"),
+    ("error.split_total_mismatch", "Split amounts must be positive and add up to {amount}"),
+    ("error.no_statements", "No statements analyzed yet"),
+    ("error.reduction_out_of_range", "Reduction must be between 0 and 100 percent"),
+    ("error.category_empty", "No spending found in category {category}"),
+    ("error.period_invalid", "Invalid period {period}; use a month like 2024-01 or a year like 2024"),
+    ("error.bulk_filter_empty", "Choose a merchant, category, tag or period to edit"),
+    ("error.bulk_no_matches", "No transactions match this filter"),
+    ("error.bulk_nothing_to_undo", "There's no bulk edit to undo"),
+    ("error.merchant_not_found", "No transactions found for merchant {merchant}"),
+    ("error.no_discretionary_spending", "No spending in categories classed as wants"),
+    ("error.goal_amount_invalid", "Goal amount must be a positive number"),
+    ("error.goal_needs_history", "Analyze at least one statement before setting a reduction goal"),
+    ("error.goal_not_found", "Goal {id} not found"),
+    ("error.card_not_found", "Card {id} not found"),
+    ("error.card_name_empty", "Card name can't be empty"),
+    ("error.card_fee_invalid", "Annual fee must be zero or more"),
+    ("error.reward_rate_invalid", "Reward rate must be between 0 and 100 percent"),
+    ("error.benefit_invalid", "Benefit \"{name}\" needs a name and a positive amount"),
+    ("error.benefit_unmatched", "Benefit \"{name}\" needs merchants or a category to match purchases against"),
+    ("error.threshold_invalid", "Micro-spending threshold must be a positive amount"),
+    ("error.round_up_invalid", "Round-up increment must be at least $0.01"),
+    ("error.locale_unsupported", "Unsupported language: {locale}"),
+    ("error.secret_empty", "Secret value can't be empty"),
+    ("error.keychain_unavailable", "Could not access the system keychain: {reason}"),
+    ("error.pdf_password_required", "This PDF is password-protected. Enter its password to continue"),
+    ("error.pdf_password_invalid", "The password for this PDF is incorrect"),
+    ("error.columns_ambiguous", "Could not tell which column holds the {column}. Set up a bank profile for this file to say which is which"),
+    ("column.date", "date"),
+    ("column.description", "description"),
+    ("column.amount", "amount"),
+    ("column.debit_credit", "money out and money in"),
+    ("column.other", "data"),
+    ("error.reminder_days_invalid", "Reminder lead time must be between 0 and 28 days"),
+    ("error.income_invalid", "Monthly income must be a positive amount"),
+    ("error.profile_name_empty", "Bank profile needs a name"),
+    ("error.profile_columns_missing", "Bank profile needs date and description columns, and an amount column or both debit and credit columns"),
+    ("error.profile_no_header", "Could not find a header row in this file"),
+    ("error.profile_not_found", "Bank profile {name} not found"),
+    ("error.profile_invalid_file", "This file is not a bank profile"),
+    ("error.profile_sample_mismatch", "The sample file does not have the columns bank profile {name} expects"),
+    ("error.profile_sample_failed", "The bank profile could not read the sample file: {reason}"),
+    ("error.analysis_invalid_file", "This file is not a saved analysis"),
+    ("error.analysis_newer_version", "This analysis was saved by a newer version of the app (format {version}); update the app to open it"),
+    ("error.period_start_day_invalid", "Period start day must be between 1 and 28"),
+    ("error.emission_factor_invalid", "Emission factor for {category} must be zero or more"),
+    ("error.no_donations", "No donations found for {year}"),
+    ("error.business_rule_empty", "Business expense rules can't be empty"),
+    ("error.no_business_expenses", "No business expenses found for this period"),
+    ("error.shared_partner_empty", "Name who the expense is shared with"),
+    ("error.shared_share_invalid", "Partner's share must be between 0 and 100 percent"),
+    ("error.no_shared_expenses", "No shared expenses found for this period"),
+    ("error.home_state_invalid", "Home state must be a two-letter code such as NY"),
+    ("error.retention_invalid", "Originals must be kept for at least one day"),
+    ("error.snapshot_name_empty", "Give the snapshot a name"),
+    ("error.snapshot_not_found", "That snapshot no longer exists"),
+    ("error.shortcut_invalid", "{shortcut} isn't a key combination a shortcut can use"),
+    ("error.shortcut_unavailable", "{shortcut} is already used by another app"),
+    ("error.statements_folder_required", "Choose a statements folder to import from in the background"),
+    ("error.statements_folder_missing", "The statements folder {folder} does not exist"),
+    ("error.store_newer_version", "Your data was saved by a newer version of the app (data version {version}); update the app to open it"),
+    ("error.update_wrong_channel", "Version {version} is from a different update channel"),
+    ("error.update_older_schema", "Version {version} cannot read data saved by this version"),
+    ("error.update_schema_unsupported", "Version {version} can no longer upgrade your data; install an earlier update first"),
+    ("error.config_invalid_file", "That file isn't a Credit Card Analyzer configuration"),
+    ("error.sync_not_built", "This build of the app does not include sync"),
+    ("error.sync_demo", "Sync is off while demo data is loaded"),
+    ("error.sync_folder_required", "Choose a folder to sync through"),
+    ("error.sync_folder_missing", "The sync folder {folder} does not exist"),
+    ("error.sync_key_missing", "Create a sync key on your first device and enter it on this one"),
+    ("error.sync_key_invalid", "The sync key should be 64 letters and digits, as shown on the device that created it"),
+    ("carbon.title", "Carbon footprint"),
+    ("reminder.title", "Credit card payment due"),
+    ("reminder.body", "{card}: ${amount} due {date} ({days} days left)"),
+    ("reminder.body_no_amount", "{card}: payment due {date} ({days} days left)"),
+    ("reminder.card_ending", "Card ending {last4}"),
+    ("reminder.card_unknown", "Your card"),
+    ("tray.spend", "{month}: ${amount} spent"),
+    ("tray.no_spend", "No spending imported yet"),
+    ("tray.budgets", "{met} of {total} budgets on track"),
+    ("tray.no_budgets", "No budgets set"),
+    ("tray.quick_import", "Quick import…"),
+    ("tray.open", "Open Credit Card Analyzer"),
+    ("tray.quit", "Quit"),
+    ("background.alerts_title", "New alerts"),
+    ("background.alerts_body", "{count} new alerts in statements imported from {folder}"),
+    ("update.migration_step", "Upgrade stored data from version {from} to {to}"),
+    ("window.ledger", "Ledger: {period}"),
+    ("window.ledger_all", "Ledger: all transactions"),
+    ("window.comparison", "Statement {a} vs {b}"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("summary.analyzed", "Se analizaron {count} transacciones de {file}"),
+    ("summary.top_category", "Tu mayor categoría de gasto es {category}, con el {percentage}% del total"),
+    ("micro_spending.summary", "Tienes {count} compras pequeñas (menos de ${threshold}) que suman ${total}, unos ${annual} al año a este ritmo"),
+    ("micro_spending.summary_led_by", "Tienes {count} compras pequeñas (menos de ${threshold}) que suman ${total}, unos ${annual} al año a este ritmo, encabezadas por {merchant} ({visits} visitas)"),
+    ("travel.spent_abroad", "Gastaste ${total} en el extranjero en {count} transacciones ({countries} países)"),
+    ("travel.fx_fees_paid", "Pagaste ${fees} en comisiones por transacciones en el extranjero ({rate}% del gasto en el extranjero); una tarjeta sin estas comisiones te lo habría ahorrado"),
+    ("travel.fx_fees_none", "No se encontraron comisiones por transacciones en el extranjero. Si tu tarjeta cobra la comisión habitual del {rate}%, revisa si hay ${estimate} facturados aparte; las tarjetas sin comisión la evitan"),
+    ("growth.top_merchant", "El gasto en {merchant} fue el que más creció: ${current} frente a tus ${previous} habituales (+${change})"),
+    ("growth.new_merchants", "{count} comercios nuevos en este estado de cuenta suman ${total}"),
+    ("concentration.pareto", "El {share}% de tus comercios ({merchants} de {total_merchants}) concentra el 80% de tu gasto"),
+    ("round_up.savings", "Redondear cada compra a múltiplos de ${increment} te habría permitido ahorrar ${total}, unos ${monthly} al mes o ${annual} al año"),
+    ("income.breakdown", "Tu gasto con tarjeta es el {spend}% de tus ingresos. Las necesidades se llevan el {needs}%, los deseos el {wants}% y queda un {savings}% para ahorro (objetivos 50/30/20)"),
+    ("carbon.footprint", "Los gastos de este estado de cuenta suponen unos {total} kg de CO2e. {category} es la mayor parte"),
+    ("carbon.footprint_up", "Los gastos de este estado de cuenta suponen unos {total} kg de CO2e, un {change}% más que el mes pasado. {category} es la mayor parte"),
+    ("carbon.footprint_down", "Los gastos de este estado de cuenta suponen unos {total} kg de CO2e, un {change}% menos que el mes pasado. {category} es la mayor parte"),
+    ("fuel.drift_up", "El combustible te está costando ${monthly} al mes, ${drift} más que tus ${baseline} habituales. Repostas {fill_ups} veces al mes frente a {usual_fill_ups} antes"),
+    ("food.dining_share_up", "Comer fuera se llevó el {share}% de tu gasto en comida, frente al {previous}% antes. Son ${dining} en restaurantes frente a ${groceries} en el supermercado"),
+    ("subscriptions.price_hike", "{merchant} pasó de ${from} a ${to} el {date}, ${annual} más al año (${total_annual} al año más que cuando empezaste)"),
+    ("subscriptions.trial_converted", "{merchant} te cobró ${amount} el {date}, alrededor de un mes después de una autorización de ${trial_amount}. Es probable que una prueba gratuita haya pasado a ser de pago; cancélala si no quieres conservarla"),
+    ("subscriptions.new_plan", "{merchant} te cobró ${amount} por primera vez el {date}. Si es el final de una prueba gratuita, comprueba que quieres conservarla"),
+    ("subscriptions.overlap", "Pagas {count} servicios {service} ({merchants}), ${monthly} al mes. Si conservas solo {keep} y cancelas {others}, ahorrarías unos ${savings} al año"),
+    ("goal.on_track", "La meta \"{name}\" va por buen camino: ${actual} de ${target} este mes"),
+    ("goal.off_track", "La meta \"{name}\" va mal: ${over} por encima del objetivo de ${target} ({trend})"),
+    ("cash_advance.cost", "Los adelantos en efectivo de ${advanced} costarán unos ${cost} en comisiones e intereses, una TAE efectiva del {apr}%. Los intereses se generan de inmediato, así que págalos primero"),
+    ("cash_advance.fees_only", "Pagaste ${fees} en comisiones por adelantos en efectivo este periodo"),
+    ("tip.spending_alerts", "Considera configurar alertas de gasto para tus categorías principales"),
+    ("demo.active", "Se muestran datos de demostración generados; tus estados de cuenta no se han modificado"),
+    ("trend.improving", "mejorando"),
+    ("trend.worsening", "empeorando"),
+    ("trend.steady", "estable"),
+    ("trend.new", "nueva"),
+    ("service.music_streaming", "de música en streaming"),
+    ("service.video_streaming", "de vídeo en streaming"),
+    ("service.cloud_storage", "de almacenamiento en la nube"),
+    ("service.news", "de noticias"),
+    ("service.fitness", "de fitness"),
+    ("service.vpn", "de VPN"),
+    ("service.password_manager", "de gestión de contraseñas"),
+    ("service.gaming", "de videojuegos"),
+    ("error.file_not_found", "Archivo no encontrado"),
+    ("error.parse_failed", "No se pudo leer este archivo: {reason}"),
+    ("error.no_transactions", "No se encontraron transacciones en este archivo"),
+    ("error.statement_not_found", "No se encontró el estado de cuenta {id}"),
+    ("error.transaction_not_found", "No se encontró la transacción {index} en el estado de cuenta {id}"),
+    ("error.trash_not_found", "No se encontró la transacción eliminada {id}; las transacciones eliminadas se guardan 30 días"),
+    ("error.entry_date_invalid", "No se pudo leer la fecha {date}; use una fecha como 2024-01-31"),
+    ("error.entry_description_empty", "La descripción no puede estar vacía"),
+    ("error.entry_amount_invalid", "El importe debe ser un número positivo"),
+    ("error.ocr_unavailable", "Para leer imágenes de recibos hay que instalar Tesseract OCR"),
+    ("error.split_total_mismatch", "Los importes divididos deben ser positivos y sumar {amount}"),
+    ("error.no_statements", "Todavía no se ha analizado ningún estado de cuenta"),
+    ("error.reduction_out_of_range", "La reducción debe estar entre 0 y 100 por ciento"),
+    ("error.category_empty", "No hay gastos en la categoría {category}"),
+    ("error.period_invalid", "Periodo no válido {period}; usa un mes como 2024-01 o un año como 2024"),
+    ("error.bulk_filter_empty", "Elija un comercio, categoría, etiqueta o periodo para editar"),
+    ("error.bulk_no_matches", "Ninguna transacción coincide con este filtro"),
+    ("error.bulk_nothing_to_undo", "No hay ninguna edición masiva que deshacer"),
+    ("error.merchant_not_found", "No se encontraron transacciones del comercio {merchant}"),
+    ("error.no_discretionary_spending", "No hay gastos en categorías clasificadas como deseos"),
+    ("error.goal_amount_invalid", "El importe de la meta debe ser un número positivo"),
+    ("error.goal_needs_history", "Analiza al menos un estado de cuenta antes de fijar una meta de reducción"),
+    ("error.goal_not_found", "No se encontró la meta {id}"),
+    ("error.card_not_found", "No se encontró la tarjeta {id}"),
+    ("error.card_name_empty", "El nombre de la tarjeta no puede estar vacío"),
+    ("error.card_fee_invalid", "La cuota anual debe ser cero o más"),
+    ("error.reward_rate_invalid", "La tasa de recompensa debe estar entre 0 y 100 por ciento"),
+    ("error.benefit_invalid", "El beneficio \"{name}\" necesita un nombre y un importe positivo"),
+    ("error.benefit_unmatched", "El beneficio \"{name}\" necesita comercios o una categoría con los que comparar las compras"),
+    ("error.threshold_invalid", "El umbral de gastos pequeños debe ser un importe positivo"),
+    ("error.round_up_invalid", "El redondeo debe ser de al menos $0.01"),
+    ("error.locale_unsupported", "Idioma no compatible: {locale}"),
+    ("error.secret_empty", "El valor secreto no puede estar vacío"),
+    ("error.keychain_unavailable", "No se pudo acceder al llavero del sistema: {reason}"),
+    ("error.pdf_password_required", "Este PDF está protegido con contraseña. Introduce la contraseña para continuar"),
+    ("error.pdf_password_invalid", "La contraseña de este PDF es incorrecta"),
+    ("error.columns_ambiguous", "No se pudo saber qué columna contiene {column}. Configura un perfil bancario para este archivo para indicar cuál es cuál"),
+    ("column.date", "la fecha"),
+    ("column.description", "la descripción"),
+    ("column.amount", "el importe"),
+    ("column.debit_credit", "los cargos y los abonos"),
+    ("column.other", "los datos"),
+    ("error.reminder_days_invalid", "La antelación del recordatorio debe estar entre 0 y 28 días"),
+    ("error.income_invalid", "Los ingresos mensuales deben ser un importe positivo"),
+    ("error.profile_name_empty", "El perfil bancario necesita un nombre"),
+    ("error.profile_columns_missing", "El perfil bancario necesita columnas de fecha y descripción, y una columna de importe o columnas de cargo y abono"),
+    ("error.profile_no_header", "No se encontró una fila de encabezado en este archivo"),
+    ("error.profile_not_found", "No se encontró el perfil bancario {name}"),
+    ("error.profile_invalid_file", "Este archivo no es un perfil bancario"),
+    ("error.profile_sample_mismatch", "El archivo de muestra no tiene las columnas que espera el perfil bancario {name}"),
+    ("error.profile_sample_failed", "El perfil bancario no pudo leer el archivo de muestra: {reason}"),
+    ("error.analysis_invalid_file", "Este archivo no es un análisis guardado"),
+    ("error.analysis_newer_version", "Este análisis se guardó con una versión más reciente de la aplicación (formato {version}); actualiza la aplicación para abrirlo"),
+    ("error.period_start_day_invalid", "El día de inicio del periodo debe estar entre 1 y 28"),
+    ("error.emission_factor_invalid", "El factor de emisión de {category} debe ser cero o mayor"),
+    ("error.no_donations", "No se encontraron donativos en {year}"),
+    ("error.business_rule_empty", "Las reglas de gastos de empresa no pueden estar vacías"),
+    ("error.no_business_expenses", "No se encontraron gastos de empresa en este periodo"),
+    ("error.shared_partner_empty", "Indica con quién compartes el gasto"),
+    ("error.shared_share_invalid", "La parte de la otra persona debe estar entre 0 y 100 por ciento"),
+    ("error.no_shared_expenses", "No se encontraron gastos compartidos en este periodo"),
+    ("error.home_state_invalid", "El estado de residencia debe ser un código de dos letras como NY"),
+    ("error.retention_invalid", "Los originales deben conservarse al menos un día"),
+    ("error.snapshot_name_empty", "Ponle un nombre a la instantánea"),
+    ("error.snapshot_not_found", "Esa instantánea ya no existe"),
+    ("error.shortcut_invalid", "{shortcut} no es una combinación de teclas válida para un atajo"),
+    ("error.shortcut_unavailable", "Otra aplicación ya usa {shortcut}"),
+    ("error.statements_folder_required", "Elige una carpeta de extractos para importar en segundo plano"),
+    ("error.statements_folder_missing", "La carpeta de extractos {folder} no existe"),
+    ("error.store_newer_version", "Tus datos se guardaron con una versión más reciente de la aplicación (versión de datos {version}); actualiza la aplicación para abrirlos"),
+    ("error.update_wrong_channel", "La versión {version} es de otro canal de actualizaciones"),
+    ("error.update_older_schema", "La versión {version} no puede leer los datos guardados por esta versión"),
+    ("error.update_schema_unsupported", "La versión {version} ya no puede actualizar tus datos; instala antes una actualización anterior"),
+    ("error.config_invalid_file", "Ese archivo no es una configuración de Credit Card Analyzer"),
+    ("error.sync_not_built", "Esta versión de la aplicación no incluye sincronización"),
+    ("error.sync_demo", "La sincronización está desactivada mientras hay datos de demostración"),
+    ("error.sync_folder_required", "Elige una carpeta para sincronizar"),
+    ("error.sync_folder_missing", "La carpeta de sincronización {folder} no existe"),
+    ("error.sync_key_missing", "Crea una clave de sincronización en tu primer dispositivo e introdúcela en este"),
+    ("error.sync_key_invalid", "La clave de sincronización debe tener 64 letras y dígitos, como se muestra en el dispositivo que la creó"),
+    ("carbon.title", "Huella de carbono"),
+    ("reminder.title", "Vence el pago de la tarjeta"),
+    ("reminder.body", "{card}: ${amount} vence el {date} (quedan {days} días)"),
+    ("reminder.body_no_amount", "{card}: el pago vence el {date} (quedan {days} días)"),
+    ("reminder.card_ending", "Tarjeta terminada en {last4}"),
+    ("reminder.card_unknown", "Tu tarjeta"),
+    ("tray.spend", "{month}: ${amount} gastados"),
+    ("tray.no_spend", "Aún no hay gastos importados"),
+    ("tray.budgets", "{met} de {total} presupuestos en orden"),
+    ("tray.no_budgets", "No hay presupuestos"),
+    ("tray.quick_import", "Importación rápida…"),
+    ("tray.open", "Abrir Credit Card Analyzer"),
+    ("tray.quit", "Salir"),
+    ("background.alerts_title", "Nuevas alertas"),
+    ("background.alerts_body", "{count} alertas nuevas en extractos importados de {folder}"),
+    ("update.migration_step", "Actualizar los datos guardados de la versión {from} a la {to}"),
+    ("window.ledger", "Movimientos: {period}"),
+    ("window.ledger_all", "Movimientos: todas las transacciones"),
+    ("window.comparison", "Estado de cuenta {a} frente a {b}"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("summary.analyzed", "{count} Transaktionen aus {file} erfolgreich analysiert"),
+    ("summary.top_category", "Ihre größte Ausgabenkategorie ist {category} mit {percentage}% der Gesamtausgaben"),
+    ("micro_spending.summary", "Sie haben {count} Kleinbeträge (unter ${threshold}) von insgesamt ${total} – bei diesem Tempo etwa ${annual} pro Jahr"),
+    ("micro_spending.summary_led_by", "Sie haben {count} Kleinbeträge (unter ${threshold}) von insgesamt ${total} – bei diesem Tempo etwa ${annual} pro Jahr, angeführt von {merchant} ({visits} Besuche)"),
+    ("travel.spent_abroad", "Sie haben im Ausland ${total} in {count} Transaktionen ausgegeben ({countries} Länder)"),
+    ("travel.fx_fees_paid", "Sie haben ${fees} an Auslandseinsatzgebühren gezahlt ({rate}% der Auslandsausgaben) – eine Karte ohne Auslandseinsatzgebühr hätte das komplett gespart"),
+    ("travel.fx_fees_none", "Keine Auslandseinsatzgebühren gefunden. Falls Ihre Karte die üblichen {rate}% berechnet, prüfen Sie separat abgerechnete ${estimate}; Karten ohne Auslandsgebühr vermeiden das"),
+    ("growth.top_merchant", "Die Ausgaben bei {merchant} sind am stärksten gestiegen: ${current} statt der üblichen ${previous} (+${change})"),
+    ("growth.new_merchants", "{count} neue Händler in dieser Abrechnung machen ${total} aus"),
+    ("concentration.pareto", "{share}% Ihrer Händler ({merchants} von {total_merchants}) machen 80% Ihrer Ausgaben aus"),
+    ("round_up.savings", "Wenn Sie jeden Einkauf auf ${increment} aufrunden, hätten Sie ${total} gespart – etwa ${monthly} pro Monat oder ${annual} pro Jahr"),
+    ("income.breakdown", "Ihre Kartenausgaben betragen {spend}% Ihres Einkommens. Auf Bedarf entfallen {needs}%, auf Wünsche {wants}%, und {savings}% bleiben zum Sparen (Ziel 50/30/20)"),
+    ("carbon.footprint", "Die Ausgaben dieser Abrechnung entsprechen etwa {total} kg CO2e. {category} macht den größten Anteil aus"),
+    ("carbon.footprint_up", "Die Ausgaben dieser Abrechnung entsprechen etwa {total} kg CO2e, {change}% mehr als im Vormonat. {category} macht den größten Anteil aus"),
+    ("carbon.footprint_down", "Die Ausgaben dieser Abrechnung entsprechen etwa {total} kg CO2e, {change}% weniger als im Vormonat. {category} macht den größten Anteil aus"),
+    ("fuel.drift_up", "Kraftstoff kostet Sie derzeit ${monthly} im Monat, ${drift} mehr als die üblichen ${baseline}. Sie tanken {fill_ups}-mal im Monat statt bisher {usual_fill_ups}-mal"),
+    ("food.dining_share_up", "Auswärts essen machte {share}% Ihrer Lebensmittelausgaben aus, zuvor {previous}%. Das sind ${dining} in Restaurants gegenüber ${groceries} für Lebensmitteleinkäufe"),
+    ("subscriptions.price_hike", "{merchant} ist am {date} von ${from} auf ${to} gestiegen, ${annual} mehr pro Jahr (${total_annual} pro Jahr mehr als zu Beginn)"),
+    ("subscriptions.trial_converted", "{merchant} hat am {date} ${amount} abgebucht, etwa einen Monat nach einer Autorisierung über ${trial_amount}. Vermutlich ist ein kostenloser Test in ein bezahltes Abo übergegangen; kündigen Sie, wenn Sie es nicht behalten möchten"),
+    ("subscriptions.new_plan", "{merchant} hat am {date} zum ersten Mal ${amount} abgebucht. Falls ein kostenloser Test abgelaufen ist, prüfen Sie, ob Sie das Abo behalten möchten"),
+    ("subscriptions.overlap", "Sie zahlen für {count} {service}-Dienste ({merchants}), ${monthly} im Monat. Wenn Sie nur {keep} behalten und {others} kündigen, sparen Sie etwa ${savings} pro Jahr"),
+    ("goal.on_track", "Ziel \"{name}\" liegt im Plan: ${actual} von ${target} in diesem Monat"),
+    ("goal.off_track", "Ziel \"{name}\" wird verfehlt: ${over} über dem Ziel von ${target} ({trend})"),
+    ("cash_advance.cost", "Bargeldabhebungen von ${advanced} kosten etwa ${cost} an Gebühren und Zinsen – ein effektiver Jahreszins von {apr}%. Zinsen fallen sofort an, zahlen Sie diese daher zuerst zurück"),
+    ("cash_advance.fees_only", "Sie haben in diesem Zeitraum ${fees} an Gebühren für Bargeldabhebungen gezahlt"),
+    ("tip.spending_alerts", "Richten Sie Ausgabenwarnungen für Ihre wichtigsten Kategorien ein"),
+    ("demo.active", "Es werden generierte Demodaten angezeigt; Ihre eigenen Abrechnungen bleiben unverändert"),
+    ("trend.improving", "verbessert sich"),
+    ("trend.worsening", "verschlechtert sich"),
+    ("trend.steady", "unverändert"),
+    ("trend.new", "neu"),
+    ("service.music_streaming", "Musik-Streaming"),
+    ("service.video_streaming", "Video-Streaming"),
+    ("service.cloud_storage", "Cloud-Speicher"),
+    ("service.news", "Nachrichten"),
+    ("service.fitness", "Fitness"),
+    ("service.vpn", "VPN"),
+    ("service.password_manager", "Passwortmanager"),
+    ("service.gaming", "Gaming"),
+    ("error.file_not_found", "Datei nicht gefunden"),
+    ("error.parse_failed", "Diese Datei konnte nicht gelesen werden: {reason}"),
+    ("error.no_transactions", "In dieser Datei wurden keine Transaktionen gefunden"),
+    ("error.statement_not_found", "Abrechnung {id} nicht gefunden"),
+    ("error.transaction_not_found", "Buchung {index} in Abrechnung {id} nicht gefunden"),
+    ("error.trash_not_found", "Gelöschte Buchung {id} nicht gefunden; gelöschte Buchungen werden 30 Tage aufbewahrt"),
+    ("error.entry_date_invalid", "Das Datum {date} konnte nicht gelesen werden; verwenden Sie ein Datum wie 2024-01-31"),
+    ("error.entry_description_empty", "Die Beschreibung darf nicht leer sein"),
+    ("error.entry_amount_invalid", "Der Betrag muss eine positive Zahl sein"),
+    ("error.ocr_unavailable", "Zum Lesen von Belegbildern muss Tesseract OCR installiert sein"),
+    ("error.split_total_mismatch", "Die Teilbeträge müssen positiv sein und {amount} ergeben"),
+    ("error.no_statements", "Es wurden noch keine Abrechnungen analysiert"),
+    ("error.reduction_out_of_range", "Die Reduzierung muss zwischen 0 und 100 Prozent liegen"),
+    ("error.category_empty", "Keine Ausgaben in der Kategorie {category} gefunden"),
+    ("error.period_invalid", "Ungültiger Zeitraum {period}; verwenden Sie einen Monat wie 2024-01 oder ein Jahr wie 2024"),
+    ("error.bulk_filter_empty", "Wählen Sie einen Händler, eine Kategorie, ein Schlagwort oder einen Zeitraum zum Bearbeiten"),
+    ("error.bulk_no_matches", "Keine Buchungen passen zu diesem Filter"),
+    ("error.bulk_nothing_to_undo", "Es gibt keine Sammelbearbeitung zum Rückgängigmachen"),
+    ("error.merchant_not_found", "Keine Buchungen für den Händler {merchant} gefunden"),
+    ("error.no_discretionary_spending", "Keine Ausgaben in Kategorien, die als Wünsche eingestuft sind"),
+    ("error.goal_amount_invalid", "Der Zielbetrag muss eine positive Zahl sein"),
+    ("error.goal_needs_history", "Analysieren Sie mindestens eine Abrechnung, bevor Sie ein Sparziel festlegen"),
+    ("error.goal_not_found", "Ziel {id} nicht gefunden"),
+    ("error.card_not_found", "Karte {id} nicht gefunden"),
+    ("error.card_name_empty", "Der Kartenname darf nicht leer sein"),
+    ("error.card_fee_invalid", "Die Jahresgebühr muss null oder mehr betragen"),
+    ("error.reward_rate_invalid", "Die Prämienrate muss zwischen 0 und 100 Prozent liegen"),
+    ("error.benefit_invalid", "Der Vorteil \"{name}\" braucht einen Namen und einen positiven Betrag"),
+    ("error.benefit_unmatched", "Der Vorteil \"{name}\" braucht Händler oder eine Kategorie, mit denen Einkäufe abgeglichen werden"),
+    ("error.threshold_invalid", "Die Kleinbetragsgrenze muss ein positiver Betrag sein"),
+    ("error.round_up_invalid", "Der Rundungsbetrag muss mindestens $0.01 betragen"),
+    ("error.locale_unsupported", "Nicht unterstützte Sprache: {locale}"),
+    ("error.secret_empty", "Der geheime Wert darf nicht leer sein"),
+    ("error.keychain_unavailable", "Auf den Systemschlüsselbund konnte nicht zugegriffen werden: {reason}"),
+    ("error.pdf_password_required", "Dieses PDF ist passwortgeschützt. Geben Sie das Passwort ein, um fortzufahren"),
+    ("error.pdf_password_invalid", "Das Passwort für dieses PDF ist falsch"),
+    ("error.columns_ambiguous", "Es war nicht erkennbar, welche Spalte {column} enthält. Legen Sie für diese Datei ein Bankprofil an, um die Spalten zuzuordnen"),
+    ("column.date", "das Datum"),
+    ("column.description", "die Beschreibung"),
+    ("column.amount", "den Betrag"),
+    ("column.debit_credit", "Belastungen und Gutschriften"),
+    ("column.other", "die Daten"),
+    ("error.reminder_days_invalid", "Die Vorlaufzeit der Erinnerung muss zwischen 0 und 28 Tagen liegen"),
+    ("error.income_invalid", "Das Monatseinkommen muss ein positiver Betrag sein"),
+    ("error.profile_name_empty", "Das Bankprofil braucht einen Namen"),
+    ("error.profile_columns_missing", "Das Bankprofil braucht Datums- und Beschreibungsspalten sowie eine Betragsspalte oder Soll- und Habenspalten"),
+    ("error.profile_no_header", "In dieser Datei wurde keine Kopfzeile gefunden"),
+    ("error.profile_not_found", "Bankprofil {name} nicht gefunden"),
+    ("error.profile_invalid_file", "Diese Datei ist kein Bankprofil"),
+    ("error.profile_sample_mismatch", "Die Beispieldatei hat nicht die Spalten, die das Bankprofil {name} erwartet"),
+    ("error.profile_sample_failed", "Das Bankprofil konnte die Beispieldatei nicht lesen: {reason}"),
+    ("error.analysis_invalid_file", "Diese Datei ist keine gespeicherte Analyse"),
+    ("error.analysis_newer_version", "Diese Analyse wurde mit einer neueren Version der App gespeichert (Format {version}); aktualisieren Sie die App, um sie zu öffnen"),
+    ("error.period_start_day_invalid", "Der Starttag des Zeitraums muss zwischen 1 und 28 liegen"),
+    ("error.emission_factor_invalid", "Der Emissionsfaktor für {category} muss null oder größer sein"),
+    ("error.no_donations", "Keine Spenden für {year} gefunden"),
+    ("error.business_rule_empty", "Regeln für Geschäftsausgaben dürfen nicht leer sein"),
+    ("error.no_business_expenses", "Keine Geschäftsausgaben in diesem Zeitraum gefunden"),
+    ("error.shared_partner_empty", "Geben Sie an, mit wem die Ausgabe geteilt wird"),
+    ("error.shared_share_invalid", "Der Anteil der anderen Person muss zwischen 0 und 100 Prozent liegen"),
+    ("error.no_shared_expenses", "Keine geteilten Ausgaben in diesem Zeitraum gefunden"),
+    ("error.home_state_invalid", "Der Heimatstaat muss ein Code aus zwei Buchstaben wie NY sein"),
+    ("error.retention_invalid", "Originale müssen mindestens einen Tag aufbewahrt werden"),
+    ("error.snapshot_name_empty", "Gib dem Snapshot einen Namen"),
+    ("error.snapshot_not_found", "Dieser Snapshot existiert nicht mehr"),
+    ("error.shortcut_invalid", "{shortcut} ist keine gültige Tastenkombination für ein Kürzel"),
+    ("error.shortcut_unavailable", "{shortcut} wird bereits von einer anderen App verwendet"),
+    ("error.statements_folder_required", "Wähle einen Ordner für Abrechnungen, die im Hintergrund importiert werden"),
+    ("error.statements_folder_missing", "Der Abrechnungsordner {folder} existiert nicht"),
+    ("error.store_newer_version", "Deine Daten wurden von einer neueren App-Version gespeichert (Datenversion {version}); aktualisiere die App, um sie zu öffnen"),
+    ("error.update_wrong_channel", "Version {version} stammt aus einem anderen Update-Kanal"),
+    ("error.update_older_schema", "Version {version} kann von dieser Version gespeicherte Daten nicht lesen"),
+    ("error.update_schema_unsupported", "Version {version} kann deine Daten nicht mehr aktualisieren; installiere zuerst ein früheres Update"),
+    ("error.config_invalid_file", "Diese Datei ist keine Credit-Card-Analyzer-Konfiguration"),
+    ("error.sync_not_built", "Diese Version der App enthält keine Synchronisierung"),
+    ("error.sync_demo", "Die Synchronisierung ist aus, solange Demodaten geladen sind"),
+    ("error.sync_folder_required", "Wähle einen Ordner für die Synchronisierung"),
+    ("error.sync_folder_missing", "Der Synchronisierungsordner {folder} existiert nicht"),
+    ("error.sync_key_missing", "Erstelle auf deinem ersten Gerät einen Synchronisierungsschlüssel und gib ihn hier ein"),
+    ("error.sync_key_invalid", "Der Synchronisierungsschlüssel muss aus 64 Buchstaben und Ziffern bestehen, wie auf dem Gerät angezeigt, das ihn erstellt hat"),
+    ("carbon.title", "CO2-Fußabdruck"),
+    ("reminder.title", "Kreditkartenzahlung fällig"),
+    ("reminder.body", "{card}: ${amount} fällig am {date} (noch {days} Tage)"),
+    ("reminder.body_no_amount", "{card}: Zahlung fällig am {date} (noch {days} Tage)"),
+    ("reminder.card_ending", "Karte mit Endung {last4}"),
+    ("reminder.card_unknown", "Ihre Karte"),
+    ("tray.spend", "{month}: ${amount} ausgegeben"),
+    ("tray.no_spend", "Noch keine Ausgaben importiert"),
+    ("tray.budgets", "{met} von {total} Budgets im Plan"),
+    ("tray.no_budgets", "Keine Budgets festgelegt"),
+    ("tray.quick_import", "Schnellimport…"),
+    ("tray.open", "Credit Card Analyzer öffnen"),
+    ("tray.quit", "Beenden"),
+    ("background.alerts_title", "Neue Warnungen"),
+    ("background.alerts_body", "{count} neue Warnungen in Abrechnungen aus {folder}"),
+    ("update.migration_step", "Gespeicherte Daten von Version {from} auf {to} aktualisieren"),
+    ("window.ledger", "Buchungen: {period}"),
+    ("window.ledger_all", "Buchungen: alle Transaktionen"),
+    ("window.comparison", "Abrechnung {a} gegenüber {b}"),
+];
+
+const HI: &[(&str, &str)] = &[
+    ("summary.analyzed", "{file} से {count} लेनदेन का विश्लेषण सफलतापूर्वक किया गया"),
+    ("summary.top_category", "आपकी सबसे बड़ी खर्च श्रेणी {category} है, जो कुल खर्च का {percentage}% है"),
+    ("micro_spending.summary", "आपके {count} छोटे लेनदेन (${threshold} से कम) हैं, कुल ${total} — इस रफ़्तार से सालाना लगभग ${annual}"),
+    ("micro_spending.summary_led_by", "आपके {count} छोटे लेनदेन (${threshold} से कम) हैं, कुल ${total} — इस रफ़्तार से सालाना लगभग ${annual}, सबसे ज़्यादा {merchant} पर ({visits} बार)"),
+    ("travel.spent_abroad", "आपने विदेश में {count} लेनदेन में ${total} खर्च किए ({countries} देश)"),
+    ("travel.fx_fees_paid", "आपने विदेशी लेनदेन शुल्क में ${fees} चुकाए (विदेशी खर्च का {rate}%) — बिना विदेशी शुल्क वाला कार्ड यह पूरी राशि बचा सकता था"),
+    ("travel.fx_fees_none", "कोई विदेशी लेनदेन शुल्क नहीं मिला। अगर आपका कार्ड सामान्य {rate}% शुल्क लेता है, तो अलग से लगे ${estimate} जाँचें; बिना विदेशी शुल्क वाले कार्ड यह नहीं लेते"),
+    ("growth.top_merchant", "{merchant} पर खर्च सबसे ज़्यादा बढ़ा: आपके सामान्य ${previous} की जगह ${current} (+${change})"),
+    ("growth.new_merchants", "इस स्टेटमेंट में {count} नए व्यापारी ${total} के हैं"),
+    ("concentration.pareto", "आपके {share}% व्यापारी ({total_merchants} में से {merchants}) आपके 80% खर्च के लिए ज़िम्मेदार हैं"),
+    ("round_up.savings", "हर खरीद को ${increment} तक राउंड-अप करने से ${total} बचते — लगभग ${monthly} प्रति माह या ${annual} प्रति वर्ष"),
+    ("income.breakdown", "आपका कार्ड खर्च आपकी आय का {spend}% है। ज़रूरतों पर {needs}%, इच्छाओं पर {wants}% और बचत के लिए {savings}% बचता है (50/30/20 लक्ष्य)"),
+    ("carbon.footprint", "इस स्टेटमेंट का खर्च लगभग {total} kg CO2e के बराबर है। सबसे बड़ा हिस्सा {category} का है"),
+    ("carbon.footprint_up", "इस स्टेटमेंट का खर्च लगभग {total} kg CO2e के बराबर है, पिछले महीने से {change}% अधिक। सबसे बड़ा हिस्सा {category} का है"),
+    ("carbon.footprint_down", "इस स्टेटमेंट का खर्च लगभग {total} kg CO2e के बराबर है, पिछले महीने से {change}% कम। सबसे बड़ा हिस्सा {category} का है"),
+    ("fuel.drift_up", "ईंधन पर हर महीने ${monthly} खर्च हो रहे हैं, जो आपके सामान्य ${baseline} से ${drift} अधिक है। आप महीने में {fill_ups} बार ईंधन भरवा रहे हैं, पहले {usual_fill_ups} बार"),
+    ("food.dining_share_up", "बाहर खाने पर आपके भोजन खर्च का {share}% गया, पहले यह {previous}% था। रेस्तरां पर ${dining} बनाम किराने पर ${groceries}"),
+    ("subscriptions.price_hike", "{merchant} {date} को ${from} से बढ़कर ${to} हो गया, यानी साल में ${annual} अधिक (शुरुआत की तुलना में साल में ${total_annual} अधिक)"),
+    ("subscriptions.trial_converted", "{merchant} ने {date} को ${amount} लिए, ${trial_amount} के ऑथराइज़ेशन के लगभग एक महीने बाद। संभवतः कोई मुफ़्त ट्रायल सशुल्क प्लान में बदल गया है; अगर आप इसे नहीं रखना चाहते तो रद्द करें"),
+    ("subscriptions.new_plan", "{merchant} ने {date} को पहली बार ${amount} लिए। अगर यह मुफ़्त ट्रायल का अंत है, तो जाँच लें कि आप इसे रखना चाहते हैं"),
+    ("subscriptions.overlap", "आप {count} {service} सेवाओं ({merchants}) के लिए हर महीने ${monthly} दे रहे हैं। सिर्फ़ {keep} रखकर {others} रद्द करने से साल में लगभग ${savings} बचेंगे"),
+    ("goal.on_track", "लक्ष्य \"{name}\" सही राह पर है: इस महीने ${target} में से ${actual}"),
+    ("goal.off_track", "लक्ष्य \"{name}\" पटरी से उतर गया है: ${target} के लक्ष्य से ${over} ज़्यादा ({trend})"),
+    ("cash_advance.cost", "${advanced} के कैश एडवांस पर शुल्क और ब्याज में लगभग ${cost} लगेंगे — प्रभावी वार्षिक दर {apr}%। ब्याज तुरंत लगता है, इसलिए इन्हें पहले चुकाएँ"),
+    ("cash_advance.fees_only", "इस अवधि में आपने कैश एडवांस शुल्क में ${fees} चुकाए"),
+    ("tip.spending_alerts", "अपनी मुख्य श्रेणियों के लिए खर्च अलर्ट सेट करने पर विचार करें"),
+    ("demo.active", "उत्पन्न डेमो डेटा दिखाया जा रहा है; आपके अपने स्टेटमेंट अपरिवर्तित हैं"),
+    ("trend.improving", "सुधर रहा है"),
+    ("trend.worsening", "बिगड़ रहा है"),
+    ("trend.steady", "स्थिर"),
+    ("trend.new", "नया"),
+    ("service.music_streaming", "म्यूज़िक स्ट्रीमिंग"),
+    ("service.video_streaming", "वीडियो स्ट्रीमिंग"),
+    ("service.cloud_storage", "क्लाउड स्टोरेज"),
+    ("service.news", "न्यूज़"),
+    ("service.fitness", "फ़िटनेस"),
+    ("service.vpn", "VPN"),
+    ("service.password_manager", "पासवर्ड मैनेजर"),
+    ("service.gaming", "गेमिंग"),
+    ("error.file_not_found", "फ़ाइल नहीं मिली"),
+    ("error.parse_failed", "यह फ़ाइल पढ़ी नहीं जा सकी: {reason}"),
+    ("error.no_transactions", "इस फ़ाइल में कोई लेनदेन नहीं मिला"),
+    ("error.statement_not_found", "स्टेटमेंट {id} नहीं मिला"),
+    ("error.transaction_not_found", "स्टेटमेंट {id} में लेनदेन {index} नहीं मिला"),
+    ("error.trash_not_found", "हटाया गया लेनदेन {id} नहीं मिला; हटाए गए लेनदेन 30 दिनों तक रखे जाते हैं"),
+    ("error.entry_date_invalid", "तारीख {date} पढ़ी नहीं जा सकी; 2024-01-31 जैसी तारीख का उपयोग करें"),
+    ("error.entry_description_empty", "विवरण खाली नहीं हो सकता"),
+    ("error.entry_amount_invalid", "राशि एक धनात्मक संख्या होनी चाहिए"),
+    ("error.ocr_unavailable", "रसीद की इमेज पढ़ने के लिए Tesseract OCR इंस्टॉल होना चाहिए"),
+    ("error.split_total_mismatch", "विभाजित राशियाँ धनात्मक होनी चाहिए और उनका योग {amount} होना चाहिए"),
+    ("error.no_statements", "अभी तक किसी स्टेटमेंट का विश्लेषण नहीं हुआ है"),
+    ("error.reduction_out_of_range", "कटौती 0 से 100 प्रतिशत के बीच होनी चाहिए"),
+    ("error.category_empty", "श्रेणी {category} में कोई खर्च नहीं मिला"),
+    ("error.period_invalid", "अमान्य अवधि {period}; 2024-01 जैसा महीना या 2024 जैसा वर्ष दें"),
+    ("error.bulk_filter_empty", "संपादन के लिए कोई व्यापारी, श्रेणी, टैग या अवधि चुनें"),
+    ("error.bulk_no_matches", "इस फ़िल्टर से कोई लेनदेन मेल नहीं खाता"),
+    ("error.bulk_nothing_to_undo", "पूर्ववत करने के लिए कोई सामूहिक संपादन नहीं है"),
+    ("error.merchant_not_found", "व्यापारी {merchant} के लिए कोई लेनदेन नहीं मिला"),
+    ("error.no_discretionary_spending", "इच्छा के रूप में वर्गीकृत श्रेणियों में कोई खर्च नहीं मिला"),
+    ("error.goal_amount_invalid", "लक्ष्य राशि एक धनात्मक संख्या होनी चाहिए"),
+    ("error.goal_needs_history", "कटौती लक्ष्य तय करने से पहले कम से कम एक स्टेटमेंट का विश्लेषण करें"),
+    ("error.goal_not_found", "लक्ष्य {id} नहीं मिला"),
+    ("error.card_not_found", "कार्ड {id} नहीं मिला"),
+    ("error.card_name_empty", "कार्ड का नाम खाली नहीं हो सकता"),
+    ("error.card_fee_invalid", "वार्षिक शुल्क शून्य या अधिक होना चाहिए"),
+    ("error.reward_rate_invalid", "रिवॉर्ड दर 0 से 100 प्रतिशत के बीच होनी चाहिए"),
+    ("error.benefit_invalid", "लाभ \"{name}\" के लिए नाम और धनात्मक राशि चाहिए"),
+    ("error.benefit_unmatched", "लाभ \"{name}\" के लिए खरीदारी मिलाने हेतु व्यापारी या श्रेणी चाहिए"),
+    ("error.threshold_invalid", "छोटे खर्च की सीमा एक धनात्मक राशि होनी चाहिए"),
+    ("error.round_up_invalid", "राउंड-अप राशि कम से कम $0.01 होनी चाहिए"),
+    ("error.locale_unsupported", "असमर्थित भाषा: {locale}"),
+    ("error.secret_empty", "गुप्त मान खाली नहीं हो सकता"),
+    ("error.keychain_unavailable", "सिस्टम कीचेन तक पहुँच नहीं हो सकी: {reason}"),
+    ("error.pdf_password_required", "यह PDF पासवर्ड से सुरक्षित है। जारी रखने के लिए इसका पासवर्ड दर्ज करें"),
+    ("error.pdf_password_invalid", "इस PDF का पासवर्ड गलत है"),
+    ("error.columns_ambiguous", "यह पता नहीं चल सका कि किस कॉलम में {column} है। कौन-सा कॉलम क्या है, यह बताने के लिए इस फ़ाइल के लिए बैंक प्रोफ़ाइल बनाएँ"),
+    ("column.date", "तारीख"),
+    ("column.description", "विवरण"),
+    ("column.amount", "राशि"),
+    ("column.debit_credit", "खर्च और जमा"),
+    ("column.other", "डेटा"),
+    ("error.reminder_days_invalid", "रिमाइंडर का समय 0 से 28 दिनों के बीच होना चाहिए"),
+    ("error.income_invalid", "मासिक आय एक धनात्मक राशि होनी चाहिए"),
+    ("error.profile_name_empty", "बैंक प्रोफ़ाइल का नाम होना चाहिए"),
+    ("error.profile_columns_missing", "बैंक प्रोफ़ाइल में तारीख और विवरण कॉलम, और एक राशि कॉलम या डेबिट व क्रेडिट दोनों कॉलम होने चाहिए"),
+    ("error.profile_no_header", "इस फ़ाइल में हेडर पंक्ति नहीं मिली"),
+    ("error.profile_not_found", "बैंक प्रोफ़ाइल {name} नहीं मिली"),
+    ("error.profile_invalid_file", "यह फ़ाइल बैंक प्रोफ़ाइल नहीं है"),
+    ("error.profile_sample_mismatch", "नमूना फ़ाइल में वे कॉलम नहीं हैं जिनकी बैंक प्रोफ़ाइल {name} को ज़रूरत है"),
+    ("error.profile_sample_failed", "बैंक प्रोफ़ाइल नमूना फ़ाइल नहीं पढ़ सकी: {reason}"),
+    ("error.analysis_invalid_file", "यह फ़ाइल सहेजा गया विश्लेषण नहीं है"),
+    ("error.analysis_newer_version", "यह विश्लेषण ऐप के नए संस्करण से सहेजा गया था (फ़ॉर्मेट {version}); इसे खोलने के लिए ऐप अपडेट करें"),
+    ("error.period_start_day_invalid", "अवधि का शुरुआती दिन 1 से 28 के बीच होना चाहिए"),
+    ("error.emission_factor_invalid", "{category} का उत्सर्जन कारक शून्य या उससे अधिक होना चाहिए"),
+    ("error.no_donations", "{year} के लिए कोई दान नहीं मिला"),
+    ("error.business_rule_empty", "व्यावसायिक खर्च के नियम खाली नहीं हो सकते"),
+    ("error.no_business_expenses", "इस अवधि में कोई व्यावसायिक खर्च नहीं मिला"),
+    ("error.shared_partner_empty", "बताएँ कि खर्च किसके साथ साझा है"),
+    ("error.shared_share_invalid", "साथी का हिस्सा 0 से 100 प्रतिशत के बीच होना चाहिए"),
+    ("error.no_shared_expenses", "इस अवधि में कोई साझा खर्च नहीं मिला"),
+    ("error.home_state_invalid", "गृह राज्य NY जैसा दो अक्षरों का कोड होना चाहिए"),
+    ("error.retention_invalid", "मूल फ़ाइलें कम से कम एक दिन रखी जानी चाहिए"),
+    ("error.snapshot_name_empty", "स्नैपशॉट को एक नाम दें"),
+    ("error.snapshot_not_found", "वह स्नैपशॉट अब मौजूद नहीं है"),
+    ("error.shortcut_invalid", "{shortcut} शॉर्टकट के लिए मान्य कुंजी संयोजन नहीं है"),
+    ("error.shortcut_unavailable", "{shortcut} पहले से किसी अन्य ऐप द्वारा उपयोग में है"),
+    ("error.statements_folder_required", "पृष्ठभूमि में आयात के लिए स्टेटमेंट फ़ोल्डर चुनें"),
+    ("error.statements_folder_missing", "स्टेटमेंट फ़ोल्डर {folder} मौजूद नहीं है"),
+    ("error.store_newer_version", "आपका डेटा ऐप के नए संस्करण द्वारा सहेजा गया था (डेटा संस्करण {version}); इसे खोलने के लिए ऐप अपडेट करें"),
+    ("error.update_wrong_channel", "संस्करण {version} किसी अन्य अपडेट चैनल से है"),
+    ("error.update_older_schema", "संस्करण {version} इस संस्करण द्वारा सहेजा गया डेटा नहीं पढ़ सकता"),
+    ("error.update_schema_unsupported", "संस्करण {version} अब आपका डेटा अपग्रेड नहीं कर सकता; पहले कोई पुराना अपडेट इंस्टॉल करें"),
+    ("error.config_invalid_file", "यह फ़ाइल Credit Card Analyzer कॉन्फ़िगरेशन नहीं है"),
+    ("error.sync_not_built", "ऐप के इस बिल्ड में सिंक शामिल नहीं है"),
+    ("error.sync_demo", "डेमो डेटा लोड रहने तक सिंक बंद है"),
+    ("error.sync_folder_required", "सिंक के लिए एक फ़ोल्डर चुनें"),
+    ("error.sync_folder_missing", "सिंक फ़ोल्डर {folder} मौजूद नहीं है"),
+    ("error.sync_key_missing", "अपने पहले डिवाइस पर सिंक कुंजी बनाएँ और इसे यहाँ दर्ज करें"),
+    ("error.sync_key_invalid", "सिंक कुंजी में 64 अक्षर और अंक होने चाहिए, जैसा उसे बनाने वाले डिवाइस पर दिखाया गया है"),
+    ("carbon.title", "कार्बन फ़ुटप्रिंट"),
+    ("reminder.title", "क्रेडिट कार्ड भुगतान देय"),
+    ("reminder.body", "{card}: ${amount} {date} को देय ({days} दिन बाकी)"),
+    ("reminder.body_no_amount", "{card}: भुगतान {date} को देय ({days} दिन बाकी)"),
+    ("reminder.card_ending", "{last4} पर समाप्त होने वाला कार्ड"),
+    ("reminder.card_unknown", "आपका कार्ड"),
+    ("tray.spend", "{month}: ${amount} खर्च"),
+    ("tray.no_spend", "अभी तक कोई खर्च आयात नहीं हुआ"),
+    ("tray.budgets", "{total} में से {met} बजट सही राह पर"),
+    ("tray.no_budgets", "कोई बजट तय नहीं"),
+    ("tray.quick_import", "त्वरित आयात…"),
+    ("tray.open", "Credit Card Analyzer खोलें"),
+    ("tray.quit", "बंद करें"),
+    ("background.alerts_title", "नई चेतावनियाँ"),
+    ("background.alerts_body", "{folder} से आयात किए गए स्टेटमेंट में {count} नई चेतावनियाँ"),
+    ("update.migration_step", "सहेजे गए डेटा को संस्करण {from} से {to} में अपग्रेड करें"),
+    ("window.ledger", "लेन-देन: {period}"),
+    ("window.ledger_all", "लेन-देन: सभी लेन-देन"),
+    ("window.comparison", "स्टेटमेंट {a} बनाम {b}"),
+];
+
+fn catalog(locale: &str) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        "es" => ES,
+        "de" => DE,
+        "hi" => HI,
+        _ => EN,
+    }
+}
+
+fn lookup(locale: &str, key: &str) -> Option<&'static str> {
+    catalog(locale).iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, template)| *template)
+}
+
+fn render(template: &str, params: &[(&str, String)]) -> String {
+    params.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+pub fn is_supported(locale: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&locale)
+}
+
+// Localized text for `key`, falling back to English and then to the key itself
+pub fn text(locale: &str, key: &str, params: &[(&str, String)]) -> String {
+    let template = lookup(locale, key)
+        .or_else(|| lookup(DEFAULT_LOCALE, key))
+        .unwrap_or(key);
+    render(template, params)
+}
+
+pub fn error(locale: &str, key: &str) -> String {
+    text(locale, key, &[])
+}
+
+// Re-renders insight messages in the user's language. Insights without a
+// translation keep their English message.
+pub fn localize_insights(insights: &mut [Insight], locale: &str) {
+    if locale == DEFAULT_LOCALE {
+        return;
+    }
+
+    for insight in insights.iter_mut() {
+        let Some(template) = lookup(locale, &insight.message_key) else {
+            continue;
+        };
+
+        let params: Vec<(&str, String)> = insight.params.iter()
+            .map(|(name, value)| {
+                // Trend words and service classes are passed through as keys of their own
+                let value = match name.as_str() {
+                    "trend" | "service" => text(locale, &format!("{}.{}", name, value), &[]),
+                    _ => value.clone(),
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+
+        insight.message = render(template, &params);
+    }
+}
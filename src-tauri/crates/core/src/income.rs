@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::categories::{self, CategoryClass};
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{monthly_factor, transaction_category, Transaction};
+
+// The 50/30/20 rule: half of income on needs, 30% on wants, the rest saved or paying down debt
+const NEEDS_TARGET: f64 = 50.0;
+const WANTS_TARGET: f64 = 30.0;
+const SAVINGS_TARGET: f64 = 20.0;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct BudgetShare {
+    // Per average month
+    pub amount: f64,
+    pub percent_of_income: f64,
+    pub target_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct IncomeReport {
+    pub monthly_income: f64,
+    // Statement spending scaled to an average month
+    pub monthly_spend: f64,
+    pub spend_percent: f64,
+    // Share of income left after card spending; negative when spending exceeds income
+    pub savings_rate: f64,
+    pub needs: BudgetShare,
+    pub wants: BudgetShare,
+    // Income not spent on needs or wants, which includes fees and interest paid to the card
+    pub savings: BudgetShare,
+}
+
+fn share(amount: f64, income: f64, target_percent: f64) -> BudgetShare {
+    BudgetShare { amount, percent_of_income: amount / income * 100.0, target_percent }
+}
+
+pub fn income_report(transactions: &[Transaction], monthly_income: Option<f64>, classes: &BTreeMap<String, CategoryClass>) -> Option<IncomeReport> {
+    let income = monthly_income.filter(|income| *income > 0.0)?;
+    if transactions.is_empty() {
+        return None;
+    }
+
+    let factor = monthly_factor(transactions);
+    let (mut needs, mut wants, mut debt) = (0.0, 0.0, 0.0);
+    for tx in transactions {
+        match categories::class(&transaction_category(tx), classes) {
+            CategoryClass::Need => needs += tx.amount * factor,
+            CategoryClass::Want => wants += tx.amount * factor,
+            CategoryClass::SavingsDebt => debt += tx.amount * factor,
+        }
+    }
+
+    let monthly_spend = needs + wants + debt;
+    Some(IncomeReport {
+        monthly_income: income,
+        monthly_spend,
+        spend_percent: monthly_spend / income * 100.0,
+        savings_rate: (income - monthly_spend) / income * 100.0,
+        needs: share(needs, income, NEEDS_TARGET),
+        wants: share(wants, income, WANTS_TARGET),
+        savings: share(income - needs - wants, income, SAVINGS_TARGET),
+    })
+}
+
+pub fn income_insight(report: &IncomeReport) -> Insight {
+    // Wants over target is the part of the budget most worth flagging; no savings at all is worse
+    let severity = if report.savings_rate < 0.0 {
+        Severity::Warning
+    } else if report.wants.percent_of_income > report.wants.target_percent {
+        Severity::Notice
+    } else {
+        Severity::Info
+    };
+
+    Insight::new(
+        InsightKind::Income,
+        severity,
+        "income.breakdown",
+        format!("Your card spending is {:.0}% of your income. Needs take {:.0}%, wants {:.0}% and {:.0}% is left for savings (50/30/20 targets)",
+                report.spend_percent, report.needs.percent_of_income, report.wants.percent_of_income, report.savings.percent_of_income),
+    )
+    .with_amount(report.monthly_spend)
+    .with_param("spend", format!("{:.0}", report.spend_percent))
+    .with_param("needs", format!("{:.0}", report.needs.percent_of_income))
+    .with_param("wants", format!("{:.0}", report.wants.percent_of_income))
+    .with_param("savings", format!("{:.0}", report.savings.percent_of_income))
+}
@@ -0,0 +1,186 @@
+use chrono::NaiveDate;
+
+use crate::{parse_amount, Transaction};
+
+// Column names for one issuer's export. Bank accounts split money into withdrawal/deposit
+// columns; card exports use one amount column with a Dr/Cr marker.
+struct BankProfile {
+    name: &'static str,
+    markers: &'static [&'static str],
+    date: &'static [&'static str],
+    description: &'static [&'static str],
+    debit: &'static [&'static str],
+    amount: &'static [&'static str],
+    dr_cr: &'static [&'static str],
+}
+
+const PROFILES: &[BankProfile] = &[
+    BankProfile {
+        name: "HDFC",
+        markers: &["narration", "withdrawal amt"],
+        date: &["date"],
+        description: &["narration"],
+        debit: &["withdrawal amt"],
+        amount: &[],
+        dr_cr: &[],
+    },
+    BankProfile {
+        name: "ICICI",
+        markers: &["transaction remarks", "withdrawal amount"],
+        date: &["transaction date", "value date"],
+        description: &["transaction remarks"],
+        debit: &["withdrawal amount"],
+        amount: &[],
+        dr_cr: &[],
+    },
+    BankProfile {
+        name: "SBI",
+        markers: &["txn date", "debit"],
+        date: &["txn date"],
+        description: &["description"],
+        debit: &["debit"],
+        amount: &[],
+        dr_cr: &[],
+    },
+    // Credit card exports from all three share this shape
+    BankProfile {
+        name: "Indian credit card",
+        markers: &["transaction details", "dr/cr"],
+        date: &["date"],
+        description: &["transaction details"],
+        debit: &[],
+        amount: &["amount"],
+        dr_cr: &["dr/cr", "dr / cr"],
+    },
+];
+
+fn header_cells(line: &str) -> Vec<String> {
+    line.split(',').map(|cell| cell.trim().trim_matches('"').trim().to_lowercase()).collect()
+}
+
+// Spacing varies between exports ("Dr/Cr" vs "Dr / Cr"), so it's ignored when matching
+fn matches_profile(cells: &[String], profile: &BankProfile) -> bool {
+    profile.markers.iter().all(|marker| {
+        let marker = marker.replace(' ', "");
+        cells.iter().any(|cell| cell.replace(' ', "").contains(&marker))
+    })
+}
+
+// Exports open with account details before the column header, so the header is searched for
+fn find_header(content: &str) -> Option<(usize, &'static BankProfile)> {
+    content.lines().take(40).enumerate().find_map(|(index, line)| {
+        let cells = header_cells(line);
+        PROFILES.iter().find(|profile| matches_profile(&cells, profile)).map(|profile| (index, profile))
+    })
+}
+
+pub fn is_indian_bank_export(content: &str) -> bool {
+    find_header(content).is_some()
+}
+
+pub fn bank_name(content: &str) -> Option<&'static str> {
+    find_header(content).map(|(_, profile)| profile.name)
+}
+
+// Names are tried in order, so a profile can prefer one date column over another
+fn column(headers: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+    names.iter().find_map(|name| {
+        headers.iter().position(|header| header.trim().to_lowercase().contains(name))
+    })
+}
+
+// Day-first dates, normalized to ISO so later month-first parsing can't misread them
+fn parse_indian_date(value: &str) -> Option<String> {
+    let formats = ["%d/%m/%Y", "%d/%m/%y", "%d-%m-%Y", "%d-%m-%y", "%d-%b-%Y", "%d-%b-%y", "%d %b %Y", "%d %b %y", "%d.%m.%Y"];
+    let trimmed = value.trim();
+    formats.iter()
+        .find_map(|format| NaiveDate::parse_from_str(trimmed, format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+// Handles ₹/Rs./INR prefixes, "Dr"/"Cr" suffixes and lakh grouping like 1,23,456.78.
+// Returns the amount and whether it was marked as a credit.
+fn parse_indian_amount(value: &str) -> Option<(f64, bool)> {
+    let mut cleaned = value.trim().to_lowercase();
+    let credit = cleaned.ends_with("cr");
+    for marker in ["₹", "rs.", "rs", "inr", "dr", "cr"] {
+        cleaned = cleaned.replace(marker, "");
+    }
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() || cleaned == "-" {
+        return None;
+    }
+    parse_amount(cleaned).ok().map(|amount| (amount, credit))
+}
+
+// Narrations carry the channel and reference around the merchant, e.g.
+// "POS 416021XXXXXX1234 SWIGGY BANGALORE" or "UPI/312345678901/ZOMATO/paytm@ybl"
+fn clean_narration(narration: &str) -> String {
+    let narration = narration.trim();
+    if let Some(rest) = narration.strip_prefix("UPI/").or_else(|| narration.strip_prefix("UPI-")) {
+        let parts: Vec<&str> = rest.split(['/', '-']).collect();
+        if let Some(payee) = parts.iter().find(|part| !part.is_empty() && !part.chars().all(|c| c.is_ascii_digit())) {
+            return payee.trim().to_string();
+        }
+    }
+
+    narration.split_whitespace()
+        .filter(|word| !matches!(word.to_uppercase().as_str(), "POS" | "ECOM" | "NEFT" | "IMPS"))
+        .filter(|word| !word.chars().any(|c| c.is_ascii_digit()) || word.len() < 6)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn parse_indian_bank(content: &str) -> Result<Vec<Transaction>, String> {
+    let (header_line, profile) = find_header(content)
+        .ok_or("Not a recognised Indian bank export")?;
+    log!("Using {} statement profile", profile.name);
+
+    let body = content.lines().skip(header_line).collect::<Vec<_>>().join("\n");
+    let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(body.as_bytes());
+    let headers = rdr.headers()
+        .map_err(|e| format!("Could not read {} headers: {}", profile.name, e))?
+        .clone();
+
+    let date_col = column(&headers, profile.date)
+        .ok_or_else(|| format!("{} export has no date column", profile.name))?;
+    let description_col = column(&headers, profile.description)
+        .ok_or_else(|| format!("{} export has no narration column", profile.name))?;
+    let debit_col = column(&headers, profile.debit);
+    let amount_col = column(&headers, profile.amount);
+    let dr_cr_col = column(&headers, profile.dr_cr);
+
+    let mut transactions = Vec::new();
+
+    for result in rdr.records() {
+        let record = result.map_err(|e| format!("Could not read {} export: {}", profile.name, e))?;
+
+        // Footer rows (totals, legends) have no parseable date
+        let Some(date) = record.get(date_col).and_then(parse_indian_date) else {
+            continue;
+        };
+
+        let amount = match (debit_col, amount_col) {
+            (Some(col), _) => record.get(col)
+                .and_then(parse_indian_amount)
+                .map(|(amount, _)| amount),
+            (None, Some(col)) => {
+                let marker = dr_cr_col.and_then(|col| record.get(col)).unwrap_or("").trim().to_lowercase();
+                record.get(col)
+                    .and_then(parse_indian_amount)
+                    .filter(|(_, credit)| !credit && !marker.starts_with("cr"))
+                    .map(|(amount, _)| amount)
+            }
+            (None, None) => None,
+        };
+
+        let Some(amount) = amount.filter(|amount| *amount > 0.0) else {
+            continue;
+        };
+
+        let description = clean_narration(record.get(description_col).unwrap_or(""));
+        transactions.push(Transaction::new(date, description, amount));
+    }
+
+    Ok(transactions)
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Notice,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum InsightKind {
+    CashAdvance,
+    Summary,
+    TopCategory,
+    MicroSpending,
+    Travel,
+    ForeignFees,
+    MerchantGrowth,
+    NewMerchants,
+    Concentration,
+    RoundUp,
+    Income,
+    Carbon,
+    Fuel,
+    DiningOut,
+    PriceHike,
+    TrialConversion,
+    OverlappingServices,
+    Goal,
+    Tip,
+    SampleData,
+    Custom,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Insight {
+    pub kind: InsightKind,
+    pub severity: Severity,
+    // Stable key + params so the frontend can localize; `message` is the English rendering
+    pub message_key: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    pub message: String,
+    pub amount: Option<f64>,
+    #[serde(default)]
+    pub merchants: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+impl Insight {
+    pub fn new(kind: InsightKind, severity: Severity, message_key: &str, message: String) -> Self {
+        Insight {
+            kind,
+            severity,
+            message_key: message_key.to_string(),
+            params: BTreeMap::new(),
+            message,
+            amount: None,
+            merchants: Vec::new(),
+            categories: Vec::new(),
+        }
+    }
+
+    pub fn with_amount(mut self, amount: f64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_merchant(mut self, merchant: &str) -> Self {
+        self.merchants.push(merchant.to_string());
+        self
+    }
+
+    pub fn with_category(mut self, category: &str) -> Self {
+        self.categories.push(category.to_string());
+        self
+    }
+}
+
+// Most severe first; insights of equal severity keep the order they were generated in
+pub fn rank(insights: &mut [Insight]) {
+    insights.sort_by_key(|insight| Reverse(insight.severity));
+}
@@ -0,0 +1,92 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::history::StoredStatement;
+use crate::{extract_merchant_name, parse_date, transaction_category, Transaction};
+
+// Index into an Interner's table of names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+// Each distinct name stored once, handed out as small copyable ids
+#[derive(Debug, Default)]
+pub struct Interner {
+    ids: HashMap<String, Symbol>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.ids.get(name) {
+            return symbol;
+        }
+        let symbol = Symbol(self.names.len() as u32);
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), symbol);
+        symbol
+    }
+
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.0 as usize]
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CompactTransaction {
+    pub date: Option<NaiveDate>,
+    pub merchant: Symbol,
+    pub category: Symbol,
+    pub amount: f64,
+}
+
+// Stored history with merchants and categories interned and dates parsed once, for grouping
+// across years of statements without a String per transaction. It's built for one analysis
+// pass and dropped after; the store itself still keeps owned Strings, since its file format,
+// sync and every edit work on Transaction, and moving those to ids would be a store migration.
+// Callers that only need dates should use latest_date below rather than build one of these.
+#[derive(Debug, Default)]
+pub struct CompactHistory {
+    pub merchants: Interner,
+    pub categories: Interner,
+    pub transactions: Vec<CompactTransaction>,
+}
+
+impl CompactHistory {
+    pub fn from_statements<'a>(statements: impl IntoIterator<Item = &'a StoredStatement>) -> CompactHistory {
+        let mut history = CompactHistory::default();
+        for statement in statements {
+            history.extend(&statement.transactions);
+        }
+        history
+    }
+
+    // Adds transactions not yet in a stored statement, like the one being analyzed
+    pub fn extend(&mut self, transactions: &[Transaction]) {
+        for tx in transactions {
+            let merchant = self.merchants.intern(&extract_merchant_name(&tx.description));
+            let category = self.categories.intern(&transaction_category(tx));
+            self.transactions.push(CompactTransaction {
+                date: parse_date(&tx.date),
+                merchant,
+                category,
+                amount: tx.amount,
+            });
+        }
+    }
+
+    pub fn latest_date(&self) -> Option<NaiveDate> {
+        self.transactions.iter().filter_map(|tx| tx.date).max()
+    }
+}
+
+// The latest transaction date in the statements, without interning anything
+pub fn latest_date<'a>(statements: impl IntoIterator<Item = &'a StoredStatement>) -> Option<NaiveDate> {
+    statements.into_iter()
+        .flat_map(|s| &s.transactions)
+        .filter_map(|tx| parse_date(&tx.date))
+        .max()
+}
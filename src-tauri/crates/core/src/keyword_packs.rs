@@ -0,0 +1,67 @@
+use crate::text;
+
+// Category keywords for descriptions in scripts the English rules can't match, one pack per
+// language. Scripts without spaces between words (Chinese, Japanese) are matched as
+// substrings like everything else, so a keyword only needs to appear somewhere in the
+// descriptor. Keywords are stored folded.
+type KeywordPack = &'static [(&'static str, &'static [&'static str])];
+
+const FOOD: &str = "Food & Dining";
+const TRANSPORT: &str = "Gas & Transportation";
+const SHOPPING: &str = "Shopping";
+const ENTERTAINMENT: &str = "Entertainment";
+const HEALTH: &str = "Healthcare";
+
+const PACKS: &[KeywordPack] = &[
+    // Arabic
+    &[
+        (FOOD, &["مطعم", "مقهى", "كافيه", "مخبز", "مشويات"]),
+        (TRANSPORT, &["وقود", "بنزين", "محطة", "تاكسي", "أوبر", "مواقف"]),
+        (SHOPPING, &["سوبرماركت", "هايبرماركت", "سوق", "متجر"]),
+        (ENTERTAINMENT, &["سينما", "ترفيه", "ألعاب"]),
+        (HEALTH, &["صيدلية", "مستشفى", "عيادة", "طبيب"]),
+    ],
+    // Hebrew
+    &[
+        (FOOD, &["מסעדה", "מסעדת", "קפה", "מאפייה", "פיצה"]),
+        (TRANSPORT, &["דלק", "תחנת", "מונית", "רכבת", "חניה", "סונול"]),
+        // Ahead of shopping, which "סופר" in "סופר-פארם" would otherwise hit
+        (HEALTH, &["בית מרקחת", "סופר-פארם", "מרפאה", "רופא", "קופת חולים"]),
+        (SHOPPING, &["סופר", "שופרסל", "רמי לוי", "קניון", "חנות"]),
+        (ENTERTAINMENT, &["קולנוע", "סינמה", "הופעה"]),
+    ],
+    // Chinese, simplified and traditional
+    &[
+        (FOOD, &["餐厅", "餐廳", "饭店", "飯店", "咖啡", "美团", "饿了么", "外卖"]),
+        (TRANSPORT, &["加油", "石油", "石化", "出租车", "計程車", "滴滴", "地铁", "停车"]),
+        (SHOPPING, &["超市", "商场", "百货", "百貨", "淘宝", "天猫", "京东"]),
+        (ENTERTAINMENT, &["电影", "電影", "影院", "影城", "游戏"]),
+        (HEALTH, &["药房", "藥房", "药店", "医院", "醫院", "诊所"]),
+    ],
+    // Japanese
+    &[
+        (FOOD, &["レストラン", "カフェ", "食堂", "居酒屋", "ラーメン", "寿司"]),
+        (TRANSPORT, &["ガソリン", "エネオス", "タクシー", "駐車場", "鉄道", "交通"]),
+        (SHOPPING, &["スーパー", "コンビニ", "百貨店", "楽天", "ドンキ"]),
+        (ENTERTAINMENT, &["映画", "シネマ", "カラオケ"]),
+        (HEALTH, &["薬局", "ドラッグ", "病院", "クリニック", "医院"]),
+    ],
+    // Korean
+    &[
+        (FOOD, &["식당", "카페", "커피", "음식점", "배달의민족", "치킨"]),
+        (TRANSPORT, &["주유", "택시", "카카오t", "주차", "교통"]),
+        (SHOPPING, &["마트", "이마트", "백화점", "쿠팡", "편의점"]),
+        (ENTERTAINMENT, &["영화", "cgv", "노래방"]),
+        (HEALTH, &["약국", "병원", "의원", "클리닉"]),
+    ],
+];
+
+// The category the first matching keyword in any pack points to. A statement's language
+// doesn't follow the app's locale, so every pack is tried.
+pub fn category(description: &str) -> Option<&'static str> {
+    let folded = text::fold(description);
+    PACKS.iter()
+        .flat_map(|pack| pack.iter())
+        .find(|(_, keywords)| keywords.iter().any(|keyword| folded.contains(keyword)))
+        .map(|(category, _)| *category)
+}
@@ -0,0 +1,1122 @@
+// Statement parsing, categorization and analytics, with no Tauri or storage dependencies so
+// the same analysis can run outside the desktop app
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+
+// First, so its log! macro is in scope for the modules below
+#[macro_use]
+pub mod privacy;
+mod alerts;
+pub mod amazon;
+pub mod analyzers;
+mod apple_card;
+pub mod bank_profiles;
+pub mod business;
+mod carbon;
+pub mod cards;
+mod camt053;
+pub mod cash_advance;
+pub mod categories;
+pub mod custom_insights;
+pub mod food;
+pub mod fuel;
+pub mod giving;
+pub mod goals;
+pub mod growth;
+pub mod heatmap;
+pub mod history;
+mod html;
+pub mod i18n;
+pub mod income;
+mod indian_banks;
+pub mod insights;
+pub mod interner;
+pub mod keyword_packs;
+pub mod manual;
+pub mod merchants;
+pub mod micro_spending;
+pub mod migration;
+mod mt940;
+pub mod ocr;
+pub mod pareto;
+pub mod parse_mode;
+pub mod pasted;
+mod pdf;
+mod pdf_layout;
+pub mod periods;
+pub mod records;
+pub mod round_up;
+pub mod services;
+pub mod settings;
+pub mod shared;
+pub mod sign_convention;
+pub mod statement_language;
+pub mod statement_meta;
+pub mod subscriptions;
+pub mod synthetic;
+pub mod text;
+pub mod travel;
+pub mod trips;
+pub mod wallets;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use analyzers::AnalyzerSection;
+use bank_profiles::BankProfile;
+use business::BusinessSplit;
+use cash_advance::CashAdvanceWarning;
+use categories::ClassTotal;
+use food::FoodReport;
+use fuel::VehicleReport;
+use goals::GoalStatus;
+use growth::MerchantGrowthReport;
+use heatmap::SpendingHeatmap;
+use history::StoredStatement;
+use income::IncomeReport;
+use insights::{Insight, InsightKind, Severity};
+use merchants::MerchantInfo;
+use micro_spending::MicroSpendingReport;
+use pareto::ParetoAnalysis;
+use parse_mode::{ParseMode, ParseWarning, RowIssues};
+use records::{LargeTransaction, LifetimeRecords};
+use round_up::RoundUpSimulation;
+use settings::Settings;
+use shared::SharedExpense;
+use sign_convention::{SignConvention, SignDetection};
+use statement_language::StatementLanguage;
+use statement_meta::StatementMeta;
+use subscriptions::{ServiceOverlap, Subscription, TrialConversion};
+use travel::TravelSummary;
+use trips::Trip;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct Transaction {
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub time: Option<String>,
+    // Cashback earned on the purchase, when the issuer reports it (e.g. Apple Card Daily Cash)
+    #[serde(default)]
+    pub rewards: Option<f64>,
+    // Set once the charge has been matched to an Amazon order
+    #[serde(default)]
+    pub order_id: Option<String>,
+    // Set on PayPal/Venmo spending and on the card charges linked to it
+    #[serde(default)]
+    pub payment_id: Option<String>,
+    // Which block of a multi-section export the row came from
+    #[serde(default)]
+    pub section: Option<SectionKind>,
+    #[serde(default)]
+    pub transaction_type: TransactionType,
+    // User annotations, kept across re-imports of the same statement
+    #[serde(default)]
+    pub note: Option<String>,
+    // Merchant category code, from exports that include one
+    #[serde(default)]
+    pub mcc: Option<String>,
+    // Copy of the receipt inside the app data directory
+    #[serde(default)]
+    pub receipt_path: Option<String>,
+    // Lowercase labels such as "donation" that feed reports
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Set when the charge is split with a partner
+    #[serde(default)]
+    pub shared: Option<SharedExpense>,
+    // Name, logo domain and category from the merchant knowledge base, when it knows the merchant
+    #[serde(default)]
+    pub merchant_info: Option<MerchantInfo>,
+    // Entered by hand rather than read from a statement
+    #[serde(default)]
+    pub manual: bool,
+    // What the rules made of it when it was stored. Later rule changes only reach stored
+    // history when recategorize_all re-runs them, so past months don't shift under the user.
+    #[serde(default)]
+    pub rule_category: Option<String>,
+    // The row as its file had it, so a re-import still finds it after the user edits it
+    #[serde(default)]
+    pub import_key: Option<String>,
+}
+
+// Debits are spending; credits are payments and refunds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionType {
+    #[default]
+    Debit,
+    Credit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    Purchases,
+    Payments,
+    Fees,
+    Interest,
+    CashAdvances,
+    Other,
+}
+
+impl SectionKind {
+    // Reads a section title such as "Payments and Other Credits"
+    fn from_title(title: &str) -> SectionKind {
+        let lower = title.to_lowercase();
+        if lower.contains("payment") || lower.contains("credit") || lower.contains("refund") {
+            SectionKind::Payments
+        } else if lower.contains("cash advance") {
+            SectionKind::CashAdvances
+        } else if lower.contains("fee") {
+            SectionKind::Fees
+        } else if lower.contains("interest") {
+            SectionKind::Interest
+        } else if lower.contains("purchase") || lower.contains("transaction") || lower.contains("debit") {
+            SectionKind::Purchases
+        } else {
+            SectionKind::Other
+        }
+    }
+}
+
+impl Transaction {
+    // A plain purchase; importers fill in whatever optional detail their format carries
+    pub fn new(date: String, description: String, amount: f64) -> Self {
+        Transaction {
+            date,
+            description: text::clean(&description),
+            amount,
+            category: None,
+            currency: None,
+            country: None,
+            time: None,
+            mcc: None,
+            rewards: None,
+            order_id: None,
+            payment_id: None,
+            section: None,
+            transaction_type: TransactionType::Debit,
+            note: None,
+            receipt_path: None,
+            tags: Vec::new(),
+            shared: None,
+            merchant_info: None,
+            manual: false,
+            rule_category: None,
+            import_key: None,
+        }
+    }
+}
+
+// Defaults fill in fields that saved analyses from older versions don't have
+#[derive(Debug, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AnalysisResult {
+    pub spending_categories: Vec<CategoryTotal>,
+    // The same spending grouped into needs, wants and savings/debt
+    pub spending_classes: Vec<ClassTotal>,
+    pub top_merchants: Vec<MerchantTotal>,
+    pub largest_transactions: Vec<LargeTransaction>,
+    // All-time highs across the stored history, this statement included
+    pub records: Option<LifetimeRecords>,
+    pub monthly_total: f64,
+    pub insights: Vec<Insight>,
+    pub transaction_count: usize,
+    pub cash_advance: Option<CashAdvanceWarning>,
+    pub travel: Option<TravelSummary>,
+    // Oldest first
+    pub trips: Vec<Trip>,
+    // Active as of the statement's last charge, judged on it and everything imported before
+    pub subscriptions: Vec<Subscription>,
+    // Merchants that started charging in this statement, likely at the end of a free trial
+    pub trial_conversions: Vec<TrialConversion>,
+    // Active subscriptions that do the same job, e.g. two music services
+    pub service_overlaps: Vec<ServiceOverlap>,
+    // Food & Dining split into groceries and eating out
+    pub food: Option<FoodReport>,
+    // Fill-ups and fuel spend, when the statement has any
+    pub vehicle: Option<VehicleReport>,
+    pub merchant_growth: Option<MerchantGrowthReport>,
+    pub heatmap: Option<SpendingHeatmap>,
+    pub merchant_concentration: Option<ParetoAnalysis>,
+    pub micro_spending: Option<MicroSpendingReport>,
+    pub round_up: Option<RoundUpSimulation>,
+    // Spending against the monthly income from settings, when one is set
+    pub income: Option<IncomeReport>,
+    // Business against personal spending, when any charge counts as business
+    pub business: Option<BusinessSplit>,
+    pub goals: Vec<GoalStatus>,
+    pub rewards_earned: Option<f64>,
+    pub statement_meta: Option<StatementMeta>,
+    // How signs in a single-amount CSV were read, so a wrong guess can be overridden
+    pub sign_convention: Option<SignDetection>,
+    // Rows a lenient parse skipped, and why
+    #[serde(default)]
+    pub parse_warnings: Vec<ParseWarning>,
+    // From registered analyzers that don't have a field above
+    pub sections: Vec<AnalyzerSection>,
+}
+
+// Bump when a parser change should invalidate cached parses
+pub const PARSER_VERSION: u32 = 4;
+
+// A CSV reader's transactions, how their signs were read, and the rows it skipped
+pub type ParsedRows = (Vec<Transaction>, Option<SignDetection>, Vec<ParseWarning>);
+
+// What a parser got out of a file: the transactions, plus the statement summary if it has one
+pub struct ParsedStatement {
+    pub transactions: Vec<Transaction>,
+    pub meta: Option<StatementMeta>,
+    pub sign_convention: Option<SignDetection>,
+    // Rows skipped in lenient mode
+    pub warnings: Vec<ParseWarning>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CategoryTotal {
+    pub category: String,
+    // Stable id, color and icon from the category config, so charts don't need their own mapping
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub color: String,
+    #[serde(default)]
+    pub icon: String,
+    pub total: f64,
+    pub percentage: f64,
+}
+
+impl CategoryTotal {
+    pub fn new(category: String, total: f64, percentage: f64) -> Self {
+        let style = categories::style(&category);
+        CategoryTotal {
+            category,
+            id: style.id,
+            color: style.color,
+            icon: style.icon,
+            total,
+            percentage,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantTotal {
+    pub merchant: String,
+    pub total: f64,
+    pub count: u32,
+    // From the merchant knowledge base, or looked up online when merchant logos are on
+    #[serde(default)]
+    pub domain: Option<String>,
+    // Cached logo image on disk, only when merchant logos are on
+    #[serde(default)]
+    pub logo_path: Option<String>,
+}
+
+// parse_file's boxed errors can't cross threads, so they're reduced to what the handler needs
+pub enum ParseFailure {
+    NeedsPassword,
+    WrongPassword,
+    // The user has to say which column is which, through a bank profile
+    AmbiguousColumns(bank_profiles::AmbiguousColumns),
+    Failed(String),
+}
+
+impl From<Box<dyn std::error::Error>> for ParseFailure {
+    fn from(e: Box<dyn std::error::Error>) -> Self {
+        if let Some(ambiguity) = e.downcast_ref::<bank_profiles::AmbiguousColumns>() {
+            return ParseFailure::AmbiguousColumns(bank_profiles::AmbiguousColumns { role: ambiguity.role });
+        }
+        match e.downcast_ref::<pdf::PdfError>() {
+            Some(pdf::PdfError::NeedsPassword) => ParseFailure::NeedsPassword,
+            Some(pdf::PdfError::WrongPassword) => ParseFailure::WrongPassword,
+            _ => ParseFailure::Failed(e.to_string()),
+        }
+    }
+}
+
+// `bytes` is the file as already read by the caller, if it could be read as a file
+pub fn parse_file(file_path: &str, bytes: Option<Vec<u8>>, password: Option<&str>, sign_override: Option<SignConvention>, profiles: &[BankProfile], mode: ParseMode, language: StatementLanguage) -> Result<ParsedStatement, Box<dyn std::error::Error>> {
+    // Purchase alert emails stand in for statements the user doesn't have
+    if alerts::is_alert_source(std::path::Path::new(file_path)) {
+        let transactions = alerts::parse_alerts(std::path::Path::new(file_path))?;
+        log!("Reconstructed {} transactions from alert emails", transactions.len());
+        return Ok(ParsedStatement { transactions, meta: None, sign_convention: None, warnings: Vec::new() });
+    }
+    
+    let bytes = match bytes {
+        Some(bytes) => bytes,
+        None => fs::read(file_path)?,
+    };
+    parse_bytes(file_path, bytes, password, sign_override, profiles, mode, language)
+}
+
+// A file already in memory. `file_name` is only looked at for its extension, so nothing here
+// touches the filesystem; the WASM build parses uploads through this.
+pub fn parse_bytes(file_name: &str, bytes: Vec<u8>, password: Option<&str>, sign_override: Option<SignConvention>, profiles: &[BankProfile], mode: ParseMode, language: StatementLanguage) -> Result<ParsedStatement, Box<dyn std::error::Error>> {
+    if file_name.to_lowercase().ends_with(".pdf") {
+        let (transactions, text) = pdf::parse_pdf(&bytes, password, language)?;
+        log!("Parsed {} transactions from PDF", transactions.len());
+        return Ok(ParsedStatement { transactions, meta: statement_meta::extract(&text), sign_convention: None, warnings: Vec::new() });
+    }
+    
+    let mut content = String::from_utf8(bytes)?;
+    // Excel writes a byte-order mark that would otherwise stick to the first header
+    if content.starts_with('\u{feff}') {
+        content.remove(0);
+    }
+    // Saved web pages are reduced to their transaction tables, then read like any other CSV
+    let mut is_csv = file_name.to_lowercase().ends_with(".csv");
+    let mut meta_text = None;
+    if html::is_html(file_name, &content) {
+        meta_text = Some(html::visible_text(&content));
+        content = html::transactions_csv(&content)?;
+        is_csv = true;
+    }
+    let mut transactions = Vec::new();
+    let mut sign_convention = None;
+    let mut warnings = Vec::new();
+    
+    if camt053::is_camt053(&content) {
+        transactions = camt053::parse_camt053(&content)?;
+    } else if mt940::is_mt940(&content) {
+        transactions = mt940::parse_mt940(&content)?;
+    } else if let Some(profile) = bank_profiles::matching(profiles, &content) {
+        log!("Reading with bank profile {}", profile.name);
+        (transactions, sign_convention, warnings) = bank_profiles::parse_with_profile(&content, profile, sign_override, mode)?;
+    } else if let Some(source) = migration::detect_source(&content) {
+        transactions = migration::parse_export(&content, source)?;
+    } else if let Some(wallet) = wallets::detect_wallet(&content) {
+        transactions = wallets::wallet_transactions(&wallets::parse_activity(&content, wallet)?);
+    } else if indian_banks::is_indian_bank_export(&content) {
+        transactions = indian_banks::parse_indian_bank(&content)?;
+    } else if is_csv && apple_card::is_apple_card_export(&content) {
+        transactions = apple_card::parse_apple_card(&content)?;
+    } else if is_csv {
+        (transactions, sign_convention, warnings) = parse_csv(&content, sign_override, mode)?;
+    }
+    
+    log!("Parsed {} transactions", transactions.len());
+    if !warnings.is_empty() {
+        log!("Skipped {} unreadable rows", warnings.len());
+    }
+    let meta = statement_meta::extract(meta_text.as_deref().unwrap_or(&content));
+    Ok(ParsedStatement { transactions, meta, sign_convention, warnings })
+}
+
+// The bank or export format parse_bytes reads `content` as, in the same order, for naming a
+// file before it's imported. PDFs and plain CSVs have no format to name; the issuer on the
+// statement, when there is one, is the better label for those.
+pub fn statement_source(content: &str, profiles: &[BankProfile]) -> Option<String> {
+    if camt053::is_camt053(content) {
+        Some("CAMT.053".to_string())
+    } else if mt940::is_mt940(content) {
+        Some("MT940".to_string())
+    } else if let Some(profile) = bank_profiles::matching(profiles, content) {
+        Some(profile.name.clone())
+    } else if let Some(source) = migration::detect_source(content) {
+        Some(match source {
+            migration::MigrationSource::Mint => "Mint",
+            migration::MigrationSource::Ynab => "YNAB",
+            migration::MigrationSource::Monarch => "Monarch",
+        }.to_string())
+    } else if let Some(wallet) = wallets::detect_wallet(content) {
+        Some(match wallet {
+            wallets::Wallet::PayPal => "PayPal",
+            wallets::Wallet::Venmo => "Venmo",
+        }.to_string())
+    } else if let Some(bank) = indian_banks::bank_name(content) {
+        Some(bank.to_string())
+    } else if apple_card::is_apple_card_export(content) {
+        Some("Apple Card".to_string())
+    } else {
+        None
+    }
+}
+
+// Words that mark a row as the table's column names
+const HEADER_WORDS: &[&str] = &["date", "description", "amount", "merchant", "payee", "details", "narration", "debit", "credit"];
+// Preambles are a handful of account lines; a header further down than this isn't one
+const MAX_PREAMBLE_LINES: usize = 30;
+
+// Exports can open with account details ("Account: ****1234") before the column names. The
+// header is the first row of several cells where none is data and one names a known column.
+fn find_header_row(content: &str) -> Option<usize> {
+    content.lines().take(MAX_PREAMBLE_LINES).position(|line| {
+        let cells: Vec<String> = line.split(',')
+            .map(|cell| cell.trim().trim_matches('"').trim().to_lowercase())
+            .collect();
+        cells.len() >= 3 &&
+            !cells.iter().any(|cell| parse_amount(cell).is_ok() || parse_date(cell).is_some()) &&
+            cells.iter().any(|cell| HEADER_WORDS.iter().any(|word| cell.contains(word)))
+    })
+}
+
+// Blank-line separated blocks that each start with their own header, with the title line
+// above the header if there is one. A file with a single table yields nothing.
+fn csv_sections(content: &str) -> Vec<(SectionKind, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let blocks: Vec<&[&str]> = lines.split(|line| line.trim().trim_matches(',').is_empty())
+        .filter(|block| !block.is_empty())
+        .collect();
+
+    let sections: Vec<(SectionKind, String)> = blocks.iter().filter_map(|block| {
+        let body = block.join("\n");
+        let header_row = find_header_row(&body)?;
+        // The title is the last non-header line above the header, else the header itself
+        let title = header_row.checked_sub(1).map_or(block[header_row], |row| block[row]);
+        Some((SectionKind::from_title(title), block[header_row..].join("\n")))
+    }).collect();
+
+    if sections.len() > 1 { sections } else { Vec::new() }
+}
+
+// Transactions, plus how the signs were read if any table had a single signed amount column
+fn parse_csv(content: &str, sign_override: Option<SignConvention>, mode: ParseMode) -> Result<ParsedRows, Box<dyn std::error::Error>> {
+    let sections = csv_sections(content);
+    let tables: Vec<(Option<SectionKind>, String)> = if sections.is_empty() {
+        vec![(None, content.to_string())]
+    } else {
+        sections.into_iter().map(|(kind, body)| (Some(kind), body)).collect()
+    };
+
+    // Rows paired with whether their amount is still signed
+    let mut rows: Vec<(Transaction, bool)> = Vec::new();
+    let mut issues = RowIssues::new(mode);
+    for (kind, body) in tables {
+        if let Some(kind) = kind {
+            log!("Parsing {:?} section", kind);
+        }
+        let (transactions, signed) = parse_csv_table(&body, kind, &mut issues)?;
+        rows.extend(transactions.into_iter().map(|tx| (tx, signed)));
+    }
+
+    let signed: Vec<&Transaction> = rows.iter().filter(|(_, signed)| *signed).map(|(tx, _)| tx).collect();
+    let detection = (!signed.is_empty()).then(|| match sign_override {
+        Some(convention) => SignDetection::overridden(convention),
+        None => sign_convention::detect(&signed),
+    });
+
+    let transactions = rows.into_iter().map(|(mut tx, signed)| {
+        if let (true, Some(detection)) = (signed, &detection) {
+            sign_convention::apply(&mut tx, detection.convention);
+        }
+        tx
+    }).collect();
+    Ok((transactions, detection, issues.warnings))
+}
+
+// Rows of one table. Amounts from a single amount column are left signed (the flag is true)
+// for parse_csv to interpret; split debit/credit columns already say which way money went.
+fn parse_csv_table(content: &str, section: Option<SectionKind>, issues: &mut RowIssues) -> Result<(Vec<Transaction>, bool), Box<dyn std::error::Error>> {
+    let mut transactions = Vec::new();
+    let header_row = find_header_row(content);
+    if let Some(row) = header_row.filter(|row| *row > 0) {
+        log!("Skipping {} preamble lines", row);
+    }
+    let body = content.lines().skip(header_row.unwrap_or(0)).collect::<Vec<_>>().join("\n");
+    let mut rdr = csv::ReaderBuilder::new().has_headers(false).from_reader(body.as_bytes());
+    let mut records: Vec<csv::StringRecord> = rdr.records().collect::<Result<_, _>>()?;
+    
+    // A first line with a date or amount in it is already data: the file has no header
+    let headerless = header_row.is_none() && records.first()
+        .is_some_and(|first| first.iter().any(|cell| parse_date(cell).is_some() || parse_amount(cell).is_ok()));
+    let headers = if headerless || records.is_empty() { csv::StringRecord::new() } else { records.remove(0) };
+    log!("CSV Headers: {:?}", headers);
+    
+    let named_date = find_column(&headers, &["date"]);
+    let named_amount = find_column(&headers, &["amount"]);
+    // Some exports split money out and in across "Debit" and "Credit" columns instead
+    let named_debit = find_column(&headers, &["debit", "withdrawal"]);
+    let named_credit = find_column(&headers, &["credit", "deposit"]).filter(|col| Some(*col) != named_debit);
+    let named_split = named_debit.is_some() && named_credit.is_some();
+    
+    // Named columns when the header has them; otherwise the values say which is which, and
+    // failing that it's date, description, amount in order
+    let inferred = if named_date.is_none() || (named_amount.is_none() && !named_split) {
+        bank_profiles::infer_columns(&records)?
+    } else {
+        None
+    };
+    let (date_col, description_col, amount_col, debit_col, credit_col) = match inferred {
+        Some(inferred) => {
+            log!("Inferred columns from their values: {:?}", inferred);
+            (inferred.date, inferred.description, inferred.amount.unwrap_or(2), inferred.debit, inferred.credit)
+        }
+        None => (
+            named_date.unwrap_or(0),
+            find_column(&headers, &["description", "merchant", "payee", "details", "narration"]).unwrap_or(1),
+            named_amount.unwrap_or(2),
+            named_debit,
+            named_credit,
+        ),
+    };
+    let split = debit_col.is_some() && credit_col.is_some();
+    
+    // Optional columns some issuers include for foreign purchases
+    let currency_col = find_column(&headers, &["currency"]);
+    let country_col = find_column(&headers, &["country"]);
+    let time_col = find_column(&headers, &["time"]);
+    let mcc_col = find_column(&headers, &["mcc"]);
+    
+    for (index, record) in records.into_iter().enumerate() {
+        let row = index + 1;
+        if record.len() < 3 {
+            if record.iter().any(|cell| !cell.trim().is_empty()) {
+                issues.report(row, format!("expected at least 3 columns, found {}", record.len()))?;
+            }
+            continue;
+        }
+        let date = record.get(date_col).unwrap_or("").to_string();
+        let description = text::clean(record.get(description_col).unwrap_or(""));
+        
+        // Skip header rows repeated in the data. Only an exact "transaction" is a header:
+        // real rows like "FOREIGN TRANSACTION FEE" must be kept.
+        let desc_lower = description.trim().to_lowercase();
+        if desc_lower.contains("description") || desc_lower == "transaction" || desc_lower == "transaction details" {
+            continue;
+        }
+        
+        // Split columns leave the unused side blank; debits come out positive
+        let amount = match (debit_col, credit_col) {
+            (Some(debit_col), Some(credit_col)) => {
+                let debit = optional_field(&record, Some(debit_col)).map(|d| parse_amount(&d)).transpose();
+                let credit = optional_field(&record, Some(credit_col)).map(|c| parse_amount(&c)).transpose();
+                debit.and_then(|debit| Ok(debit.unwrap_or(0.0).abs() - credit?.unwrap_or(0.0).abs()))
+            }
+            _ => parse_amount(record.get(amount_col).unwrap_or("0")),
+        };
+        let amount = match amount {
+            Ok(amount) => amount,
+            Err(e) => {
+                issues.report(row, format!("amount could not be read ({})", e))?;
+                continue;
+            }
+        };
+        if amount == 0.0 {
+            continue;
+        }
+        if parse_date(&date).is_none() {
+            issues.report(row, format!("date \"{}\" could not be read", date.trim()))?;
+            continue;
+        }
+        
+        transactions.push(Transaction {
+            date,
+            description,
+            amount: if split { amount.abs() } else { amount },
+            category: None,
+            currency: optional_field(&record, currency_col),
+            country: optional_field(&record, country_col),
+            time: optional_field(&record, time_col),
+            mcc: optional_field(&record, mcc_col),
+            rewards: None,
+            order_id: None,
+            payment_id: None,
+            section,
+            transaction_type: if (split && amount < 0.0) || section == Some(SectionKind::Payments) {
+                TransactionType::Credit
+            } else {
+                TransactionType::Debit
+            },
+            note: None,
+            receipt_path: None,
+            tags: Vec::new(),
+            shared: None,
+            merchant_info: None,
+            manual: false,
+            rule_category: None,
+            import_key: None,
+        });
+    }
+    
+    Ok((transactions, !split))
+}
+
+fn find_column(headers: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+    headers.iter().position(|header| {
+        let header_lower = header.trim().to_lowercase();
+        names.iter().any(|name| header_lower.contains(name))
+    })
+}
+
+fn optional_field(record: &csv::StringRecord, column: Option<usize>) -> Option<String> {
+    column
+        .and_then(|index| record.get(index))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn parse_amount(amount_str: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    // Remove common currency symbols and formatting
+    let cleaned = amount_str
+        .replace("$", "")
+        .replace(",", "")
+        .replace("(", "-")
+        .replace(")", "")
+        .trim()
+        .to_string();
+    
+    let amount = cleaned.parse::<f64>()?;
+    // f64 parsing accepts "NaN" and "inf", which would poison every total and sort
+    if !amount.is_finite() {
+        return Err(format!("Amount is not a number: {}", amount_str).into());
+    }
+    Ok(amount)
+}
+
+pub fn parse_date(date_str: &str) -> Option<NaiveDate> {
+    // Common export formats, US month-first before day-first
+    let formats = ["%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y", "%d/%m/%Y", "%Y/%m/%d", "%b %d, %Y", "%d %b %Y"];
+    let trimmed = date_str.trim();
+    
+    // %Y happily reads "24" as the year 24, so two-digit years fall through to %y
+    formats.iter()
+        .find_map(|format| NaiveDate::parse_from_str(trimmed, format).ok().filter(|date| date.year() >= 1900))
+        .or_else(|| parse_datetime(trimmed).map(|dt| dt.date()))
+}
+
+// Days covered by the transactions, used to project totals to a month or year
+pub fn statement_period_days(transactions: &[Transaction]) -> i64 {
+    let dates: Vec<NaiveDate> = transactions.iter().filter_map(|t| parse_date(&t.date)).collect();
+    
+    match (dates.iter().min(), dates.iter().max()) {
+        // A statement with a few days of activity still covers a full cycle
+        (Some(first), Some(last)) => ((*last - *first).num_days() + 1).max(28),
+        _ => 30,
+    }
+}
+
+// Scales a statement's totals to an average month
+pub fn monthly_factor(transactions: &[Transaction]) -> f64 {
+    (365.0 / 12.0) / statement_period_days(transactions) as f64
+}
+
+// Calendar month ("2024-01") a transaction date falls in
+pub fn month_key(date_str: &str) -> Option<String> {
+    parse_date(date_str).map(periods::period_key)
+}
+
+fn parse_datetime(datetime_str: &str) -> Option<NaiveDateTime> {
+    let formats = [
+        "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M",
+        "%m/%d/%Y %H:%M:%S", "%m/%d/%Y %H:%M", "%m/%d/%Y %I:%M %p", "%m/%d/%Y %I:%M:%S %p",
+    ];
+    let trimmed = datetime_str.trim();
+    
+    formats.iter().find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+}
+
+// Timestamps come either as a separate time column or embedded in the date
+pub fn parse_timestamp(date_str: &str, time_str: Option<&str>) -> Option<NaiveDateTime> {
+    if let Some(time_str) = time_str {
+        let formats = ["%H:%M:%S", "%H:%M", "%I:%M %p", "%I:%M:%S %p"];
+        let time = formats.iter().find_map(|format| NaiveTime::parse_from_str(time_str.trim(), format).ok());
+        if let (Some(date), Some(time)) = (parse_date(date_str), time) {
+            return Some(date.and_time(time));
+        }
+        if let Some(datetime) = parse_datetime(time_str) {
+            return Some(datetime);
+        }
+    }
+    
+    parse_datetime(date_str)
+}
+
+pub async fn analyze_transactions(transactions: Vec<Transaction>, file_path: &str, history: &[&StoredStatement], settings: &Settings) -> AnalysisResult {
+    let total_amount: f64 = transactions.iter().map(|t| t.amount).sum();
+    
+    // Categorize transactions
+    let categorized = categorize_transactions(&transactions);
+    let categories = calculate_categories(&categorized, total_amount);
+    let classes = categories::class_totals(&transactions, &settings.category_classes);
+    
+    // Find top merchants
+    let merchants = find_top_merchants(&transactions);
+    let largest_transactions = records::largest_transactions(&transactions, records::LARGEST_TRANSACTIONS);
+    
+    let mut analysis = AnalysisResult {
+        spending_categories: categories,
+        spending_classes: classes,
+        top_merchants: merchants,
+        largest_transactions,
+        monthly_total: total_amount,
+        transaction_count: transactions.len(),
+        ..AnalysisResult::default()
+    };
+    
+    // Everything past the totals comes from the registered analyzers, built-ins included
+    let input = analyzers::AnalyzerInput { transactions: &transactions, history, settings, file_path };
+    analyzers::run_all(&input, &mut analysis);
+    insights::rank(&mut analysis.insights);
+    analysis
+}
+
+fn categorize_transactions(transactions: &[Transaction]) -> Vec<Transaction> {
+    transactions.iter().map(|t| {
+        let mut tx = t.clone();
+        tx.category = Some(transaction_category(t));
+        tx
+    }).collect()
+}
+
+// Categories set on import (e.g. from Amazon order history) or by the user win over the rules,
+// then what the rules said when the transaction was stored
+pub fn transaction_category(transaction: &Transaction) -> String {
+    transaction.category.clone()
+        .or_else(|| transaction.rule_category.clone())
+        .unwrap_or_else(|| rule_category(transaction))
+}
+
+// The rules as they stand now: a food MCC, then what the merchant knowledge base says, then
+// the keyword rules
+pub fn rule_category(transaction: &Transaction) -> String {
+    food::mcc_kind(transaction).map(|_| "Food & Dining".to_string())
+        .or_else(|| merchants::known_category(transaction))
+        .unwrap_or_else(|| categorize_description(&transaction.description))
+}
+
+// Card charges rather than purchases: fees, interest, and the fees on cash advances
+pub fn is_fee_or_interest(transaction: &Transaction) -> bool {
+    matches!(transaction.section, Some(SectionKind::Fees | SectionKind::Interest))
+        || transaction_category(transaction) == "Fees"
+        || cash_advance::is_cash_advance_fee(&transaction.description)
+}
+
+pub fn categorize_description(description: &str) -> String {
+    let desc_lower = text::fold(description);
+    
+    // Simple keyword-based categorization
+    if cash_advance::is_cash_advance(description) || cash_advance::is_cash_advance_fee(description) {
+        "Cash Advance".to_string()
+    } else if travel::is_fx_fee(description) {
+        "Fees".to_string()
+    } else if desc_lower.contains("restaurant") || desc_lower.contains("food") || 
+       desc_lower.contains("starbucks") || desc_lower.contains("mcdonald") ||
+       desc_lower.contains("pizza") || desc_lower.contains("cafe") ||
+       food::description_kind(description).is_some() {
+        "Food & Dining".to_string()
+    } else if desc_lower.contains("gas") || desc_lower.contains("fuel") ||
+              desc_lower.contains("shell") || desc_lower.contains("chevron") ||
+              desc_lower.contains("exxon") || desc_lower.contains("uber") ||
+              desc_lower.contains("lyft") {
+        "Gas & Transportation".to_string()
+    } else if desc_lower.contains("amazon") || desc_lower.contains("target") ||
+              desc_lower.contains("walmart") || desc_lower.contains("store") {
+        "Shopping".to_string()
+    } else if desc_lower.contains("netflix") || desc_lower.contains("spotify") ||
+              desc_lower.contains("movie") || desc_lower.contains("entertainment") {
+        "Entertainment".to_string()
+    } else if desc_lower.contains("pharmacy") || desc_lower.contains("medical") ||
+              desc_lower.contains("doctor") || desc_lower.contains("health") {
+        "Healthcare".to_string()
+    } else if let Some(category) = keyword_packs::category(description) {
+        category.to_string()
+    } else {
+        "Other".to_string()
+    }
+}
+
+fn calculate_categories(transactions: &[Transaction], total: f64) -> Vec<CategoryTotal> {
+    let mut category_totals: HashMap<String, f64> = HashMap::new();
+    
+    for tx in transactions {
+        if let Some(category) = &tx.category {
+            *category_totals.entry(category.clone()).or_insert(0.0) += tx.amount;
+        }
+    }
+    
+    let mut categories: Vec<CategoryTotal> = category_totals
+        .into_iter()
+        .map(|(category, amount)| CategoryTotal::new(category, amount, (amount / total) * 100.0))
+        .collect();
+    
+    categories.sort_by(|a, b| b.total.total_cmp(&a.total));
+    categories
+}
+
+fn find_top_merchants(transactions: &[Transaction]) -> Vec<MerchantTotal> {
+    let mut merchants = merchant_totals(transactions);
+    merchants.truncate(5); // Top 5 merchants
+    merchants
+}
+
+// Every merchant with its total and transaction count, largest first
+pub fn merchant_totals<'a>(transactions: impl IntoIterator<Item = &'a Transaction>) -> Vec<MerchantTotal> {
+    let mut merchant_totals: HashMap<String, (f64, u32, Option<String>)> = HashMap::new();
+    
+    for tx in transactions {
+        // Extract merchant name (first few words)
+        let merchant = extract_merchant_name(&tx.description);
+        let entry = merchant_totals.entry(merchant).or_insert((0.0, 0, None));
+        entry.0 += tx.amount;
+        entry.1 += 1;
+        if entry.2.is_none() {
+            entry.2 = tx.merchant_info.clone().or_else(|| merchants::lookup(&tx.description)).map(|info| info.domain);
+        }
+    }
+    
+    let mut merchants: Vec<MerchantTotal> = merchant_totals
+        .into_iter()
+        .map(|(merchant, (total, count, domain))| MerchantTotal {
+            merchant,
+            total,
+            count,
+            domain,
+            logo_path: None,
+        })
+        .collect();
+    
+    merchants.sort_by(|a, b| b.total.total_cmp(&a.total));
+    merchants
+}
+
+// Chinese and Japanese descriptors run the merchant and branch together with no spaces, so
+// the first two "words" can be the whole line
+const MAX_MERCHANT_GRAPHEMES: usize = 24;
+
+pub fn extract_merchant_name(description: &str) -> String {
+    // Simple merchant name extraction - take first 2-3 words
+    let description = text::clean(description);
+    let words: Vec<&str> = description.split_whitespace().take(2).collect();
+    text::fold(text::prefix(&words.join(" "), MAX_MERCHANT_GRAPHEMES)).to_uppercase()
+}
+
+fn generate_insights(transactions: &[Transaction], categories: &[CategoryTotal], micro_spending: Option<&MicroSpendingReport>, file_path: &str) -> Vec<Insight> {
+    let mut insights = Vec::new();
+    let file_name = file_path.split('/').next_back().unwrap_or(file_path);
+    
+    insights.push(Insight::new(
+        InsightKind::Summary,
+        Severity::Info,
+        "summary.analyzed",
+        format!("Successfully analyzed {} transactions from {}", transactions.len(), file_name),
+    )
+    .with_param("count", transactions.len())
+    .with_param("file", file_name));
+    
+    if let Some(top_category) = categories.first() {
+        insights.push(Insight::new(
+            InsightKind::TopCategory,
+            Severity::Info,
+            "summary.top_category",
+            format!("Your largest spending category is {} at {:.1}% of total spending", 
+                    top_category.category, top_category.percentage),
+        )
+        .with_amount(top_category.total)
+        .with_category(&top_category.category)
+        .with_param("category", &top_category.category)
+        .with_param("percentage", format!("{:.1}", top_category.percentage)));
+    }
+    
+    // Frequent small transactions
+    if let Some(insight) = micro_spending.and_then(micro_spending::micro_spending_insight) {
+        insights.push(insight);
+    }
+    
+    insights.push(Insight::new(
+        InsightKind::Tip,
+        Severity::Info,
+        "tip.spending_alerts",
+        "Consider setting up spending alerts for your top categories".to_string(),
+    ));
+    
+    insights
+}
+
+// Entry points for the cargo-fuzz targets in fuzz/, only built under `cargo fuzz`. Each runs
+// a parser and the aggregation its output feeds, discarding errors: only panics are failures.
+#[cfg(fuzzing)]
+pub mod fuzzing {
+    use crate::{calculate_categories, categorize_transactions, find_top_merchants, Transaction};
+
+    fn aggregate(transactions: &[Transaction]) {
+        let total: f64 = transactions.iter().map(|t| t.amount).sum();
+        let _ = calculate_categories(&categorize_transactions(transactions), total);
+        let _ = find_top_merchants(transactions);
+    }
+
+    pub fn parse_csv(content: &str) {
+        if let Ok((transactions, _, _)) = crate::parse_csv(content, None, crate::parse_mode::ParseMode::Lenient) {
+            aggregate(&transactions);
+        }
+    }
+
+    pub fn parse_amount(amount: &str) {
+        let _ = crate::parse_amount(amount);
+    }
+
+    pub fn parse_pdf_text(text: &str) {
+        use crate::statement_language::StatementLanguage;
+        for language in [StatementLanguage::English, StatementLanguage::French, StatementLanguage::German, StatementLanguage::Spanish] {
+            if let Ok(transactions) = crate::pdf::parse_statement_text(text, language) {
+                aggregate(&transactions);
+            }
+        }
+    }
+
+    pub fn parse_bank_text(content: &str) {
+        if let Ok(transactions) = crate::camt053::parse_camt053(content) {
+            aggregate(&transactions);
+        }
+        if let Ok(transactions) = crate::mt940::parse_mt940(content) {
+            aggregate(&transactions);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Groups the integer part with commas, either in threes or Indian lakh style (1,23,456)
+    fn group_digits(whole: u64, lakh: bool) -> String {
+        let digits = whole.to_string();
+        if digits.len() <= 3 {
+            return digits;
+        }
+        let (head, tail) = digits.split_at(digits.len() - 3);
+        let size = if lakh { 2 } else { 3 };
+        let mut groups: Vec<String> = Vec::new();
+        let mut rest = head;
+        while rest.len() > size {
+            let (front, back) = rest.split_at(rest.len() - size);
+            groups.insert(0, back.to_string());
+            rest = front;
+        }
+        groups.insert(0, rest.to_string());
+        format!("{},{}", groups.join(","), tail)
+    }
+
+    proptest! {
+        #[test]
+        fn parse_amount_never_panics(input in "\\PC*") {
+            if let Ok(amount) = parse_amount(&input) {
+                prop_assert!(amount.is_finite());
+            }
+        }
+
+        #[test]
+        fn parse_amount_round_trips_formatted_amounts(
+            cents in 0u64..10_000_000_000,
+            lakh in any::<bool>(),
+            symbol in any::<bool>(),
+            sign in 0..3u8,
+        ) {
+            let body = format!("{}{}.{:02}", if symbol { "$" } else { "" }, group_digits(cents / 100, lakh), cents % 100);
+            // Unsigned, leading minus, or accounting parentheses
+            let input = match sign {
+                0 => body,
+                1 => format!("-{}", body),
+                _ => format!("({})", body),
+            };
+            let expected = cents as f64 / 100.0 * if sign == 0 { 1.0 } else { -1.0 };
+
+            let amount = parse_amount(&input).unwrap();
+            prop_assert!((amount - expected).abs() < 0.005, "{} parsed as {}", input, amount);
+        }
+
+        #[test]
+        fn parse_amount_keeps_sign(cents in 1u64..1_000_000_000) {
+            let body = format!("{}.{:02}", cents / 100, cents % 100);
+            prop_assert!(parse_amount(&body).unwrap() > 0.0);
+            let negative = parse_amount(&format!("-{}", body)).unwrap();
+            prop_assert!(negative < 0.0);
+            let parenthesised = parse_amount(&format!("({})", body)).unwrap();
+            prop_assert!(parenthesised < 0.0);
+        }
+
+        #[test]
+        fn parse_date_never_panics(input in "\\PC*") {
+            let _ = parse_date(&input);
+        }
+
+        #[test]
+        fn parse_date_round_trips_supported_formats(days in 0i64..60_000, format_index in 0..6usize) {
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days(days);
+            // Month-first formats win on ambiguous dates, so day-first is only checked where unambiguous
+            let format = ["%Y-%m-%d", "%m/%d/%Y", "%Y/%m/%d", "%b %d, %Y", "%d %b %Y", "%d/%m/%Y"][format_index];
+            prop_assume!(format != "%d/%m/%Y" || date.day() > 12);
+
+            let text = date.format(format).to_string();
+            prop_assert_eq!(parse_date(&text), Some(date), "{}", text);
+        }
+
+        #[test]
+        fn parse_date_reads_two_digit_years_as_recent(days in 0i64..36_000) {
+            // %y covers 1969-2068
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days(days);
+            let text = date.format("%m/%d/%y").to_string();
+            prop_assert_eq!(parse_date(&text), Some(date), "{}", text);
+        }
+
+        #[test]
+        fn strict_parse_fails_exactly_where_lenient_skips(rows in prop::collection::vec((any::<bool>(), any::<bool>(), 1u32..100_000), 1..20)) {
+            let mut content = String::from("Date,Description,Amount\n");
+            for (good_date, good_amount, cents) in &rows {
+                let date = if *good_date { "2024-03-15" } else { "someday" };
+                let amount = if *good_amount { format!("{}.{:02}", cents / 100, cents % 100) } else { "n/a".to_string() };
+                content.push_str(&format!("{},COFFEE SHOP,{}\n", date, amount));
+            }
+
+            let (transactions, _, warnings) = parse_csv(&content, None, ParseMode::Lenient).unwrap();
+            let bad = rows.iter().filter(|(good_date, good_amount, _)| !good_date || !good_amount).count();
+            prop_assert_eq!(warnings.len(), bad);
+            prop_assert_eq!(transactions.len(), rows.len() - bad);
+            prop_assert_eq!(parse_csv(&content, None, ParseMode::Strict).is_err(), bad > 0);
+        }
+
+        #[test]
+        fn european_statement_lines_parse(whole in 0u64..10_000_000, cents in 0u64..100, day in 1u32..29, month in 1u32..13) {
+            let grouped = group_digits(whole, false).replace(',', ".");
+            let text = format!("{:02}.{:02}. KARTENZAHLUNG REWE MARKT {},{:02} €\n", day, month, grouped, cents);
+            let transactions = crate::pdf::parse_statement_text(&text, StatementLanguage::German).unwrap();
+            prop_assert_eq!(transactions.len(), 1);
+            prop_assert_eq!(&transactions[0].description, "REWE MARKT");
+            prop_assert!((transactions[0].amount - (whole as f64 + cents as f64 / 100.0)).abs() < 0.005);
+        }
+
+        #[test]
+        fn truncation_never_splits_a_grapheme(text in "\\PC{0,40}", max in 0usize..20) {
+            use unicode_segmentation::UnicodeSegmentation;
+            let shortened = text::prefix(&text, max);
+            prop_assert!(text.starts_with(shortened));
+            prop_assert!(shortened.graphemes(true).count() <= max);
+        }
+
+        #[test]
+        fn keyword_packs_match_unspaced_descriptors(branch in "[0-9]{0,6}", keyword in prop::sample::select(vec!["咖啡", "カフェ", "카페", "مقهى", "קפה"])) {
+            let description = format!("星巴克{}上海店{}", keyword, branch);
+            prop_assert_eq!(categorize_description(&description), "Food & Dining");
+            prop_assert_eq!(categorize_description(&format!("\u{200F}{}\u{200F}", description)), "Food & Dining");
+        }
+
+        #[test]
+        fn latest_date_matches_the_interned_history(seed in 1u64..u64::MAX) {
+            let profile = synthetic::SyntheticProfile { seed: Some(seed), ..Default::default() };
+            let statements: Vec<history::StoredStatement> = synthetic::generate(&profile, NaiveDate::from_ymd_opt(2024, 7, 15).unwrap())
+                .into_iter()
+                .enumerate()
+                .map(|(id, (source_path, transactions))| history::StoredStatement {
+                    id: id as u64,
+                    source_path,
+                    imported_at: String::new(),
+                    transactions,
+                    meta: None,
+                    sync_id: String::new(),
+                })
+                .collect();
+            prop_assert_eq!(interner::latest_date(&statements), interner::CompactHistory::from_statements(&statements).latest_date());
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{i18n, merchants, parse_date, rule_category, Transaction};
+
+// What the user types in for a transaction missing from their statements, such as a cash
+// purchase, or when correcting one
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ManualEntry {
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    // None leaves it to the keyword rules, as for imported rows
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl ManualEntry {
+    pub fn validate(&self, locale: &str) -> Result<(), String> {
+        if parse_date(&self.date).is_none() {
+            return Err(i18n::text(locale, "error.entry_date_invalid", &[("date", self.date.clone())]));
+        }
+        if self.description.trim().is_empty() {
+            return Err(i18n::error(locale, "error.entry_description_empty"));
+        }
+        if !self.amount.is_finite() || self.amount <= 0.0 {
+            return Err(i18n::error(locale, "error.entry_amount_invalid"));
+        }
+        Ok(())
+    }
+
+    // Overwrites what the entry covers, keeping tags, receipts and the rest
+    pub fn apply(&self, transaction: &mut Transaction) {
+        transaction.date = parse_date(&self.date).map_or_else(|| self.date.clone(), |date| date.format("%Y-%m-%d").to_string());
+        transaction.description = self.description.trim().to_string();
+        transaction.amount = self.amount;
+        transaction.category = self.category.as_deref().map(str::trim).filter(|c| !c.is_empty()).map(str::to_string);
+        transaction.note = self.note.as_deref().map(str::trim).filter(|n| !n.is_empty()).map(str::to_string);
+        transaction.merchant_info = merchants::lookup(&transaction.description);
+        transaction.rule_category = Some(rule_category(transaction));
+    }
+
+    pub fn to_transaction(&self) -> Transaction {
+        let mut transaction = Transaction::new(String::new(), String::new(), 0.0);
+        transaction.manual = true;
+        self.apply(&mut transaction);
+        transaction
+    }
+}
+
+// Entries not added to a particular statement are kept together by month, like demo data
+pub fn statement_source(transaction: &Transaction) -> String {
+    format!("manual:{}", transaction.date.get(..7).unwrap_or(&transaction.date))
+}
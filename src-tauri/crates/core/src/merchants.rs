@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::services::ServiceClass::{self, *};
+use crate::{cash_advance, text, travel, Transaction};
+
+// What the knowledge base knows about a merchant, attached to its transactions on import
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct MerchantInfo {
+    // Display name, e.g. "Spotify" for "SPOTIFY USA 877-778-1161"
+    pub name: String,
+    // Where the frontend fetches the logo from
+    pub domain: String,
+    // None where the merchant sells too many kinds of thing to pick one
+    pub category: Option<String>,
+    pub service: Option<ServiceClass>,
+}
+
+struct KnownMerchant {
+    // Lowercase, matched anywhere in the descriptor
+    pattern: &'static str,
+    name: &'static str,
+    domain: &'static str,
+    category: Option<&'static str>,
+    service: Option<ServiceClass>,
+}
+
+const fn known(pattern: &'static str, name: &'static str, domain: &'static str, category: Option<&'static str>, service: Option<ServiceClass>) -> KnownMerchant {
+    KnownMerchant { pattern, name, domain, category, service }
+}
+
+const FOOD: Option<&str> = Some("Food & Dining");
+const TRANSPORT: Option<&str> = Some("Gas & Transportation");
+const SHOPPING: Option<&str> = Some("Shopping");
+const ENTERTAINMENT: Option<&str> = Some("Entertainment");
+const HEALTH: Option<&str> = Some("Healthcare");
+
+// First match wins, so a more specific pattern ("amazon music") goes before a broader one
+// ("amazon"). Bundles like Apple One or Amazon Prime cover several service classes and are
+// left without one rather than guessed at.
+const MERCHANTS: &[KnownMerchant] = &[
+    known("spotify", "Spotify", "spotify.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("apple music", "Apple Music", "music.apple.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("youtube music", "YouTube Music", "music.youtube.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("tidal", "Tidal", "tidal.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("deezer", "Deezer", "deezer.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("pandora", "Pandora", "pandora.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("amazon music", "Amazon Music", "music.amazon.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("siriusxm", "SiriusXM", "siriusxm.com", ENTERTAINMENT, Some(MusicStreaming)),
+    known("netflix", "Netflix", "netflix.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("hulu", "Hulu", "hulu.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("disney plus", "Disney+", "disneyplus.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("disneyplus", "Disney+", "disneyplus.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("hbo max", "Max", "max.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("max.com", "Max", "max.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("paramount", "Paramount+", "paramountplus.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("peacock", "Peacock", "peacocktv.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("apple tv", "Apple TV+", "tv.apple.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("youtube premium", "YouTube Premium", "youtube.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("crunchyroll", "Crunchyroll", "crunchyroll.com", ENTERTAINMENT, Some(VideoStreaming)),
+    known("dropbox", "Dropbox", "dropbox.com", None, Some(CloudStorage)),
+    known("google storage", "Google One", "one.google.com", None, Some(CloudStorage)),
+    known("google one", "Google One", "one.google.com", None, Some(CloudStorage)),
+    known("icloud", "iCloud+", "icloud.com", None, Some(CloudStorage)),
+    known("onedrive", "OneDrive", "onedrive.com", None, Some(CloudStorage)),
+    known("box.com", "Box", "box.com", None, Some(CloudStorage)),
+    known("backblaze", "Backblaze", "backblaze.com", None, Some(CloudStorage)),
+    known("nytimes", "The New York Times", "nytimes.com", None, Some(News)),
+    known("ny times", "The New York Times", "nytimes.com", None, Some(News)),
+    known("wsj", "The Wall Street Journal", "wsj.com", None, Some(News)),
+    known("washington post", "The Washington Post", "washingtonpost.com", None, Some(News)),
+    known("economist", "The Economist", "economist.com", None, Some(News)),
+    known("medium", "Medium", "medium.com", None, Some(News)),
+    known("substack", "Substack", "substack.com", None, Some(News)),
+    known("peloton", "Peloton", "onepeloton.com", HEALTH, Some(Fitness)),
+    known("planet fitness", "Planet Fitness", "planetfitness.com", HEALTH, Some(Fitness)),
+    known("strava", "Strava", "strava.com", HEALTH, Some(Fitness)),
+    known("classpass", "ClassPass", "classpass.com", HEALTH, Some(Fitness)),
+    known("fitbit premium", "Fitbit Premium", "fitbit.com", HEALTH, Some(Fitness)),
+    known("nordvpn", "NordVPN", "nordvpn.com", None, Some(Vpn)),
+    known("expressvpn", "ExpressVPN", "expressvpn.com", None, Some(Vpn)),
+    known("surfshark", "Surfshark", "surfshark.com", None, Some(Vpn)),
+    known("protonvpn", "Proton VPN", "protonvpn.com", None, Some(Vpn)),
+    known("1password", "1Password", "1password.com", None, Some(PasswordManager)),
+    known("lastpass", "LastPass", "lastpass.com", None, Some(PasswordManager)),
+    known("dashlane", "Dashlane", "dashlane.com", None, Some(PasswordManager)),
+    known("bitwarden", "Bitwarden", "bitwarden.com", None, Some(PasswordManager)),
+    known("xbox", "Xbox", "xbox.com", ENTERTAINMENT, Some(Gaming)),
+    known("playstation", "PlayStation", "playstation.com", ENTERTAINMENT, Some(Gaming)),
+    known("nintendo", "Nintendo", "nintendo.com", ENTERTAINMENT, Some(Gaming)),
+    known("ea play", "EA Play", "ea.com", ENTERTAINMENT, Some(Gaming)),
+    known("steam", "Steam", "steampowered.com", ENTERTAINMENT, None),
+    known("uber eats", "Uber Eats", "ubereats.com", FOOD, None),
+    known("ubereats", "Uber Eats", "ubereats.com", FOOD, None),
+    known("uber", "Uber", "uber.com", TRANSPORT, None),
+    known("lyft", "Lyft", "lyft.com", TRANSPORT, None),
+    known("doordash", "DoorDash", "doordash.com", FOOD, None),
+    known("grubhub", "Grubhub", "grubhub.com", FOOD, None),
+    known("instacart", "Instacart", "instacart.com", FOOD, None),
+    known("starbucks", "Starbucks", "starbucks.com", FOOD, None),
+    known("mcdonald", "McDonald's", "mcdonalds.com", FOOD, None),
+    known("chipotle", "Chipotle", "chipotle.com", FOOD, None),
+    known("dunkin", "Dunkin'", "dunkindonuts.com", FOOD, None),
+    known("whole foods", "Whole Foods Market", "wholefoodsmarket.com", FOOD, None),
+    known("trader joe", "Trader Joe's", "traderjoes.com", FOOD, None),
+    known("kroger", "Kroger", "kroger.com", FOOD, None),
+    known("safeway", "Safeway", "safeway.com", FOOD, None),
+    known("costco", "Costco", "costco.com", SHOPPING, None),
+    known("walmart", "Walmart", "walmart.com", SHOPPING, None),
+    known("target", "Target", "target.com", SHOPPING, None),
+    known("best buy", "Best Buy", "bestbuy.com", SHOPPING, None),
+    known("ikea", "IKEA", "ikea.com", SHOPPING, None),
+    known("home depot", "The Home Depot", "homedepot.com", SHOPPING, None),
+    known("etsy", "Etsy", "etsy.com", SHOPPING, None),
+    known("ebay", "eBay", "ebay.com", SHOPPING, None),
+    known("amzn", "Amazon", "amazon.com", SHOPPING, None),
+    known("amazon", "Amazon", "amazon.com", SHOPPING, None),
+    known("cvs", "CVS", "cvs.com", HEALTH, None),
+    known("walgreens", "Walgreens", "walgreens.com", HEALTH, None),
+    known("shell", "Shell", "shell.com", TRANSPORT, None),
+    known("chevron", "Chevron", "chevron.com", TRANSPORT, None),
+    known("exxon", "ExxonMobil", "exxon.com", TRANSPORT, None),
+    known("apple.com", "Apple", "apple.com", None, None),
+    known("paypal", "PayPal", "paypal.com", None, None),
+];
+
+fn is_card_charge(description: &str) -> bool {
+    travel::is_fx_fee(description) || cash_advance::is_cash_advance(description) || cash_advance::is_cash_advance_fee(description)
+}
+
+// Fees and cash advances often repeat the merchant's name but aren't a purchase from it
+pub fn lookup(description: &str) -> Option<MerchantInfo> {
+    if is_card_charge(description) {
+        return None;
+    }
+    let lower = text::fold(description);
+    MERCHANTS.iter()
+        .find(|merchant| lower.contains(merchant.pattern))
+        .map(|merchant| MerchantInfo {
+            name: merchant.name.to_string(),
+            domain: merchant.domain.to_string(),
+            category: merchant.category.map(str::to_string),
+            service: merchant.service,
+        })
+}
+
+// Attaches what's known about each transaction's merchant. Returns how many were recognized.
+pub fn enrich_transactions(transactions: &mut [Transaction]) -> usize {
+    let mut known = 0;
+    for tx in transactions.iter_mut() {
+        tx.merchant_info = lookup(&tx.description);
+        if tx.merchant_info.is_some() {
+            known += 1;
+        }
+    }
+    known
+}
+
+// The knowledge base's category, from the attached info or, for transactions imported before
+// enrichment existed, from the descriptor
+pub fn known_category(transaction: &Transaction) -> Option<String> {
+    match &transaction.merchant_info {
+        Some(info) => info.category.clone(),
+        None => lookup(&transaction.description)?.category,
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{merchant_totals, statement_period_days, MerchantTotal, Transaction};
+
+const TOP_MERCHANTS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MicroSpendingReport {
+    pub threshold: f64,
+    pub count: usize,
+    pub total: f64,
+    pub top_merchants: Vec<MerchantTotal>,
+    pub annualized: f64,
+}
+
+pub fn micro_spending_report(transactions: &[Transaction], threshold: f64) -> Option<MicroSpendingReport> {
+    let micro: Vec<&Transaction> = transactions.iter()
+        .filter(|t| t.amount < threshold)
+        .collect();
+
+    if micro.is_empty() {
+        return None;
+    }
+
+    let total: f64 = micro.iter().map(|t| t.amount).sum();
+    let mut top_merchants = merchant_totals(micro.iter().copied());
+    top_merchants.truncate(TOP_MERCHANTS);
+
+    let period_days = statement_period_days(transactions);
+    let annualized = total / period_days as f64 * 365.0;
+
+    Some(MicroSpendingReport {
+        threshold,
+        count: micro.len(),
+        total,
+        top_merchants,
+        annualized,
+    })
+}
+
+pub fn micro_spending_insight(report: &MicroSpendingReport) -> Option<Insight> {
+    // A handful of small purchases isn't a habit
+    if report.count <= 5 {
+        return None;
+    }
+
+    let mut message = format!("You have {} small transactions (under ${}) totaling ${:.2} — about ${:.0} a year at this pace",
+                              report.count, report.threshold, report.total, report.annualized);
+    let mut message_key = "micro_spending.summary";
+    if let Some(top) = report.top_merchants.first() {
+        message.push_str(&format!(", led by {} ({} visits)", top.merchant, top.count));
+        message_key = "micro_spending.summary_led_by";
+    }
+
+    let mut insight = Insight::new(InsightKind::MicroSpending, Severity::Notice, message_key, message)
+        .with_amount(report.total)
+        .with_param("count", report.count)
+        .with_param("threshold", report.threshold)
+        .with_param("total", format!("{:.2}", report.total))
+        .with_param("annual", format!("{:.0}", report.annualized));
+    if let Some(top) = report.top_merchants.first() {
+        insight = insight
+            .with_param("merchant", &top.merchant)
+            .with_param("visits", top.count);
+    }
+    for merchant in &report.top_merchants {
+        insight = insight.with_merchant(&merchant.merchant);
+    }
+    Some(insight)
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{optional_field, parse_amount, Transaction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationSource {
+    Mint,
+    Ynab,
+    Monarch,
+}
+
+// Categories these tools use for money moving between accounts rather than spending
+const NON_SPENDING_CATEGORIES: &[&str] = &[
+    "transfer", "credit card payment", "income", "paycheck", "ready to assign",
+    "inflow", "to be budgeted", "interest income", "reimbursement",
+];
+
+pub fn detect_source(content: &str) -> Option<MigrationSource> {
+    let header = content.lines().next().unwrap_or("").to_lowercase();
+
+    if header.contains("original description") && header.contains("transaction type") {
+        Some(MigrationSource::Mint)
+    } else if header.contains("outflow") && header.contains("inflow") && header.contains("payee") {
+        Some(MigrationSource::Ynab)
+    } else if header.contains("original statement") && header.contains("merchant") {
+        Some(MigrationSource::Monarch)
+    } else {
+        None
+    }
+}
+
+fn column(headers: &csv::StringRecord, name: &str) -> Option<usize> {
+    headers.iter().position(|header| header.trim().eq_ignore_ascii_case(name))
+}
+
+fn is_spending_category(category: &str) -> bool {
+    let category_lower = category.to_lowercase();
+    !NON_SPENDING_CATEGORIES.iter().any(|skip| category_lower.contains(skip))
+}
+
+fn spending(date: String, description: String, amount: f64, category: Option<String>) -> Transaction {
+    Transaction {
+        category,
+        ..Transaction::new(date, description, amount)
+    }
+}
+
+fn amount_field(record: &csv::StringRecord, column: Option<usize>) -> f64 {
+    optional_field(record, column)
+        .and_then(|value| parse_amount(&value).ok())
+        .unwrap_or(0.0)
+}
+
+// Category assignments are carried over verbatim so migrated history keeps the user's own labels
+pub fn parse_export(content: &str, source: MigrationSource) -> Result<Vec<Transaction>, String> {
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
+    let headers = rdr.headers()
+        .map_err(|e| format!("Could not read export headers: {}", e))?
+        .clone();
+
+    let date_col = column(&headers, "Date").ok_or("Export has no Date column")?;
+    let category_col = column(&headers, "Category");
+    let mut transactions = Vec::new();
+
+    match source {
+        MigrationSource::Mint => {
+            let description_col = column(&headers, "Description");
+            let amount_col = column(&headers, "Amount");
+            let type_col = column(&headers, "Transaction Type");
+
+            for result in rdr.records() {
+                let record = result.map_err(|e| format!("Could not read Mint export: {}", e))?;
+                // Mint amounts are unsigned; the type says which way the money went
+                let kind = optional_field(&record, type_col).unwrap_or_default().to_lowercase();
+                let category = optional_field(&record, category_col);
+                if kind != "debit" || category.as_deref().is_some_and(|c| !is_spending_category(c)) {
+                    continue;
+                }
+
+                let amount = amount_field(&record, amount_col).abs();
+                if amount > 0.0 {
+                    transactions.push(spending(
+                        record.get(date_col).unwrap_or("").to_string(),
+                        optional_field(&record, description_col).unwrap_or_default(),
+                        amount,
+                        category,
+                    ));
+                }
+            }
+        }
+        MigrationSource::Ynab => {
+            let payee_col = column(&headers, "Payee");
+            let outflow_col = column(&headers, "Outflow");
+
+            for result in rdr.records() {
+                let record = result.map_err(|e| format!("Could not read YNAB register: {}", e))?;
+                let payee = optional_field(&record, payee_col).unwrap_or_default();
+                let category = optional_field(&record, category_col);
+                // Transfers between budget accounts show up as "Transfer : <account>" payees
+                if payee.to_lowercase().starts_with("transfer") ||
+                   category.as_deref().is_some_and(|c| !is_spending_category(c)) {
+                    continue;
+                }
+
+                let amount = amount_field(&record, outflow_col).abs();
+                if amount > 0.0 {
+                    transactions.push(spending(record.get(date_col).unwrap_or("").to_string(), payee, amount, category));
+                }
+            }
+        }
+        MigrationSource::Monarch => {
+            let merchant_col = column(&headers, "Merchant");
+            let statement_col = column(&headers, "Original Statement");
+            let amount_col = column(&headers, "Amount");
+
+            for result in rdr.records() {
+                let record = result.map_err(|e| format!("Could not read Monarch export: {}", e))?;
+                let category = optional_field(&record, category_col);
+                if category.as_deref().is_some_and(|c| !is_spending_category(c)) {
+                    continue;
+                }
+
+                // Monarch signs spending negative; positive rows are income and refunds
+                let amount = amount_field(&record, amount_col);
+                if amount < 0.0 {
+                    transactions.push(spending(
+                        record.get(date_col).unwrap_or("").to_string(),
+                        optional_field(&record, merchant_col)
+                            .or_else(|| optional_field(&record, statement_col))
+                            .unwrap_or_default(),
+                        amount.abs(),
+                        category,
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(transactions)
+}
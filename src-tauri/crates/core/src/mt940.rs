@@ -0,0 +1,104 @@
+use regex::Regex;
+
+use crate::{parse_amount, Transaction};
+
+// SWIFT MT940 customer statement: tagged lines such as :25: (account), :61: (entry), :86: (details)
+pub fn is_mt940(content: &str) -> bool {
+    content.contains(":20:") && content.contains(":61:")
+}
+
+// Joins continuation lines onto their tag, yielding (tag, value) pairs in order
+fn fields(content: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim_end();
+        let tagged = line.strip_prefix(':')
+            .and_then(|rest| rest.split_once(':'))
+            .filter(|(tag, _)| !tag.is_empty() && tag.len() <= 3 && tag.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        match tagged {
+            Some((tag, value)) => fields.push((tag.to_string(), value.to_string())),
+            // "-" closes a message; anything else continues the previous field
+            None if line == "-" || line.starts_with('{') => {}
+            None => {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+// Structured :86: fields (common in Germany) split details into ?NN subfields:
+// ?20–?29 purpose text, ?32–?33 counterparty name
+fn describe(details: &str) -> String {
+    let details = details.replace('\n', "");
+    if !details.contains('?') {
+        return details.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+
+    let mut purpose = Vec::new();
+    let mut counterparty = Vec::new();
+    for part in details.split('?').skip(1) {
+        // A char boundary, as the value after the code may start with any script
+        let split = part.char_indices().nth(2).map_or(part.len(), |(index, _)| index);
+        let (code, value) = part.split_at(split);
+        match code {
+            "32" | "33" => counterparty.push(value.trim()),
+            code if code.starts_with('2') => purpose.push(value.trim()),
+            _ => {}
+        }
+    }
+
+    let description = if counterparty.is_empty() { purpose.concat() } else { counterparty.concat() };
+    description.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Only plain debits (D) are spending. Credits (C) are income, and reversals (RC, RD) undo an
+// earlier booking rather than moving new money.
+pub fn parse_mt940(content: &str) -> Result<Vec<Transaction>, String> {
+    // Value date YYMMDD, optional entry date MMDD, mark, optional funds code, amount with decimal comma
+    let entry = Regex::new(r"^(\d{2})(\d{2})(\d{2})(?:\d{4})?(RC|RD|C|D)[A-Z]?(\d+,\d*)")
+        .map_err(|e| format!("Invalid MT940 pattern: {}", e))?;
+
+    let mut transactions = Vec::new();
+    let mut pending: Option<Transaction> = None;
+
+    for (tag, value) in fields(content) {
+        match tag.as_str() {
+            "61" => {
+                transactions.extend(pending.take());
+
+                let captures = entry.captures(&value)
+                    .ok_or_else(|| format!("Unreadable MT940 entry: {}", value.lines().next().unwrap_or("")))?;
+                if &captures[4] != "D" {
+                    continue;
+                }
+
+                let amount = parse_amount(&captures[5].replace(',', "."))
+                    .map_err(|e| format!("Invalid MT940 amount: {}", e))?;
+                let date = format!("20{}-{}-{}", &captures[1], &captures[2], &captures[3]);
+                // Without a :86: the bank reference after "//" is the only description
+                let reference = value.split("//").nth(1).unwrap_or("").lines().next().unwrap_or("").trim().to_string();
+
+                pending = Some(Transaction::new(date, reference, amount));
+            }
+            "86" => {
+                if let Some(tx) = pending.as_mut() {
+                    let description = describe(&value);
+                    if !description.is_empty() {
+                        tx.description = description;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    transactions.extend(pending);
+
+    Ok(transactions)
+}
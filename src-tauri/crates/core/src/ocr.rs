@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::statement_language::StatementLanguage;
+use crate::Transaction;
+
+// Tesseract's word confidence runs 0-100; rows below this are worth a look before importing
+const REVIEW_CONFIDENCE: f64 = 80.0;
+// "Mar 14", "14 Mar 2024" and "03/14/2024" are at most three words
+const MAX_DATE_WORDS: usize = 3;
+
+// A line of text as OCR read it, with the average confidence of its words
+#[derive(Debug, Clone)]
+pub struct OcrLine {
+    pub text: String,
+    pub confidence: f64,
+}
+
+// Page, block, paragraph and line number: where a word sits in tesseract's layout
+type LineKey = (u32, u32, u32, u32);
+
+// A transaction read off a screenshot, for the user to check and correct before it's imported
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct OcrRow {
+    // ISO date
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    // 0-100, the least confident of the lines the row was built from
+    pub confidence: f64,
+    pub needs_review: bool,
+    // The line as OCR read it, so a misread can be checked against the image
+    pub source: String,
+}
+
+// Tesseract's `tsv` output: one word per row with its confidence, grouped into lines by the
+// page, block, paragraph and line numbers
+pub fn lines_from_tsv(tsv: &str) -> Vec<OcrLine> {
+    let mut lines: BTreeMap<LineKey, (Vec<String>, Vec<f64>)> = BTreeMap::new();
+    for row in tsv.lines().skip(1) {
+        let fields: Vec<&str> = row.split('\t').collect();
+        if fields.len() < 12 {
+            continue;
+        }
+        let (Ok(confidence), text) = (fields[10].parse::<f64>(), fields[11].trim()) else {
+            continue;
+        };
+        // Layout rows carry a confidence of -1 and no text
+        if confidence < 0.0 || text.is_empty() {
+            continue;
+        }
+        let key = |i: usize| fields[i].parse::<u32>().unwrap_or(0);
+        let line = lines.entry((key(1), key(2), key(3), key(4))).or_default();
+        line.0.push(text.to_string());
+        line.1.push(confidence);
+    }
+
+    lines.into_values()
+        .map(|(words, confidences)| OcrLine {
+            text: words.join(" "),
+            confidence: confidences.iter().sum::<f64>() / confidences.len() as f64,
+        })
+        .collect()
+}
+
+// Prices carry cents, which keeps store numbers and reference codes from passing for one.
+// European statements write the cents after a comma and may put the euro sign or minus last.
+fn looks_like_amount(word: &str, language: StatementLanguage) -> bool {
+    let separator = if language == StatementLanguage::English { '.' } else { ',' };
+    word.trim_end_matches([')', '€', '-']).rsplit_once(separator)
+        .is_some_and(|(_, cents)| cents.len() == 2 && cents.chars().all(|c| c.is_ascii_digit()))
+}
+
+// The date at the start of `words` and how many words it took. Screenshots of an app often
+// leave the year off, so `year` fills it in.
+fn leading_date(words: &[&str], year: i32, language: StatementLanguage) -> Option<(String, usize)> {
+    (1..=MAX_DATE_WORDS.min(words.len())).rev().find_map(|count| {
+        let text = words[..count].join(" ");
+        let text = text.trim_end_matches([',', ':']);
+        let candidates = match language {
+            StatementLanguage::English => vec![text.to_string(), format!("{}, {}", text, year), format!("{} {}", text, year), format!("{}/{}", text, year)],
+            _ => vec![text.to_string(), language.complete_date(text, &year.to_string())],
+        };
+        candidates.iter()
+            .find_map(|candidate| language.parse_date(candidate))
+            .map(|date| (date.format("%Y-%m-%d").to_string(), count))
+    })
+}
+
+// Rows of date, description and amount. A line that's only a date heads the rows under it,
+// as transaction lists in banking apps group by day.
+pub fn rows_from_lines(lines: &[OcrLine], year: i32, language: StatementLanguage) -> Vec<OcrRow> {
+    let mut rows = Vec::new();
+    let mut heading: Option<(String, f64)> = None;
+
+    for line in lines {
+        let words: Vec<&str> = line.text.split_whitespace().collect();
+        let dated = leading_date(&words, year, language);
+        let rest = &words[dated.as_ref().map_or(0, |(_, count)| *count)..];
+
+        let Some((last, description)) = rest.split_last() else {
+            if let Some((date, _)) = dated {
+                heading = Some((date, line.confidence));
+            }
+            continue;
+        };
+        let Some(amount) = Some(*last).filter(|word| looks_like_amount(word, language)).and_then(|word| language.parse_amount(word).ok()) else {
+            continue;
+        };
+        let (date, date_confidence) = match (dated, &heading) {
+            (Some((date, _)), _) => (date, line.confidence),
+            (None, Some((date, confidence))) => (date.clone(), *confidence),
+            (None, None) => continue,
+        };
+        if description.is_empty() {
+            continue;
+        }
+
+        let confidence = line.confidence.min(date_confidence);
+        rows.push(OcrRow {
+            date,
+            description: description.join(" "),
+            // Lists show spending either way round; refunds are left for the review to catch
+            amount: amount.abs(),
+            confidence,
+            needs_review: confidence < REVIEW_CONFIDENCE,
+            source: line.text.clone(),
+        });
+    }
+    rows
+}
+
+pub fn to_transactions(rows: &[OcrRow]) -> Vec<Transaction> {
+    rows.iter()
+        .map(|row| Transaction::new(row.date.clone(), row.description.clone(), row.amount))
+        .collect()
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{merchant_totals, Transaction};
+
+const PARETO_SHARE: f64 = 80.0;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ParetoAnalysis {
+    pub merchant_count: usize,
+    pub merchants_for_80_percent: usize,
+    pub merchant_share_for_80_percent: f64,
+    pub cumulative: Vec<ParetoPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ParetoPoint {
+    pub merchant: String,
+    pub total: f64,
+    pub cumulative_percent: f64,
+}
+
+pub fn analyze_concentration(transactions: &[Transaction]) -> Option<ParetoAnalysis> {
+    let merchants = merchant_totals(transactions);
+    let total: f64 = merchants.iter().map(|m| m.total).sum();
+
+    if merchants.is_empty() || total <= 0.0 {
+        return None;
+    }
+
+    let mut running = 0.0;
+    let cumulative: Vec<ParetoPoint> = merchants
+        .into_iter()
+        .map(|m| {
+            running += m.total;
+            ParetoPoint {
+                merchant: m.merchant,
+                total: m.total,
+                cumulative_percent: (running / total) * 100.0,
+            }
+        })
+        .collect();
+
+    let merchant_count = cumulative.len();
+    // Small epsilon so 79.9999% from float sums still counts as reaching 80%
+    let merchants_for_80_percent = cumulative.iter()
+        .position(|p| p.cumulative_percent >= PARETO_SHARE - 1e-9)
+        .map(|index| index + 1)
+        .unwrap_or(merchant_count);
+
+    Some(ParetoAnalysis {
+        merchant_count,
+        merchants_for_80_percent,
+        merchant_share_for_80_percent: (merchants_for_80_percent as f64 / merchant_count as f64) * 100.0,
+        cumulative,
+    })
+}
+
+pub fn pareto_insight(analysis: &ParetoAnalysis) -> Insight {
+    let mut insight = Insight::new(
+        InsightKind::Concentration,
+        Severity::Info,
+        "concentration.pareto",
+        format!("{:.0}% of your merchants ({} of {}) account for 80% of your spending",
+                analysis.merchant_share_for_80_percent,
+                analysis.merchants_for_80_percent,
+                analysis.merchant_count),
+    )
+    .with_param("share", format!("{:.0}", analysis.merchant_share_for_80_percent))
+    .with_param("merchants", analysis.merchants_for_80_percent)
+    .with_param("total_merchants", analysis.merchant_count);
+    for point in analysis.cumulative.iter().take(analysis.merchants_for_80_percent) {
+        insight = insight.with_merchant(&point.merchant);
+    }
+    insight
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseMode {
+    // Fail on the first row that can't be read, saying which and why
+    Strict,
+    // Skip unreadable rows and report them alongside the analysis
+    #[default]
+    Lenient,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ParseWarning {
+    // 1-based data row within its table, header excluded
+    pub row: usize,
+    pub reason: String,
+}
+
+// Row problems met while parsing: strict mode turns the first into the error, lenient mode
+// keeps them all and lets the parser skip the row
+pub struct RowIssues {
+    mode: ParseMode,
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl RowIssues {
+    pub fn new(mode: ParseMode) -> Self {
+        RowIssues { mode, warnings: Vec::new() }
+    }
+
+    pub fn report(&mut self, row: usize, reason: String) -> Result<(), Box<dyn std::error::Error>> {
+        match self.mode {
+            ParseMode::Strict => Err(format!("Row {}: {}", row, reason).into()),
+            ParseMode::Lenient => {
+                self.warnings.push(ParseWarning { row, reason });
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::bank_profiles::BankProfile;
+use crate::parse_mode::ParseMode;
+use crate::sign_convention::SignConvention;
+use crate::statement_language::StatementLanguage;
+use crate::{parse_bytes, ParsedStatement};
+
+// Pasted text goes through the CSV branches of the file pipeline under this name
+const PASTED_FILE_NAME: &str = "pasted.csv";
+// Enough lines to see past a title row without reading the whole paste
+const SNIFF_LINES: usize = 10;
+
+// How pasted text is laid out, when the user knows better than the sniffing
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TextHint {
+    Csv,
+    // Tables copied out of a web page
+    Tsv,
+}
+
+fn looks_tab_separated(content: &str) -> bool {
+    let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).take(SNIFF_LINES).collect();
+    let tabbed = lines.iter().filter(|line| line.contains('\t')).count();
+    tabbed * 2 > lines.len()
+}
+
+// Cells keep their commas ("$1,234.56") by being quoted
+fn tabs_to_csv(content: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+    for line in content.lines() {
+        writer.write_record(line.split('\t').map(str::trim))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+// Statement text pasted in rather than read from a file: a copied CSV, an MT940 or CAMT.053
+// message, or a table copied from the bank's website
+pub fn parse_text(content: &str, hint: Option<TextHint>, sign_override: Option<SignConvention>, profiles: &[BankProfile], mode: ParseMode, language: StatementLanguage) -> Result<ParsedStatement, Box<dyn std::error::Error>> {
+    let tab_separated = match hint {
+        Some(TextHint::Tsv) => true,
+        Some(TextHint::Csv) => false,
+        None => looks_tab_separated(content),
+    };
+    let content = if tab_separated { tabs_to_csv(content)? } else { content.to_string() };
+    parse_bytes(PASTED_FILE_NAME, content.into_bytes(), None, sign_override, profiles, mode, language)
+}
@@ -0,0 +1,125 @@
+use lopdf::Document;
+use regex::Regex;
+use std::fmt;
+
+use crate::pdf_layout::{self, Patterns, RunCollector};
+use crate::statement_language::StatementLanguage;
+use crate::Transaction;
+
+#[derive(Debug)]
+pub enum PdfError {
+    NeedsPassword,
+    WrongPassword,
+    Unreadable(String),
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PdfError::NeedsPassword => write!(f, "PDF is password-protected"),
+            PdfError::WrongPassword => write!(f, "PDF password is incorrect"),
+            PdfError::Unreadable(reason) => write!(f, "Could not read PDF: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+// Encrypted PDFs carry an /Encrypt entry in their trailer
+fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.windows(8).any(|window| window == b"/Encrypt")
+}
+
+fn extract_text(bytes: &[u8], password: Option<&str>) -> Result<String, PdfError> {
+    if !is_encrypted(bytes) {
+        return pdf_extract::extract_text_from_mem(bytes)
+            .map_err(|e| PdfError::Unreadable(e.to_string()));
+    }
+
+    match password {
+        Some(password) => pdf_extract::extract_text_from_mem_encrypted(bytes, password)
+            .map_err(|_| PdfError::WrongPassword),
+        // Many issuers only restrict printing/copying, which an empty user password opens
+        None => pdf_extract::extract_text_from_mem_encrypted(bytes, "")
+            .map_err(|_| PdfError::NeedsPassword),
+    }
+}
+
+fn load_document(bytes: &[u8], password: Option<&str>) -> Result<Document, PdfError> {
+    let mut document = Document::load_mem(bytes)
+        .map_err(|e| PdfError::Unreadable(e.to_string()))?;
+
+    if document.is_encrypted() {
+        // Same empty-password fallback as for plain text extraction
+        document.decrypt(password.unwrap_or(""))
+            .map_err(|_| if password.is_some() { PdfError::WrongPassword } else { PdfError::NeedsPassword })?;
+    }
+    Ok(document)
+}
+
+// Lines often omit the year, so borrow it from the statement header
+fn statement_year(text: &str) -> String {
+    Regex::new(r"\b(20\d{2})\b").ok()
+        .and_then(|pattern| pattern.captures(text).map(|captures| captures[1].to_string()))
+        .unwrap_or_else(|| chrono::Local::now().format("%Y").to_string())
+}
+
+// Layout-aware extraction first, so amounts stay on the rows they're printed beside even in
+// multi-column statements; flattened text lines are the fallback when no table is found.
+// The statement text comes back too, for metadata extraction.
+pub fn parse_pdf(bytes: &[u8], password: Option<&str>, language: StatementLanguage) -> Result<(Vec<Transaction>, String), PdfError> {
+    let document = load_document(bytes, password)?;
+    let mut collector = RunCollector::default();
+    pdf_extract::output_doc(&document, &mut collector)
+        .map_err(|e| PdfError::Unreadable(e.to_string()))?;
+
+    let all_text = collector.pages.iter()
+        .map(pdf_layout::page_text)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let year = statement_year(&all_text);
+
+    let patterns = Patterns::new(language).map_err(|e| PdfError::Unreadable(e.to_string()))?;
+    let transactions = pdf_layout::table_transactions(&collector.pages, &patterns, |date| language.complete_date(date, &year));
+    if !transactions.is_empty() {
+        return Ok((transactions, all_text));
+    }
+
+    let text = extract_text(bytes, password)?;
+    Ok((parse_statement_text(&text, language)?, text))
+}
+
+// Statement lines look like "01/15 01/16 STARBUCKS STORE 1234 SEATTLE WA 5.75", with an
+// optional posting date and year. Credits are marked with a minus or a trailing "CR", or in
+// other languages by words like "AVOIR" or "Gutschrift".
+pub fn parse_statement_text(text: &str, language: StatementLanguage) -> Result<Vec<Transaction>, PdfError> {
+    let invalid = |e: regex::Error| PdfError::Unreadable(e.to_string());
+    let date = language.date_pattern();
+    let line_pattern = Regex::new(&format!(
+        r"^\s*({date})\s+(?:(?:{date})\s+)?(.+?)\s+({amount})\s*(CR)?\s*$",
+        date = date,
+        amount = language.amount_pattern(),
+    )).map_err(invalid)?;
+    let year = statement_year(text);
+
+    let mut transactions = Vec::new();
+    for line in text.lines() {
+        let Some(captures) = line_pattern.captures(line) else {
+            continue;
+        };
+        if captures.get(4).is_some() || captures[3].contains('-') {
+            continue;
+        }
+        let Ok(amount) = language.parse_amount(&captures[3]) else {
+            continue;
+        };
+        let description = language.strip_card_label(captures[2].trim());
+        if language.is_credit(&description) {
+            continue;
+        }
+
+        transactions.push(Transaction::new(language.complete_date(&captures[1], &year), description, amount));
+    }
+
+    Ok(transactions)
+}
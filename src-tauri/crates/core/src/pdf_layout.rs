@@ -0,0 +1,331 @@
+use pdf_extract::{MediaBox, OutputDev, OutputError, Transform};
+use regex::Regex;
+
+use crate::statement_language::StatementLanguage;
+use crate::Transaction;
+
+// A gutter narrower than this is just the gap between two table columns
+const MIN_GUTTER_WIDTH: f64 = 12.0;
+// Rows whose baselines differ by less than this fraction of the font size are the same row
+const ROW_TOLERANCE: f64 = 0.4;
+// Words closer than this fraction of the font size belong to the same cell
+const CELL_GAP: f64 = 0.8;
+
+#[derive(Debug, Clone)]
+pub struct TextRun {
+    pub x: f64,
+    pub end_x: f64,
+    pub y: f64,
+    pub size: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Page {
+    pub width: f64,
+    pub runs: Vec<TextRun>,
+}
+
+// Collects positioned words from pdf-extract instead of its flattened text
+#[derive(Default)]
+pub struct RunCollector {
+    pub pages: Vec<Page>,
+    current: Option<TextRun>,
+}
+
+impl RunCollector {
+    fn flush(&mut self) {
+        if let (Some(run), Some(page)) = (self.current.take(), self.pages.last_mut()) {
+            if !run.text.trim().is_empty() {
+                page.runs.push(run);
+            }
+        }
+    }
+}
+
+impl OutputDev for RunCollector {
+    fn begin_page(&mut self, _page_num: u32, media_box: &MediaBox, _art_box: Option<(f64, f64, f64, f64)>) -> Result<(), OutputError> {
+        self.pages.push(Page { width: media_box.urx - media_box.llx, runs: Vec::new() });
+        Ok(())
+    }
+
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        self.flush();
+        Ok(())
+    }
+
+    fn output_character(&mut self, trm: &Transform, width: f64, _spacing: f64, font_size: f64, char: &str) -> Result<(), OutputError> {
+        let (x, y) = (trm.m31, trm.m32);
+        // The text matrix scales the nominal font size
+        let size = font_size * (trm.m11 * trm.m22 - trm.m12 * trm.m21).abs().sqrt();
+        let advance = width * size;
+
+        if char.trim().is_empty() {
+            self.flush();
+            return Ok(());
+        }
+
+        let continues = self.current.as_ref().is_some_and(|run| {
+            (run.y - y).abs() < size * ROW_TOLERANCE && (x - run.end_x).abs() < size * 0.25
+        });
+        if !continues {
+            self.flush();
+            self.current = Some(TextRun { x, end_x: x, y, size, text: String::new() });
+        }
+        if let Some(run) = self.current.as_mut() {
+            run.text.push_str(char);
+            run.end_x = x + advance;
+        }
+        Ok(())
+    }
+
+    fn begin_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> Result<(), OutputError> {
+        self.flush();
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Cell {
+    x: f64,
+    text: String,
+}
+
+#[derive(Debug)]
+struct Row {
+    y: f64,
+    size: f64,
+    cells: Vec<Cell>,
+}
+
+// Groups runs into rows by baseline, top of the page first, then merges nearby words into cells
+fn rows(runs: &[TextRun]) -> Vec<Row> {
+    let mut sorted: Vec<&TextRun> = runs.iter().collect();
+    sorted.sort_by(|a, b| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
+
+    let mut grouped: Vec<Vec<&TextRun>> = Vec::new();
+    for run in sorted {
+        match grouped.last_mut() {
+            Some(row) if (row[0].y - run.y).abs() < run.size.max(row[0].size) * ROW_TOLERANCE => row.push(run),
+            _ => grouped.push(vec![run]),
+        }
+    }
+
+    grouped.into_iter().map(|mut words| {
+        words.sort_by(|a, b| a.x.total_cmp(&b.x));
+        let size = words.iter().map(|w| w.size).fold(0.0, f64::max);
+        let mut cells: Vec<Cell> = Vec::new();
+        let mut last_end = f64::NEG_INFINITY;
+
+        for word in &words {
+            match cells.last_mut() {
+                Some(cell) if word.x - last_end < size * CELL_GAP => {
+                    cell.text.push(' ');
+                    cell.text.push_str(&word.text);
+                }
+                _ => cells.push(Cell { x: word.x, text: word.text.clone() }),
+            }
+            last_end = word.end_x;
+        }
+
+        Row { y: words[0].y, size, cells }
+    }).collect()
+}
+
+// The page as plain text in reading order, one line per row
+pub fn page_text(page: &Page) -> String {
+    rows(&page.runs).iter()
+        .map(|row| row.cells.iter().map(|cell| cell.text.as_str()).collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Finds an empty vertical band near the middle of the page that no word crosses
+fn find_gutter(page: &Page) -> Option<f64> {
+    if page.width <= 0.0 {
+        return None;
+    }
+
+    let bins = page.width.ceil() as usize;
+    let mut covered = vec![false; bins + 1];
+    for run in &page.runs {
+        let start = run.x.max(0.0) as usize;
+        let end = (run.end_x.max(run.x).ceil() as usize).min(bins);
+        for bin in covered.iter_mut().take(end + 1).skip(start) {
+            *bin = true;
+        }
+    }
+
+    let (low, high) = ((page.width * 0.3) as usize, (page.width * 0.7) as usize);
+    let mut best: Option<(usize, usize)> = None;
+    let mut start = None;
+    for (bin, &is_covered) in covered.iter().enumerate().take(high + 1).skip(low) {
+        match (is_covered, start) {
+            (false, None) => start = Some(bin),
+            (true, Some(begin)) => {
+                if best.is_none_or(|(b, e)| bin - begin > e - b) {
+                    best = Some((begin, bin));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        if best.is_none_or(|(b, e)| high - begin > e - b) {
+            best = Some((begin, high));
+        }
+    }
+
+    best.filter(|(begin, end)| (end - begin) as f64 >= MIN_GUTTER_WIDTH)
+        .map(|(begin, end)| (begin + end) as f64 / 2.0)
+}
+
+// The column streams of a page in reading order. A gap only counts as a gutter between two
+// statement columns if the right side has dated rows of its own; otherwise it's the space
+// between a table's description and amount columns.
+fn columns(page: &Page, date: &Regex) -> Vec<Vec<Row>> {
+    if let Some(gutter) = find_gutter(page) {
+        let (left, right): (Vec<TextRun>, Vec<TextRun>) = page.runs.iter().cloned().partition(|run| run.x < gutter);
+        let right_rows = rows(&right);
+        let dated = right_rows.iter()
+            .filter(|row| row.cells.first().is_some_and(|cell| date.is_match(&cell.text)))
+            .count();
+        if dated >= 2 {
+            return vec![rows(&left), right_rows];
+        }
+    }
+    vec![rows(&page.runs)]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Debits,
+    Credits,
+}
+
+fn section_heading(text: &str) -> Option<Section> {
+    let lower = text.to_lowercase();
+    // Summary lines like "Payment Due Date" aren't table headings
+    if lower.contains("due") || lower.contains("minimum") {
+        None
+    } else if lower.contains("payment") || lower.contains("credits") || lower.contains("refund") {
+        Some(Section::Credits)
+    } else if lower.contains("purchase") || lower.contains("transactions") || lower.contains("fees charged") ||
+              lower.contains("interest charged") || lower.contains("cash advance") {
+        Some(Section::Debits)
+    } else {
+        None
+    }
+}
+
+pub struct Patterns {
+    date: Regex,
+    amount: Regex,
+    language: StatementLanguage,
+}
+
+impl Patterns {
+    pub fn new(language: StatementLanguage) -> Result<Patterns, regex::Error> {
+        Ok(Patterns {
+            // Leading date, with the rest of the cell (if any) after it
+            date: Regex::new(&format!(r"^({})(?:\s+(.*))?$", language.date_pattern()))?,
+            amount: Regex::new(&format!(r"^(?:{})(?: ?CR)?$", language.amount_pattern()))?,
+            language,
+        })
+    }
+
+    // Splits "01/15 01/16 STARBUCKS" into the transaction date and "STARBUCKS",
+    // dropping a posting date if there is one
+    fn split_date<'a>(&self, text: &'a str) -> Option<(&'a str, &'a str)> {
+        let captures = self.date.captures(text)?;
+        let date = captures.get(1)?.as_str();
+        let mut rest = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+        if let Some(posted) = self.date.captures(rest) {
+            rest = posted.get(2).map(|m| m.as_str()).unwrap_or("");
+        }
+        Some((date, rest))
+    }
+}
+
+// Reads transactions out of table rows: a date at the start and an amount in the last cell.
+// Rows under payment/credit headings are skipped, as are negative or CR amounts.
+pub fn table_transactions(pages: &[Page], patterns: &Patterns, complete_date: impl Fn(&str) -> String) -> Vec<Transaction> {
+    let mut transactions = Vec::new();
+    let mut section = Section::Debits;
+
+    for page in pages {
+        for column in columns(page, &patterns.date) {
+            let mut index = 0;
+            while index < column.len() {
+                let row = &column[index];
+                index += 1;
+
+                let Some(first) = row.cells.first() else {
+                    continue;
+                };
+                let last = row.cells.last()
+                    .filter(|cell| row.cells.len() > 1 && patterns.amount.is_match(&cell.text));
+
+                let Some((date, rest)) = patterns.split_date(&first.text) else {
+                    // A heading is a lone cell of text
+                    if row.cells.len() == 1 {
+                        if let Some(heading) = section_heading(&first.text) {
+                            section = heading;
+                        }
+                    }
+                    continue;
+                };
+
+                // An amount set on a slightly different baseline lands in its own row just below
+                let mut amount_cell = last;
+                if amount_cell.is_none() {
+                    if let Some(next) = column.get(index) {
+                        let close = (row.y - next.y).abs() < row.size.max(next.size) * 1.2;
+                        let lone_amount = next.cells.len() == 1 && patterns.amount.is_match(&next.cells[0].text);
+                        if close && lone_amount {
+                            amount_cell = next.cells.first();
+                            index += 1;
+                        }
+                    }
+                }
+                let Some(amount_cell) = amount_cell else {
+                    continue;
+                };
+
+                if section == Section::Credits || amount_cell.text.contains('-') || amount_cell.text.ends_with("CR") {
+                    continue;
+                }
+                let Ok(amount) = patterns.language.parse_amount(amount_cell.text.trim_end_matches("CR").trim()) else {
+                    continue;
+                };
+
+                // Everything between the date and the amount column is description
+                let mut parts: Vec<&str> = Vec::new();
+                if !rest.is_empty() {
+                    parts.push(rest);
+                }
+                parts.extend(row.cells.iter()
+                    .skip(1)
+                    .filter(|cell| cell.x < amount_cell.x)
+                    .map(|cell| cell.text.as_str())
+                    .skip_while(|text| patterns.split_date(text).is_some_and(|(_, rest)| rest.is_empty())));
+                let description = patterns.language.strip_card_label(&parts.join(" "));
+
+                if !description.is_empty() && !patterns.language.is_credit(&description) {
+                    transactions.push(Transaction::new(complete_date(date), description, amount));
+                }
+            }
+        }
+    }
+
+    transactions
+}
@@ -0,0 +1,91 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::history::StoredStatement;
+
+// Later start days would leave February without one
+pub const MAX_START_DAY: u32 = 28;
+
+// Where one spending period ends and the next begins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PeriodBoundary {
+    Calendar,
+    // Periods start on this day of the month
+    StartDay { day: u32 },
+    // Periods follow the card's billing cycle, starting the day after the latest statement closed
+    StatementCycle,
+}
+
+// Day of the month periods start on, 1 for calendar months. Month grouping happens deep in
+// code that has no settings at hand, so the resolved boundary is kept here.
+static START_DAY: AtomicU32 = AtomicU32::new(1);
+
+pub fn start_day() -> u32 {
+    START_DAY.load(Ordering::Relaxed)
+}
+
+pub fn set_start_day(day: u32) {
+    START_DAY.store(day, Ordering::Relaxed);
+}
+
+pub fn previous_month(month: NaiveDate) -> NaiveDate {
+    if month.month() == 1 {
+        NaiveDate::from_ymd_opt(month.year() - 1, 12, 1).unwrap_or(month)
+    } else {
+        NaiveDate::from_ymd_opt(month.year(), month.month() - 1, 1).unwrap_or(month)
+    }
+}
+
+fn next_month(month: NaiveDate) -> NaiveDate {
+    if month.month() == 12 {
+        NaiveDate::from_ymd_opt(month.year() + 1, 1, 1).unwrap_or(month)
+    } else {
+        NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1).unwrap_or(month)
+    }
+}
+
+// First of the month that names the period `date` falls in. A period is named after the month
+// it ends in, the way a statement closing Feb 17 is the February statement.
+pub fn period_month(date: NaiveDate) -> NaiveDate {
+    let first = date.with_day(1).unwrap_or(date);
+    if start_day() > 1 && date.day() >= start_day() {
+        next_month(first)
+    } else {
+        first
+    }
+}
+
+fn period_start(month: NaiveDate) -> NaiveDate {
+    match start_day() {
+        day if day <= 1 => month,
+        day => previous_month(month).with_day(day).unwrap_or(month),
+    }
+}
+
+// First and last day of the period named by `month`
+pub fn period_bounds(month: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let end = period_start(next_month(month)).pred_opt().unwrap_or(month);
+    (period_start(month), end)
+}
+
+// "2024-01"
+pub fn period_key(date: NaiveDate) -> String {
+    period_month(date).format("%Y-%m").to_string()
+}
+
+// Day of the month the boundary puts period starts on, given the imported statements
+pub fn resolve_start_day(boundary: &PeriodBoundary, statements: &[StoredStatement]) -> u32 {
+    match boundary {
+        PeriodBoundary::Calendar => 1,
+        PeriodBoundary::StartDay { day } => (*day).clamp(1, MAX_START_DAY),
+        PeriodBoundary::StatementCycle => statements.iter()
+            .filter_map(|s| s.meta.as_ref()?.statement_date.as_deref())
+            .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .max()
+            // A cycle closing at the very end of the month is close enough to calendar months
+            .map_or(1, |closed| if closed.day() >= MAX_START_DAY { 1 } else { closed.day() + 1 }),
+    }
+}
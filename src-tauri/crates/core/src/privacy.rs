@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Private analyses in flight. Parsers deep in the call tree log as they go, and a log is a
+// write to disk on most setups, so nothing is logged while any private analysis is running.
+static PRIVATE_ANALYSES: AtomicUsize = AtomicUsize::new(0);
+
+// Held for the length of a private analysis
+pub struct PrivateScope;
+
+impl Drop for PrivateScope {
+    fn drop(&mut self) {
+        PRIVATE_ANALYSES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn begin() -> PrivateScope {
+    PRIVATE_ANALYSES.fetch_add(1, Ordering::SeqCst);
+    PrivateScope
+}
+
+pub fn quiet() -> bool {
+    PRIVATE_ANALYSES.load(Ordering::SeqCst) > 0
+}
+
+// println! that stays silent during private analyses. Exported for the store and app crates.
+#[macro_export]
+macro_rules! log {
+    ($($arg:tt)*) => {
+        if !$crate::privacy::quiet() {
+            println!($($arg)*);
+        }
+    };
+}
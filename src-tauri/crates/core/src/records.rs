@@ -0,0 +1,80 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+use crate::interner::{CompactHistory, Symbol};
+use crate::periods;
+use crate::history::StoredStatement;
+use crate::{is_fee_or_interest, transaction_category, Transaction};
+
+pub const LARGEST_TRANSACTIONS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LargeTransaction {
+    pub date: String,
+    pub description: String,
+    pub category: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantMonth {
+    pub merchant: String,
+    // "2024-01"
+    pub month: String,
+    pub total: f64,
+    pub count: usize,
+}
+
+// All-time highs across every imported statement
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct LifetimeRecords {
+    pub largest_purchase: Option<LargeTransaction>,
+    pub biggest_merchant_month: Option<MerchantMonth>,
+}
+
+fn large_transaction(tx: &Transaction) -> LargeTransaction {
+    LargeTransaction {
+        date: tx.date.clone(),
+        description: tx.description.clone(),
+        category: transaction_category(tx),
+        amount: tx.amount,
+    }
+}
+
+// The period's biggest purchases, largest first. Fees and interest aren't purchases.
+pub fn largest_transactions(transactions: &[Transaction], count: usize) -> Vec<LargeTransaction> {
+    let mut purchases: Vec<&Transaction> = transactions.iter().filter(|t| !is_fee_or_interest(t)).collect();
+    purchases.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+    purchases.into_iter().take(count).map(large_transaction).collect()
+}
+
+pub fn lifetime_records(statements: &[StoredStatement]) -> Option<LifetimeRecords> {
+    let largest_purchase = statements.iter()
+        .flat_map(|s| &s.transactions)
+        .filter(|t| !is_fee_or_interest(t))
+        .max_by(|a, b| a.amount.total_cmp(&b.amount))
+        .map(large_transaction);
+
+    let history = CompactHistory::from_statements(statements);
+    let mut merchant_months: HashMap<(Symbol, NaiveDate), (f64, usize)> = HashMap::new();
+    for tx in &history.transactions {
+        if let Some(month) = tx.date.map(periods::period_month) {
+            let cell = merchant_months.entry((tx.merchant, month)).or_insert((0.0, 0));
+            cell.0 += tx.amount;
+            cell.1 += 1;
+        }
+    }
+    let biggest_merchant_month = merchant_months.into_iter()
+        .max_by(|a, b| a.1.0.total_cmp(&b.1.0))
+        .map(|((merchant, month), (total, count))| MerchantMonth {
+            merchant: history.merchants.resolve(merchant).to_string(),
+            month: month.format("%Y-%m").to_string(),
+            total,
+            count,
+        });
+
+    (largest_purchase.is_some() || biggest_merchant_month.is_some())
+        .then_some(LifetimeRecords { largest_purchase, biggest_merchant_month })
+}
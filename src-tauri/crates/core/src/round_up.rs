@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::{month_key, statement_period_days, Transaction};
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct RoundUpSimulation {
+    pub increment: f64,
+    pub total: f64,
+    pub monthly: Vec<MonthlyRoundUp>,
+    pub monthly_average: f64,
+    pub annualized: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MonthlyRoundUp {
+    pub month: String,
+    pub amount: f64,
+}
+
+// Work in cents so $4.50 rounded to $0.50 doesn't pick up float dust
+fn round_up_amount(amount: f64, increment: f64) -> f64 {
+    let cents = (amount * 100.0).round() as i64;
+    let increment_cents = (increment * 100.0).round() as i64;
+
+    if increment_cents <= 0 {
+        return 0.0;
+    }
+
+    let remainder = cents % increment_cents;
+    if remainder == 0 {
+        0.0
+    } else {
+        (increment_cents - remainder) as f64 / 100.0
+    }
+}
+
+pub fn simulate_round_up(transactions: &[Transaction], increment: f64) -> Option<RoundUpSimulation> {
+    let mut monthly_totals: BTreeMap<String, f64> = BTreeMap::new();
+
+    for tx in transactions {
+        let month = month_key(&tx.date).unwrap_or_else(|| "Unknown".to_string());
+        *monthly_totals.entry(month).or_insert(0.0) += round_up_amount(tx.amount, increment);
+    }
+
+    let total: f64 = monthly_totals.values().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let period_days = statement_period_days(transactions) as f64;
+
+    Some(RoundUpSimulation {
+        increment,
+        total,
+        monthly: monthly_totals
+            .into_iter()
+            .map(|(month, amount)| MonthlyRoundUp { month, amount })
+            .collect(),
+        monthly_average: total / period_days * (365.0 / 12.0),
+        annualized: total / period_days * 365.0,
+    })
+}
+
+pub fn round_up_insight(simulation: &RoundUpSimulation) -> Insight {
+    Insight::new(
+        InsightKind::RoundUp,
+        Severity::Info,
+        "round_up.savings",
+        format!("Rounding every purchase up to the nearest ${} would have saved ${:.2} — about ${:.0} a month or ${:.0} a year",
+                simulation.increment, simulation.total, simulation.monthly_average, simulation.annualized),
+    )
+    .with_amount(simulation.annualized)
+    .with_param("increment", simulation.increment)
+    .with_param("total", format!("{:.2}", simulation.total))
+    .with_param("monthly", format!("{:.0}", simulation.monthly_average))
+    .with_param("annual", format!("{:.0}", simulation.annualized))
+}
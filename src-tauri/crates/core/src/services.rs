@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::merchants::lookup;
+
+// What a subscription is for, so plans that do the same job can be spotted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceClass {
+    MusicStreaming,
+    VideoStreaming,
+    CloudStorage,
+    News,
+    Fitness,
+    Vpn,
+    PasswordManager,
+    Gaming,
+}
+
+// Which class a merchant belongs to, from the merchant knowledge base
+pub fn service_class(merchant: &str) -> Option<ServiceClass> {
+    lookup(merchant)?.service
+}
+
+// The class's i18n key suffix, matching its serialized name
+pub fn class_key(class: ServiceClass) -> &'static str {
+    match class {
+        ServiceClass::MusicStreaming => "music_streaming",
+        ServiceClass::VideoStreaming => "video_streaming",
+        ServiceClass::CloudStorage => "cloud_storage",
+        ServiceClass::News => "news",
+        ServiceClass::Fitness => "fitness",
+        ServiceClass::Vpn => "vpn",
+        ServiceClass::PasswordManager => "password_manager",
+        ServiceClass::Gaming => "gaming",
+    }
+}
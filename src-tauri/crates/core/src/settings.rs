@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::categories::CategoryClass;
+use crate::i18n;
+use crate::parse_mode::ParseMode;
+use crate::statement_language::StatementLanguage;
+use crate::periods::{self, PeriodBoundary};
+
+const MAX_REMINDER_DAYS: u32 = 28;
+
+// Which releases the app updates to. Beta builds can move the data ahead of stable, so leaving
+// beta can mean waiting for stable to catch up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct Settings {
+    // Purchases below this count as micro-spending ("latte factor")
+    pub micro_spend_threshold: f64,
+    // Round-up savings simulation rounds each purchase up to a multiple of this
+    pub round_up_increment: f64,
+    // Language for insights and error messages
+    pub locale: String,
+    // Desktop notification ahead of each card's payment due date
+    pub payment_reminders: bool,
+    pub reminder_days_before: u32,
+    // Take-home pay per month, for reporting spending as a share of income
+    pub monthly_income: Option<f64>,
+    // Need / want / savings-debt per category name, overriding the built-in classes
+    pub category_classes: BTreeMap<String, CategoryClass>,
+    // Where months begin for every per-month grouping
+    pub period_boundary: PeriodBoundary,
+    // Estimate the CO2e behind each category's spending
+    pub carbon_footprint: bool,
+    // kg CO2e per dollar by category name, overriding the built-in factors
+    pub emission_factors: BTreeMap<String, f64>,
+    // Charges whose description contains any of these count as business expenses
+    pub business_rules: Vec<String>,
+    // Two-letter US state the user lives in, so charges from other states can mark trips
+    pub home_state: Option<String>,
+    // Look up logos for merchants online and cache them; off keeps the app entirely offline
+    pub merchant_logos: bool,
+    // Whether a row that can't be read fails the import or is skipped with a warning;
+    // each import can override it
+    pub parse_mode: ParseMode,
+    // Language PDF and screenshot statements are printed in, for their dates, amounts and
+    // wording
+    pub statement_language: StatementLanguage,
+    // Keep a compressed copy of each imported file, so statements can be parsed again after
+    // the parsers improve
+    pub keep_originals: bool,
+    // Days kept copies are held for; None keeps them until their statement is re-imported
+    pub originals_retention_days: Option<u32>,
+    // System-wide keys that bring the app forward and open the import picker, such as
+    // "CommandOrControl+Alt+I"; None leaves every key to other apps
+    pub import_shortcut: Option<String>,
+    // Start with the system, minimized to the tray
+    pub autostart: bool,
+    // While running, even minimized, import statements saved to statements_folder without
+    // asking, and only notify when they raise alerts
+    pub background_import: bool,
+    pub statements_folder: Option<String>,
+    pub update_channel: UpdateChannel,
+    // A folder another service syncs between devices, such as Dropbox or Syncthing, that the
+    // statement history is synced through; None keeps it on this device
+    pub sync_folder: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            micro_spend_threshold: 10.0,
+            round_up_increment: 1.0,
+            locale: i18n::DEFAULT_LOCALE.to_string(),
+            payment_reminders: true,
+            reminder_days_before: 3,
+            monthly_income: None,
+            category_classes: BTreeMap::new(),
+            period_boundary: PeriodBoundary::Calendar,
+            carbon_footprint: false,
+            emission_factors: BTreeMap::new(),
+            business_rules: Vec::new(),
+            home_state: None,
+            merchant_logos: false,
+            parse_mode: ParseMode::Lenient,
+            statement_language: StatementLanguage::English,
+            keep_originals: false,
+            originals_retention_days: None,
+            import_shortcut: None,
+            autostart: false,
+            background_import: false,
+            statements_folder: None,
+            update_channel: UpdateChannel::Stable,
+            sync_folder: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load(path: &Path) -> Result<Settings, String> {
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read settings: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Settings file is invalid: {}", e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize settings: {}", e))?;
+        fs::write(path, content)
+            .map_err(|e| format!("Could not write settings: {}", e))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !i18n::is_supported(&self.locale) {
+            return Err(i18n::text(i18n::DEFAULT_LOCALE, "error.locale_unsupported", &[("locale", self.locale.clone())]));
+        }
+        if !self.micro_spend_threshold.is_finite() || self.micro_spend_threshold <= 0.0 {
+            return Err(i18n::error(&self.locale, "error.threshold_invalid"));
+        }
+        if !self.round_up_increment.is_finite() || self.round_up_increment < 0.01 {
+            return Err(i18n::error(&self.locale, "error.round_up_invalid"));
+        }
+        // Statements are monthly, so a longer lead time would overlap the previous cycle
+        if self.reminder_days_before > MAX_REMINDER_DAYS {
+            return Err(i18n::error(&self.locale, "error.reminder_days_invalid"));
+        }
+        if let PeriodBoundary::StartDay { day } = self.period_boundary {
+            if !(1..=periods::MAX_START_DAY).contains(&day) {
+                return Err(i18n::error(&self.locale, "error.period_start_day_invalid"));
+            }
+        }
+        if self.monthly_income.is_some_and(|income| !income.is_finite() || income <= 0.0) {
+            return Err(i18n::error(&self.locale, "error.income_invalid"));
+        }
+        if let Some((category, _)) = self.emission_factors.iter().find(|(_, factor)| !factor.is_finite() || **factor < 0.0) {
+            return Err(i18n::text(&self.locale, "error.emission_factor_invalid", &[("category", category.clone())]));
+        }
+        // An empty rule would match every charge
+        if self.business_rules.iter().any(|rule| rule.trim().is_empty()) {
+            return Err(i18n::error(&self.locale, "error.business_rule_empty"));
+        }
+        if self.home_state.as_deref().is_some_and(|state| state.len() != 2 || !state.chars().all(|c| c.is_ascii_alphabetic())) {
+            return Err(i18n::error(&self.locale, "error.home_state_invalid"));
+        }
+        if self.originals_retention_days == Some(0) {
+            return Err(i18n::error(&self.locale, "error.retention_invalid"));
+        }
+        if self.import_shortcut.as_deref().is_some_and(|keys| keys.trim().is_empty()) {
+            return Err(i18n::text(&self.locale, "error.shortcut_invalid", &[("shortcut", String::new())]));
+        }
+        if self.background_import && self.statements_folder.as_deref().is_none_or(|folder| folder.trim().is_empty()) {
+            return Err(i18n::error(&self.locale, "error.statements_folder_required"));
+        }
+        if self.sync_folder.as_deref().is_some_and(|folder| folder.trim().is_empty()) {
+            return Err(i18n::error(&self.locale, "error.sync_folder_required"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::i18n;
+
+// A charge split with someone else, such as a partner on the same card
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct SharedExpense {
+    pub partner: String,
+    // Percent of the charge that's the partner's, 50 for an even split
+    pub partner_share: f64,
+    // Set on the partner's own purchases, e.g. from their card's statement, where the user owes
+    // their part instead
+    #[serde(default)]
+    pub paid_by_partner: bool,
+}
+
+impl SharedExpense {
+    pub fn validate(&self, locale: &str) -> Result<(), String> {
+        if self.partner.trim().is_empty() {
+            return Err(i18n::error(locale, "error.shared_partner_empty"));
+        }
+        if !self.partner_share.is_finite() || !(0.0..=100.0).contains(&self.partner_share) {
+            return Err(i18n::error(locale, "error.shared_share_invalid"));
+        }
+        Ok(())
+    }
+
+    // What the partner owes the user for a charge of `amount`; negative when the user owes them
+    pub fn owed_by_partner(&self, amount: f64) -> f64 {
+        if self.paid_by_partner {
+            -amount * (100.0 - self.partner_share) / 100.0
+        } else {
+            amount * self.partner_share / 100.0
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{Transaction, TransactionType};
+
+// Descriptions of a payment to the card, the one row whose direction is never in doubt
+const PAYMENT_MARKERS: &[&str] = &["payment", "thank you", "autopay", "pymt"];
+// Summary rows some exports append ("Total Purchases  1,234.56") carry the purchase sign
+const PURCHASE_SUMMARY_MARKERS: &[&str] = &["total purchases", "total debits", "total charges"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SignConvention {
+    PositivePurchases,
+    NegativePurchases,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionBasis {
+    Override,
+    PaymentRows,
+    SummaryRows,
+    Majority,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SignDetection {
+    pub convention: SignConvention,
+    pub basis: DetectionBasis,
+}
+
+impl SignDetection {
+    pub fn overridden(convention: SignConvention) -> SignDetection {
+        SignDetection { convention, basis: DetectionBasis::Override }
+    }
+}
+
+fn sign_of_matching(transactions: &[&Transaction], markers: &[&str]) -> f64 {
+    transactions.iter()
+        .filter(|t| {
+            let lower = t.description.to_lowercase();
+            markers.iter().any(|marker| lower.contains(marker))
+        })
+        .map(|t| t.amount.signum())
+        .sum()
+}
+
+// Infers which sign purchases carry from signed amounts: payments to the card first, then
+// summary rows, then whichever sign most rows have, since purchases outnumber credits
+pub fn detect(transactions: &[&Transaction]) -> SignDetection {
+    let payments = sign_of_matching(transactions, PAYMENT_MARKERS);
+    if payments != 0.0 {
+        let convention = if payments < 0.0 { SignConvention::PositivePurchases } else { SignConvention::NegativePurchases };
+        return SignDetection { convention, basis: DetectionBasis::PaymentRows };
+    }
+
+    let summaries = sign_of_matching(transactions, PURCHASE_SUMMARY_MARKERS);
+    if summaries != 0.0 {
+        let convention = if summaries > 0.0 { SignConvention::PositivePurchases } else { SignConvention::NegativePurchases };
+        return SignDetection { convention, basis: DetectionBasis::SummaryRows };
+    }
+
+    let negatives = transactions.iter().filter(|t| t.amount < 0.0).count();
+    let convention = if negatives * 2 > transactions.len() {
+        SignConvention::NegativePurchases
+    } else {
+        SignConvention::PositivePurchases
+    };
+    SignDetection { convention, basis: DetectionBasis::Majority }
+}
+
+// Turns a signed amount into the absolute amount the analysis uses, marking credits
+pub fn apply(transaction: &mut Transaction, convention: SignConvention) {
+    let purchase = match convention {
+        SignConvention::PositivePurchases => transaction.amount > 0.0,
+        SignConvention::NegativePurchases => transaction.amount < 0.0,
+    };
+    if !purchase {
+        transaction.transaction_type = TransactionType::Credit;
+    }
+    transaction.amount = transaction.amount.abs();
+}
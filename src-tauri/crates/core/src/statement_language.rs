@@ -0,0 +1,151 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{parse_amount, parse_date};
+
+// The language a PDF or scanned statement is printed in, which decides the OCR model and how
+// dates and amounts are written. CSV exports keep their own conventions and don't use this.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum StatementLanguage {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+// Month names and the abbreviations statements use, by language. Matched on their start, so
+// "févr." and "février" both hit "fév".
+const FRENCH_MONTHS: &[&str] = &["janv", "fév", "mars", "avr", "mai", "juin", "juil", "août", "sept", "oct", "nov", "déc"];
+const GERMAN_MONTHS: &[&str] = &["jan", "feb", "mär", "apr", "mai", "jun", "jul", "aug", "sep", "okt", "nov", "dez"];
+const SPANISH_MONTHS: &[&str] = &["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic"];
+
+// Day-first numeric dates, the European habit
+const DAY_FIRST_FORMATS: &[&str] = &["%d/%m/%Y", "%d.%m.%Y", "%d-%m-%Y", "%d/%m/%y", "%d.%m.%y", "%Y-%m-%d"];
+
+impl StatementLanguage {
+    // Tesseract's traineddata name; the model has to be installed alongside tesseract
+    pub fn ocr_model(self) -> &'static str {
+        match self {
+            StatementLanguage::English => "eng",
+            StatementLanguage::French => "fra",
+            StatementLanguage::German => "deu",
+            StatementLanguage::Spanish => "spa",
+        }
+    }
+
+    fn months(self) -> &'static [&'static str] {
+        match self {
+            StatementLanguage::English => &[],
+            StatementLanguage::French => FRENCH_MONTHS,
+            StatementLanguage::German => GERMAN_MONTHS,
+            StatementLanguage::Spanish => SPANISH_MONTHS,
+        }
+    }
+
+    // "1.234,56", "1 234,56 €" and German "12,50-" for the continental languages
+    pub fn parse_amount(self, text: &str) -> Result<f64, Box<dyn std::error::Error>> {
+        if self == StatementLanguage::English {
+            return parse_amount(text);
+        }
+        let mut cleaned: String = text.trim()
+            .trim_end_matches("EUR")
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '€' && *c != '.')
+            .collect();
+        let negative = cleaned.ends_with('-');
+        if negative {
+            cleaned.pop();
+            cleaned.insert(0, '-');
+        }
+        parse_amount(&cleaned.replace(',', "."))
+    }
+
+    // Day-first dates and local month names ("15 mars 2024", "3. Okt. 2024")
+    pub fn parse_date(self, text: &str) -> Option<NaiveDate> {
+        if self == StatementLanguage::English {
+            return parse_date(text);
+        }
+        let trimmed = text.trim();
+        DAY_FIRST_FORMATS.iter()
+            .find_map(|format| NaiveDate::parse_from_str(trimmed, format).ok())
+            .or_else(|| {
+                let numeric: Vec<String> = trimmed.split_whitespace()
+                    .map(|word| {
+                        let word = word.trim_end_matches(['.', ',']).to_lowercase();
+                        match self.months().iter().position(|month| word.starts_with(month)) {
+                            Some(index) => (index + 1).to_string(),
+                            None => word,
+                        }
+                    })
+                    .collect();
+                NaiveDate::parse_from_str(&numeric.join(" "), "%d %m %Y").ok()
+            })
+    }
+
+    // A date as printed in a transaction list, which tends to leave the year off
+    pub fn complete_date(self, date: &str, year: &str) -> String {
+        let date = date.trim().trim_end_matches('.');
+        if self.parse_date(date).is_some() {
+            return date.to_string();
+        }
+        let separator = match self {
+            _ if date.contains('.') => ".",
+            _ if date.contains('/') => "/",
+            // "Jan 15, 2024", as parse_date reads it
+            StatementLanguage::English => ", ",
+            _ => " ",
+        };
+        if date.matches(separator).count() >= 2 {
+            return date.to_string();
+        }
+        format!("{}{}{}", date, separator, year)
+    }
+
+    // Regex for a transaction date, day and month with an optional year
+    pub fn date_pattern(self) -> String {
+        match self {
+            StatementLanguage::English => r"\d{1,2}/\d{1,2}(?:/\d{2,4})?|(?i:jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]* \d{1,2}".to_string(),
+            _ => format!(r"\d{{1,2}}[./-]\d{{1,2}}(?:[./-]\d{{2,4}})?\.?|\d{{1,2}}\.? (?i:{})[a-zéû]*\.?", self.months().join("|")),
+        }
+    }
+
+    // Regex for an amount, without the "CR" English statements mark credits with
+    pub fn amount_pattern(self) -> &'static str {
+        match self {
+            StatementLanguage::English => r"-?\$?-?[\d,]+\.\d{2}",
+            _ => r"-?\d{1,3}(?:[. \x{a0}]\d{3})*,\d{2}-?(?: ?(?:€|EUR))?",
+        }
+    }
+
+    // Wording on refunds and credits, which aren't spending
+    pub fn is_credit(self, description: &str) -> bool {
+        let words: &[&str] = match self {
+            StatementLanguage::English => &[],
+            StatementLanguage::French => &["remboursement", "avoir", "virement reçu"],
+            StatementLanguage::German => &["gutschrift", "erstattung", "rückbuchung"],
+            StatementLanguage::Spanish => &["abono", "devolución", "reembolso"],
+        };
+        let lower = description.to_lowercase();
+        words.iter().any(|word| lower.contains(word))
+    }
+
+    // Drops the label banks put before the merchant, e.g. "PAIEMENT CB" or "KARTENZAHLUNG"
+    pub fn strip_card_label(self, description: &str) -> String {
+        let labels: &[&str] = match self {
+            StatementLanguage::English => &[],
+            StatementLanguage::French => &["paiement par carte", "paiement cb", "achat cb", "carte"],
+            StatementLanguage::German => &["kartenzahlung", "kartenumsatz", "lastschrift"],
+            StatementLanguage::Spanish => &["compra con tarjeta", "compra tarjeta", "pago con tarjeta", "compra"],
+        };
+        let trimmed = description.trim();
+        labels.iter()
+            .find(|label| trimmed.to_lowercase().starts_with(*label))
+            .and_then(|label| trimmed.get(label.len()..))
+            .map(|rest| rest.trim().to_string())
+            .filter(|rest| !rest.is_empty())
+            .unwrap_or_else(|| trimmed.to_string())
+    }
+}
@@ -0,0 +1,93 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{parse_amount, parse_date};
+
+// Checked in order, so a co-brand like "Apple Card" wins over its bank's name in the fine print
+const ISSUERS: &[(&str, &str)] = &[
+    ("apple card", "Apple Card"),
+    ("american express", "American Express"),
+    ("amex", "American Express"),
+    ("chase", "Chase"),
+    ("citibank", "Citi"),
+    ("citi ", "Citi"),
+    ("capital one", "Capital One"),
+    ("discover", "Discover"),
+    ("bank of america", "Bank of America"),
+    ("wells fargo", "Wells Fargo"),
+    ("barclays", "Barclays"),
+    ("u.s. bank", "U.S. Bank"),
+    ("synchrony", "Synchrony"),
+    ("hdfc", "HDFC Bank"),
+    ("icici", "ICICI Bank"),
+    ("state bank of india", "SBI"),
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct StatementMeta {
+    pub issuer: Option<String>,
+    pub card_last4: Option<String>,
+    // ISO dates
+    pub statement_date: Option<String>,
+    pub payment_due_date: Option<String>,
+    pub minimum_payment: Option<f64>,
+    pub new_balance: Option<f64>,
+    pub purchase_apr: Option<f64>,
+}
+
+fn capture(text: &str, pattern: &str) -> Option<String> {
+    Regex::new(pattern).ok()?
+        .captures(text)
+        .and_then(|captures| captures.get(1))
+        .map(|m| m.as_str().trim().to_string())
+}
+
+fn iso_date(value: &str) -> Option<String> {
+    parse_date(value).map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+const DATE: &str = r"(\d{1,2}/\d{1,2}/\d{2,4}|[A-Za-z]{3,9} \d{1,2}, \d{4}|\d{4}-\d{2}-\d{2})";
+const MONEY: &str = r"\$?\s?([\d,]+\.\d{2})";
+
+// Pulls the summary block most issuers print on the first page. Returns None when nothing
+// recognisable is found, e.g. for a bare transaction CSV.
+pub fn extract(text: &str) -> Option<StatementMeta> {
+    let lower = text.to_lowercase();
+    let issuer = ISSUERS.iter()
+        .find(|(marker, _)| lower.contains(marker))
+        .map(|(_, name)| name.to_string());
+
+    let card_last4 = capture(text, r"(?i)(?:ending in|card ending|account ending)\s*:?\s*(\d{4})\b")
+        .or_else(|| capture(text, r"(?i)(?:x{4}|\*{4})[\s-]?(?:(?:x{4}|\*{4})[\s-]?){0,2}(\d{4})\b"));
+
+    // "Opening/Closing Date 01/01/24 - 01/31/24" puts the closing date second
+    let statement_date = capture(text, r"(?i)opening/closing date\s*:?\s*\S+\s*-\s*(\d{1,2}/\d{1,2}/\d{2,4})")
+        .or_else(|| capture(text, &format!(r"(?i)(?:statement|closing) date\s*:?\s*{}", DATE)))
+        .and_then(|date| iso_date(&date));
+    let payment_due_date = capture(text, &format!(r"(?i)payment due date\s*:?\s*{}", DATE))
+        .and_then(|date| iso_date(&date));
+
+    let minimum_payment = capture(text, &format!(r"(?i)minimum (?:payment|amount) due\s*:?\s*{}", MONEY))
+        .and_then(|amount| parse_amount(&amount).ok());
+    let new_balance = capture(text, &format!(r"(?i)(?:new|statement) balance\s*:?\s*{}", MONEY))
+        .and_then(|amount| parse_amount(&amount).ok());
+    let purchase_apr = capture(text, r"(?i)purchases?\s+(?:apr\s+)?(\d{1,2}\.\d{1,2})\s?%")
+        .or_else(|| capture(text, r"(?i)annual percentage rate[^%]{0,60}?(\d{1,2}\.\d{1,2})\s?%"))
+        .and_then(|apr| apr.parse().ok());
+
+    let meta = StatementMeta {
+        issuer,
+        card_last4,
+        statement_date,
+        payment_due_date,
+        minimum_payment,
+        new_balance,
+        purchase_apr,
+    };
+
+    let found = meta.issuer.is_some() || meta.card_last4.is_some() || meta.statement_date.is_some() ||
+        meta.payment_due_date.is_some() || meta.minimum_payment.is_some() ||
+        meta.new_balance.is_some() || meta.purchase_apr.is_some();
+    found.then_some(meta)
+}
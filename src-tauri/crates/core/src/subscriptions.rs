@@ -0,0 +1,308 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::i18n::{self, DEFAULT_LOCALE};
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::interner::{CompactHistory, Symbol};
+use crate::services::{class_key, service_class, ServiceClass};
+use crate::{categorize_description, merchants};
+
+// Billing cycles vary with month length and posting delays
+const MIN_CYCLE_DAYS: i64 = 25;
+const MAX_CYCLE_DAYS: i64 = 35;
+// Charges more than this far apart in amount aren't the same price
+const AMOUNT_TOLERANCE: f64 = 0.05;
+// A bigger jump than this is a different plan or a different purchase, not a price rise
+const MAX_PRICE_RISE: f64 = 0.5;
+// Card-check authorizations that start a free trial: $0 where the importer keeps the row, or $1
+const TRIAL_AUTH_MAX: f64 = 1.0;
+// Most trials run a month; allow for posting delays either side
+const MIN_TRIAL_DAYS: i64 = 25;
+const MAX_TRIAL_DAYS: i64 = 35;
+// Recurring charges at or under this from a merchant nobody recognizes are easy to miss,
+// which is what makes them the usual way small-scale billing fraud goes unnoticed
+const GRAY_CHARGE_MAX: f64 = 15.0;
+// Merchant names that bill on a plan
+const SUBSCRIPTION_HINTS: &[&str] = &["subscription", "membership", "premium", "monthly", "recurring", ".com/bill", "plus", "prime"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PriceChange {
+    // ISO date of the first charge at the new price
+    pub date: String,
+    pub from: f64,
+    pub to: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Subscription {
+    pub merchant: String,
+    pub amount: f64,
+    pub charges: usize,
+    // ISO dates
+    pub first_charged: String,
+    pub last_charged: String,
+    // Oldest first
+    #[serde(default)]
+    pub price_changes: Vec<PriceChange>,
+    // What the rises since the first charge add up to over a year
+    #[serde(default)]
+    pub extra_annual_cost: f64,
+}
+
+// A merchant's first real charge, when it looks like a free trial that turned into a paid plan
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TrialConversion {
+    pub merchant: String,
+    // The small authorization that started the trial, if one was imported
+    pub trial_charged: Option<String>,
+    pub trial_amount: Option<f64>,
+    // ISO date
+    pub first_charged: String,
+    pub amount: f64,
+}
+
+// Two or more active subscriptions in the same service class
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ServiceOverlap {
+    pub class: ServiceClass,
+    // Priciest first
+    pub merchants: Vec<String>,
+    pub monthly_total: f64,
+    // The one to keep if the rest are cancelled
+    pub keep: String,
+    // What cancelling the others saves over a year
+    pub annual_savings: f64,
+}
+
+fn charges_by_merchant(history: &CompactHistory) -> HashMap<Symbol, Vec<(NaiveDate, f64)>> {
+    let mut by_merchant: HashMap<Symbol, Vec<(NaiveDate, f64)>> = HashMap::new();
+    for tx in &history.transactions {
+        if let Some(date) = tx.date {
+            by_merchant.entry(tx.merchant).or_default().push((date, tx.amount));
+        }
+    }
+    by_merchant
+}
+
+fn same_price(a: f64, b: f64) -> bool {
+    (a - b).abs() <= b * AMOUNT_TOLERANCE
+}
+
+// The run of monthly charges ending with the latest one, oldest first. The price may step up
+// along the way, but every price before the current one has to have been charged at least
+// twice, so a one-off purchase from the same merchant doesn't pass for the old price.
+fn monthly_run(charges: &[(NaiveDate, f64)]) -> Vec<(NaiveDate, f64)> {
+    let Some(&latest) = charges.last() else {
+        return Vec::new();
+    };
+    let mut run = vec![latest];
+    for &(date, charge) in charges.iter().rev().skip(1) {
+        let (next_date, next_charge) = run[run.len() - 1];
+        let gap = (next_date - date).num_days();
+        if gap < MIN_CYCLE_DAYS {
+            continue;
+        }
+        let rose_into_next = charge < next_charge && next_charge <= charge * (1.0 + MAX_PRICE_RISE);
+        if gap > MAX_CYCLE_DAYS || !(same_price(charge, next_charge) || rose_into_next) {
+            break;
+        }
+        run.push((date, charge));
+    }
+    run.reverse();
+
+    // Keep the newest prices, dropping from the first older one that wasn't charged twice
+    let mut levels: Vec<Vec<(NaiveDate, f64)>> = Vec::new();
+    for charge in run {
+        match levels.last_mut() {
+            Some(level) if same_price(charge.1, level[level.len() - 1].1) => level.push(charge),
+            _ => levels.push(vec![charge]),
+        }
+    }
+    let current = levels.len() - 1;
+    let keep_from = (0..current).rev()
+        .find(|&i| levels[i].len() < 2)
+        .map_or(0, |i| i + 1);
+    levels.drain(keep_from..).flatten().collect()
+}
+
+fn price_changes(run: &[(NaiveDate, f64)]) -> Vec<PriceChange> {
+    run.windows(2)
+        .filter(|pair| !same_price(pair[0].1, pair[1].1))
+        .map(|pair| PriceChange {
+            date: pair[1].0.format("%Y-%m-%d").to_string(),
+            from: pair[0].1,
+            to: pair[1].1,
+        })
+        .collect()
+}
+
+fn looks_like_subscription(merchant: &str) -> bool {
+    let lower = merchant.to_lowercase();
+    SUBSCRIPTION_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+// Merchants whose first charge above a card-check amount falls on or after `since`, either
+// about a month after a $0/$1 authorization or from a merchant whose name bills on a plan.
+// On a first import every merchant is new, so only the authorizations count then.
+pub fn detect_trial_conversions(history: &CompactHistory, since: NaiveDate) -> Vec<TrialConversion> {
+    let has_earlier = history.transactions.iter().any(|tx| tx.date.is_some_and(|date| date < since));
+    let mut conversions: Vec<TrialConversion> = charges_by_merchant(history).into_iter().filter_map(|(merchant, mut charges)| {
+        charges.sort_by_key(|(date, _)| *date);
+        let &(first_date, amount) = charges.iter().find(|(_, amount)| *amount > TRIAL_AUTH_MAX)?;
+        if first_date < since {
+            return None;
+        }
+        let trial = charges.iter()
+            .rfind(|(date, amount)| *amount <= TRIAL_AUTH_MAX && (MIN_TRIAL_DAYS..=MAX_TRIAL_DAYS).contains(&(first_date - *date).num_days()));
+        let name = history.merchants.resolve(merchant);
+        (trial.is_some() || (has_earlier && looks_like_subscription(name))).then(|| TrialConversion {
+            merchant: name.to_string(),
+            trial_charged: trial.map(|(date, _)| date.format("%Y-%m-%d").to_string()),
+            trial_amount: trial.map(|(_, amount)| *amount),
+            first_charged: first_date.format("%Y-%m-%d").to_string(),
+            amount,
+        })
+    }).collect();
+
+    conversions.sort_by(|a, b| a.first_charged.cmp(&b.first_charged));
+    conversions
+}
+
+pub fn trial_insight(conversion: &TrialConversion) -> Insight {
+    let insight = match (&conversion.trial_charged, conversion.trial_amount) {
+        (Some(trial_date), Some(trial_amount)) => Insight::new(
+            InsightKind::TrialConversion,
+            Severity::Warning,
+            "subscriptions.trial_converted",
+            format!("{} charged ${:.2} on {}, about a month after a ${:.2} authorization. A free trial has likely turned into a paid plan; cancel it if you don't mean to keep it",
+                    conversion.merchant, conversion.amount, conversion.first_charged, trial_amount),
+        )
+        .with_param("trial_date", trial_date)
+        .with_param("trial_amount", format!("{:.2}", trial_amount)),
+        _ => Insight::new(
+            InsightKind::TrialConversion,
+            Severity::Notice,
+            "subscriptions.new_plan",
+            format!("{} charged you ${:.2} for the first time on {}. If that's the end of a free trial, check you meant to keep it",
+                    conversion.merchant, conversion.amount, conversion.first_charged),
+        ),
+    };
+    insight
+        .with_amount(conversion.amount)
+        .with_merchant(&conversion.merchant)
+        .with_param("merchant", &conversion.merchant)
+        .with_param("amount", format!("{:.2}", conversion.amount))
+        .with_param("date", &conversion.first_charged)
+}
+
+// Merchants billing a similar amount about once a month. A subscription counts as active
+// if it was charged within the last cycle before `as_of`.
+pub fn detect_subscriptions(history: &CompactHistory, as_of: NaiveDate) -> Vec<Subscription> {
+    let mut subscriptions: Vec<Subscription> = charges_by_merchant(history).into_iter().filter_map(|(merchant, mut charges)| {
+        charges.sort_by_key(|(date, _)| *date);
+        // Overlapping imports can hold the same charge twice
+        charges.dedup_by(|a, b| a.0 == b.0 && (a.1 - b.1).abs() < 0.01);
+
+        let run = monthly_run(&charges);
+        let (&(first_date, first_amount), &(last_date, amount)) = (run.first()?, run.last()?);
+        let active = (as_of - last_date).num_days() <= MAX_CYCLE_DAYS;
+        (run.len() >= 2 && active).then(|| Subscription {
+            merchant: history.merchants.resolve(merchant).to_string(),
+            amount,
+            charges: run.len(),
+            first_charged: first_date.format("%Y-%m-%d").to_string(),
+            last_charged: last_date.format("%Y-%m-%d").to_string(),
+            price_changes: price_changes(&run),
+            extra_annual_cost: ((amount - first_amount) * 12.0).max(0.0),
+        })
+    }).collect();
+
+    subscriptions.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+    subscriptions
+}
+
+// Price rises that took effect on or after `since`, i.e. within the statement being analyzed
+pub fn price_hike_insights(subscriptions: &[Subscription], since: NaiveDate) -> Vec<Insight> {
+    subscriptions.iter()
+        .filter_map(|subscription| {
+            let change = subscription.price_changes.last().filter(|c| c.to > c.from)?;
+            let date = NaiveDate::parse_from_str(&change.date, "%Y-%m-%d").ok().filter(|d| *d >= since)?;
+            let annual = (change.to - change.from) * 12.0;
+            Some(Insight::new(
+                InsightKind::PriceHike,
+                Severity::Notice,
+                "subscriptions.price_hike",
+                format!("{} went from ${:.2} to ${:.2} in {}, an extra ${:.2} a year (${:.2} a year more than when you started)",
+                        subscription.merchant, change.from, change.to, date.format("%B"), annual, subscription.extra_annual_cost),
+            )
+            .with_amount(annual)
+            .with_merchant(&subscription.merchant)
+            .with_param("merchant", &subscription.merchant)
+            .with_param("from", format!("{:.2}", change.from))
+            .with_param("to", format!("{:.2}", change.to))
+            .with_param("date", &change.date)
+            .with_param("annual", format!("{:.2}", annual))
+            .with_param("total_annual", format!("{:.2}", subscription.extra_annual_cost)))
+        })
+        .collect()
+}
+
+// A small subscription from a merchant that's neither in the knowledge base nor in any category
+pub fn is_gray_charge(subscription: &Subscription) -> bool {
+    subscription.amount <= GRAY_CHARGE_MAX
+        && merchants::lookup(&subscription.merchant).is_none()
+        && categorize_description(&subscription.merchant) == "Other"
+}
+
+// Groups subscriptions by what they're for. Savings assume the priciest plan is kept, since
+// it's usually the one in use, so the estimate errs on the low side.
+pub fn service_overlaps(subscriptions: &[Subscription]) -> Vec<ServiceOverlap> {
+    let mut by_class: BTreeMap<ServiceClass, Vec<&Subscription>> = BTreeMap::new();
+    for subscription in subscriptions {
+        if let Some(class) = service_class(&subscription.merchant) {
+            by_class.entry(class).or_default().push(subscription);
+        }
+    }
+
+    let mut overlaps: Vec<ServiceOverlap> = by_class.into_iter()
+        .filter(|(_, subs)| subs.len() >= 2)
+        .map(|(class, mut subs)| {
+            subs.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+            let monthly_total: f64 = subs.iter().map(|s| s.amount).sum();
+            ServiceOverlap {
+                class,
+                merchants: subs.iter().map(|s| s.merchant.clone()).collect(),
+                monthly_total,
+                keep: subs[0].merchant.clone(),
+                annual_savings: (monthly_total - subs[0].amount) * 12.0,
+            }
+        })
+        .collect();
+
+    overlaps.sort_by(|a, b| b.annual_savings.total_cmp(&a.annual_savings));
+    overlaps
+}
+
+pub fn overlap_insight(overlap: &ServiceOverlap) -> Insight {
+    let service = class_key(overlap.class);
+    let others = overlap.merchants[1..].join(", ");
+    Insight::new(
+        InsightKind::OverlappingServices,
+        Severity::Notice,
+        "subscriptions.overlap",
+        format!("You're paying for {} {} services ({}), ${:.2} a month. Keeping just {} and cancelling {} would save about ${:.2} a year",
+                overlap.merchants.len(), i18n::text(DEFAULT_LOCALE, &format!("service.{}", service), &[]),
+                overlap.merchants.join(", "), overlap.monthly_total, overlap.keep, others, overlap.annual_savings),
+    )
+    .with_amount(overlap.annual_savings)
+    .with_merchant(&overlap.keep)
+    .with_param("count", overlap.merchants.len().to_string())
+    .with_param("service", service)
+    .with_param("merchants", overlap.merchants.join(", "))
+    .with_param("monthly", format!("{:.2}", overlap.monthly_total))
+    .with_param("keep", &overlap.keep)
+    .with_param("others", others)
+    .with_param("savings", format!("{:.2}", overlap.annual_savings))
+}
@@ -0,0 +1,200 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::periods::previous_month;
+use crate::Transaction;
+
+pub const DEFAULT_MONTHS: usize = 6;
+pub const MAX_MONTHS: usize = 36;
+// Same seed, same dataset, so screenshots and fixtures stay stable
+const DEFAULT_SEED: u64 = 0x5eed_cafe_f00d_d00d;
+// How many more restaurant visits a heavy-dining month has
+const DINING_FACTOR: u32 = 3;
+
+// Characteristics of a generated dataset. Everything off is an ordinary household's card.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct SyntheticProfile {
+    #[serde(default)]
+    pub months: Option<usize>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    // Restaurants and delivery several times a week
+    #[serde(default)]
+    pub heavy_dining: bool,
+    // A dozen streaming, software and news plans on top of the usual few
+    #[serde(default)]
+    pub many_subscriptions: bool,
+    // A stolen-card burst in the latest month: a test charge, foreign charges, an outsized order
+    #[serde(default)]
+    pub fraud_event: bool,
+}
+
+struct Merchant {
+    description: &'static str,
+    min: f64,
+    max: f64,
+    visits_per_month: u32,
+}
+
+struct Plan {
+    description: &'static str,
+    amount: f64,
+    day: u32,
+}
+
+const DINING: &[Merchant] = &[
+    Merchant { description: "STARBUCKS STORE 1024", min: 4.25, max: 9.80, visits_per_month: 9 },
+    Merchant { description: "TONY'S PIZZA", min: 18.00, max: 42.00, visits_per_month: 2 },
+    Merchant { description: "GREEN LEAF RESTAURANT", min: 35.00, max: 95.00, visits_per_month: 2 },
+];
+
+// Only heavy diners get these at all
+const EXTRA_DINING: &[Merchant] = &[
+    Merchant { description: "DOORDASH FOOD DELIVERY", min: 22.00, max: 58.00, visits_per_month: 4 },
+    Merchant { description: "SAKURA SUSHI RESTAURANT", min: 40.00, max: 120.00, visits_per_month: 2 },
+    Merchant { description: "BLUE DOOR CAFE", min: 9.00, max: 26.00, visits_per_month: 3 },
+];
+
+const EVERYDAY: &[Merchant] = &[
+    Merchant { description: "SHELL OIL 5741", min: 38.00, max: 62.00, visits_per_month: 3 },
+    Merchant { description: "UBER TRIP", min: 11.00, max: 34.00, visits_per_month: 3 },
+    Merchant { description: "AMAZON MKTPLACE", min: 12.99, max: 89.99, visits_per_month: 4 },
+    Merchant { description: "TARGET T-1832", min: 22.00, max: 140.00, visits_per_month: 2 },
+    Merchant { description: "CVS PHARMACY", min: 8.50, max: 46.00, visits_per_month: 1 },
+    Merchant { description: "REGAL MOVIE THEATER", min: 24.00, max: 48.00, visits_per_month: 1 },
+    Merchant { description: "CITY PARKING GARAGE", min: 6.00, max: 18.00, visits_per_month: 2 },
+];
+
+const SUBSCRIPTIONS: &[Plan] = &[
+    Plan { description: "NETFLIX.COM", amount: 15.49, day: 3 },
+    Plan { description: "SPOTIFY USA", amount: 10.99, day: 11 },
+    Plan { description: "PLANET FITNESS HEALTH CLUB", amount: 24.99, day: 17 },
+    Plan { description: "ICLOUD STORAGE", amount: 2.99, day: 24 },
+];
+
+const EXTRA_SUBSCRIPTIONS: &[Plan] = &[
+    Plan { description: "HULU 877-8244858", amount: 17.99, day: 1 },
+    Plan { description: "DISNEY PLUS", amount: 13.99, day: 5 },
+    Plan { description: "YOUTUBE PREMIUM", amount: 13.99, day: 7 },
+    Plan { description: "AUDIBLE US", amount: 14.95, day: 9 },
+    Plan { description: "NYTIMES DIGITAL", amount: 17.00, day: 12 },
+    Plan { description: "ADOBE CREATIVE CLOUD", amount: 59.99, day: 14 },
+    Plan { description: "DROPBOX PLUS", amount: 11.99, day: 19 },
+    Plan { description: "XBOX GAME PASS", amount: 16.99, day: 21 },
+    Plan { description: "PELOTON APP", amount: 12.99, day: 26 },
+];
+
+// xorshift64: plenty for made-up spending, and no extra dependency
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u32) -> u32 {
+        (self.next() % u64::from(n.max(1))) as u32
+    }
+
+    // Rounded to cents
+    fn amount(&mut self, min: f64, max: f64) -> f64 {
+        let fraction = (self.next() % 10_000) as f64 / 10_000.0;
+        ((min + fraction * (max - min)) * 100.0).round() / 100.0
+    }
+}
+
+fn days_in_month(month: NaiveDate) -> u32 {
+    let next = if month.month() == 12 {
+        NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+    };
+    next.and_then(|n| n.pred_opt()).map_or(28, |last| last.day())
+}
+
+fn iso_date(month: NaiveDate, day: u32) -> String {
+    month.with_day(day.clamp(1, days_in_month(month))).unwrap_or(month).format("%Y-%m-%d").to_string()
+}
+
+fn visits(transactions: &mut Vec<Transaction>, merchants: &[Merchant], factor: u32, month: NaiveDate, rng: &mut Rng) {
+    let days = days_in_month(month);
+    for merchant in merchants {
+        // A visit more or less than usual, so months don't look identical
+        let count = (merchant.visits_per_month * factor + rng.below(3)).saturating_sub(1);
+        for _ in 0..count {
+            let day = 1 + rng.below(days);
+            transactions.push(Transaction::new(iso_date(month, day), merchant.description.to_string(), rng.amount(merchant.min, merchant.max)));
+        }
+    }
+}
+
+// Someone else using the card over two days late in the month
+fn fraud_burst(month: NaiveDate, rng: &mut Rng) -> Vec<Transaction> {
+    let day = days_in_month(month) - 5;
+    let foreign = |date: String, description: &str, amount: f64, country: &str, currency: &str| {
+        let mut tx = Transaction::new(date, description.to_string(), amount);
+        tx.country = Some(country.to_string());
+        tx.currency = Some(currency.to_string());
+        tx
+    };
+    vec![
+        // Card testers check a stolen number with a tiny charge first
+        Transaction::new(iso_date(month, day), "ONLINE SVC VERIFY 00412".to_string(), 1.00),
+        foreign(iso_date(month, day), "ELEKTRO MARKT BUCURESTI", rng.amount(420.0, 690.0), "RO", "RON"),
+        foreign(iso_date(month, day + 1), "LUXE WATCHES BUCURESTI", rng.amount(1_150.0, 1_900.0), "RO", "RON"),
+        foreign(iso_date(month, day + 1), "GIFT CARD KIOSK SOFIA", rng.amount(300.0, 500.0), "BG", "BGN"),
+        // Far above the usual Amazon basket, so it shows up as an anomaly too
+        Transaction::new(iso_date(month, day + 1), "AMAZON MKTPLACE".to_string(), rng.amount(899.0, 1_299.0)),
+    ]
+}
+
+fn month_transactions(month: NaiveDate, profile: &SyntheticProfile, latest: bool, rng: &mut Rng) -> Vec<Transaction> {
+    let mut plans: Vec<&Plan> = SUBSCRIPTIONS.iter().collect();
+    if profile.many_subscriptions {
+        plans.extend(EXTRA_SUBSCRIPTIONS);
+    }
+    let mut transactions: Vec<Transaction> = plans.iter()
+        .map(|plan| Transaction::new(iso_date(month, plan.day), plan.description.to_string(), plan.amount))
+        .collect();
+
+    if profile.heavy_dining {
+        visits(&mut transactions, DINING, DINING_FACTOR, month, rng);
+        visits(&mut transactions, EXTRA_DINING, 1, month, rng);
+    } else {
+        visits(&mut transactions, DINING, 1, month, rng);
+    }
+    visits(&mut transactions, EVERYDAY, 1, month, rng);
+
+    if profile.fraud_event && latest {
+        transactions.extend(fraud_burst(month, rng));
+    }
+    transactions.sort_by(|a, b| a.date.cmp(&b.date));
+    transactions
+}
+
+// One statement per calendar month, oldest first, ending with the month before `today`. Each
+// comes with the source path it's stored under.
+pub fn generate(profile: &SyntheticProfile, today: NaiveDate) -> Vec<(String, Vec<Transaction>)> {
+    let months = profile.months.unwrap_or(DEFAULT_MONTHS).clamp(1, MAX_MONTHS);
+    let mut rng = Rng(profile.seed.filter(|seed| *seed != 0).unwrap_or(DEFAULT_SEED));
+
+    let mut month = previous_month(today.with_day(1).unwrap_or(today));
+    let mut firsts = Vec::with_capacity(months);
+    for _ in 0..months {
+        firsts.push(month);
+        month = previous_month(month);
+    }
+    firsts.reverse();
+
+    firsts.iter()
+        .enumerate()
+        .map(|(i, month)| {
+            let transactions = month_transactions(*month, profile, i + 1 == months, &mut rng);
+            (format!("demo:{}", month.format("%Y-%m")), transactions)
+        })
+        .collect()
+}
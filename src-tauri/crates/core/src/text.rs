@@ -0,0 +1,54 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+// Invisible direction marks and embeddings that Arabic and Hebrew exports wrap merchant names
+// in, plus the byte-order mark. They'd make the same merchant look like two.
+fn is_invisible_control(c: char) -> bool {
+    matches!(c, '\u{200E}' | '\u{200F}' | '\u{061C}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{FEFF}')
+}
+
+// A description as it should be stored: composed (NFC), so "é" typed two ways compares
+// equal, and without direction controls
+pub fn clean(text: &str) -> String {
+    text.nfc().filter(|c| !is_invisible_control(*c)).collect::<String>().trim().to_string()
+}
+
+// For case-insensitive matching in any script. Compatibility forms fold too, so the
+// full-width "ＳＴＡＲＢＵＣＫＳ" on Japanese and Chinese statements matches "starbucks", and
+// Arabic presentation forms match the letters they're drawn from.
+pub fn fold(text: &str) -> String {
+    text.nfkc()
+        .filter(|c| !is_invisible_control(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c == 'ς' { 'σ' } else { c })
+        .collect::<String>()
+        .replace('ß', "ss")
+}
+
+// Whether `haystack` contains `needle`, ignoring case in any script
+pub fn contains_folded(haystack: &str, needle: &str) -> bool {
+    fold(haystack).contains(&fold(needle))
+}
+
+pub fn eq_folded(a: &str, b: &str) -> bool {
+    fold(a) == fold(b)
+}
+
+// The first `max` user-perceived characters. Cutting on graphemes rather than bytes or chars
+// keeps Hangul syllables, emoji and letters with combining marks whole.
+pub fn prefix(text: &str, max: usize) -> &str {
+    match text.grapheme_indices(true).nth(max) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+// Shortened to `max` graphemes with an ellipsis, for display
+pub fn truncate(text: &str, max: usize) -> String {
+    let shortened = prefix(text, max);
+    if shortened.len() == text.len() {
+        text.to_string()
+    } else {
+        format!("{}…", shortened.trim_end())
+    }
+}
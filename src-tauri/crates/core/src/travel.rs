@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+use crate::insights::{Insight, InsightKind, Severity};
+use crate::Transaction;
+
+const HOME_CURRENCY: &str = "USD";
+// What most cards without a no-FX-fee benefit charge on foreign purchases
+const TYPICAL_FX_FEE_RATE: f64 = 0.03;
+
+// Country codes issuers append to foreign merchant descriptors. Two-letter codes
+// that double as US state abbreviations (CA, DE, IN, ...) are left out on purpose.
+const COUNTRY_CODES: &[(&str, &str)] = &[
+    ("GB", "United Kingdom"), ("UK", "United Kingdom"), ("GBR", "United Kingdom"),
+    ("FR", "France"), ("FRA", "France"),
+    ("DEU", "Germany"),
+    ("IT", "Italy"), ("ITA", "Italy"),
+    ("ES", "Spain"), ("ESP", "Spain"),
+    ("PT", "Portugal"), ("PRT", "Portugal"),
+    ("NL", "Netherlands"), ("NLD", "Netherlands"),
+    ("BE", "Belgium"), ("BEL", "Belgium"),
+    ("CH", "Switzerland"), ("CHE", "Switzerland"),
+    ("AT", "Austria"), ("AUT", "Austria"),
+    ("IE", "Ireland"), ("IRL", "Ireland"),
+    ("GR", "Greece"), ("GRC", "Greece"),
+    ("SE", "Sweden"), ("SWE", "Sweden"),
+    ("NO", "Norway"), ("NOR", "Norway"),
+    ("DK", "Denmark"), ("DNK", "Denmark"),
+    ("FI", "Finland"), ("FIN", "Finland"),
+    ("CZ", "Czech Republic"), ("CZE", "Czech Republic"),
+    ("PL", "Poland"), ("POL", "Poland"),
+    ("IS", "Iceland"), ("ISL", "Iceland"),
+    ("TR", "Turkey"), ("TUR", "Turkey"),
+    ("CAN", "Canada"),
+    ("MX", "Mexico"), ("MEX", "Mexico"),
+    ("CR", "Costa Rica"), ("CRI", "Costa Rica"),
+    ("BR", "Brazil"), ("BRA", "Brazil"),
+    ("PE", "Peru"), ("PER", "Peru"),
+    ("JP", "Japan"), ("JPN", "Japan"),
+    ("CN", "China"), ("CHN", "China"),
+    ("HK", "Hong Kong"), ("HKG", "Hong Kong"),
+    ("KR", "South Korea"), ("KOR", "South Korea"),
+    ("SG", "Singapore"), ("SGP", "Singapore"),
+    ("TH", "Thailand"), ("THA", "Thailand"),
+    ("VN", "Vietnam"), ("VNM", "Vietnam"),
+    ("PH", "Philippines"), ("PHL", "Philippines"),
+    ("MY", "Malaysia"), ("MYS", "Malaysia"),
+    ("IND", "India"),
+    ("AE", "United Arab Emirates"), ("ARE", "United Arab Emirates"),
+    ("AU", "Australia"), ("AUS", "Australia"),
+    ("NZ", "New Zealand"), ("NZL", "New Zealand"),
+    ("ZA", "South Africa"), ("ZAF", "South Africa"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct TravelSummary {
+    pub total_spent_abroad: f64,
+    pub transaction_count: usize,
+    pub countries: Vec<CountryTotal>,
+    pub total_fx_fees: f64,
+    pub fx_fee_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CountryTotal {
+    pub country: String,
+    pub total: f64,
+    pub count: u32,
+}
+
+pub fn is_fx_fee(description: &str) -> bool {
+    let desc_lower = description.to_lowercase();
+
+    desc_lower.contains("foreign transaction fee") || desc_lower.contains("foreign trans fee") ||
+    desc_lower.contains("intl transaction fee") || desc_lower.contains("international transaction fee") ||
+    desc_lower.contains("currency conversion fee") || desc_lower.contains("fx fee")
+}
+
+fn country_from_description(description: &str) -> Option<String> {
+    let last_word = description.split_whitespace().last()?.to_uppercase();
+
+    COUNTRY_CODES.iter()
+        .find(|(code, _)| *code == last_word)
+        .map(|(_, name)| name.to_string())
+}
+
+pub(crate) fn foreign_country(tx: &Transaction) -> Option<String> {
+    if let Some(country) = &tx.country {
+        let upper = country.trim().to_uppercase();
+        if !upper.is_empty() && upper != "US" && upper != "USA" && upper != "UNITED STATES" {
+            return Some(country.trim().to_string());
+        }
+    }
+
+    if let Some(currency) = &tx.currency {
+        if !currency.trim().is_empty() && !currency.trim().eq_ignore_ascii_case(HOME_CURRENCY) {
+            return Some(country_from_description(&tx.description)
+                .unwrap_or_else(|| format!("Unknown ({})", currency.trim().to_uppercase())));
+        }
+    }
+
+    country_from_description(&tx.description)
+}
+
+pub fn summarize_travel(transactions: &[Transaction]) -> Option<TravelSummary> {
+    let mut country_totals: HashMap<String, (f64, u32)> = HashMap::new();
+    let mut total_fx_fees = 0.0;
+
+    for tx in transactions {
+        if is_fx_fee(&tx.description) {
+            total_fx_fees += tx.amount;
+        } else if let Some(country) = foreign_country(tx) {
+            let entry = country_totals.entry(country).or_insert((0.0, 0));
+            entry.0 += tx.amount;
+            entry.1 += 1;
+        }
+    }
+
+    if country_totals.is_empty() && total_fx_fees == 0.0 {
+        return None;
+    }
+
+    let mut countries: Vec<CountryTotal> = country_totals
+        .into_iter()
+        .map(|(country, (total, count))| CountryTotal { country, total, count })
+        .collect();
+
+    countries.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    let total_spent_abroad: f64 = countries.iter().map(|c| c.total).sum();
+    let transaction_count = countries.iter().map(|c| c.count as usize).sum();
+    let fx_fee_rate = if total_spent_abroad > 0.0 {
+        (total_fx_fees / total_spent_abroad) * 100.0
+    } else {
+        0.0
+    };
+
+    Some(TravelSummary {
+        total_spent_abroad,
+        transaction_count,
+        countries,
+        total_fx_fees,
+        fx_fee_rate,
+    })
+}
+
+pub fn travel_insights(summary: &TravelSummary) -> Vec<Insight> {
+    let mut insights = Vec::new();
+
+    if summary.total_spent_abroad > 0.0 {
+        let countries = summary.countries.len();
+        insights.push(Insight::new(
+            InsightKind::Travel,
+            Severity::Info,
+            "travel.spent_abroad",
+            format!("You spent ${:.2} abroad across {} transactions in {} {}",
+                    summary.total_spent_abroad,
+                    summary.transaction_count,
+                    countries,
+                    if countries == 1 { "country" } else { "countries" }),
+        )
+        .with_amount(summary.total_spent_abroad)
+        .with_param("total", format!("{:.2}", summary.total_spent_abroad))
+        .with_param("count", summary.transaction_count)
+        .with_param("countries", countries));
+    }
+
+    if summary.total_fx_fees > 0.0 {
+        insights.push(Insight::new(
+            InsightKind::ForeignFees,
+            Severity::Warning,
+            "travel.fx_fees_paid",
+            format!("You paid ${:.2} in foreign transaction fees ({:.1}% of foreign spending) — a card with no foreign transaction fees would have saved all of it",
+                    summary.total_fx_fees, summary.fx_fee_rate),
+        )
+        .with_amount(summary.total_fx_fees)
+        .with_param("fees", format!("{:.2}", summary.total_fx_fees))
+        .with_param("rate", format!("{:.1}", summary.fx_fee_rate)));
+    } else if summary.total_spent_abroad > 0.0 {
+        let estimate = summary.total_spent_abroad * TYPICAL_FX_FEE_RATE;
+        insights.push(Insight::new(
+            InsightKind::ForeignFees,
+            Severity::Info,
+            "travel.fx_fees_none",
+            format!("No foreign transaction fees found. If your card charges the typical {:.0}% fee, check for ${:.2} billed separately; no-FX-fee cards avoid it entirely",
+                    TYPICAL_FX_FEE_RATE * 100.0, estimate),
+        )
+        .with_amount(estimate)
+        .with_param("rate", format!("{:.0}", TYPICAL_FX_FEE_RATE * 100.0))
+        .with_param("estimate", format!("{:.2}", estimate)));
+    }
+
+    insights
+}
@@ -0,0 +1,158 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+use crate::travel::{foreign_country, is_fx_fee};
+use crate::{parse_date, transaction_category, Transaction};
+
+// Days without an away-from-home charge before the next one counts as a new trip
+const TRIP_GAP_DAYS: i64 = 3;
+// A lone charge from out of town is usually a mail-order merchant, not travel
+const MIN_TRIP_TRANSACTIONS: usize = 2;
+
+const US_STATES: &[&str] = &[
+    "AL", "AK", "AZ", "AR", "CA", "CO", "CT", "DE", "DC", "FL", "GA", "HI", "ID", "IL", "IN", "IA",
+    "KS", "KY", "LA", "ME", "MD", "MA", "MI", "MN", "MS", "MO", "MT", "NE", "NV", "NH", "NJ", "NM",
+    "NY", "NC", "ND", "OH", "OK", "OR", "PA", "RI", "SC", "SD", "TN", "TX", "UT", "VT", "VA", "WA",
+    "WV", "WI", "WY",
+];
+
+const FLIGHT_KEYWORDS: &[&str] = &[
+    "airline", "airlines", "airways", "delta air", "united air", "american air", "southwest",
+    "jetblue", "alaska air", "spirit air", "frontier", "ryanair", "easyjet", "lufthansa", "air canada",
+];
+const LODGING_KEYWORDS: &[&str] = &[
+    "hotel", "motel", "inn ", "resort", "hostel", "airbnb", "vrbo", "booking.com", "expedia",
+    "marriott", "hilton", "hyatt", "sheraton", "westin", "holiday inn", "best western",
+];
+const LOCAL_TRANSPORT_KEYWORDS: &[&str] = &[
+    "uber", "lyft", "taxi", "cab ", "metro", "subway", "transit", "rail", "train", "amtrak", "bus ",
+    "parking", "hertz", "avis", "enterprise rent", "budget rent", "sixt", "toll",
+];
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct Trip {
+    // ISO dates, inclusive
+    pub start: String,
+    pub end: String,
+    pub days: i64,
+    // The country or state most of the trip's charges came from
+    pub destination: Option<String>,
+    pub total: f64,
+    pub transaction_count: usize,
+    pub flights: f64,
+    pub lodging: f64,
+    pub dining: f64,
+    pub local_transport: f64,
+    pub other: f64,
+}
+
+fn matches_any(description: &str, keywords: &[&str]) -> bool {
+    let padded = format!("{} ", description.to_lowercase());
+    keywords.iter().any(|keyword| padded.contains(keyword))
+}
+
+// The US state a descriptor like "SHELL OIL 123 AUSTIN TX" ends with
+fn us_state(description: &str) -> Option<&'static str> {
+    let last_word = description.split_whitespace().last()?.to_uppercase();
+    US_STATES.iter().copied().find(|state| *state == last_word)
+}
+
+// Where the charge was made, if it was away from home. Without a home state set only foreign
+// charges count, since every domestic descriptor names some state.
+fn away_location(tx: &Transaction, home_state: Option<&str>) -> Option<String> {
+    if let Some(country) = foreign_country(tx) {
+        return Some(country);
+    }
+    let home_state = home_state?;
+    us_state(&tx.description)
+        .filter(|state| !state.eq_ignore_ascii_case(home_state))
+        .map(str::to_string)
+}
+
+fn is_travel_booking(tx: &Transaction) -> bool {
+    matches_any(&tx.description, FLIGHT_KEYWORDS) || matches_any(&tx.description, LODGING_KEYWORDS)
+}
+
+fn add_to_trip(trip: &mut Trip, tx: &Transaction) {
+    trip.total += tx.amount;
+    trip.transaction_count += 1;
+    if matches_any(&tx.description, FLIGHT_KEYWORDS) {
+        trip.flights += tx.amount;
+    } else if matches_any(&tx.description, LODGING_KEYWORDS) {
+        trip.lodging += tx.amount;
+    } else if matches_any(&tx.description, LOCAL_TRANSPORT_KEYWORDS) {
+        trip.local_transport += tx.amount;
+    } else if transaction_category(tx) == "Food & Dining" {
+        trip.dining += tx.amount;
+    } else {
+        trip.other += tx.amount;
+    }
+}
+
+fn build_trip(charges: &[(NaiveDate, &Transaction, Option<String>)]) -> Option<Trip> {
+    if charges.len() < MIN_TRIP_TRANSACTIONS {
+        return None;
+    }
+    let (start, end) = (charges.first()?.0, charges.last()?.0);
+
+    let mut places: HashMap<&str, usize> = HashMap::new();
+    for place in charges.iter().filter_map(|(_, _, place)| place.as_deref()) {
+        *places.entry(place).or_insert(0) += 1;
+    }
+    // Bookings alone, with nowhere recorded, aren't enough to say a trip happened
+    if places.is_empty() {
+        return None;
+    }
+    let destination = places.into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(place, _)| place.to_string());
+
+    let mut trip = Trip {
+        start: start.format("%Y-%m-%d").to_string(),
+        end: end.format("%Y-%m-%d").to_string(),
+        days: (end - start).num_days() + 1,
+        destination,
+        total: 0.0,
+        transaction_count: 0,
+        flights: 0.0,
+        lodging: 0.0,
+        dining: 0.0,
+        local_transport: 0.0,
+        other: 0.0,
+    };
+    for (_, tx, _) in charges {
+        add_to_trip(&mut trip, tx);
+    }
+    Some(trip)
+}
+
+// Groups away-from-home charges, plus flight and hotel bookings, into trips: runs of such
+// charges with no more than a few days between them. Oldest trip first.
+pub fn detect_trips(transactions: &[Transaction], home_state: Option<&str>) -> Vec<Trip> {
+    let mut charges: Vec<(NaiveDate, &Transaction, Option<String>)> = transactions.iter()
+        .filter(|tx| !is_fx_fee(&tx.description))
+        .filter_map(|tx| {
+            let place = away_location(tx, home_state);
+            if place.is_none() && !is_travel_booking(tx) {
+                return None;
+            }
+            Some((parse_date(&tx.date)?, tx, place))
+        })
+        .collect();
+    charges.sort_by_key(|(date, _, _)| *date);
+
+    let mut trips = Vec::new();
+    let mut current: Vec<(NaiveDate, &Transaction, Option<String>)> = Vec::new();
+    for charge in charges {
+        let continues = current.last().is_some_and(|(last, _, _)| (charge.0 - *last).num_days() <= TRIP_GAP_DAYS);
+        if !continues {
+            trips.extend(build_trip(&current));
+            current.clear();
+        }
+        current.push(charge);
+    }
+    trips.extend(build_trip(&current));
+    trips
+}
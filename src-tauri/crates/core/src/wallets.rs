@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{optional_field, parse_amount, parse_date, Transaction, TransactionType};
+
+// A card-funded payment can post to the card a few days after it shows in the app
+const MAX_POSTING_DELAY_DAYS: i64 = 3;
+const AMOUNT_TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Wallet {
+    PayPal,
+    Venmo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WalletPayment {
+    pub wallet: Wallet,
+    pub id: String,
+    pub date: String,
+    pub time: Option<String>,
+    pub counterparty: String,
+    pub note: Option<String>,
+    // Signed as in the export: money out is negative. net = gross + fee, with fees negative.
+    pub gross: f64,
+    pub fee: f64,
+    pub net: f64,
+    // Paid with a card, so the same money also appears on that card's statement
+    pub card_funded: bool,
+}
+
+pub fn detect_wallet(content: &str) -> Option<Wallet> {
+    let mut lines = content.lines().take(5).map(|line| line.to_lowercase());
+    let first = lines.next().unwrap_or_default();
+
+    if first.contains("transaction id") && first.contains("gross") && first.contains("net") {
+        Some(Wallet::PayPal)
+    } else if first.contains("account statement") || first.contains("datetime") ||
+              lines.any(|line| line.contains("datetime") && line.contains("funding source")) {
+        Some(Wallet::Venmo)
+    } else {
+        None
+    }
+}
+
+fn column(headers: &csv::StringRecord, name: &str) -> Option<usize> {
+    headers.iter().position(|header| header.trim().eq_ignore_ascii_case(name))
+}
+
+fn amount_field(record: &csv::StringRecord, column: Option<usize>) -> f64 {
+    // Venmo writes amounts as "- $25.00"
+    optional_field(record, column)
+        .and_then(|value| parse_amount(&value.replace(' ', "")).ok())
+        .unwrap_or(0.0)
+}
+
+fn is_card_funding(source: &str) -> bool {
+    let source_lower = source.to_lowercase();
+    ["visa", "mastercard", "amex", "american express", "discover", "credit", "card"]
+        .iter()
+        .any(|network| source_lower.contains(network))
+}
+
+fn parse_paypal(content: &str) -> Result<Vec<WalletPayment>, String> {
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
+    let headers = rdr.headers()
+        .map_err(|e| format!("Could not read PayPal headers: {}", e))?
+        .clone();
+
+    let date_col = column(&headers, "Date").ok_or("PayPal export has no Date column")?;
+    let gross_col = column(&headers, "Gross").ok_or("PayPal export has no Gross column")?;
+    let id_col = column(&headers, "Transaction ID").ok_or("PayPal export has no Transaction ID column")?;
+    let time_col = column(&headers, "Time");
+    let name_col = column(&headers, "Name");
+    let type_col = column(&headers, "Type");
+    let status_col = column(&headers, "Status");
+    let fee_col = column(&headers, "Fee");
+    let net_col = column(&headers, "Net");
+    let subject_col = column(&headers, "Item Title").or_else(|| column(&headers, "Subject"));
+
+    let mut payments = Vec::new();
+    // PayPal logs card funding as its own "General Card Deposit" row next to the payment
+    let mut card_deposits: Vec<(String, f64)> = Vec::new();
+
+    for result in rdr.records() {
+        let record = result.map_err(|e| format!("Could not read PayPal activity: {}", e))?;
+
+        let status = optional_field(&record, status_col).unwrap_or_default().to_lowercase();
+        if !status.is_empty() && status != "completed" {
+            continue;
+        }
+
+        let date = record.get(date_col).unwrap_or("").trim().to_string();
+        let gross = amount_field(&record, Some(gross_col));
+        let kind = optional_field(&record, type_col).unwrap_or_default().to_lowercase();
+
+        if kind.contains("card deposit") {
+            card_deposits.push((date, gross.abs()));
+            continue;
+        }
+        if kind.contains("deposit") || kind.contains("transfer") || kind.contains("currency conversion") ||
+           kind.contains("authorization") || kind.contains("hold") {
+            continue;
+        }
+
+        let fee = amount_field(&record, fee_col);
+        let net = net_col.map(|_| amount_field(&record, net_col)).unwrap_or(gross + fee);
+
+        payments.push(WalletPayment {
+            wallet: Wallet::PayPal,
+            id: optional_field(&record, Some(id_col)).unwrap_or_default(),
+            date,
+            time: optional_field(&record, time_col),
+            counterparty: optional_field(&record, name_col).unwrap_or_else(|| "PayPal".to_string()),
+            note: optional_field(&record, subject_col),
+            gross,
+            fee,
+            net,
+            card_funded: false,
+        });
+    }
+
+    for payment in payments.iter_mut().filter(|p| p.gross < 0.0) {
+        if let Some(index) = card_deposits.iter()
+            .position(|(date, amount)| *date == payment.date && (amount - payment.gross.abs()).abs() < AMOUNT_TOLERANCE) {
+            card_deposits.swap_remove(index);
+            payment.card_funded = true;
+        }
+    }
+
+    Ok(payments)
+}
+
+fn parse_venmo(content: &str) -> Result<Vec<WalletPayment>, String> {
+    // Statements open with a few title lines before the real header row
+    let header_line = content.lines()
+        .position(|line| {
+            let line_lower = line.to_lowercase();
+            line_lower.contains("datetime") && line_lower.contains("amount")
+        })
+        .ok_or("Venmo statement has no activity header")?;
+    let body: String = content.lines().skip(header_line).collect::<Vec<_>>().join("\n");
+
+    let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(body.as_bytes());
+    let headers = rdr.headers()
+        .map_err(|e| format!("Could not read Venmo headers: {}", e))?
+        .clone();
+
+    let id_col = column(&headers, "ID").ok_or("Venmo statement has no ID column")?;
+    let datetime_col = column(&headers, "Datetime").ok_or("Venmo statement has no Datetime column")?;
+    let amount_col = column(&headers, "Amount (total)").ok_or("Venmo statement has no Amount (total) column")?;
+    let type_col = column(&headers, "Type");
+    let status_col = column(&headers, "Status");
+    let note_col = column(&headers, "Note");
+    let from_col = column(&headers, "From");
+    let to_col = column(&headers, "To");
+    let fee_col = column(&headers, "Amount (fee)");
+    let funding_col = column(&headers, "Funding Source");
+
+    let mut payments = Vec::new();
+
+    for result in rdr.records() {
+        let record = result.map_err(|e| format!("Could not read Venmo activity: {}", e))?;
+
+        // Summary rows at the bottom have no transaction ID
+        let Some(id) = optional_field(&record, Some(id_col)) else {
+            continue;
+        };
+        let status = optional_field(&record, status_col).unwrap_or_default().to_lowercase();
+        if !status.is_empty() && status != "complete" && status != "completed" {
+            continue;
+        }
+
+        let kind = optional_field(&record, type_col).unwrap_or_default().to_lowercase();
+        if kind.contains("transfer") {
+            continue;
+        }
+
+        let gross = amount_field(&record, Some(amount_col));
+        let fee = -amount_field(&record, fee_col).abs();
+        // A charge is requested by the other person, so they appear in From
+        let counterparty = if kind == "charge" {
+            optional_field(&record, from_col)
+        } else if gross < 0.0 {
+            optional_field(&record, to_col)
+        } else {
+            optional_field(&record, from_col)
+        };
+
+        let datetime = record.get(datetime_col).unwrap_or("").trim().to_string();
+        let (date, time) = match datetime.split_once('T') {
+            Some((date, time)) => (date.to_string(), Some(time.to_string())),
+            None => (datetime, None),
+        };
+
+        payments.push(WalletPayment {
+            wallet: Wallet::Venmo,
+            id,
+            date,
+            time,
+            counterparty: counterparty.unwrap_or_else(|| "Venmo".to_string()),
+            note: optional_field(&record, note_col),
+            gross,
+            fee,
+            net: gross + fee,
+            card_funded: optional_field(&record, funding_col).is_some_and(|source| is_card_funding(&source)),
+        });
+    }
+
+    Ok(payments)
+}
+
+pub fn parse_activity(content: &str, wallet: Wallet) -> Result<Vec<WalletPayment>, String> {
+    match wallet {
+        Wallet::PayPal => parse_paypal(content),
+        Wallet::Venmo => parse_venmo(content),
+    }
+}
+
+fn wallet_name(wallet: Wallet) -> &'static str {
+    match wallet {
+        Wallet::PayPal => "PAYPAL",
+        Wallet::Venmo => "VENMO",
+    }
+}
+
+// Spending as seen from the wallet itself. Card-funded payments are left out because the
+// card statement already carries them; fees are always kept since no card statement shows them.
+pub fn wallet_transactions(payments: &[WalletPayment]) -> Vec<Transaction> {
+    let mut transactions = Vec::new();
+
+    for payment in payments {
+        if payment.gross < 0.0 && !payment.card_funded {
+            transactions.push(Transaction {
+                date: payment.date.clone(),
+                description: format!("{} *{}", wallet_name(payment.wallet), payment.counterparty),
+                amount: payment.gross.abs(),
+                category: None,
+                currency: None,
+                country: None,
+                time: payment.time.clone(),
+                mcc: None,
+                rewards: None,
+                order_id: None,
+                payment_id: Some(payment.id.clone()),
+                section: None,
+                transaction_type: TransactionType::Debit,
+                note: None,
+                receipt_path: None,
+                tags: Vec::new(),
+                shared: None,
+                merchant_info: None,
+                manual: false,
+                rule_category: None,
+                import_key: None,
+            });
+        }
+
+        if payment.fee < 0.0 {
+            transactions.push(Transaction {
+                date: payment.date.clone(),
+                description: format!("{} FEE", wallet_name(payment.wallet)),
+                amount: payment.fee.abs(),
+                category: Some("Fees".to_string()),
+                currency: None,
+                country: None,
+                time: payment.time.clone(),
+                mcc: None,
+                rewards: None,
+                order_id: None,
+                payment_id: Some(payment.id.clone()),
+                section: None,
+                transaction_type: TransactionType::Debit,
+                note: None,
+                receipt_path: None,
+                tags: Vec::new(),
+                shared: None,
+                merchant_info: None,
+                manual: false,
+                rule_category: None,
+                import_key: None,
+            });
+        }
+    }
+
+    transactions
+}
+
+fn is_wallet_charge(description: &str, wallet: Wallet) -> bool {
+    let desc_lower = description.to_lowercase();
+    match wallet {
+        Wallet::PayPal => desc_lower.contains("paypal") || desc_lower.starts_with("pp*") || desc_lower.starts_with("pp *"),
+        Wallet::Venmo => desc_lower.contains("venmo"),
+    }
+}
+
+// Names the real payee on card charges that went through PayPal or Venmo, linking each
+// charge to its payment so the two aren't counted as separate spending.
+pub fn link_card_charges(transactions: &mut [Transaction], payments: &[WalletPayment]) -> usize {
+    let mut claimed: Vec<bool> = payments.iter()
+        .map(|payment| transactions.iter().any(|t| t.payment_id.as_deref() == Some(payment.id.as_str())))
+        .collect();
+    let mut linked = 0;
+
+    for tx in transactions.iter_mut().filter(|t| t.payment_id.is_none()) {
+        let Some(charge_date) = parse_date(&tx.date) else {
+            continue;
+        };
+
+        let best = payments.iter().enumerate()
+            .filter(|(index, payment)| {
+                !claimed[*index] && payment.card_funded && payment.gross < 0.0 &&
+                is_wallet_charge(&tx.description, payment.wallet) &&
+                (payment.gross.abs() - tx.amount).abs() < AMOUNT_TOLERANCE
+            })
+            .filter_map(|(index, payment)| {
+                let delay = (charge_date - parse_date(&payment.date)?).num_days();
+                (0..=MAX_POSTING_DELAY_DAYS).contains(&delay).then_some((index, delay))
+            })
+            .min_by_key(|(_, delay)| *delay);
+
+        if let Some((index, _)) = best {
+            claimed[index] = true;
+            linked += 1;
+            tx.description = format!("{} - {}", tx.description, payments[index].counterparty);
+            tx.payment_id = Some(payments[index].id.clone());
+        }
+    }
+
+    linked
+}
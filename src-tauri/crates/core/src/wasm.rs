@@ -0,0 +1,52 @@
+// Entry points for the in-browser build (`--features wasm`). Statements arrive as the bytes of
+// a file the user picked, since there's no filesystem to read them from, and nothing is kept
+// between calls: no history, cache or bank profiles.
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+
+use crate::settings::Settings;
+use crate::{analyze_transactions, categorize_description, i18n, insights, parse_bytes, ParseFailure, TransactionType};
+
+fn settings_from(value: JsValue) -> Result<Settings, JsError> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(Settings::default());
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&format!("Invalid settings: {}", e)))
+}
+
+// Analyzes one statement. `file_name` picks the format by its extension, as on the desktop.
+#[wasm_bindgen(js_name = analyzeStatement)]
+pub async fn analyze_statement(file_name: String, bytes: Vec<u8>, password: Option<String>, settings: JsValue) -> Result<JsValue, JsError> {
+    let settings = settings_from(settings)?;
+    let locale = settings.locale.clone();
+
+    let parsed = parse_bytes(&file_name, bytes, password.as_deref(), None, &[], settings.parse_mode, settings.statement_language).map_err(|e| match ParseFailure::from(e) {
+        ParseFailure::NeedsPassword => JsError::new(&i18n::error(&locale, "error.pdf_password_required")),
+        ParseFailure::WrongPassword => JsError::new(&i18n::error(&locale, "error.pdf_password_invalid")),
+        ParseFailure::AmbiguousColumns(ambiguity) => JsError::new(&ambiguity.message(&locale)),
+        ParseFailure::Failed(reason) => JsError::new(&i18n::text(&locale, "error.parse_failed", &[("reason", reason)])),
+    })?;
+
+    let mut transactions = parsed.transactions;
+    transactions.retain(|t| t.transaction_type == TransactionType::Debit);
+    if transactions.is_empty() {
+        return Err(JsError::new(&i18n::error(&locale, "error.no_transactions")));
+    }
+
+    let mut analysis = analyze_transactions(transactions, &file_name, &[], &settings).await;
+    analysis.statement_meta = parsed.meta;
+    analysis.sign_convention = parsed.sign_convention;
+    analysis.parse_warnings = parsed.warnings;
+    insights::rank(&mut analysis.insights);
+    i18n::localize_insights(&mut analysis.insights, &locale);
+
+    // Plain objects rather than Maps, so the result reads like the desktop app's JSON
+    analysis.serialize(&Serializer::json_compatible()).map_err(|e| JsError::new(&e.to_string()))
+}
+
+// The category a single description would be filed under, for previews while typing
+#[wasm_bindgen]
+pub fn categorize(description: &str) -> String {
+    categorize_description(description)
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::Store;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Import,
+    CategoryOverride,
+    // Notes, tags, receipts, splits and hand corrections to a single transaction
+    Edit,
+    Add,
+    // Deleted transactions go to the trash, and restores bring them back
+    Delete,
+    Restore,
+    BulkEdit,
+    Undo,
+    // Stored history brought up to date with the current categorization rules
+    Recategorize,
+    // Inconsistencies verify_data found and fixed
+    Repair,
+    // Bank profiles, goals, custom insights and settings that change how the numbers come out
+    RuleChange,
+}
+
+// One change to the stored data, with what it replaced
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub at: String,
+    pub action: AuditAction,
+    // What changed, e.g. "statement 3, transaction 12" or "bank profile Chase"
+    pub target: String,
+    // Values as JSON, None where there was nothing before (an import) or after (a deletion)
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+pub fn transaction_target(statement_id: u64, index: usize) -> String {
+    format!("statement {}, transaction {}", statement_id, index)
+}
+
+fn to_json<T: Serialize + ?Sized>(value: Option<&T>) -> Option<String> {
+    value.and_then(|value| serde_json::to_string(value).ok())
+}
+
+impl Store {
+    // Appends to the audit log. The log is never trimmed: it's what lets the user check how
+    // their numbers came to be.
+    pub fn audit<B: Serialize + ?Sized, A: Serialize + ?Sized>(&mut self, action: AuditAction, target: impl Into<String>, before: Option<&B>, after: Option<&A>) {
+        let id = self.allocate_id();
+        self.audit_log.push(AuditEntry {
+            id,
+            at: chrono::Utc::now().to_rfc3339(),
+            action,
+            target: target.into(),
+            before: to_json(before),
+            after: to_json(after),
+        });
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+
+use credit_analyzer_core::periods;
+
+use crate::audit::AuditAction;
+use crate::{rollups, Store};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum IssueKind {
+    // A month and category whose rollup doesn't match the transactions it sums
+    RollupMismatch,
+    // Two statements share an id, so edits to one can land on the other
+    DuplicateStatementId,
+    // The id counter is behind ids already handed out, so new ones would collide
+    IdCounterBehind,
+    // A trashed transaction whose statement is gone, so it can never be restored
+    OrphanTrash,
+    // A due-date reminder for a statement that's gone
+    OrphanReminder,
+    // Tags that aren't trimmed, lowercase, sorted and unique, which breaks tag matching
+    UnnormalizedTags,
+    // An amount that isn't a number, which turns every total it's in to NaN
+    InvalidAmount,
+    // A shared charge with no partner or a share outside 0-100%
+    InvalidSharedExpense,
+}
+
+// One inconsistency in the stored data. Repairs never touch what the user entered, so
+// amounts and shared charges are only reported, for fixing by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HealthIssue {
+    pub kind: IssueKind,
+    // Where, e.g. "2024-03 Dining: stored 120.00, statements sum to 130.00"
+    pub detail: String,
+    pub repairable: bool,
+}
+
+impl HealthIssue {
+    fn new(kind: IssueKind, detail: String) -> HealthIssue {
+        let repairable = !matches!(kind, IssueKind::InvalidAmount | IssueKind::InvalidSharedExpense);
+        HealthIssue { kind, detail, repairable }
+    }
+}
+
+// Tags as set_tags stores them
+pub fn normalized_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags.iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn rollup_issues(store: &Store) -> Vec<HealthIssue> {
+    if store.rollup_start_day.is_some_and(|day| day != periods::start_day()) {
+        return vec![HealthIssue::new(IssueKind::RollupMismatch, "rollups are grouped by a different period start day".to_string())];
+    }
+
+    let stored: HashMap<(&str, &str), (f64, u32)> = store.rollups.iter()
+        .map(|r| ((r.month.as_str(), r.category.as_str()), (r.total, r.count)))
+        .collect();
+    let expected = rollups::computed(&store.statements);
+    let expected_cells: HashSet<(&str, &str)> = expected.iter().map(|r| (r.month.as_str(), r.category.as_str())).collect();
+
+    let mut issues: Vec<HealthIssue> = expected.iter()
+        .filter_map(|r| {
+            let (total, count) = stored.get(&(r.month.as_str(), r.category.as_str())).copied().unwrap_or((0.0, 0));
+            ((total - r.total).abs() >= 0.005 || count != r.count).then(|| HealthIssue::new(
+                IssueKind::RollupMismatch,
+                format!("{} {}: stored {:.2} over {} rows, statements sum to {:.2} over {}", r.month, r.category, total, count, r.total, r.count),
+            ))
+        })
+        .collect();
+    issues.extend(store.rollups.iter()
+        .filter(|r| !expected_cells.contains(&(r.month.as_str(), r.category.as_str())))
+        .map(|r| HealthIssue::new(
+            IssueKind::RollupMismatch,
+            format!("{} {}: stored {:.2} over {} rows, no statements left", r.month, r.category, r.total, r.count),
+        )));
+    issues
+}
+
+fn duplicate_ids(store: &Store) -> Vec<u64> {
+    let mut seen = HashSet::new();
+    store.statements.iter().map(|s| s.id).filter(|id| !seen.insert(*id)).collect()
+}
+
+fn highest_id(store: &Store) -> u64 {
+    store.statements.iter().map(|s| s.id)
+        .chain(store.trash.iter().map(|t| t.id))
+        .chain(store.audit_log.iter().map(|e| e.id))
+        .chain(store.bulk_undo.iter().map(|u| u.edit_id))
+        .max()
+        .unwrap_or(0)
+}
+
+impl Store {
+    pub fn check_health(&self) -> Vec<HealthIssue> {
+        let mut issues = rollup_issues(self);
+
+        issues.extend(duplicate_ids(self).into_iter()
+            .map(|id| HealthIssue::new(IssueKind::DuplicateStatementId, format!("statement {}", id))));
+        let highest = highest_id(self);
+        if self.next_id < highest {
+            issues.push(HealthIssue::new(IssueKind::IdCounterBehind, format!("next id {}, highest in use {}", self.next_id, highest)));
+        }
+
+        let statement_ids: HashSet<u64> = self.statements.iter().map(|s| s.id).collect();
+        issues.extend(self.trash.iter()
+            .filter(|t| !statement_ids.contains(&t.statement_id))
+            .map(|t| HealthIssue::new(IssueKind::OrphanTrash, format!("trash entry {} from statement {}", t.id, t.statement_id))));
+        issues.extend(self.reminded_statements.iter()
+            .filter(|id| !statement_ids.contains(id))
+            .map(|id| HealthIssue::new(IssueKind::OrphanReminder, format!("statement {}", id))));
+
+        for statement in &self.statements {
+            for (index, tx) in statement.transactions.iter().enumerate() {
+                let at = format!("statement {}, transaction {}", statement.id, index);
+                if tx.tags != normalized_tags(&tx.tags) {
+                    issues.push(HealthIssue::new(IssueKind::UnnormalizedTags, at.clone()));
+                }
+                if !tx.amount.is_finite() {
+                    issues.push(HealthIssue::new(IssueKind::InvalidAmount, at.clone()));
+                }
+                if tx.shared.as_ref().is_some_and(|shared| shared.validate("en").is_err()) {
+                    issues.push(HealthIssue::new(IssueKind::InvalidSharedExpense, at));
+                }
+            }
+        }
+        issues
+    }
+
+    // Fixes every repairable issue and records the repair in the audit log. Returns the issues
+    // that were found before repairing.
+    pub fn repair(&mut self) -> Vec<HealthIssue> {
+        let issues = self.check_health();
+        if !issues.iter().any(|i| i.repairable) {
+            return issues;
+        }
+
+        // Later statements with a taken id get a fresh one; trash entries stay with the first
+        let mut seen = HashSet::new();
+        let duplicates: Vec<usize> = self.statements.iter().enumerate()
+            .filter(|(_, s)| !seen.insert(s.id))
+            .map(|(position, _)| position)
+            .collect();
+        self.next_id = self.next_id.max(highest_id(self));
+        for position in duplicates {
+            self.statements[position].id = self.allocate_id();
+        }
+
+        let statement_ids: HashSet<u64> = self.statements.iter().map(|s| s.id).collect();
+        self.trash.retain(|t| statement_ids.contains(&t.statement_id));
+        self.reminded_statements.retain(|id| statement_ids.contains(id));
+        for tx in self.statements.iter_mut().flat_map(|s| s.transactions.iter_mut()) {
+            tx.tags = normalized_tags(&tx.tags);
+        }
+        rollups::rebuild(self);
+
+        let repaired: Vec<&HealthIssue> = issues.iter().filter(|i| i.repairable).collect();
+        self.audit(AuditAction::Repair, "stored data", Some(&repaired), None::<&serde_json::Value>);
+        issues
+    }
+}
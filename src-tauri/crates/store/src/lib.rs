@@ -0,0 +1,322 @@
+// The statement history and everything recorded alongside it, persisted as one JSON file
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+pub mod audit;
+pub mod health;
+pub mod originals;
+pub mod preview;
+pub mod recategorize;
+pub mod rollups;
+pub mod sync;
+pub mod trash;
+
+use credit_analyzer_core::amazon::AmazonOrder;
+use credit_analyzer_core::bank_profiles::BankProfile;
+use credit_analyzer_core::cards::Card;
+use credit_analyzer_core::goals::Goal;
+use credit_analyzer_core::history::StoredStatement;
+use credit_analyzer_core::sign_convention::SignDetection;
+use credit_analyzer_core::statement_meta::StatementMeta;
+use credit_analyzer_core::wallets::WalletPayment;
+use credit_analyzer_core::Transaction;
+use audit::{AuditAction, AuditEntry};
+use originals::StoredOriginal;
+use rollups::MonthlyRollup;
+use trash::TrashedTransaction;
+
+// Parsed files kept around for re-analysis; older entries are dropped first
+const PARSE_CACHE_LIMIT: usize = 24;
+// Bump when stored data has to be converted on load, and describe the step in MIGRATIONS.
+// Fields that are only added need no bump: serde fills them in with their default.
+pub const SCHEMA_VERSION: u32 = 1;
+// What `migrate` does to reach each version, from version 0; shown before an update applies it
+pub const MIGRATIONS: &[&str] = &[
+    "Build monthly rollups from the stored statements",
+];
+const _: () = assert!(MIGRATIONS.len() == SCHEMA_VERSION as usize);
+
+#[derive(Debug, Default, Serialize, Deserialize, Type)]
+pub struct Store {
+    // The SCHEMA_VERSION of the app that last upgraded it; 0 for stores from before versioning
+    #[serde(default)]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub next_id: u64,
+    #[serde(default)]
+    pub statements: Vec<StoredStatement>,
+    #[serde(default)]
+    pub goals: Vec<Goal>,
+    #[serde(default)]
+    pub amazon_orders: Vec<AmazonOrder>,
+    #[serde(default)]
+    pub wallet_payments: Vec<WalletPayment>,
+    // Statements whose due-date notification has already been shown
+    #[serde(default)]
+    pub reminded_statements: Vec<u64>,
+    // Anomaly ids the user has looked at and dismissed
+    #[serde(default)]
+    pub reviewed_anomalies: Vec<String>,
+    // Merchants whose small recurring charges the user has checked and recognized
+    #[serde(default)]
+    pub reviewed_gray_charges: Vec<String>,
+    #[serde(default)]
+    pub rollups: Vec<MonthlyRollup>,
+    // Period start day the rollups are grouped by; None for stores grouped by calendar month
+    #[serde(default)]
+    pub rollup_start_day: Option<u32>,
+    #[serde(default)]
+    pub parse_cache: Vec<CachedParse>,
+    // User-made mappings for CSV exports the built-in parsers don't know
+    #[serde(default)]
+    pub bank_profiles: Vec<BankProfile>,
+    // Cards the user has described, with their fees and benefits
+    #[serde(default)]
+    pub cards: Vec<Card>,
+    // Only the most recent bulk edit can be undone
+    #[serde(default)]
+    pub bulk_undo: Option<BulkUndo>,
+    // Oldest first
+    #[serde(default)]
+    pub audit_log: Vec<AuditEntry>,
+    // Deleted transactions, restorable until they're TRASH_DAYS old
+    #[serde(default)]
+    pub trash: Vec<TrashedTransaction>,
+    // Imported files kept for re-parsing, when the setting is on
+    #[serde(default)]
+    pub originals: Vec<StoredOriginal>,
+}
+
+// The statements a bulk edit touched, as they were before it
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BulkUndo {
+    pub edit_id: u64,
+    pub performed_at: String,
+    // Transactions the edit changed or deleted
+    pub affected: usize,
+    pub statements: Vec<StoredStatement>,
+    // Trash ids of the rows a bulk delete moved there
+    #[serde(default)]
+    pub trashed: Vec<u64>,
+}
+
+// Transactions as the parser produced them, before any enrichment
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CachedParse {
+    pub content_hash: String,
+    pub parser_version: u32,
+    pub parsed_at: String,
+    pub transactions: Vec<Transaction>,
+    pub meta: Option<StatementMeta>,
+    #[serde(default)]
+    pub sign_convention: Option<SignDetection>,
+}
+
+// Identifies a file by its contents, so a renamed or moved copy still hits the cache. Std's
+// hasher isn't guaranteed stable across Rust releases; a changed hash only costs a re-parse.
+pub fn content_hash(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}-{}", hasher.finish(), bytes.len())
+}
+
+// A row by what its file says, before any edit
+fn row_key(tx: &Transaction) -> String {
+    format!("{}|{}|{:.2}", tx.date, tx.description, tx.amount)
+}
+
+// Whether `old` is the row `new` was read from. Rows stored before import keys existed are
+// matched on their values.
+fn same_row(old: &Transaction, new: &Transaction) -> bool {
+    match (&old.import_key, &new.import_key) {
+        (Some(old_key), Some(new_key)) => old_key == new_key,
+        _ => old.date == new.date && old.description == new.description && (old.amount - new.amount).abs() < 0.01,
+    }
+}
+
+impl Store {
+    pub fn load(path: &Path) -> Result<Store, String> {
+        let mut store = Store::load_unmigrated(path)?;
+        store.migrate();
+        Ok(store)
+    }
+
+    // The store exactly as written, for a caller that wants to act before it's upgraded
+    pub fn load_unmigrated(path: &Path) -> Result<Store, String> {
+        if !path.exists() {
+            return Ok(Store::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read store: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Store is corrupted: {}", e))
+    }
+
+    // An empty store has nothing to convert, so it's only stamped with the current version
+    pub fn needs_migration(&self) -> bool {
+        self.schema_version < SCHEMA_VERSION && !self.statements.is_empty()
+    }
+
+    // Written by a later version, which may have stored things this one can't keep on saving
+    pub fn is_newer(&self) -> bool {
+        self.schema_version > SCHEMA_VERSION
+    }
+
+    // Stamps the store with SCHEMA_VERSION on every load, converting it first if an older
+    // version wrote data. `before` sees the store as it was, and only when there's data to convert.
+    pub fn upgrade(&mut self, before: impl FnOnce(&Store)) {
+        if self.needs_migration() {
+            before(self);
+        }
+        self.migrate();
+    }
+
+    // Brings a store written by an older version up to date
+    pub fn migrate(&mut self) {
+        // Stores written before rollups existed get them on first load
+        if self.schema_version < 1 && self.rollups.is_empty() && !self.statements.is_empty() {
+            rollups::rebuild(self);
+        }
+        self.schema_version = self.schema_version.max(SCHEMA_VERSION);
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize store: {}", e))?;
+
+        // Write to a temp file first so a crash mid-write can't truncate the store
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, content)
+            .map_err(|e| format!("Could not write store: {}", e))?;
+        fs::rename(&tmp_path, path)
+            .map_err(|e| format!("Could not replace store: {}", e))
+    }
+
+    // Statements other than the one imported from `source_path`, oldest first
+    pub fn history_excluding(&self, source_path: &str) -> Vec<&StoredStatement> {
+        self.statements.iter()
+            .filter(|s| s.source_path != source_path)
+            .collect()
+    }
+
+    pub fn allocate_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    pub fn statement(&self, id: u64) -> Option<&StoredStatement> {
+        self.statements.iter().find(|s| s.id == id)
+    }
+
+    pub fn latest_statement(&self) -> Option<&StoredStatement> {
+        self.statements.last()
+    }
+
+    pub fn cached_parse(&self, content_hash: &str, parser_version: u32) -> Option<&CachedParse> {
+        self.parse_cache.iter()
+            .find(|c| c.content_hash == content_hash && c.parser_version == parser_version)
+    }
+
+    pub fn cache_parse(&mut self, content_hash: &str, parser_version: u32, transactions: &[Transaction], meta: Option<&StatementMeta>, sign_convention: Option<&SignDetection>) {
+        self.parse_cache.retain(|c| c.content_hash != content_hash);
+        self.parse_cache.push(CachedParse {
+            content_hash: content_hash.to_string(),
+            parser_version,
+            parsed_at: chrono::Utc::now().to_rfc3339(),
+            transactions: transactions.to_vec(),
+            meta: meta.cloned(),
+            sign_convention: sign_convention.cloned(),
+        });
+        if self.parse_cache.len() > PARSE_CACHE_LIMIT {
+            let excess = self.parse_cache.len() - PARSE_CACHE_LIMIT;
+            self.parse_cache.drain(..excess);
+        }
+    }
+
+    // Puts statements back as they were in `snapshots`, keeping the rollups in step. Statements
+    // removed since are left out; returns the ids restored.
+    pub fn restore_statements(&mut self, snapshots: Vec<StoredStatement>) -> Vec<u64> {
+        let mut restored = Vec::new();
+        for snapshot in snapshots {
+            let Some(statement) = self.statements.iter_mut().find(|s| s.id == snapshot.id) else {
+                continue;
+            };
+            rollups::subtract(&mut self.rollups, &statement.transactions);
+            rollups::add(&mut self.rollups, &snapshot.transactions);
+            restored.push(snapshot.id);
+            *statement = snapshot;
+        }
+        restored
+    }
+
+    // Records an import, replacing any earlier import of the same file
+    pub fn record_statement(&mut self, source_path: &str, mut transactions: Vec<Transaction>, meta: Option<StatementMeta>) -> u64 {
+        // The log keeps counts rather than every row, which the statement itself holds
+        let replaced_summary = self.statements.iter().find(|s| s.source_path == source_path)
+            .map(|s| serde_json::json!({ "statement_id": s.id, "transactions": s.transactions.len() }));
+        let replaced_ids: Vec<u64> = self.statements.iter().filter(|s| s.source_path == source_path).map(|s| s.id).collect();
+        // A re-import is the same statement to other devices
+        let sync_id = self.statements.iter().find(|s| s.source_path == source_path).map(|s| s.sync_id.clone())
+            .filter(|sync_id| !sync_id.is_empty())
+            .unwrap_or_else(|| self.new_sync_id(&sync::row_seed(&transactions)));
+        for tx in transactions.iter_mut().filter(|tx| !tx.manual && tx.import_key.is_none()) {
+            tx.import_key = Some(row_key(tx));
+        }
+        for replaced in self.statements.iter().filter(|s| s.source_path == source_path) {
+            rollups::subtract(&mut self.rollups, &replaced.transactions);
+            // Notes, receipts, tags, splits, categories and corrections belong to the charge, not
+            // the import
+            for tx in transactions.iter_mut() {
+                let Some(earlier) = replaced.transactions.iter().find(|old| same_row(old, tx)) else {
+                    continue;
+                };
+                tx.category = tx.category.take().or_else(|| earlier.category.clone());
+                tx.note = tx.note.take().or_else(|| earlier.note.clone());
+                tx.receipt_path = tx.receipt_path.take().or_else(|| earlier.receipt_path.clone());
+                if tx.tags.is_empty() {
+                    tx.tags = earlier.tags.clone();
+                }
+                tx.shared = tx.shared.take().or_else(|| earlier.shared.clone());
+                if earlier.import_key.as_ref().is_some_and(|key| *key != row_key(earlier)) {
+                    tx.date = earlier.date.clone();
+                    tx.description = earlier.description.clone();
+                    tx.amount = earlier.amount;
+                    tx.merchant_info = earlier.merchant_info.clone();
+                    tx.rule_category = earlier.rule_category.clone();
+                }
+            }
+            // Entries the user added by hand aren't in the file, so they'd be lost otherwise
+            transactions.extend(replaced.transactions.iter().filter(|old| old.manual).cloned());
+        }
+        self.statements.retain(|s| s.source_path != source_path);
+        let id = self.allocate_id();
+        // Rows deleted from the earlier import stay in the trash rather than coming back
+        for trashed in self.trash.iter_mut().filter(|t| replaced_ids.contains(&t.statement_id)) {
+            if let Some(index) = transactions.iter().position(|tx| same_row(&trashed.transaction, tx)) {
+                transactions.remove(index);
+            }
+            trashed.statement_id = id;
+        }
+        for tx in transactions.iter_mut().filter(|tx| tx.rule_category.is_none()) {
+            tx.rule_category = Some(credit_analyzer_core::rule_category(tx));
+        }
+        rollups::add(&mut self.rollups, &transactions);
+
+        let summary = serde_json::json!({ "statement_id": id, "transactions": transactions.len() });
+        self.statements.push(StoredStatement {
+            id,
+            source_path: source_path.to_string(),
+            imported_at: chrono::Utc::now().to_rfc3339(),
+            transactions,
+            meta,
+            sync_id,
+        });
+        self.audit(AuditAction::Import, source_path, replaced_summary.as_ref(), Some(&summary));
+        id
+    }
+}
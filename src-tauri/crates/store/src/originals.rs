@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::Store;
+
+// An imported file kept beside the store, compressed, so it can be parsed again after a parser
+// upgrade or looked at when auditing an import. Files are named by content hash, so the same
+// file imported from two places is kept once.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StoredOriginal {
+    // The statement it was imported as
+    pub source_path: String,
+    pub content_hash: String,
+    pub stored_at: String,
+    // PARSER_VERSION when it was last parsed, so re-imports can skip what's current
+    pub parser_version: u32,
+    pub size: u64,
+    pub compressed_size: u64,
+}
+
+impl StoredOriginal {
+    fn expired(&self, now: chrono::DateTime<chrono::Utc>, days: u32) -> bool {
+        chrono::DateTime::parse_from_rfc3339(&self.stored_at)
+            .map_or(true, |stored| now.signed_duration_since(stored) > chrono::Duration::days(days.into()))
+    }
+}
+
+impl Store {
+    // Records the original for a statement, replacing the one kept for an earlier import of
+    // it. Returns the hashes of files nothing refers to any more, for the caller to delete.
+    pub fn keep_original(&mut self, original: StoredOriginal) -> Vec<String> {
+        let replaced: Vec<String> = self.originals.iter()
+            .filter(|o| o.source_path == original.source_path)
+            .map(|o| o.content_hash.clone())
+            .collect();
+        self.originals.retain(|o| o.source_path != original.source_path);
+        self.originals.push(original);
+        self.unreferenced(replaced)
+    }
+
+    // Drops originals kept longer than `days`, returning the files to delete
+    pub fn expire_originals(&mut self, days: u32) -> Vec<String> {
+        let now = chrono::Utc::now();
+        let (expired, kept) = std::mem::take(&mut self.originals).into_iter().partition(|o| o.expired(now, days));
+        self.originals = kept;
+        self.unreferenced(expired.into_iter().map(|o: StoredOriginal| o.content_hash).collect())
+    }
+
+    fn unreferenced(&self, mut hashes: Vec<String>) -> Vec<String> {
+        hashes.sort();
+        hashes.dedup();
+        hashes.retain(|hash| !self.originals.iter().any(|o| &o.content_hash == hash));
+        hashes
+    }
+}
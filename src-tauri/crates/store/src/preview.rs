@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+use credit_analyzer_core::Transaction;
+
+// Rows shown in a preview; the counts and shifts still cover every row
+pub const PREVIEW_SAMPLES: usize = 20;
+
+// One stored transaction an operation changes, as it is and as it would be
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RowChange {
+    pub statement_id: u64,
+    pub index: usize,
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    pub category_from: String,
+    // None when the row would be deleted
+    pub category_to: Option<String>,
+    pub tags_from: Vec<String>,
+    pub tags_to: Vec<String>,
+}
+
+impl RowChange {
+    pub fn new(statement_id: u64, index: usize, from: &Transaction, to: Option<&Transaction>) -> RowChange {
+        RowChange {
+            statement_id,
+            index,
+            date: from.date.clone(),
+            description: from.description.clone(),
+            amount: from.amount,
+            category_from: credit_analyzer_core::transaction_category(from),
+            category_to: to.map(credit_analyzer_core::transaction_category),
+            tags_from: from.tags.clone(),
+            tags_to: to.map(|t| t.tags.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+// How much a category's total would move, over the rows the operation touches
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CategoryShift {
+    pub category: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+// What an operation would do to stored history, for a dry run or a record of what it did
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ChangePreview {
+    pub affected: usize,
+    pub samples: Vec<RowChange>,
+    // Largest move first; categories that come out even aren't listed
+    pub shifts: Vec<CategoryShift>,
+}
+
+impl ChangePreview {
+    pub fn new(changes: Vec<RowChange>) -> ChangePreview {
+        let mut totals: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+        for change in &changes {
+            totals.entry(&change.category_from).or_default().0 += change.amount;
+            if let Some(to) = &change.category_to {
+                totals.entry(to).or_default().1 += change.amount;
+            }
+        }
+        let mut shifts: Vec<CategoryShift> = totals.into_iter()
+            .filter(|(_, (before, after))| (before - after).abs() >= 0.005)
+            .map(|(category, (before, after))| CategoryShift { category: category.to_string(), before, after })
+            .collect();
+        shifts.sort_by(|a, b| (b.after - b.before).abs().total_cmp(&(a.after - a.before).abs()));
+
+        ChangePreview {
+            affected: changes.len(),
+            samples: changes.into_iter().take(PREVIEW_SAMPLES).collect(),
+            shifts,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use credit_analyzer_core::{merchants, rule_category, transaction_category, Transaction};
+
+use crate::audit::AuditAction;
+use crate::preview::RowChange;
+use crate::{rollups, Store};
+
+// The transaction as the rules see it today, with the merchant looked up afresh as the
+// knowledge base may have learned it since the import
+fn rerun(transaction: &Transaction) -> Transaction {
+    let mut updated = transaction.clone();
+    updated.merchant_info = merchants::lookup(&transaction.description);
+    updated.rule_category = Some(rule_category(&updated));
+    updated
+}
+
+impl Store {
+    // What re-running the rules over stored history would change. Transactions with a
+    // category the user or an import set are left alone, and so never show up here.
+    pub fn category_changes(&self) -> Vec<RowChange> {
+        self.statements.iter()
+            .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+            .filter(|(_, _, t)| t.category.is_none())
+            .filter_map(|(statement_id, index, t)| {
+                let updated = rerun(t);
+                (transaction_category(t) != transaction_category(&updated)).then(|| RowChange::new(statement_id, index, t, Some(&updated)))
+            })
+            .collect()
+    }
+
+    // Re-runs the rules over every stored transaction and rebuilds the rollups to match,
+    // returning what changed
+    pub fn recategorize(&mut self) -> Vec<RowChange> {
+        let changes = self.category_changes();
+        for tx in self.statements.iter_mut().flat_map(|s| s.transactions.iter_mut()).filter(|t| t.category.is_none()) {
+            *tx = rerun(tx);
+        }
+        rollups::rebuild(self);
+
+        let summary = serde_json::json!({ "changed": changes.len() });
+        self.audit(AuditAction::Recategorize, "all transactions", None::<&serde_json::Value>, Some(&summary));
+        changes
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+use credit_analyzer_core::history::StoredStatement;
+use credit_analyzer_core::interner::{CompactHistory, Symbol};
+use credit_analyzer_core::periods;
+use credit_analyzer_core::{month_key, transaction_category, Transaction};
+
+use crate::Store;
+
+// Spend per month (or custom period, see periods.rs in the core crate) and category, kept up
+// to date as statements change so summaries don't have to walk the whole history
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MonthlyRollup {
+    // "2024-01", or "Unknown" for undated transactions
+    pub month: String,
+    pub category: String,
+    pub total: f64,
+    pub count: u32,
+}
+
+fn apply(rollups: &mut Vec<MonthlyRollup>, transactions: &[Transaction], sign: f64) {
+    for tx in transactions {
+        let month = month_key(&tx.date).unwrap_or_else(|| "Unknown".to_string());
+        let category = transaction_category(tx);
+        let index = match rollups.iter().position(|r| r.month == month && r.category == category) {
+            Some(index) => index,
+            None => {
+                rollups.push(MonthlyRollup { month, category, total: 0.0, count: 0 });
+                rollups.len() - 1
+            }
+        };
+
+        let rollup = &mut rollups[index];
+        rollup.total += sign * tx.amount;
+        if sign > 0.0 {
+            rollup.count += 1;
+        } else {
+            rollup.count = rollup.count.saturating_sub(1);
+        }
+    }
+
+    // Cells emptied by a removal go away rather than lingering as float noise
+    rollups.retain(|r| r.count > 0);
+}
+
+pub fn add(rollups: &mut Vec<MonthlyRollup>, transactions: &[Transaction]) {
+    apply(rollups, transactions, 1.0);
+}
+
+pub fn subtract(rollups: &mut Vec<MonthlyRollup>, transactions: &[Transaction]) {
+    apply(rollups, transactions, -1.0);
+}
+
+// Recomputes every rollup from the stored statements
+pub fn rebuild(store: &mut Store) {
+    store.rollups = computed(&store.statements);
+    store.rollup_start_day = Some(periods::start_day());
+}
+
+// The rollups the statements add up to, sorted by month then category
+pub fn computed(statements: &[StoredStatement]) -> Vec<MonthlyRollup> {
+    let history = CompactHistory::from_statements(statements);
+    // Keyed by the first of the month naming the period
+    let mut cells: HashMap<(Option<NaiveDate>, Symbol), (f64, u32)> = HashMap::new();
+    for tx in &history.transactions {
+        let cell = cells.entry((tx.date.map(periods::period_month), tx.category)).or_insert((0.0, 0));
+        cell.0 += tx.amount;
+        cell.1 += 1;
+    }
+
+    let mut rollups: Vec<MonthlyRollup> = cells.into_iter().map(|((month, category), (total, count))| MonthlyRollup {
+        month: month.map_or_else(|| "Unknown".to_string(), |m| m.format("%Y-%m").to_string()),
+        category: history.categories.resolve(category).to_string(),
+        total,
+        count,
+    }).collect();
+    rollups.sort_by(|a, b| a.month.cmp(&b.month).then(a.category.cmp(&b.category)));
+    rollups
+}
+
+// Rollups for one month, largest category first
+pub fn for_month<'a>(rollups: &'a [MonthlyRollup], month: &str) -> Vec<&'a MonthlyRollup> {
+    let mut cells: Vec<&MonthlyRollup> = rollups.iter().filter(|r| r.month == month).collect();
+    cells.sort_by(|a, b| b.total.total_cmp(&a.total));
+    cells
+}
@@ -0,0 +1,161 @@
+// Last-writer-wins sync of statements between devices. Statements are matched by sync id, and
+// the ledger remembers which version of each this device last saw and when it was written, so
+// a change made here can be told from one that arrived. Transactions and everything set on them,
+// such as category overrides, notes, tags and splits, travel with their statement, so two devices
+// editing one statement between syncs keep the later edit.
+//
+// Only statements sync. Goals, cards, bank profiles, the trash and settings stay with the device;
+// a configuration bundle carries them to another one.
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+
+use credit_analyzer_core::history::StoredStatement;
+use credit_analyzer_core::Transaction;
+
+use crate::{rollups, Store};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncVersion {
+    // Of the statement as this device holds it; None once it's deleted
+    pub fingerprint: Option<String>,
+    // Milliseconds since the epoch, by the clock of the device that made the change
+    pub modified_at: i64,
+    pub device_id: String,
+}
+
+impl SyncVersion {
+    // The device id settles a tie, so every device picks the same winner
+    fn wins_over(&self, other: &SyncVersion) -> bool {
+        (self.modified_at, &self.device_id) > (other.modified_at, &other.device_id)
+    }
+}
+
+// Kept beside the store on each device, never synced itself
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncLedger {
+    pub device_id: String,
+    pub versions: BTreeMap<String, SyncVersion>,
+}
+
+// One statement as a device publishes it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub sync_id: String,
+    pub modified_at: i64,
+    pub device_id: String,
+    // None for a deleted statement, so the deletion reaches devices that still have it
+    pub statement: Option<StoredStatement>,
+}
+
+// Seeds a statement's sync id from its rows, so one file imported separately on two devices
+// pairs up rather than showing twice
+pub fn row_seed(transactions: &[Transaction]) -> Vec<u8> {
+    let rows: Vec<(&str, &str, String)> = transactions.iter()
+        .map(|t| (t.date.as_str(), t.description.as_str(), format!("{:.2}", t.amount)))
+        .collect();
+    serde_json::to_vec(&rows).unwrap_or_default()
+}
+
+// SHA-256 rather than content_hash, whose std hasher can change with the toolchain: a device
+// built with another Rust would otherwise see every statement as edited, or pair none up
+fn stable_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn fingerprint(statement: &StoredStatement) -> String {
+    // Ids are per device, so they're left out
+    let json = serde_json::to_vec(&StoredStatement { id: 0, ..statement.clone() }).unwrap_or_default();
+    stable_digest(&json)
+}
+
+impl Store {
+    // A sync id no statement here has yet
+    pub fn new_sync_id(&self, seed: &[u8]) -> String {
+        let base = stable_digest(seed);
+        let taken: HashSet<&str> = self.statements.iter().map(|s| s.sync_id.as_str()).collect();
+        (1..).map(|n| if n == 1 { base.clone() } else { format!("{}-{}", base, n) })
+            .find(|id| !taken.contains(id.as_str()))
+            .unwrap_or(base)
+    }
+
+    // Statements from before sync get their id on the first sync
+    pub fn assign_sync_ids(&mut self) {
+        for position in 0..self.statements.len() {
+            if self.statements[position].sync_id.is_empty() {
+                let seed = row_seed(&self.statements[position].transactions);
+                self.statements[position].sync_id = self.new_sync_id(&seed);
+            }
+        }
+    }
+
+    // Stamps each statement added, changed or deleted here since the last sync with `now`
+    pub fn note_local_changes(&self, ledger: &mut SyncLedger, now: i64) {
+        let mut present = HashSet::new();
+        for statement in &self.statements {
+            present.insert(statement.sync_id.as_str());
+            let current = Some(fingerprint(statement));
+            if ledger.versions.get(&statement.sync_id).is_none_or(|v| v.fingerprint != current) {
+                let version = SyncVersion { fingerprint: current, modified_at: now, device_id: ledger.device_id.clone() };
+                ledger.versions.insert(statement.sync_id.clone(), version);
+            }
+        }
+        let device_id = ledger.device_id.clone();
+        for (sync_id, version) in ledger.versions.iter_mut() {
+            if version.fingerprint.is_some() && !present.contains(sync_id.as_str()) {
+                *version = SyncVersion { fingerprint: None, modified_at: now, device_id: device_id.clone() };
+            }
+        }
+    }
+
+    // Every version in the ledger, for publishing to the other devices
+    pub fn sync_records(&self, ledger: &SyncLedger) -> Vec<SyncRecord> {
+        ledger.versions.iter()
+            .map(|(sync_id, version)| SyncRecord {
+                sync_id: sync_id.clone(),
+                modified_at: version.modified_at,
+                device_id: version.device_id.clone(),
+                statement: version.fingerprint.as_ref()
+                    .and_then(|_| self.statements.iter().find(|s| &s.sync_id == sync_id).cloned()),
+            })
+            .collect()
+    }
+
+    // Takes each record that's newer than the version here. Returns how many were taken.
+    pub fn apply_sync_records(&mut self, ledger: &mut SyncLedger, records: Vec<SyncRecord>) -> usize {
+        let mut taken = 0;
+        for record in records {
+            let incoming = SyncVersion {
+                fingerprint: record.statement.as_ref().map(fingerprint),
+                modified_at: record.modified_at,
+                device_id: record.device_id,
+            };
+            if ledger.versions.get(&record.sync_id).is_some_and(|current| !incoming.wins_over(current)) {
+                continue;
+            }
+
+            let position = self.statements.iter().position(|s| s.sync_id == record.sync_id);
+            match (record.statement, position) {
+                (Some(statement), Some(position)) => {
+                    let id = self.statements[position].id;
+                    self.statements[position] = StoredStatement { id, ..statement };
+                }
+                (Some(statement), None) => {
+                    let id = self.allocate_id();
+                    self.statements.push(StoredStatement { id, ..statement });
+                }
+                (None, Some(position)) => {
+                    let removed = self.statements.remove(position);
+                    self.trash.retain(|t| t.statement_id != removed.id);
+                }
+                (None, None) => {}
+            }
+            ledger.versions.insert(record.sync_id, incoming);
+            taken += 1;
+        }
+        if taken > 0 {
+            rollups::rebuild(self);
+        }
+        taken
+    }
+}
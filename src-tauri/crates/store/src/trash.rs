@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use credit_analyzer_core::Transaction;
+
+use crate::audit::{transaction_target, AuditAction};
+use crate::{rollups, Store};
+
+// How long a deleted transaction can still be restored
+pub const TRASH_DAYS: i64 = 30;
+
+// A deleted transaction, out of its statement and so out of every analysis
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TrashedTransaction {
+    pub id: u64,
+    pub statement_id: u64,
+    // Where it sat in the statement, so a restore puts it back in place
+    pub index: usize,
+    pub deleted_at: String,
+    pub transaction: Transaction,
+}
+
+impl TrashedTransaction {
+    fn expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        chrono::DateTime::parse_from_rfc3339(&self.deleted_at)
+            .map_or(true, |deleted| now.signed_duration_since(deleted) > chrono::Duration::days(TRASH_DAYS))
+    }
+}
+
+impl Store {
+    // Moves a transaction from its statement to the trash, returning the trash id
+    pub fn trash_transaction(&mut self, statement_id: u64, index: usize) -> Option<u64> {
+        let statement = self.statements.iter_mut().find(|s| s.id == statement_id)?;
+        if index >= statement.transactions.len() {
+            return None;
+        }
+        let transaction = statement.transactions.remove(index);
+        rollups::subtract(&mut self.rollups, std::slice::from_ref(&transaction));
+        self.audit(AuditAction::Delete, transaction_target(statement_id, index), Some(&transaction), None::<&Transaction>);
+
+        let id = self.allocate_id();
+        self.trash.push(TrashedTransaction {
+            id,
+            statement_id,
+            index,
+            deleted_at: chrono::Utc::now().to_rfc3339(),
+            transaction,
+        });
+        Some(id)
+    }
+
+    // Puts a trashed transaction back, returning where it went. None if it's past its time in
+    // the trash or its statement has since been removed.
+    pub fn restore_transaction(&mut self, id: u64) -> Option<(u64, usize)> {
+        self.purge_trash();
+        let position = self.trash.iter().position(|t| t.id == id)?;
+        let statement = self.statements.iter_mut().find(|s| s.id == self.trash[position].statement_id)?;
+        let trashed = self.trash.remove(position);
+
+        let index = trashed.index.min(statement.transactions.len());
+        statement.transactions.insert(index, trashed.transaction.clone());
+        rollups::add(&mut self.rollups, std::slice::from_ref(&trashed.transaction));
+        self.audit(AuditAction::Restore, transaction_target(trashed.statement_id, index), None::<&Transaction>, Some(&trashed.transaction));
+        Some((trashed.statement_id, index))
+    }
+
+    // Drops what's been in the trash longer than TRASH_DAYS
+    pub fn purge_trash(&mut self) {
+        let now = chrono::Utc::now();
+        self.trash.retain(|t| !t.expired(now));
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::amazon::{enrich_transactions, is_amazon_charge, parse_order_history};
+use credit_analyzer_core::{i18n, Transaction};
+use credit_analyzer_store::audit::AuditAction;
+use credit_analyzer_store::{rollups, Store};
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct AmazonImportSummary {
+    pub orders_imported: usize,
+    pub orders_total: usize,
+    // Against the latest statement
+    pub charges_matched: usize,
+    pub charges_unmatched: Vec<Transaction>,
+}
+
+#[command]
+#[specta::specta]
+pub async fn import_amazon_orders(app: AppHandle, file_path: String) -> Result<AmazonImportSummary, String> {
+    let locale = settings::load_settings(&app)?.locale;
+
+    let content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    let orders = parse_order_history(&content)?;
+
+    let mut open_store = state::write_store(&app).await?;
+    // A plain Store, so its fields can be borrowed separately
+    let store: &mut Store = &mut open_store;
+
+    // Re-importing an overlapping report replaces orders rather than duplicating them
+    let orders_imported = orders.len();
+    store.amazon_orders.retain(|existing| !orders.iter().any(|o| o.order_id == existing.order_id));
+    store.amazon_orders.extend(orders);
+    store.audit(AuditAction::Import, file_path.as_str(), None::<&()>, Some(&serde_json::json!({ "amazon_orders": orders_imported })));
+
+    // Enrich the latest statement now so goals and simulations see the items without a re-analysis
+    let (charges_matched, charges_unmatched) = match store.statements.last_mut() {
+        Some(statement) => {
+            rollups::subtract(&mut store.rollups, &statement.transactions);
+            let matched = enrich_transactions(&mut statement.transactions, &store.amazon_orders);
+            rollups::add(&mut store.rollups, &statement.transactions);
+            let unmatched = statement.transactions.iter()
+                .filter(|t| t.order_id.is_none() && is_amazon_charge(&t.description))
+                .cloned()
+                .collect();
+            (matched, unmatched)
+        }
+        None => (0, Vec::new()),
+    };
+
+    open_store.save()?;
+    if charges_matched > 0 {
+        events::transactions_changed(&app, open_store.statements.last().map(|s| s.id).into_iter().collect());
+    }
+
+    Ok(AmazonImportSummary {
+        orders_imported,
+        orders_total: open_store.amazon_orders.len(),
+        charges_matched,
+        charges_unmatched,
+    })
+}
@@ -0,0 +1,136 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::periods::previous_month;
+use credit_analyzer_core::{i18n, is_fee_or_interest, month_key, transaction_category, SectionKind, Transaction};
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+const MONTHS: usize = 12;
+const LARGEST_PURCHASES: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct AnnualCategory {
+    pub category: String,
+    pub total: f64,
+    // One entry per month of the summary, for sparklines
+    pub monthly: Vec<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct LargePurchase {
+    pub statement_id: u64,
+    pub date: String,
+    pub description: String,
+    pub category: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct AnnualSummary {
+    // Calendar year, or None for the twelve months ending with the latest imported month
+    pub year: Option<i32>,
+    // "2024-01", oldest first
+    pub months: Vec<String>,
+    pub total_spend: f64,
+    pub monthly_totals: Vec<f64>,
+    // Largest category first
+    pub categories: Vec<AnnualCategory>,
+    pub largest_purchases: Vec<LargePurchase>,
+    pub fees_paid: f64,
+    pub interest_paid: f64,
+}
+
+fn is_interest(tx: &Transaction) -> bool {
+    tx.section == Some(SectionKind::Interest) || tx.description.to_lowercase().contains("interest")
+}
+
+// The twelve months covered, oldest first
+fn summary_months(store: &Store, year: Option<i32>) -> Option<Vec<String>> {
+    let last = match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, 12, 1)?,
+        None => store.rollups.iter()
+            .filter_map(|r| NaiveDate::parse_from_str(&format!("{}-01", r.month), "%Y-%m-%d").ok())
+            .max()?,
+    };
+
+    let mut months = vec![last];
+    while months.len() < MONTHS {
+        let previous = previous_month(months[months.len() - 1]);
+        months.push(previous);
+    }
+    months.reverse();
+    Some(months.into_iter().map(|m| m.format("%Y-%m").to_string()).collect())
+}
+
+pub fn build_summary(store: &Store, year: Option<i32>) -> Option<AnnualSummary> {
+    let months = summary_months(store, year)?;
+    let index_of = |month: &str| months.iter().position(|m| m == month);
+
+    // Category totals come from the rollups, like the dashboard's
+    let mut by_category: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for rollup in &store.rollups {
+        if let Some(index) = index_of(&rollup.month) {
+            by_category.entry(rollup.category.clone()).or_insert_with(|| vec![0.0; MONTHS])[index] += rollup.total;
+        }
+    }
+
+    let mut categories: Vec<AnnualCategory> = by_category.into_iter()
+        .map(|(category, monthly)| AnnualCategory { category, total: monthly.iter().sum(), monthly })
+        .collect();
+    categories.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    let monthly_totals: Vec<f64> = (0..MONTHS)
+        .map(|i| categories.iter().map(|c| c.monthly[i]).sum())
+        .collect();
+
+    let mut largest_purchases = Vec::new();
+    let (mut fees_paid, mut interest_paid) = (0.0, 0.0);
+    for statement in &store.statements {
+        for tx in &statement.transactions {
+            if month_key(&tx.date).and_then(|m| index_of(&m)).is_none() {
+                continue;
+            }
+            if is_interest(tx) {
+                interest_paid += tx.amount;
+            } else if is_fee_or_interest(tx) {
+                fees_paid += tx.amount;
+            } else {
+                largest_purchases.push(LargePurchase {
+                    statement_id: statement.id,
+                    date: tx.date.clone(),
+                    description: tx.description.clone(),
+                    category: transaction_category(tx),
+                    amount: tx.amount,
+                });
+            }
+        }
+    }
+    largest_purchases.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+    largest_purchases.truncate(LARGEST_PURCHASES);
+
+    Some(AnnualSummary {
+        year,
+        months,
+        total_spend: monthly_totals.iter().sum(),
+        monthly_totals,
+        categories,
+        largest_purchases,
+        fees_paid,
+        interest_paid,
+    })
+}
+
+// A calendar year's summary, or the rolling last twelve months when `year` is None
+#[command]
+#[specta::specta]
+pub async fn annual_summary(app: AppHandle, year: Option<i32>) -> Result<AnnualSummary, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+    build_summary(&store, year).ok_or_else(|| i18n::error(&locale, "error.no_statements"))
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::extract_merchant_name;
+use credit_analyzer_core::interner::{CompactHistory, Symbol};
+use credit_analyzer_store::Store;
+
+use crate::state;
+
+// A charge this many times a merchant's usual amount is worth a second look
+const OUTLIER_FACTOR: f64 = 3.0;
+// Fewer earlier charges than this don't establish what's usual
+const MIN_HISTORY: usize = 3;
+// Small absolute amounts aren't worth flagging however unusual
+const MIN_AMOUNT: f64 = 20.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Anomaly {
+    // "<statement id>:<transaction index>", stable across reloads
+    pub id: String,
+    pub date: String,
+    pub description: String,
+    pub merchant: String,
+    pub amount: f64,
+    pub typical_amount: f64,
+}
+
+fn median(amounts: &mut [f64]) -> f64 {
+    amounts.sort_by(|a, b| a.total_cmp(b));
+    let mid = amounts.len() / 2;
+    if amounts.len().is_multiple_of(2) {
+        (amounts[mid - 1] + amounts[mid]) / 2.0
+    } else {
+        amounts[mid]
+    }
+}
+
+// Charges in the latest statement far above what the same merchant usually charges
+pub fn detect_anomalies(store: &Store) -> Vec<Anomaly> {
+    let Some(latest) = store.latest_statement() else {
+        return Vec::new();
+    };
+
+    let history = CompactHistory::from_statements(store.statements.iter().filter(|s| s.id != latest.id));
+    let mut usual: HashMap<Symbol, Vec<f64>> = HashMap::new();
+    for tx in &history.transactions {
+        usual.entry(tx.merchant).or_default().push(tx.amount);
+    }
+
+    latest.transactions.iter().enumerate().filter_map(|(index, tx)| {
+        let merchant = extract_merchant_name(&tx.description);
+        let symbol = history.merchants.get(&merchant)?;
+        let amounts = usual.get_mut(&symbol).filter(|amounts| amounts.len() >= MIN_HISTORY)?;
+        let typical_amount = median(amounts);
+        (tx.amount >= MIN_AMOUNT && tx.amount > typical_amount * OUTLIER_FACTOR).then(|| Anomaly {
+            id: format!("{}:{}", latest.id, index),
+            date: tx.date.clone(),
+            description: tx.description.clone(),
+            merchant,
+            amount: tx.amount,
+            typical_amount,
+        })
+    }).collect()
+}
+
+// Anomalies the user hasn't dismissed yet
+pub fn unreviewed(store: &Store) -> Vec<Anomaly> {
+    detect_anomalies(store).into_iter()
+        .filter(|anomaly| !store.reviewed_anomalies.contains(&anomaly.id))
+        .collect()
+}
+
+#[command]
+#[specta::specta]
+pub async fn review_anomaly(app: AppHandle, id: String) -> Result<(), String> {
+    let mut store = state::write_store(&app).await?;
+    if !store.reviewed_anomalies.contains(&id) {
+        store.reviewed_anomalies.push(id);
+    }
+    store.save()
+}
@@ -0,0 +1,21 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_store::audit::{AuditAction, AuditEntry};
+
+use crate::state;
+
+// Most recent first. `since` is an RFC 3339 timestamp; entries at or after it are returned.
+#[command]
+#[specta::specta]
+pub async fn get_audit_log(app: AppHandle, action: Option<AuditAction>, since: Option<String>, limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let store = state::read_store(&app).await?;
+    let since = since.as_deref()
+        .map(|since| chrono::DateTime::parse_from_rfc3339(since).map_err(|e| format!("Invalid timestamp {}: {}", since, e)))
+        .transpose()?;
+    Ok(store.audit_log.iter().rev()
+        .filter(|entry| action.is_none_or(|action| entry.action == action))
+        .filter(|entry| since.is_none_or(|since| chrono::DateTime::parse_from_rfc3339(&entry.at).is_ok_and(|at| at >= since)))
+        .take(limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect())
+}
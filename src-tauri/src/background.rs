@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, Window, WindowEvent};
+use tauri_plugin_notification::NotificationExt;
+
+use credit_analyzer_core::i18n;
+#[cfg(desktop)]
+use credit_analyzer_core::settings::Settings;
+
+use crate::{analyze_statement, anomalies, folder_import, settings, state, AnalyzeError};
+
+// Passed by the login item, so a launch at login starts in the tray instead of on screen
+pub const BACKGROUND_ARG: &str = "--background";
+// Often enough that a statement saved from the bank's site shows up while it's still in mind
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+// A file written this recently may still be downloading
+const SETTLE_TIME: Duration = Duration::from_secs(10);
+
+pub fn launched_in_background() -> bool {
+    std::env::args().skip(1).any(|arg| arg == BACKGROUND_ARG)
+}
+
+#[cfg(desktop)]
+pub fn autostart_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_autostart::init(tauri_plugin_autostart::MacosLauncher::LaunchAgent, Some(vec![BACKGROUND_ARG]))
+}
+
+// Adds or removes the login item to match the setting
+#[cfg(desktop)]
+pub fn apply_autostart(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    use tauri_plugin_autostart::ManagerExt;
+
+    let autolaunch = app.autolaunch();
+    let enabled = autolaunch.is_enabled()
+        .map_err(|e| format!("Could not read launch at login: {}", e))?;
+    match (settings.autostart, enabled) {
+        (true, false) => autolaunch.enable(),
+        (false, true) => autolaunch.disable(),
+        _ => Ok(()),
+    }
+    .map_err(|e| format!("Could not update launch at login: {}", e))
+}
+
+// With background import on, closing the main window only hides it, so the folder keeps being
+// watched; the tray's Quit exits
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+    let keep_running = settings::load_settings(window.app_handle()).is_ok_and(|s| s.background_import);
+    if window.label() == "main" && keep_running {
+        api.prevent_close();
+        let _ = window.hide();
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+// Imported before its file last changed, or never
+fn is_stale(imported_at: Option<&String>, modified: SystemTime) -> bool {
+    imported_at
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+        .is_none_or(|at| SystemTime::from(at) < modified)
+}
+
+fn notify(app: &AppHandle, count: usize, folder: &str, locale: &str) -> Result<(), String> {
+    app.notification()
+        .builder()
+        .title(i18n::text(locale, "background.alerts_title", &[]))
+        .body(i18n::text(locale, "background.alerts_body", &[("count", count.to_string()), ("folder", folder.to_string())]))
+        .show()
+        .map_err(|e| format!("Could not show notification: {}", e))
+}
+
+// Imports the statements in the folder that are new or changed since they were last imported.
+// Files that failed are remembered with the time they were modified, and only tried again once
+// they change, so a password-protected PDF isn't re-read every minute.
+async fn import_new(app: &AppHandle, failed: &mut HashMap<PathBuf, SystemTime>) -> Result<(), String> {
+    let settings = settings::load_settings(app)?;
+    let Some(folder) = settings.statements_folder.clone().filter(|_| settings.background_import) else {
+        return Ok(());
+    };
+
+    let (imported, alerts_before): (HashMap<String, String>, HashSet<String>) = {
+        let store = state::read_store(app).await?;
+        (
+            store.statements.iter().map(|s| (s.source_path.clone(), s.imported_at.clone())).collect(),
+            anomalies::unreviewed(&store).into_iter().map(|a| a.id).collect(),
+        )
+    };
+    let scanned = folder.clone();
+    let found = tokio::task::spawn_blocking(move || folder_import::discover(&[scanned]))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let now = SystemTime::now();
+    let mut count = 0;
+    for path in found {
+        let Some(modified) = modified(&path) else { continue };
+        let settled = now.duration_since(modified).is_ok_and(|age| age >= SETTLE_TIME);
+        let path_text = path.to_string_lossy().into_owned();
+        if !settled || failed.get(&path) == Some(&modified) || !is_stale(imported.get(&path_text), modified) {
+            continue;
+        }
+        // Never private: the point is to keep the history current
+        match analyze_statement(app.clone(), path_text, None, None, None, Some(false), None).await {
+            Ok(_) => {
+                failed.remove(&path);
+                count += 1;
+            }
+            Err(e) => {
+                log!("Background import of {} failed: {}", path.display(), AnalyzeError::into_message(e));
+                failed.insert(path, modified);
+            }
+        }
+    }
+    if count == 0 {
+        return Ok(());
+    }
+    log!("Imported {} statements from {} in the background", count, folder);
+
+    let store = state::read_store(app).await?;
+    let new_alerts = anomalies::unreviewed(&store).into_iter().filter(|a| !alerts_before.contains(&a.id)).count();
+    if new_alerts > 0 {
+        notify(app, new_alerts, &folder, &settings.locale)?;
+    }
+    Ok(())
+}
+
+// Polls the statements folder every POLL_INTERVAL for as long as the app runs. Settings are read
+// on every pass, so turning background import on or off needs no restart.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut failed = HashMap::new();
+        loop {
+            if let Err(e) = import_new(&app, &mut failed).await {
+                log!("Background import failed: {}", e);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
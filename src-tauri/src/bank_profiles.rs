@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::bank_profiles::{check_against_sample, suggest_profile, validate, BankProfile, ProfileSuggestion};
+use credit_analyzer_core::{i18n, Transaction};
+use credit_analyzer_store::audit::AuditAction;
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+// Marks a file as a shared bank profile, so any other JSON is rejected up front
+const SHARE_FORMAT: &str = "credit_analyzer.bank_profile";
+const SHARE_VERSION: u32 = 1;
+// Rows shown back after an import
+const PREVIEW_ROWS: usize = 5;
+
+// Adds the profile, replacing any with the same name
+pub fn save(store: &mut Store, profile: BankProfile) {
+    let before = store.bank_profiles.iter().find(|p| p.name.eq_ignore_ascii_case(&profile.name)).cloned();
+    store.audit(AuditAction::RuleChange, format!("bank profile {}", profile.name), before.as_ref(), Some(&profile));
+    store.bank_profiles.retain(|p| !p.name.eq_ignore_ascii_case(&profile.name));
+    store.bank_profiles.push(profile);
+    // Cached parses may have been read without the new profile
+    store.parse_cache.clear();
+}
+
+// Turns a file no parser understood into a profile template, named after the file
+#[command]
+#[specta::specta]
+pub async fn suggest_bank_profile(app: AppHandle, file_path: String) -> Result<ProfileSuggestion, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    if content.starts_with('\u{feff}') {
+        content.remove(0);
+    }
+
+    let name = std::path::Path::new(&file_path).file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+    suggest_profile(&content, &name).ok_or_else(|| i18n::error(&locale, "error.profile_no_header"))
+}
+
+#[command]
+#[specta::specta]
+pub async fn save_bank_profile(app: AppHandle, profile: BankProfile) -> Result<BankProfile, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    validate(&profile, &locale)?;
+
+    let mut store = state::write_store(&app).await?;
+    save(&mut store, profile.clone());
+    store.save()?;
+    Ok(profile)
+}
+
+#[command]
+#[specta::specta]
+pub async fn list_bank_profiles(app: AppHandle) -> Result<Vec<BankProfile>, String> {
+    let store = state::read_store(&app).await?;
+    Ok(store.bank_profiles.clone())
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SharedProfile {
+    pub format: String,
+    pub version: u32,
+    pub profile: BankProfile,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ProfileImport {
+    pub profile: BankProfile,
+    pub transaction_count: usize,
+    // The first rows as the profile read them, so the user can check them
+    pub preview: Vec<Transaction>,
+}
+
+#[command]
+#[specta::specta]
+pub async fn export_bank_profile(app: AppHandle, name: String, file_path: String) -> Result<(), String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+    let profile = store.bank_profiles.iter()
+        .find(|p| p.name.eq_ignore_ascii_case(&name))
+        .cloned()
+        .ok_or_else(|| i18n::text(&locale, "error.profile_not_found", &[("name", name)]))?;
+
+    let shared = SharedProfile { format: SHARE_FORMAT.to_string(), version: SHARE_VERSION, profile };
+    let content = serde_json::to_string_pretty(&shared)
+        .map_err(|e| format!("Could not serialize profile: {}", e))?;
+    tokio::fs::write(&file_path, content).await
+        .map_err(|e| format!("Could not write profile: {}", e))
+}
+
+// Imports a profile someone else made. It is only saved once it reads `sample_path`, one of
+// the user's own exports from that bank.
+#[command]
+#[specta::specta]
+pub async fn import_bank_profile(app: AppHandle, file_path: String, sample_path: String) -> Result<ProfileImport, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    let shared: SharedProfile = serde_json::from_str(&content)
+        .ok()
+        .filter(|shared: &SharedProfile| shared.format == SHARE_FORMAT && shared.version <= SHARE_VERSION)
+        .ok_or_else(|| i18n::error(&locale, "error.profile_invalid_file"))?;
+    validate(&shared.profile, &locale)?;
+
+    let mut sample = tokio::fs::read_to_string(&sample_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    if sample.starts_with('\u{feff}') {
+        sample.remove(0);
+    }
+    let transactions = check_against_sample(&shared.profile, &sample, &locale)?;
+
+    let mut store = state::write_store(&app).await?;
+    save(&mut store, shared.profile.clone());
+    store.save()?;
+
+    Ok(ProfileImport {
+        profile: shared.profile,
+        transaction_count: transactions.len(),
+        preview: transactions.into_iter().take(PREVIEW_ROWS).collect(),
+    })
+}
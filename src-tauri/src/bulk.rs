@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::history::StoredStatement;
+use credit_analyzer_core::{extract_merchant_name, i18n, transaction_category, Transaction};
+use credit_analyzer_store::audit::AuditAction;
+use credit_analyzer_store::preview::{ChangePreview, RowChange};
+use credit_analyzer_store::trash::TrashedTransaction;
+use credit_analyzer_store::{rollups, BulkUndo, Store};
+
+use crate::category_detail::{in_period, valid_period};
+use crate::events;
+use crate::settings;
+use crate::snapshots::SnapshotReason;
+use crate::state::{self, OpenStore};
+
+// Which transactions a bulk edit applies to; every field given has to match
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct TransactionFilter {
+    // Matched on the normalized name, as in the merchant view
+    pub merchant: Option<String>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    // A month ("2024-01") or a year ("2024")
+    pub period: Option<String>,
+}
+
+impl TransactionFilter {
+    fn is_empty(&self) -> bool {
+        [&self.merchant, &self.category, &self.tag, &self.period].iter()
+            .all(|field| field.as_deref().is_none_or(|value| value.trim().is_empty()))
+    }
+
+    fn matches(&self, transaction: &Transaction) -> bool {
+        let merchant = self.merchant.as_deref().map(extract_merchant_name);
+        merchant.is_none_or(|merchant| extract_merchant_name(&transaction.description).eq_ignore_ascii_case(&merchant))
+            && self.category.as_deref().is_none_or(|category| transaction_category(transaction).eq_ignore_ascii_case(category.trim()))
+            && self.tag.as_deref().is_none_or(|tag| transaction.tags.contains(&tag.trim().to_lowercase()))
+            && in_period(&transaction.date, self.period.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BulkAction {
+    // None returns the transactions to the keyword rules
+    Recategorize { category: Option<String> },
+    Retag { add: Vec<String>, remove: Vec<String> },
+    // e.g. an imported merchant's rows that shouldn't count
+    Delete,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct BulkSummary {
+    // Only the latest edit can be undone, by undo_bulk_edit. None for a dry run, which
+    // changes nothing.
+    pub edit_id: Option<u64>,
+    pub affected: usize,
+    pub statement_ids: Vec<u64>,
+    // Rows and category totals the edit moves; empty for an undo
+    pub preview: ChangePreview,
+}
+
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    tags.iter().map(|tag| tag.trim().to_lowercase()).filter(|tag| !tag.is_empty()).collect()
+}
+
+// Runs `change` on each matching row, returning how many there were
+fn each_match(statement: &mut StoredStatement, filter: &TransactionFilter, mut change: impl FnMut(&mut Transaction)) -> usize {
+    let mut touched = 0;
+    for transaction in statement.transactions.iter_mut().filter(|t| filter.matches(t)) {
+        change(transaction);
+        touched += 1;
+    }
+    touched
+}
+
+// Recategorizes or retags one row. Deletes go through the trash instead, see trash_matches.
+fn edit(transaction: &mut Transaction, action: &BulkAction) {
+    match action {
+        BulkAction::Recategorize { category } => {
+            transaction.category = category.as_deref().map(str::trim).filter(|c| !c.is_empty()).map(str::to_string);
+        }
+        BulkAction::Retag { add, remove } => {
+            let (add, remove) = (normalize_tags(add), normalize_tags(remove));
+            transaction.tags.retain(|tag| !remove.contains(tag));
+            transaction.tags.extend(add);
+            transaction.tags.sort();
+            transaction.tags.dedup();
+        }
+        BulkAction::Delete => {}
+    }
+}
+
+// Applies an edit to one statement's matching rows, returning how many it touched
+fn apply(statement: &mut StoredStatement, filter: &TransactionFilter, action: &BulkAction) -> usize {
+    each_match(statement, filter, |t| edit(t, action))
+}
+
+// Every matching row as it is and as the edit would leave it
+fn preview(statements: &[StoredStatement], filter: &TransactionFilter, action: &BulkAction) -> ChangePreview {
+    let changes = statements.iter()
+        .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+        .filter(|(_, _, t)| filter.matches(t))
+        .map(|(statement_id, index, t)| {
+            let edited = (!matches!(action, BulkAction::Delete)).then(|| {
+                let mut edited = t.clone();
+                edit(&mut edited, action);
+                edited
+            });
+            RowChange::new(statement_id, index, t, edited.as_ref())
+        })
+        .collect();
+    ChangePreview::new(changes)
+}
+
+// What a failed save rolls back to, so the store in memory never holds half of an edit the
+// file doesn't
+pub(crate) struct Checkpoint {
+    statements: Vec<StoredStatement>,
+    undo: Option<BulkUndo>,
+    audit_len: usize,
+    trash: Vec<TrashedTransaction>,
+}
+
+impl Checkpoint {
+    pub(crate) fn new(store: &Store, statements: Vec<StoredStatement>) -> Self {
+        Checkpoint {
+            statements,
+            undo: store.bulk_undo.clone(),
+            audit_len: store.audit_log.len(),
+            trash: store.trash.clone(),
+        }
+    }
+}
+
+pub(crate) fn save_or_revert(store: &mut OpenStore, checkpoint: Checkpoint) -> Result<(), String> {
+    store.save().inspect_err(|_| {
+        store.restore_statements(checkpoint.statements);
+        store.bulk_undo = checkpoint.undo;
+        store.audit_log.truncate(checkpoint.audit_len);
+        store.trash = checkpoint.trash;
+    })
+}
+
+// Moves every matching row of the given statements to the trash, returning the trash ids
+fn trash_matches(store: &mut Store, filter: &TransactionFilter, statement_ids: &[u64]) -> Vec<u64> {
+    let mut trashed = Vec::new();
+    for &statement_id in statement_ids {
+        let matching: Vec<usize> = store.statement(statement_id)
+            .map(|s| s.transactions.iter().enumerate().filter(|(_, t)| filter.matches(t)).map(|(index, _)| index).collect())
+            .unwrap_or_default();
+        // Last first, so the indices still to go don't shift
+        for index in matching.into_iter().rev() {
+            trashed.extend(store.trash_transaction(statement_id, index));
+        }
+    }
+    trashed
+}
+
+// Recategorizes, retags or deletes every transaction matching the filter in one step.
+// Deleted rows go to the trash like single deletions. A dry run reports what the edit would
+// do, down to how each category's total moves, without changing anything.
+#[command]
+#[specta::specta]
+pub async fn bulk_edit(app: AppHandle, filter: TransactionFilter, action: BulkAction, dry_run: Option<bool>) -> Result<BulkSummary, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if filter.is_empty() {
+        return Err(i18n::error(&locale, "error.bulk_filter_empty"));
+    }
+    if let Some(period) = filter.period.as_deref().filter(|p| !valid_period(p)) {
+        return Err(i18n::text(&locale, "error.period_invalid", &[("period", period.to_string())]));
+    }
+    let mut open_store = state::write_store(&app).await?;
+    // A plain Store, so its fields can be borrowed separately
+    let store: &mut Store = &mut open_store;
+
+    let snapshots: Vec<StoredStatement> = store.statements.iter()
+        .filter(|s| s.transactions.iter().any(|t| filter.matches(t)))
+        .cloned()
+        .collect();
+    if snapshots.is_empty() {
+        return Err(i18n::error(&locale, "error.bulk_no_matches"));
+    }
+    let preview = preview(&snapshots, &filter, &action);
+    if dry_run.unwrap_or(false) {
+        let statement_ids = snapshots.iter().map(|s| s.id).collect();
+        return Ok(BulkSummary { edit_id: None, affected: preview.affected, statement_ids, preview });
+    }
+    // `snapshots` here are the statements touched, for undo; this one is the whole store
+    crate::snapshots::take(&app, store, SnapshotReason::BulkEdit)?;
+    let checkpoint = Checkpoint::new(store, snapshots.clone());
+    let statement_ids: Vec<u64> = snapshots.iter().map(|s| s.id).collect();
+
+    // Each trashed row is logged as it goes; other edits are logged as one entry with the rows
+    // as they were
+    let (affected, trashed) = match &action {
+        BulkAction::Delete => {
+            let trashed = trash_matches(store, &filter, &statement_ids);
+            (trashed.len(), trashed)
+        }
+        _ => {
+            let mut affected = 0;
+            for statement in store.statements.iter_mut().filter(|s| statement_ids.contains(&s.id)) {
+                rollups::subtract(&mut store.rollups, &statement.transactions);
+                affected += apply(statement, &filter, &action);
+                rollups::add(&mut store.rollups, &statement.transactions);
+            }
+            let matched: Vec<&Transaction> = snapshots.iter().flat_map(|s| &s.transactions).filter(|t| filter.matches(t)).collect();
+            let target = serde_json::to_string(&filter).unwrap_or_default();
+            store.audit(AuditAction::BulkEdit, target, Some(&matched), Some(&action));
+            (affected, Vec::new())
+        }
+    };
+
+    let edit_id = store.allocate_id();
+    store.bulk_undo = Some(BulkUndo {
+        edit_id,
+        performed_at: chrono::Utc::now().to_rfc3339(),
+        affected,
+        statements: snapshots,
+        trashed,
+    });
+    save_or_revert(&mut open_store, checkpoint)?;
+    log!("Bulk edit {} changed {} transactions", edit_id, affected);
+    events::transactions_changed(&app, statement_ids.clone());
+    Ok(BulkSummary { edit_id: Some(edit_id), affected, statement_ids, preview })
+}
+
+// Puts back the statements the latest bulk edit touched, taking rows it deleted back out of
+// the trash. Changes made to those statements since are undone with it.
+#[command]
+#[specta::specta]
+pub async fn undo_bulk_edit(app: AppHandle) -> Result<BulkSummary, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+    let current: Vec<StoredStatement> = store.bulk_undo.iter()
+        .flat_map(|undo| &undo.statements)
+        .filter_map(|before| store.statement(before.id).cloned())
+        .collect();
+    let checkpoint = Checkpoint::new(&store, current.clone());
+    let undo = store.bulk_undo.take().ok_or_else(|| i18n::error(&locale, "error.bulk_nothing_to_undo"))?;
+
+    let statement_ids = store.restore_statements(undo.statements.clone());
+    store.trash.retain(|t| !undo.trashed.contains(&t.id));
+    store.audit(AuditAction::Undo, format!("bulk edit {}", undo.edit_id), Some(&current), Some(&undo.statements));
+    save_or_revert(&mut store, checkpoint)?;
+    log!("Undid bulk edit {}", undo.edit_id);
+    events::transactions_changed(&app, statement_ids.clone());
+    Ok(BulkSummary { edit_id: Some(undo.edit_id), affected: undo.affected, statement_ids, preview: ChangePreview::default() })
+}
@@ -0,0 +1,94 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::business;
+use credit_analyzer_core::{extract_merchant_name, i18n, month_key, parse_date, transaction_category};
+use credit_analyzer_store::Store;
+
+use crate::category_detail::{in_period, valid_period};
+use crate::settings;
+use crate::state;
+
+struct Expense {
+    month: String,
+    date: String,
+    merchant: String,
+    category: String,
+    description: String,
+    amount: f64,
+    receipt_path: String,
+    note: String,
+}
+
+fn business_expenses(store: &Store, rules: &[String], period: Option<&str>) -> Vec<Expense> {
+    let mut expenses: Vec<Expense> = store.statements.iter()
+        .flat_map(|s| s.transactions.iter())
+        .filter(|t| business::is_business(t, rules) && in_period(&t.date, period))
+        .map(|t| Expense {
+            month: month_key(&t.date).unwrap_or_else(|| "Unknown".to_string()),
+            date: parse_date(&t.date).map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| t.date.clone()),
+            merchant: extract_merchant_name(&t.description),
+            category: transaction_category(t),
+            description: t.description.clone(),
+            amount: t.amount,
+            receipt_path: t.receipt_path.clone().unwrap_or_default(),
+            note: t.note.clone().unwrap_or_default(),
+        })
+        .collect();
+    expenses.sort_by(|a, b| (&a.month, &a.category, &a.date).cmp(&(&b.month, &b.category, &b.date)));
+    expenses
+}
+
+// Line items grouped by month and category, with a subtotal after each month and a grand
+// total, the shape most expense-report tools take as an upload
+fn expenses_csv(expenses: &[Expense]) -> Result<Vec<u8>, String> {
+    let write_error = |e: &dyn std::fmt::Display| format!("Could not write expense report: {}", e);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["Month", "Date", "Merchant", "Category", "Description", "Amount", "Receipt", "Note"])
+        .map_err(|e| write_error(&e))?;
+
+    for (i, expense) in expenses.iter().enumerate() {
+        writer.write_record([
+            expense.month.as_str(),
+            expense.date.as_str(),
+            expense.merchant.as_str(),
+            expense.category.as_str(),
+            expense.description.as_str(),
+            &format!("{:.2}", expense.amount),
+            expense.receipt_path.as_str(),
+            expense.note.as_str(),
+        ]).map_err(|e| write_error(&e))?;
+
+        if expenses.get(i + 1).is_none_or(|next| next.month != expense.month) {
+            let subtotal: f64 = expenses.iter().filter(|e| e.month == expense.month).map(|e| e.amount).sum();
+            writer.write_record([expense.month.as_str(), "", "", "", "Subtotal", &format!("{:.2}", subtotal), "", ""])
+                .map_err(|e| write_error(&e))?;
+        }
+    }
+
+    let total: f64 = expenses.iter().map(|e| e.amount).sum();
+    writer.write_record(["", "", "", "", "Total", &format!("{:.2}", total), "", ""])
+        .map_err(|e| write_error(&e))?;
+    writer.into_inner().map_err(|e| write_error(&e))
+}
+
+// Writes business expenses as CSV for an expense report. `period` is a month ("2024-01"), a
+// year ("2024"), or None for everything imported.
+#[command]
+#[specta::specta]
+pub async fn export_business_expenses(app: AppHandle, period: Option<String>, file_path: String) -> Result<(), String> {
+    let settings = settings::load_settings(&app)?;
+    if let Some(period) = period.as_deref().filter(|p| !valid_period(p)) {
+        return Err(i18n::text(&settings.locale, "error.period_invalid", &[("period", period.to_string())]));
+    }
+
+    let expenses = {
+        let store = state::read_store(&app).await?;
+        business_expenses(&store, &settings.business_rules, period.as_deref())
+    };
+    if expenses.is_empty() {
+        return Err(i18n::error(&settings.locale, "error.no_business_expenses"));
+    }
+    let content = expenses_csv(&expenses)?;
+    tokio::fs::write(&file_path, content).await
+        .map_err(|e| format!("Could not write expense report: {}", e))
+}
@@ -0,0 +1,78 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::cards::{benefit_report, latest_year, portfolio_break_even, BenefitReport, Card, PortfolioBreakEven};
+use credit_analyzer_core::i18n;
+use credit_analyzer_store::audit::AuditAction;
+
+use crate::settings;
+use crate::state;
+
+// Adds a card, or with the id of an existing one replaces it
+#[command]
+#[specta::specta]
+pub async fn save_card(app: AppHandle, card: Card) -> Result<Card, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    card.validate(&locale)?;
+    let mut store = state::write_store(&app).await?;
+
+    let before = store.cards.iter().find(|c| c.id == card.id).cloned();
+    let card = match before {
+        Some(_) => card,
+        None => Card { id: store.allocate_id(), ..card },
+    };
+    store.audit(AuditAction::RuleChange, format!("card {}", card.id), before.as_ref(), Some(&card));
+    store.cards.retain(|c| c.id != card.id);
+    store.cards.push(card.clone());
+
+    store.save()?;
+    Ok(card)
+}
+
+#[command]
+#[specta::specta]
+pub async fn list_cards(app: AppHandle) -> Result<Vec<Card>, String> {
+    let store = state::read_store(&app).await?;
+    Ok(store.cards.clone())
+}
+
+#[command]
+#[specta::specta]
+pub async fn delete_card(app: AppHandle, id: u64) -> Result<(), String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+
+    let Some(position) = store.cards.iter().position(|c| c.id == id) else {
+        return Err(i18n::text(&locale, "error.card_not_found", &[("id", id.to_string())]));
+    };
+    let removed = store.cards.remove(position);
+    store.audit(AuditAction::RuleChange, format!("card {}", id), Some(&removed), None::<&Card>);
+    store.save()
+}
+
+// Which of the card's benefits were used in `year`, by default the latest one it has
+// purchases in
+#[command]
+#[specta::specta]
+pub async fn get_benefit_report(app: AppHandle, card_id: u64, year: Option<i32>) -> Result<BenefitReport, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+
+    let card = store.cards.iter().find(|c| c.id == card_id)
+        .ok_or_else(|| i18n::text(&locale, "error.card_not_found", &[("id", card_id.to_string())]))?;
+    let year = year.or_else(|| latest_year(card, &store.statements))
+        .ok_or_else(|| i18n::error(&locale, "error.no_statements"))?;
+    Ok(benefit_report(card, &store.statements, year))
+}
+
+// What each card netted in `year` after rewards, benefits, fees and interest, and all of them
+// together. Defaults to the latest year with statements.
+#[command]
+#[specta::specta]
+pub async fn get_break_even(app: AppHandle, year: Option<i32>) -> Result<PortfolioBreakEven, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+
+    let year = year.or_else(|| store.cards.iter().filter_map(|card| latest_year(card, &store.statements)).max())
+        .ok_or_else(|| i18n::error(&locale, "error.no_statements"))?;
+    Ok(portfolio_break_even(&store.cards, &store.statements, year))
+}
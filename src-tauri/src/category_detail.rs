@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::goals::{self, GoalStatus};
+use credit_analyzer_core::{i18n, merchant_totals, month_key, parse_date, transaction_category, MerchantTotal, Transaction};
+use credit_analyzer_store::rollups::MonthlyRollup;
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+const TOP_MERCHANTS: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CategoryTransaction {
+    pub statement_id: u64,
+    pub index: usize,
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CategoryDetail {
+    pub category: String,
+    // As requested: "2024-01", "2024", or None for all history
+    pub period: Option<String>,
+    pub total: f64,
+    pub count: usize,
+    // Newest first
+    pub transactions: Vec<CategoryTransaction>,
+    pub top_merchants: Vec<MerchantTotal>,
+    // Oldest first, across the whole history so the period can be seen in context
+    pub monthly: Vec<MonthlyRollup>,
+    // Goals that cap or reduce this category
+    pub budgets: Vec<GoalStatus>,
+}
+
+// A period is a month ("2024-01") or a year ("2024")
+pub fn valid_period(period: &str) -> bool {
+    match period.len() {
+        4 => period.parse::<i32>().is_ok(),
+        7 => NaiveDate::parse_from_str(&format!("{}-01", period), "%Y-%m-%d").is_ok(),
+        _ => false,
+    }
+}
+
+pub fn in_period(date: &str, period: Option<&str>) -> bool {
+    match period {
+        None => true,
+        Some(period) => month_key(date).is_some_and(|month| month.starts_with(period)),
+    }
+}
+
+pub fn category_detail(store: &Store, category: &str, period: Option<&str>) -> CategoryDetail {
+    let matching: Vec<(u64, usize, &Transaction)> = store.statements.iter()
+        .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+        .filter(|(_, _, t)| transaction_category(t).eq_ignore_ascii_case(category) && in_period(&t.date, period))
+        .collect();
+
+    let mut top_merchants = merchant_totals(matching.iter().map(|(_, _, t)| *t));
+    top_merchants.truncate(TOP_MERCHANTS);
+
+    let mut transactions: Vec<CategoryTransaction> = matching.iter()
+        .map(|(statement_id, index, t)| CategoryTransaction {
+            statement_id: *statement_id,
+            index: *index,
+            date: t.date.clone(),
+            description: t.description.clone(),
+            amount: t.amount,
+        })
+        .collect();
+    transactions.sort_by_key(|t| std::cmp::Reverse(parse_date(&t.date)));
+
+    let monthly = store.rollups.iter()
+        .filter(|r| r.category.eq_ignore_ascii_case(category))
+        .cloned()
+        .collect();
+
+    CategoryDetail {
+        category: category.to_string(),
+        period: period.map(str::to_string),
+        total: transactions.iter().map(|t| t.amount).sum(),
+        count: transactions.len(),
+        transactions,
+        top_merchants,
+        monthly,
+        budgets: goals::category_statuses(&store.goals, category),
+    }
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_category_detail(app: AppHandle, category: String, period: Option<String>) -> Result<CategoryDetail, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if let Some(period) = period.as_deref().filter(|p| !valid_period(p)) {
+        return Err(i18n::text(&locale, "error.period_invalid", &[("period", period.to_string())]));
+    }
+
+    let store = state::read_store(&app).await?;
+    let detail = category_detail(&store, &category, period.as_deref());
+    if detail.count == 0 && detail.monthly.is_empty() {
+        return Err(i18n::text(&locale, "error.category_empty", &[("category", category)]));
+    }
+    Ok(detail)
+}
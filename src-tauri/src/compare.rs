@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::history::StoredStatement;
+use credit_analyzer_core::interner::CompactHistory;
+use credit_analyzer_core::subscriptions::{self, Subscription};
+use credit_analyzer_core::{extract_merchant_name, i18n, is_fee_or_interest, transaction_category, Transaction};
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+// Differences smaller than this are rounding, not a change
+const MIN_CHANGE: f64 = 0.01;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct AmountChange {
+    pub name: String,
+    pub before: f64,
+    pub after: f64,
+    pub change: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct StatementDiff {
+    pub statement_a: u64,
+    pub statement_b: u64,
+    pub total_before: f64,
+    pub total_after: f64,
+    // Largest swing first
+    pub categories_up: Vec<AmountChange>,
+    pub categories_down: Vec<AmountChange>,
+    pub merchants_added: Vec<AmountChange>,
+    pub merchants_removed: Vec<AmountChange>,
+    pub subscriptions_added: Vec<AmountChange>,
+    pub subscriptions_cancelled: Vec<AmountChange>,
+    pub subscription_price_changes: Vec<AmountChange>,
+    pub fees: Vec<AmountChange>,
+    pub fees_before: f64,
+    pub fees_after: f64,
+}
+
+pub fn totals_by<'a>(transactions: impl IntoIterator<Item = &'a Transaction>, key: impl Fn(&Transaction) -> Option<String>) -> BTreeMap<String, f64> {
+    let mut totals = BTreeMap::new();
+    for tx in transactions {
+        if let Some(name) = key(tx) {
+            *totals.entry(name).or_insert(0.0) += tx.amount;
+        }
+    }
+    totals
+}
+
+// Every name in either map with its before/after amounts, where they differ
+pub fn changes(before: &BTreeMap<String, f64>, after: &BTreeMap<String, f64>) -> Vec<AmountChange> {
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let mut changes: Vec<AmountChange> = names.into_iter()
+        .map(|name| {
+            let before = before.get(name).copied().unwrap_or(0.0);
+            let after = after.get(name).copied().unwrap_or(0.0);
+            AmountChange { name: name.clone(), before, after, change: after - before }
+        })
+        .filter(|c| c.change.abs() >= MIN_CHANGE)
+        .collect();
+    changes.sort_by(|a, b| b.change.abs().total_cmp(&a.change.abs()));
+    changes
+}
+
+// Subscriptions active at the end of a statement, judged on the history imported up to it
+fn subscriptions_at(store: &Store, position: usize) -> BTreeMap<String, f64> {
+    let history = CompactHistory::from_statements(&store.statements[..=position]);
+    let Some(as_of) = history.latest_date() else {
+        return BTreeMap::new();
+    };
+    subscriptions::detect_subscriptions(&history, as_of)
+        .into_iter()
+        .map(|Subscription { merchant, amount, .. }| (merchant, amount))
+        .collect()
+}
+
+pub fn diff_statements(store: &Store, a: &StoredStatement, b: &StoredStatement) -> StatementDiff {
+    let position = |id: u64| store.statements.iter().position(|s| s.id == id).unwrap_or(0);
+
+    let by_category = |s: &StoredStatement| totals_by(&s.transactions, |t| Some(transaction_category(t)));
+    let (categories_up, categories_down): (Vec<_>, Vec<_>) = changes(&by_category(a), &by_category(b))
+        .into_iter()
+        .partition(|c| c.change > 0.0);
+
+    let by_merchant = |s: &StoredStatement| totals_by(&s.transactions, |t| Some(extract_merchant_name(&t.description)));
+    let (merchants_added, merchants_removed): (Vec<_>, Vec<_>) = changes(&by_merchant(a), &by_merchant(b))
+        .into_iter()
+        .filter(|c| c.before == 0.0 || c.after == 0.0)
+        .partition(|c| c.before == 0.0);
+
+    let subscriptions = changes(&subscriptions_at(store, position(a.id)), &subscriptions_at(store, position(b.id)));
+    let mut subscriptions_added = Vec::new();
+    let mut subscriptions_cancelled = Vec::new();
+    let mut subscription_price_changes = Vec::new();
+    for change in subscriptions {
+        if change.before == 0.0 {
+            subscriptions_added.push(change);
+        } else if change.after == 0.0 {
+            subscriptions_cancelled.push(change);
+        } else {
+            subscription_price_changes.push(change);
+        }
+    }
+
+    let by_fee = |s: &StoredStatement| totals_by(&s.transactions, |t| is_fee_or_interest(t).then(|| t.description.clone()));
+    let (fees_a, fees_b) = (by_fee(a), by_fee(b));
+
+    StatementDiff {
+        statement_a: a.id,
+        statement_b: b.id,
+        total_before: a.transactions.iter().map(|t| t.amount).sum(),
+        total_after: b.transactions.iter().map(|t| t.amount).sum(),
+        categories_up,
+        categories_down,
+        merchants_added,
+        merchants_removed,
+        subscriptions_added,
+        subscriptions_cancelled,
+        subscription_price_changes,
+        fees: changes(&fees_a, &fees_b),
+        fees_before: fees_a.values().sum(),
+        fees_after: fees_b.values().sum(),
+    }
+}
+
+// What changed going from statement `id_a` to statement `id_b`
+#[command]
+#[specta::specta]
+pub async fn compare_statements(app: AppHandle, id_a: u64, id_b: u64) -> Result<StatementDiff, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+
+    let find = |id: u64| store.statement(id)
+        .ok_or_else(|| i18n::text(&locale, "error.statement_not_found", &[("id", id.to_string())]));
+    let (a, b) = (find(id_a)?, find(id_b)?);
+
+    Ok(diff_statements(&store, a, b))
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::bank_profiles::BankProfile;
+use credit_analyzer_core::cards::Card;
+use credit_analyzer_core::custom_insights::{load_definitions, CustomInsightDefinition};
+use credit_analyzer_core::goals::Goal;
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::settings::Settings;
+use credit_analyzer_store::audit::AuditAction;
+use credit_analyzer_store::Store;
+
+use crate::snapshots::{self, SnapshotReason};
+use crate::{custom_insights, events, settings, state};
+
+// Marks a file as a configuration bundle, so any other JSON is rejected up front
+const BUNDLE_FORMAT: &str = "credit_analyzer.config";
+const BUNDLE_VERSION: u32 = 1;
+
+// Everything the user has set up, without any of their statements, to carry to another machine
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ConfigBundle {
+    pub format: String,
+    pub version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    // Category classes, business rules and emission factors ride along in here
+    pub settings: Settings,
+    pub custom_insights: Vec<CustomInsightDefinition>,
+    // Budgets, without the progress measured against this machine's statements
+    pub goals: Vec<Goal>,
+    pub bank_profiles: Vec<BankProfile>,
+    pub cards: Vec<Card>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigImportMode {
+    // Keep what's here and add the bundle's; an item with the same name is replaced by the
+    // bundle's, and settings that aren't lists stay as they are
+    Merge,
+    // Make the configuration exactly the bundle's
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ConfigImportSummary {
+    pub goals: usize,
+    pub bank_profiles: usize,
+    pub cards: usize,
+    pub custom_insights: usize,
+    // Items already here that a merge replaced, e.g. "bank profile HDFC"
+    pub replaced: Vec<String>,
+}
+
+// Settings that describe this machine rather than the user, so an import never carries them over
+fn keep_local(mut imported: Settings, current: &Settings) -> Settings {
+    imported.import_shortcut = current.import_shortcut.clone();
+    imported.autostart = current.autostart;
+    imported.background_import = current.background_import;
+    imported.statements_folder = current.statements_folder.clone();
+    imported.sync_folder = current.sync_folder.clone();
+    imported
+}
+
+fn merged_settings(imported: Settings, current: &Settings, mode: ConfigImportMode) -> Settings {
+    if mode == ConfigImportMode::Replace {
+        return keep_local(imported, current);
+    }
+    let mut merged = current.clone();
+    merged.category_classes.extend(imported.category_classes);
+    merged.emission_factors.extend(imported.emission_factors);
+    for rule in imported.business_rules {
+        if !merged.business_rules.iter().any(|r| r.eq_ignore_ascii_case(&rule)) {
+            merged.business_rules.push(rule);
+        }
+    }
+    merged
+}
+
+// Puts the imported items into `existing` as the mode says. Names match ignoring case, as bank
+// profiles always have; returns the names of the items a merge replaced.
+fn merge_named<T>(existing: &mut Vec<T>, imported: Vec<T>, mode: ConfigImportMode, name: impl Fn(&T) -> &str) -> Vec<String> {
+    if mode == ConfigImportMode::Replace {
+        *existing = imported;
+        return Vec::new();
+    }
+    let mut replaced = Vec::new();
+    for item in imported {
+        if let Some(position) = existing.iter().position(|e| name(e).eq_ignore_ascii_case(name(&item))) {
+            replaced.push(name(&item).to_string());
+            existing.remove(position);
+        }
+        existing.push(item);
+    }
+    replaced
+}
+
+pub(crate) fn validate(bundle: &ConfigBundle, locale: &str) -> Result<(), String> {
+    bundle.settings.validate()?;
+    credit_analyzer_core::custom_insights::validate(&bundle.custom_insights)?;
+    for goal in &bundle.goals {
+        credit_analyzer_core::goals::validate(&goal.kind, locale)?;
+    }
+    for profile in &bundle.bank_profiles {
+        credit_analyzer_core::bank_profiles::validate(profile, locale)?;
+    }
+    for card in &bundle.cards {
+        card.validate(locale)?;
+    }
+    Ok(())
+}
+
+#[command]
+#[specta::specta]
+pub async fn export_config(app: AppHandle, file_path: String) -> Result<(), String> {
+    let settings = settings::load_settings(&app)?;
+    let definitions = load_definitions(&custom_insights::definitions_path(&app)?)?;
+    let store = state::read_store(&app).await?;
+    let bundle = ConfigBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings,
+        custom_insights: definitions,
+        goals: store.goals.iter().map(|goal| Goal { progress: Vec::new(), ..goal.clone() }).collect(),
+        bank_profiles: store.bank_profiles.clone(),
+        cards: store.cards.clone(),
+    };
+    drop(store);
+
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Could not serialize configuration: {}", e))?;
+    tokio::fs::write(&file_path, content).await
+        .map_err(|e| format!("Could not write configuration: {}", e))
+}
+
+// The parts of the store an import changes, to put back if a later step fails
+pub(crate) struct StoreConfig {
+    goals: Vec<Goal>,
+    bank_profiles: Vec<BankProfile>,
+    cards: Vec<Card>,
+    audit_len: usize,
+}
+
+impl StoreConfig {
+    pub(crate) fn of(store: &Store) -> Self {
+        StoreConfig {
+            goals: store.goals.clone(),
+            bank_profiles: store.bank_profiles.clone(),
+            cards: store.cards.clone(),
+            audit_len: store.audit_log.len(),
+        }
+    }
+
+    pub(crate) fn restore(self, store: &mut Store) {
+        store.goals = self.goals;
+        store.bank_profiles = self.bank_profiles;
+        store.cards = self.cards;
+        store.audit_log.truncate(self.audit_len);
+    }
+}
+
+// Puts the bundle's goals, bank profiles and cards into the store, returning what a merge replaced
+pub(crate) fn apply_to_store(store: &mut Store, goals: Vec<Goal>, bank_profiles: Vec<BankProfile>, cards: Vec<Card>, mode: ConfigImportMode) -> Vec<String> {
+    let before = serde_json::json!({ "goals": store.goals, "bank_profiles": store.bank_profiles, "cards": store.cards });
+
+    // Ids are only unique within one store, so imported goals and cards get fresh ones here
+    let mut imported_goals = goals;
+    for goal in &mut imported_goals {
+        goal.id = store.allocate_id();
+        goal.progress.clear();
+    }
+    let mut imported_cards = cards;
+    for card in &mut imported_cards {
+        card.id = store.allocate_id();
+    }
+
+    let mut replaced = Vec::new();
+    replaced.extend(merge_named(&mut store.goals, imported_goals, mode, |g| &g.name)
+        .into_iter().map(|name| format!("goal {}", name)));
+    replaced.extend(merge_named(&mut store.bank_profiles, bank_profiles, mode, |p| &p.name)
+        .into_iter().map(|name| format!("bank profile {}", name)));
+    replaced.extend(merge_named(&mut store.cards, imported_cards, mode, |c| &c.name)
+        .into_iter().map(|name| format!("card {}", name)));
+    // Cached parses may have been read without the imported profiles
+    store.parse_cache.clear();
+
+    let after = serde_json::json!({ "goals": store.goals, "bank_profiles": store.bank_profiles, "cards": store.cards });
+    store.audit(AuditAction::RuleChange, "configuration import", Some(&before), Some(&after));
+    replaced
+}
+
+// Puts back the store, settings and custom insights as they were before an import. Each is
+// tried even if another fails, since any of them may have been written.
+async fn revert_import(app: &AppHandle, store_config: StoreConfig, settings: Settings, definitions: Vec<CustomInsightDefinition>) {
+    match state::write_store(app).await {
+        Ok(mut store) => {
+            store_config.restore(&mut store);
+            if let Err(e) = store.save() {
+                log!("Could not restore the store after a failed configuration import: {}", e);
+            }
+        }
+        Err(e) => log!("Could not restore the store after a failed configuration import: {}", e),
+    }
+    if let Err(e) = settings::update_settings(app.clone(), settings).await {
+        log!("Could not restore settings after a failed configuration import: {}", e);
+    }
+    if let Err(e) = custom_insights::save_custom_insights(app.clone(), definitions).await {
+        log!("Could not restore custom insights after a failed configuration import: {}", e);
+    }
+}
+
+// Applies a bundle from export_config. The bundle and everything it merges into are checked
+// before anything is written; the store goes first, and if settings or custom insights then
+// can't be saved, all three are put back as they were.
+#[command]
+#[specta::specta]
+pub async fn import_config(app: AppHandle, file_path: String, mode: ConfigImportMode) -> Result<ConfigImportSummary, String> {
+    let current = settings::load_settings(&app)?;
+    let locale = current.locale.clone();
+    let content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    let bundle: ConfigBundle = serde_json::from_str(&content)
+        .ok()
+        .filter(|bundle: &ConfigBundle| bundle.format == BUNDLE_FORMAT && bundle.version <= BUNDLE_VERSION)
+        .ok_or_else(|| i18n::error(&locale, "error.config_invalid_file"))?;
+    validate(&bundle, &locale)?;
+
+    let mut summary = ConfigImportSummary {
+        goals: bundle.goals.len(),
+        bank_profiles: bundle.bank_profiles.len(),
+        cards: bundle.cards.len(),
+        custom_insights: bundle.custom_insights.len(),
+        replaced: Vec::new(),
+    };
+
+    let imported_settings = merged_settings(bundle.settings, &current, mode);
+    imported_settings.validate()?;
+    let previous_definitions = load_definitions(&custom_insights::definitions_path(&app)?)?;
+    let mut definitions = previous_definitions.clone();
+    summary.replaced.extend(merge_named(&mut definitions, bundle.custom_insights, mode, |d| &d.name)
+        .into_iter().map(|name| format!("custom insight {}", name)));
+    credit_analyzer_core::custom_insights::validate(&definitions)?;
+
+    let mut store = state::write_store(&app).await?;
+    // A replace drops every goal, bank profile and card already here
+    if mode == ConfigImportMode::Replace {
+        snapshots::take(&app, &store, SnapshotReason::ConfigImport)?;
+    }
+    let store_config = StoreConfig::of(&store);
+    summary.replaced.extend(apply_to_store(&mut store, bundle.goals, bundle.bank_profiles, bundle.cards, mode));
+    if let Err(e) = store.save() {
+        store_config.restore(&mut store);
+        return Err(e);
+    }
+    let goal_ids = store.goals.iter().map(|g| g.id).collect();
+    // Settings and custom insights take the store lock themselves to record their audit entries
+    drop(store);
+
+    let saved = match settings::update_settings(app.clone(), imported_settings).await {
+        Ok(_) => custom_insights::save_custom_insights(app.clone(), definitions).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = saved {
+        revert_import(&app, store_config, current, previous_definitions).await;
+        return Err(e);
+    }
+    events::budgets_updated(&app, goal_ids);
+
+    log!("Imported configuration ({:?}), {} items replaced", mode, summary.replaced.len());
+    Ok(summary)
+}
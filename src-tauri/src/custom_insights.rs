@@ -0,0 +1,44 @@
+use std::fs;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Manager};
+
+use credit_analyzer_core::custom_insights::{load_definitions, validate, CustomInsightDefinition};
+use credit_analyzer_store::audit::AuditAction;
+
+use crate::state;
+
+const CUSTOM_INSIGHTS_FILE: &str = "custom_insights.json";
+
+// Only resolved, like settings_path; saving makes the directory
+pub fn definitions_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir()
+        .map_err(|e| format!("Could not resolve config directory: {}", e))?;
+    Ok(dir.join(CUSTOM_INSIGHTS_FILE))
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_custom_insights(app: AppHandle) -> Result<Vec<CustomInsightDefinition>, String> {
+    load_definitions(&definitions_path(&app)?)
+}
+
+#[command]
+#[specta::specta]
+pub async fn save_custom_insights(app: AppHandle, definitions: Vec<CustomInsightDefinition>) -> Result<(), String> {
+    validate(&definitions)?;
+    let path = definitions_path(&app)?;
+    let previous = load_definitions(&path).ok();
+
+    let content = serde_json::to_string_pretty(&definitions)
+        .map_err(|e| format!("Could not serialize custom insights: {}", e))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Could not create config directory: {}", e))?;
+    }
+    fs::write(&path, content)
+        .map_err(|e| format!("Could not write custom insights: {}", e))?;
+
+    let mut store = state::write_store(&app).await?;
+    store.audit(AuditAction::RuleChange, "custom insights", previous.as_ref(), Some(&definitions));
+    store.save()
+}
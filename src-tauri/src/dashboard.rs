@@ -0,0 +1,93 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::goals::{self, GoalStatus};
+use credit_analyzer_core::interner::CompactHistory;
+use credit_analyzer_core::periods;
+use credit_analyzer_core::subscriptions::{self, Subscription};
+use credit_analyzer_core::CategoryTotal;
+use credit_analyzer_store::rollups;
+use credit_analyzer_store::Store;
+
+use crate::anomalies::{self, Anomaly};
+use crate::gray_charges;
+use crate::reminders::{self, PaymentReminder};
+use crate::state;
+
+const TOP_CATEGORIES: usize = 5;
+
+// Everything the home screen shows, in one call
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct Dashboard {
+    // "2024-01"; the latest month with imported spending, which lags the calendar
+    pub current_month: Option<String>,
+    pub current_month_spend: f64,
+    pub previous_month_spend: f64,
+    pub change_percent: Option<f64>,
+    pub top_categories: Vec<CategoryTotal>,
+    pub subscriptions: Vec<Subscription>,
+    pub upcoming_payments: Vec<PaymentReminder>,
+    pub anomalies: Vec<Anomaly>,
+    // Small recurring charges from merchants nobody recognizes, awaiting review
+    pub gray_charges: Vec<Subscription>,
+    pub budgets: Vec<GoalStatus>,
+}
+
+pub fn month_total(store: &Store, month: &str) -> f64 {
+    rollups::for_month(&store.rollups, month).iter().map(|r| r.total).sum()
+}
+
+pub fn build_dashboard(store: &Store, today: NaiveDate) -> Dashboard {
+    let history = CompactHistory::from_statements(&store.statements);
+    let latest = history.latest_date();
+
+    // Month totals come from the rollups rather than re-aggregating the history
+    let month = latest.map(periods::period_month);
+    let current_month = month.map(|m| m.format("%Y-%m").to_string());
+    let previous = month.map(|m| periods::previous_month(m).format("%Y-%m").to_string());
+
+    let current_month_spend = current_month.as_deref().map_or(0.0, |m| month_total(store, m));
+    let previous_month_spend = previous.as_deref().map_or(0.0, |m| month_total(store, m));
+    let change_percent = (previous_month_spend > 0.0)
+        .then(|| (current_month_spend - previous_month_spend) / previous_month_spend * 100.0);
+
+    let top_categories = current_month.as_deref()
+        .map(|m| rollups::for_month(&store.rollups, m))
+        .unwrap_or_default()
+        .into_iter()
+        .take(TOP_CATEGORIES)
+        .map(|r| {
+            let percentage = if current_month_spend > 0.0 { r.total / current_month_spend * 100.0 } else { 0.0 };
+            CategoryTotal::new(r.category.clone(), r.total, percentage)
+        })
+        .collect();
+
+    let subscriptions = match latest {
+        Some(as_of) => subscriptions::detect_subscriptions(&history, as_of),
+        None => Vec::new(),
+    };
+
+    let gray_charges = gray_charges::unreviewed(store, &subscriptions);
+
+    Dashboard {
+        current_month,
+        current_month_spend,
+        previous_month_spend,
+        change_percent,
+        top_categories,
+        subscriptions,
+        upcoming_payments: reminders::upcoming(store, today),
+        anomalies: anomalies::unreviewed(store),
+        gray_charges,
+        budgets: goals::latest_statuses(&store.goals),
+    }
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_dashboard(app: AppHandle) -> Result<Dashboard, String> {
+    let store = state::read_store(&app).await?;
+    Ok(build_dashboard(&store, Local::now().date_naive()))
+}
@@ -0,0 +1,78 @@
+use chrono::Local;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::insights::{self, Insight, InsightKind, Severity};
+use credit_analyzer_core::synthetic::{self, SyntheticProfile};
+use credit_analyzer_core::{analyze_transactions, i18n, records, AnalysisResult};
+use credit_analyzer_store::Store;
+
+use crate::events;
+use crate::periods;
+use crate::settings;
+use crate::state;
+
+// Kept apart from store.json so demo imports never mix with the user's statements
+pub const DEMO_STORE_FILE: &str = "demo_store.json";
+
+// While set, the store path points at the demo store. Not persisted: a restart is back on real data.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+// Switches to a fresh demo store filled with synthetic statements and returns the latest
+// month's analysis. The user's own store isn't read or written.
+#[command]
+#[specta::specta]
+pub async fn load_demo_data(app: AppHandle, profile: Option<SyntheticProfile>) -> Result<AnalysisResult, String> {
+    let settings = settings::load_settings(&app)?;
+    ACTIVE.store(true, Ordering::Relaxed);
+
+    let statements = synthetic::generate(&profile.unwrap_or_default(), Local::now().date_naive());
+    {
+        // Whatever an earlier demo left behind is replaced
+        let mut store = state::write_store(&app).await?;
+        **store = Store::default();
+        for (source_path, transactions) in &statements {
+            store.record_statement(source_path, transactions.clone(), None);
+        }
+        store.save()?;
+    }
+    periods::sync(&app).await?;
+
+    let store = state::read_store(&app).await?;
+    events::all_transactions_changed(&app, &store);
+    events::budgets_updated(&app, Vec::new());
+
+    let (latest_path, latest) = statements.last()
+        .ok_or_else(|| i18n::error(&settings.locale, "error.no_statements"))?;
+    let history = store.history_excluding(latest_path);
+    let mut analysis = analyze_transactions(latest.clone(), latest_path, &history, &settings).await;
+    analysis.records = records::lifetime_records(&store.statements);
+    analysis.insights.push(Insight::new(
+        InsightKind::SampleData,
+        Severity::Notice,
+        "demo.active",
+        "Showing generated demo data; your own statements are untouched".to_string(),
+    ));
+    insights::rank(&mut analysis.insights);
+
+    log!("Loaded {} demo statements", statements.len());
+    i18n::localize_insights(&mut analysis.insights, &settings.locale);
+    Ok(analysis)
+}
+
+// Back to the user's own store. The demo store stays on disk and is replaced on the next demo.
+#[command]
+#[specta::specta]
+pub async fn exit_demo_mode(app: AppHandle) -> Result<(), String> {
+    ACTIVE.store(false, Ordering::Relaxed);
+    periods::sync(&app).await?;
+
+    let store = state::read_store(&app).await?;
+    events::all_transactions_changed(&app, &store);
+    events::budgets_updated(&app, store.goals.iter().map(|g| g.id).collect());
+    Ok(())
+}
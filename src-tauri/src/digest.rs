@@ -0,0 +1,173 @@
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::goals;
+use credit_analyzer_core::interner;
+use credit_analyzer_core::periods;
+use credit_analyzer_core::{i18n, parse_date, transaction_category, Transaction};
+use credit_analyzer_store::Store;
+
+use crate::anomalies;
+use crate::compare::{self, AmountChange};
+use crate::gray_charges;
+use crate::reminders;
+use crate::settings;
+use crate::state;
+
+const TOP_CHANGES: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    // The last seven days of imported activity
+    Week,
+    // The latest month (or custom period) of imported activity
+    Month,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct Digest {
+    pub period: DigestPeriod,
+    // ISO dates
+    pub start: String,
+    pub end: String,
+    pub total_spend: f64,
+    pub previous_spend: f64,
+    // The same content twice, for rendering in the app or pasting into an email
+    pub markdown: String,
+    pub html: String,
+}
+
+struct Section {
+    title: &'static str,
+    lines: Vec<String>,
+}
+
+// Inclusive date ranges for the period ending with `latest` and the one before it
+fn windows(period: DigestPeriod, latest: NaiveDate) -> ((NaiveDate, NaiveDate), (NaiveDate, NaiveDate)) {
+    match period {
+        DigestPeriod::Week => {
+            let start = latest - Duration::days(6);
+            ((start, latest), (start - Duration::days(7), start - Duration::days(1)))
+        }
+        DigestPeriod::Month => {
+            let month = periods::period_month(latest);
+            (periods::period_bounds(month), periods::period_bounds(periods::previous_month(month)))
+        }
+    }
+}
+
+fn in_window(store: &Store, (start, end): (NaiveDate, NaiveDate)) -> Vec<&Transaction> {
+    store.statements.iter()
+        .flat_map(|s| &s.transactions)
+        .filter(|t| parse_date(&t.date).is_some_and(|d| d >= start && d <= end))
+        .collect()
+}
+
+fn change_line(change: &AmountChange) -> String {
+    let direction = if change.change > 0.0 { "up" } else { "down" };
+    format!("{}: ${:.2}, {} ${:.2} from ${:.2}", change.name, change.after, direction, change.change.abs(), change.before)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn render_markdown(heading: &str, sections: &[Section]) -> String {
+    let mut out = format!("# {}\n", heading);
+    for section in sections {
+        out.push_str(&format!("\n## {}\n\n", section.title));
+        for line in &section.lines {
+            out.push_str(&format!("- {}\n", line));
+        }
+    }
+    out
+}
+
+fn render_html(heading: &str, sections: &[Section]) -> String {
+    let mut out = format!("<h1>{}</h1>\n", escape_html(heading));
+    for section in sections {
+        out.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape_html(section.title)));
+        for line in &section.lines {
+            out.push_str(&format!("<li>{}</li>\n", escape_html(line)));
+        }
+        out.push_str("</ul>\n");
+    }
+    out
+}
+
+pub fn build_digest(store: &Store, period: DigestPeriod, today: NaiveDate) -> Option<Digest> {
+    let latest = interner::latest_date(&store.statements)?;
+    let (current, previous) = windows(period, latest);
+    let (current_txs, previous_txs) = (in_window(store, current), in_window(store, previous));
+
+    let total_spend: f64 = current_txs.iter().map(|t| t.amount).sum();
+    let previous_spend: f64 = previous_txs.iter().map(|t| t.amount).sum();
+
+    let mut summary = vec![format!("Spent ${:.2} across {} transactions", total_spend, current_txs.len())];
+    if previous_spend > 0.0 {
+        let change = (total_spend - previous_spend) / previous_spend * 100.0;
+        summary.push(format!("{:+.0}% compared with the previous {} (${:.2})", change,
+                             if period == DigestPeriod::Week { "week" } else { "month" }, previous_spend));
+    }
+
+    let by_category = |txs: &[&Transaction]| compare::totals_by(txs.iter().copied(), |t| Some(transaction_category(t)));
+    let changes: Vec<String> = compare::changes(&by_category(&previous_txs), &by_category(&current_txs))
+        .iter()
+        .take(TOP_CHANGES)
+        .map(change_line)
+        .collect();
+
+    // Unreviewed unusual charges and gray charges, and budgets that are off track
+    let mut alerts: Vec<String> = anomalies::unreviewed(store).iter()
+        .map(|a| format!("Unusual charge: {} ${:.2} on {} (usually ${:.2})", a.merchant, a.amount, a.date, a.typical_amount))
+        .collect();
+    alerts.extend(gray_charges::unreviewed(store, &gray_charges::active_subscriptions(store)).iter()
+        .map(|s| format!("Unrecognized recurring charge: {} ${:.2} a month since {}", s.merchant, s.amount, s.first_charged)));
+    alerts.extend(goals::latest_statuses(&store.goals).iter()
+        .filter(|status| !status.met)
+        .map(|status| format!("Budget \"{}\" is ${:.2} over its ${:.2} target", status.name, -status.remaining, status.target)));
+
+    let dues: Vec<String> = reminders::upcoming(store, today).iter()
+        .map(|r| {
+            let card = r.card_last4.as_deref().map_or("Card".to_string(), |last4| format!("Card ending {}", last4));
+            match r.minimum_payment {
+                Some(amount) => format!("{}: ${:.2} minimum due {} ({} days)", card, amount, r.due_date, r.days_until),
+                None => format!("{}: payment due {} ({} days)", card, r.due_date, r.days_until),
+            }
+        })
+        .collect();
+
+    let sections: Vec<Section> = [
+        Section { title: "Summary", lines: summary },
+        Section { title: "Top changes", lines: changes },
+        Section { title: "Alerts", lines: alerts },
+        Section { title: "Upcoming payments", lines: dues },
+    ]
+    .into_iter()
+    .filter(|section| !section.lines.is_empty())
+    .collect();
+
+    let (start, end) = (current.0.format("%Y-%m-%d").to_string(), current.1.format("%Y-%m-%d").to_string());
+    let heading = format!("Spending digest: {} to {}", start, end);
+    Some(Digest {
+        period,
+        markdown: render_markdown(&heading, &sections),
+        html: render_html(&heading, &sections),
+        start,
+        end,
+        total_spend,
+        previous_spend,
+    })
+}
+
+#[command]
+#[specta::specta]
+pub async fn generate_digest(app: AppHandle, period: DigestPeriod) -> Result<Digest, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+    build_digest(&store, period, Local::now().date_naive())
+        .ok_or_else(|| i18n::error(&locale, "error.no_statements"))
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use credit_analyzer_store::Store;
+
+// Emitted after the store is saved, so listeners re-fetching see the change
+pub const TRANSACTIONS_CHANGED: &str = "transactions://changed";
+pub const BUDGETS_UPDATED: &str = "budgets://updated";
+pub const ALERTS_TRIGGERED: &str = "alerts://triggered";
+pub const EXPORT_PROGRESS: &str = "export://progress";
+// Files queued by the OS; the frontend fetches them with take_opened_files
+pub const FILES_OPENED: &str = "files://opened";
+
+// Payloads only name what changed; views fetch the details they need
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TransactionsChanged {
+    pub statement_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BudgetsUpdated {
+    pub goal_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AlertsTriggered {
+    pub anomaly_ids: Vec<String>,
+    // Statements whose payment reminder just went out
+    pub reminder_statement_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExportProgress {
+    pub written: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FilesOpened {
+    pub count: usize,
+}
+
+// A missed event only leaves a view stale until its next fetch, so failures are logged
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        log!("Could not emit {}: {}", event, e);
+    }
+}
+
+pub fn transactions_changed(app: &AppHandle, statement_ids: Vec<u64>) {
+    emit(app, TRANSACTIONS_CHANGED, TransactionsChanged { statement_ids });
+}
+
+// For changes that touch every statement, like regrouping or switching stores
+pub fn all_transactions_changed(app: &AppHandle, store: &Store) {
+    transactions_changed(app, store.statements.iter().map(|s| s.id).collect());
+}
+
+pub fn budgets_updated(app: &AppHandle, goal_ids: Vec<u64>) {
+    emit(app, BUDGETS_UPDATED, BudgetsUpdated { goal_ids });
+}
+
+pub fn alerts_triggered(app: &AppHandle, anomaly_ids: Vec<String>, reminder_statement_ids: Vec<u64>) {
+    if anomaly_ids.is_empty() && reminder_statement_ids.is_empty() {
+        return;
+    }
+    emit(app, ALERTS_TRIGGERED, AlertsTriggered { anomaly_ids, reminder_statement_ids });
+}
+
+pub fn export_progress(app: &AppHandle, written: usize, total: usize) {
+    emit(app, EXPORT_PROGRESS, ExportProgress { written, total });
+}
+
+pub fn files_opened(app: &AppHandle, count: usize) {
+    emit(app, FILES_OPENED, FilesOpened { count });
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io::BufWriter;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::{i18n, transaction_category};
+use credit_analyzer_store::Store;
+
+use crate::category_detail::{in_period, valid_period};
+use crate::{events, settings, state};
+
+// How many rows go out between progress events; often enough for a progress bar to move on
+// a 500k-row history without flooding the frontend
+const PROGRESS_EVERY: usize = 10_000;
+
+fn write_error(e: &dyn std::fmt::Display) -> String {
+    format!("Could not write export: {}", e)
+}
+
+// Rows go straight from the store to a buffered file, one at a time, so memory stays flat
+// however many years are exported
+fn write_rows(app: &AppHandle, store: &Store, period: Option<&str>, file: File) -> Result<usize, String> {
+    let total = store.statements.iter()
+        .flat_map(|s| &s.transactions)
+        .filter(|t| in_period(&t.date, period))
+        .count();
+    let mut writer = csv::Writer::from_writer(BufWriter::new(file));
+    writer.write_record(["Statement", "Date", "Description", "Amount", "Category", "Tags", "Note"])
+        .map_err(|e| write_error(&e))?;
+
+    let mut written = 0;
+    for statement in &store.statements {
+        let id = statement.id.to_string();
+        for transaction in statement.transactions.iter().filter(|t| in_period(&t.date, period)) {
+            writer.write_record([
+                id.as_str(),
+                transaction.date.as_str(),
+                transaction.description.as_str(),
+                &format!("{:.2}", transaction.amount),
+                &transaction_category(transaction),
+                &transaction.tags.join(";"),
+                transaction.note.as_deref().unwrap_or(""),
+            ])
+            .map_err(|e| write_error(&e))?;
+            written += 1;
+            if written % PROGRESS_EVERY == 0 {
+                events::export_progress(app, written, total);
+            }
+        }
+    }
+    writer.flush().map_err(|e| write_error(&e))?;
+    events::export_progress(app, written, total);
+    Ok(written)
+}
+
+// Written beside the target and renamed into place at the end, so a failed export never
+// leaves half a file where the user asked for one
+fn write_transactions(app: &AppHandle, store: &Store, period: Option<&str>, file_path: &str) -> Result<usize, String> {
+    let partial = format!("{}.part", file_path);
+    let result = File::create(&partial)
+        .map_err(|e| write_error(&e))
+        .and_then(|file| write_rows(app, store, period, file))
+        .and_then(|written| std::fs::rename(&partial, file_path).map(|_| written).map_err(|e| write_error(&e)));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+// Writes every stored transaction as CSV, or only those in `period`, a month ("2024-01") or a
+// year ("2024"). Progress goes out as export://progress events. Returns how many rows were
+// written.
+#[command]
+#[specta::specta]
+pub async fn export_transactions(app: AppHandle, period: Option<String>, file_path: String) -> Result<usize, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if let Some(period) = period.as_deref().filter(|p| !valid_period(p)) {
+        return Err(i18n::text(&locale, "error.period_invalid", &[("period", period.to_string())]));
+    }
+
+    let store = state::read_store(&app).await?;
+    // File writes block, and the store can't move to a blocking task without copying it
+    let written = tokio::task::block_in_place(|| write_transactions(&app, &store, period.as_deref(), &file_path))?;
+    log!("Exported {} transactions", written);
+    Ok(written)
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::bank_profiles::BankProfile;
+use credit_analyzer_core::parse_mode::ParseMode;
+use credit_analyzer_core::statement_language::StatementLanguage;
+use credit_analyzer_core::{i18n, parse_date, parse_file, statement_source, ParseFailure};
+
+use crate::{analyze_statement, settings, state, AnalyzeError};
+
+// Extensions picked up inside dropped folders. Files dropped on their own are always tried.
+const STATEMENT_EXTENSIONS: &[&str] = &["csv", "pdf", "html", "htm", "sta", "mt940"];
+// A dropped home directory shouldn't turn into an afternoon of parsing
+const MAX_SCANNED_FILES: usize = 500;
+
+// One file found in a drop, as it would import
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ManifestEntry {
+    pub path: String,
+    // The bank or export format, or the issuer printed on a PDF
+    pub source: Option<String>,
+    // ISO dates of the earliest and latest rows
+    pub first_date: Option<String>,
+    pub last_date: Option<String>,
+    pub rows: usize,
+    // A statement from this path is already stored, and importing replaces it
+    pub already_imported: bool,
+    // Why the file can't be imported as it is; password-protected PDFs land here
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ImportManifest {
+    // Sorted by path
+    pub entries: Vec<ManifestEntry>,
+    // More than MAX_SCANNED_FILES were found and the rest were left out
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ImportFailure {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct BatchImportSummary {
+    pub imported: Vec<String>,
+    pub failed: Vec<ImportFailure>,
+}
+
+fn is_statement_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| STATEMENT_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(extension)))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('.'))
+}
+
+// Walks each dropped folder for statement files, without following symlinks so a link back up
+// the tree can't loop. Stops one past the limit, so the caller can tell it was reached.
+pub fn discover(dropped: &[String]) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut folders = Vec::new();
+    for path in dropped.iter().map(PathBuf::from) {
+        if path.is_dir() {
+            folders.push(path);
+        } else if path.is_file() {
+            found.push(path);
+        }
+    }
+
+    while let Some(folder) = folders.pop() {
+        let entries = match std::fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log!("Could not read folder {}: {}", folder.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else { continue };
+            if is_hidden(&path) {
+                continue;
+            }
+            if file_type.is_dir() {
+                folders.push(path);
+            } else if file_type.is_file() && is_statement_file(&path) {
+                found.push(path);
+            }
+        }
+        if found.len() > MAX_SCANNED_FILES {
+            break;
+        }
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn failure_message(failure: ParseFailure, locale: &str) -> String {
+    match failure {
+        ParseFailure::NeedsPassword => i18n::error(locale, "error.pdf_password_required"),
+        ParseFailure::WrongPassword => i18n::error(locale, "error.pdf_password_invalid"),
+        ParseFailure::AmbiguousColumns(ambiguity) => ambiguity.message(locale),
+        ParseFailure::Failed(reason) => i18n::text(locale, "error.parse_failed", &[("reason", reason)]),
+    }
+}
+
+fn manifest_entry(path: &Path, profiles: &[BankProfile], mode: ParseMode, language: StatementLanguage, locale: &str) -> ManifestEntry {
+    let path_text = path.to_string_lossy().into_owned();
+    let mut entry = ManifestEntry {
+        path: path_text.clone(),
+        source: None,
+        first_date: None,
+        last_date: None,
+        rows: 0,
+        already_imported: false,
+        error: None,
+    };
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            entry.error = Some(i18n::error(locale, "error.file_not_found"));
+            return entry;
+        }
+    };
+    let source = std::str::from_utf8(&bytes).ok().and_then(|content| statement_source(content, profiles));
+
+    match parse_file(&path_text, Some(bytes), None, None, profiles, mode, language) {
+        Ok(parsed) => {
+            let mut dates: Vec<_> = parsed.transactions.iter().filter_map(|t| parse_date(&t.date)).collect();
+            dates.sort();
+            entry.first_date = dates.first().map(|d| d.format("%Y-%m-%d").to_string());
+            entry.last_date = dates.last().map(|d| d.format("%Y-%m-%d").to_string());
+            entry.rows = parsed.transactions.len();
+            entry.source = source.or_else(|| parsed.meta.and_then(|meta| meta.issuer));
+        }
+        Err(e) => entry.error = Some(failure_message(ParseFailure::from(e), locale)),
+    }
+    entry
+}
+
+// Lists what dropping these files and folders would import, without importing any of it.
+// Folders are searched all the way down for statement files. Each file is parsed to show its
+// bank, dates and row count, so the user can pick which to import.
+#[command]
+#[specta::specta]
+pub async fn scan_dropped(app: AppHandle, paths: Vec<String>) -> Result<ImportManifest, String> {
+    let settings = settings::load_settings(&app)?;
+    let (profiles, stored): (Vec<BankProfile>, HashSet<String>) = {
+        let store = state::read_store(&app).await?;
+        (store.bank_profiles.clone(), store.statements.iter().map(|s| s.source_path.clone()).collect())
+    };
+
+    // Parsing is CPU-bound, so it runs off the async runtime
+    let mut manifest = tokio::task::spawn_blocking(move || {
+        let mut found = discover(&paths);
+        let truncated = found.len() > MAX_SCANNED_FILES;
+        found.truncate(MAX_SCANNED_FILES);
+        let entries = found.iter()
+            .map(|path| manifest_entry(path, &profiles, settings.parse_mode, settings.statement_language, &settings.locale))
+            .collect();
+        ImportManifest { entries, truncated }
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for entry in &mut manifest.entries {
+        entry.already_imported = stored.contains(&entry.path);
+    }
+    log!("Scanned {} dropped files{}", manifest.entries.len(), if manifest.truncated { ", more left out" } else { "" });
+    Ok(manifest)
+}
+
+// Imports the files the user kept from a scan_dropped manifest, one after another, exactly as
+// if each had been picked on its own. A file that fails doesn't stop the rest.
+#[command]
+#[specta::specta]
+pub async fn import_files(app: AppHandle, paths: Vec<String>) -> Result<BatchImportSummary, String> {
+    let mut summary = BatchImportSummary::default();
+    for path in paths {
+        match analyze_statement(app.clone(), path.clone(), None, None, None, None, None).await {
+            Ok(_) => summary.imported.push(path),
+            Err(e) => summary.failed.push(ImportFailure { path, reason: AnalyzeError::into_message(e) }),
+        }
+    }
+    log!("Imported {} dropped files, {} failed", summary.imported.len(), summary.failed.len());
+    Ok(summary)
+}
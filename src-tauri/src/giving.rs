@@ -0,0 +1,131 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::giving;
+use credit_analyzer_core::{i18n, parse_date};
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct Donation {
+    pub statement_id: u64,
+    pub index: usize,
+    // ISO date
+    pub date: String,
+    pub organization: String,
+    pub description: String,
+    pub amount: f64,
+    // Acknowledgement letters attached to the charge, which gifts above $250 need
+    pub receipt_path: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct GivingRecipient {
+    pub organization: String,
+    pub total: f64,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct GivingReport {
+    // Calendar year, which is the tax year for the receipts this backs up
+    pub year: i32,
+    pub total: f64,
+    // January first
+    pub monthly_totals: Vec<f64>,
+    // Most given first
+    pub recipients: Vec<GivingRecipient>,
+    // Oldest first
+    pub donations: Vec<Donation>,
+}
+
+pub fn build_report(store: &Store, year: i32) -> Option<GivingReport> {
+    let mut donations: Vec<(chrono::NaiveDate, Donation)> = store.statements.iter()
+        .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+        .filter(|(_, _, t)| giving::is_donation(t))
+        .filter_map(|(statement_id, index, t)| {
+            let date = parse_date(&t.date).filter(|d| d.year() == year)?;
+            Some((date, Donation {
+                statement_id,
+                index,
+                date: date.format("%Y-%m-%d").to_string(),
+                organization: giving::recipient(t),
+                description: t.description.clone(),
+                amount: t.amount,
+                receipt_path: t.receipt_path.clone(),
+                note: t.note.clone(),
+            }))
+        })
+        .collect();
+    if donations.is_empty() {
+        return None;
+    }
+    donations.sort_by_key(|(date, _)| *date);
+
+    let mut monthly_totals = vec![0.0; 12];
+    let mut recipients: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+    for (date, donation) in &donations {
+        monthly_totals[date.month0() as usize] += donation.amount;
+        let entry = recipients.entry(donation.organization.clone()).or_insert((0.0, 0));
+        entry.0 += donation.amount;
+        entry.1 += 1;
+    }
+    let mut recipients: Vec<GivingRecipient> = recipients.into_iter()
+        .map(|(organization, (total, count))| GivingRecipient { organization, total, count })
+        .collect();
+    recipients.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    Some(GivingReport {
+        year,
+        total: monthly_totals.iter().sum(),
+        monthly_totals,
+        recipients,
+        donations: donations.into_iter().map(|(_, donation)| donation).collect(),
+    })
+}
+
+// One row per gift and a closing total, the layout tax software and accountants import
+fn report_csv(report: &GivingReport) -> Result<Vec<u8>, String> {
+    let write_error = |e: &dyn std::fmt::Display| format!("Could not write giving report: {}", e);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["Date", "Organization", "Description", "Amount", "Receipt", "Note"])
+        .map_err(|e| write_error(&e))?;
+    for donation in &report.donations {
+        writer.write_record([
+            donation.date.as_str(),
+            donation.organization.as_str(),
+            donation.description.as_str(),
+            &format!("{:.2}", donation.amount),
+            donation.receipt_path.as_deref().unwrap_or(""),
+            donation.note.as_deref().unwrap_or(""),
+        ]).map_err(|e| write_error(&e))?;
+    }
+    writer.write_record(["", "", "Total", &format!("{:.2}", report.total), "", ""])
+        .map_err(|e| write_error(&e))?;
+    writer.into_inner().map_err(|e| write_error(&e))
+}
+
+#[command]
+#[specta::specta]
+pub async fn giving_report(app: AppHandle, year: i32) -> Result<GivingReport, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+    build_report(&store, year)
+        .ok_or_else(|| i18n::text(&locale, "error.no_donations", &[("year", year.to_string())]))
+}
+
+// Writes the year's gifts as CSV for tax records
+#[command]
+#[specta::specta]
+pub async fn export_giving_report(app: AppHandle, year: i32, file_path: String) -> Result<(), String> {
+    let report = giving_report(app, year).await?;
+    let content = report_csv(&report)?;
+    tokio::fs::write(&file_path, content).await
+        .map_err(|e| format!("Could not write giving report: {}", e))
+}
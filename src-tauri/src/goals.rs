@@ -0,0 +1,69 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::goals::{category_spend, validate, Goal, GoalKind};
+use credit_analyzer_core::{i18n, monthly_factor};
+use credit_analyzer_store::audit::AuditAction;
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+#[command]
+#[specta::specta]
+pub async fn add_goal(app: AppHandle, name: String, kind: GoalKind) -> Result<Goal, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    validate(&kind, &locale)?;
+
+    let mut store = state::write_store(&app).await?;
+
+    // Reduction goals need to know where spending stood when they were set
+    let baseline = match &kind {
+        GoalKind::ReduceCategory { category, .. } => {
+            if store.statements.is_empty() {
+                return Err(i18n::error(&locale, "error.goal_needs_history"));
+            }
+            let total: f64 = store.statements.iter()
+                .map(|s| category_spend(&s.transactions, category) * monthly_factor(&s.transactions))
+                .sum();
+            Some(total / store.statements.len() as f64)
+        }
+        _ => None,
+    };
+
+    let goal = Goal {
+        id: store.allocate_id(),
+        name,
+        kind,
+        baseline,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        progress: Vec::new(),
+    };
+    store.goals.push(goal.clone());
+    store.audit(AuditAction::RuleChange, format!("goal {}", goal.id), None::<&Goal>, Some(&goal));
+    store.save()?;
+    events::budgets_updated(&app, vec![goal.id]);
+    Ok(goal)
+}
+
+#[command]
+#[specta::specta]
+pub async fn list_goals(app: AppHandle) -> Result<Vec<Goal>, String> {
+    let store = state::read_store(&app).await?;
+    Ok(store.goals.clone())
+}
+
+#[command]
+#[specta::specta]
+pub async fn delete_goal(app: AppHandle, id: u64) -> Result<(), String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+
+    let Some(position) = store.goals.iter().position(|g| g.id == id) else {
+        return Err(i18n::text(&locale, "error.goal_not_found", &[("id", id.to_string())]));
+    };
+    let removed = store.goals.remove(position);
+    store.audit(AuditAction::RuleChange, format!("goal {}", id), Some(&removed), None::<&Goal>);
+    store.save()?;
+    events::budgets_updated(&app, vec![id]);
+    Ok(())
+}
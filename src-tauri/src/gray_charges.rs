@@ -0,0 +1,42 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::interner::CompactHistory;
+use credit_analyzer_core::subscriptions::{self, Subscription};
+use credit_analyzer_store::Store;
+
+use crate::state;
+
+// Active subscriptions across everything imported, as of the latest charge
+pub fn active_subscriptions(store: &Store) -> Vec<Subscription> {
+    let history = CompactHistory::from_statements(&store.statements);
+    match history.latest_date() {
+        Some(as_of) => subscriptions::detect_subscriptions(&history, as_of),
+        None => Vec::new(),
+    }
+}
+
+// Small recurring charges from unrecognized merchants the user hasn't vouched for yet
+pub fn unreviewed(store: &Store, subscriptions: &[Subscription]) -> Vec<Subscription> {
+    subscriptions.iter()
+        .filter(|s| subscriptions::is_gray_charge(s) && !store.reviewed_gray_charges.contains(&s.merchant))
+        .cloned()
+        .collect()
+}
+
+#[command]
+#[specta::specta]
+pub async fn list_gray_charges(app: AppHandle) -> Result<Vec<Subscription>, String> {
+    let store = state::read_store(&app).await?;
+    Ok(unreviewed(&store, &active_subscriptions(&store)))
+}
+
+// Marks a merchant's charges as recognized so they drop off the review list
+#[command]
+#[specta::specta]
+pub async fn review_gray_charge(app: AppHandle, merchant: String) -> Result<(), String> {
+    let mut store = state::write_store(&app).await?;
+    if !store.reviewed_gray_charges.contains(&merchant) {
+        store.reviewed_gray_charges.push(merchant);
+    }
+    store.save()
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_store::health::HealthIssue;
+
+use crate::events;
+use crate::snapshots::{self, SnapshotReason};
+use crate::state;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HealthReport {
+    pub statements: usize,
+    pub transactions: usize,
+    // Everything found, including what a repair then fixed
+    pub issues: Vec<HealthIssue>,
+    pub repaired: bool,
+}
+
+// Checks the stored data for inconsistencies: rollups that don't match the transactions they
+// sum, ids that collide, trash and reminders left pointing at deleted statements, and values
+// no total can be built from. With `repair` set, everything repairable is fixed after a
+// snapshot of the store as it was.
+#[command]
+#[specta::specta]
+pub async fn verify_data(app: AppHandle, repair: Option<bool>) -> Result<HealthReport, String> {
+    let repairing = repair.unwrap_or(false);
+    let mut store = state::write_store(&app).await?;
+    let mut issues = store.check_health();
+    let repaired = repairing && issues.iter().any(|i| i.repairable);
+    if repaired {
+        snapshots::take(&app, &store, SnapshotReason::Repair)?;
+        issues = store.repair();
+        store.save()?;
+        events::all_transactions_changed(&app, &store);
+    }
+    log!("Verified stored data: {} issues{}", issues.len(), if repaired { ", repaired" } else { "" });
+
+    Ok(HealthReport {
+        statements: store.statements.len(),
+        transactions: store.statements.iter().map(|s| s.transactions.len()).sum(),
+        issues,
+        repaired,
+    })
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::{i18n, parse_date, transaction_category};
+use credit_analyzer_store::Store;
+
+use crate::category_detail::{in_period, valid_period};
+use crate::settings;
+use crate::state;
+
+// One stored transaction as a ledger row, with where it lives so the row can be edited
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LedgerRow {
+    pub statement_id: u64,
+    pub index: usize,
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Ledger {
+    pub period: Option<String>,
+    pub total: f64,
+    // Newest first; undated rows last
+    pub rows: Vec<LedgerRow>,
+}
+
+pub fn ledger(store: &Store, period: Option<&str>) -> Ledger {
+    let mut rows: Vec<LedgerRow> = store.statements.iter()
+        .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+        .filter(|(_, _, t)| in_period(&t.date, period))
+        .map(|(statement_id, index, t)| LedgerRow {
+            statement_id,
+            index,
+            date: t.date.clone(),
+            description: t.description.clone(),
+            amount: t.amount,
+            category: transaction_category(t),
+            tags: t.tags.clone(),
+            note: t.note.clone(),
+        })
+        .collect();
+    rows.sort_by_key(|row| std::cmp::Reverse(parse_date(&row.date)));
+
+    Ledger {
+        period: period.map(str::to_string),
+        total: rows.iter().map(|row| row.amount).sum(),
+        rows,
+    }
+}
+
+// Every stored transaction, or those in `period`, a month ("2024-01") or a year ("2024")
+#[command]
+#[specta::specta]
+pub async fn get_ledger(app: AppHandle, period: Option<String>) -> Result<Ledger, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if let Some(period) = period.as_deref().filter(|p| !valid_period(p)) {
+        return Err(i18n::text(&locale, "error.period_invalid", &[("period", period.to_string())]));
+    }
+    let store = state::read_store(&app).await?;
+    Ok(ledger(&store, period.as_deref()))
+}
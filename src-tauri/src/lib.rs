@@ -0,0 +1,771 @@
+use serde::Serialize;
+use specta::Type;
+use tauri::{command, AppHandle};
+#[cfg(debug_assertions)]
+use specta_typescript::{BigIntExportBehavior, Typescript};
+
+// For log!, which stays quiet during private analyses
+#[macro_use]
+extern crate credit_analyzer_core;
+
+mod amazon;
+mod anomalies;
+mod annual;
+mod audit;
+mod background;
+mod bank_profiles;
+mod bulk;
+mod business;
+mod cards;
+mod category_detail;
+mod compare;
+mod config_bundle;
+mod custom_insights;
+mod dashboard;
+mod demo;
+mod digest;
+mod events;
+mod export;
+mod folder_import;
+mod giving;
+mod goals;
+mod gray_charges;
+mod health;
+mod ledger;
+mod logos;
+mod manual;
+mod merchant_detail;
+mod migration;
+mod notes;
+mod opened_files;
+mod originals;
+mod periods;
+mod recategorize;
+mod receipts;
+mod reminders;
+mod rollups;
+mod saved_analysis;
+mod screenshots;
+mod secrets;
+mod settings;
+mod settlement;
+#[cfg(desktop)]
+mod shortcut;
+mod simulation;
+mod snapshots;
+mod state;
+mod sync;
+mod trash;
+mod tray;
+mod updates;
+mod wallets;
+mod windows;
+
+use credit_analyzer_core::ocr::{self, OcrRow};
+use credit_analyzer_core::parse_mode::ParseMode;
+use credit_analyzer_core::pasted::{parse_text, TextHint};
+use credit_analyzer_core::settings::Settings;
+use credit_analyzer_core::sign_convention::SignConvention;
+use credit_analyzer_core::{analyze_transactions, i18n, insights, parse_file, privacy, records, AnalysisResult, ParseFailure, ParsedStatement, TransactionType, PARSER_VERSION};
+
+// Tagged so the frontend can tell a password prompt apart from a real failure
+#[derive(Debug, Serialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum AnalyzeError {
+    NeedsPassword { message: String },
+    WrongPassword { message: String },
+    // The frontend offers to set up a bank profile for the file
+    AmbiguousColumns { message: String },
+    Failed { message: String },
+}
+
+impl AnalyzeError {
+    // For reporting a failure where there's no prompting the user, as in batch imports
+    fn into_message(self) -> String {
+        match self {
+            AnalyzeError::NeedsPassword { message }
+            | AnalyzeError::WrongPassword { message }
+            | AnalyzeError::AmbiguousColumns { message }
+            | AnalyzeError::Failed { message } => message,
+        }
+    }
+}
+
+impl From<String> for AnalyzeError {
+    fn from(message: String) -> Self {
+        AnalyzeError::Failed { message }
+    }
+}
+
+// In privacy mode the statement is analyzed in memory only: it's compared against nothing,
+// nothing is cached, recorded or saved, and nothing is logged. `parse_mode` overrides the
+// setting for this import.
+#[command]
+#[specta::specta]
+async fn analyze_statement(app: AppHandle, file_path: String, password: Option<String>, force_reparse: Option<bool>, sign_convention: Option<SignConvention>, privacy_mode: Option<bool>, parse_mode: Option<ParseMode>) -> Result<AnalysisResult, AnalyzeError> {
+    let private = privacy_mode.unwrap_or(false);
+    let _private_scope = private.then(privacy::begin);
+    log!("Analyzing file: {}", file_path);
+    
+    let settings = settings::load_settings(&app)?;
+    let mode = parse_mode.unwrap_or(settings.parse_mode);
+    
+    // Check if file exists
+    if !tokio::fs::try_exists(&file_path).await.unwrap_or(false) {
+        return Err(i18n::error(&settings.locale, "error.file_not_found").into());
+    }
+    
+    // Unchanged files come from the parse cache unless the caller asks for a fresh parse or
+    // overrides how it's read
+    // Alert folders can't be read as a single file; they're hashed as nothing and never cached.
+    // Only parses without skipped rows are cached, so a cached parse holds in either mode.
+    let bytes = tokio::fs::read(&file_path).await.ok();
+    let content_hash = bytes.as_deref().map(credit_analyzer_store::content_hash);
+    let original = (settings.keep_originals && !private).then(|| bytes.clone()).flatten();
+    // A private analysis never opens the store the usual way, which can upgrade it and create
+    // its directory
+    let (cached, profiles) = if private {
+        (None, state::private_bank_profiles(&app).await?)
+    } else {
+        let store = state::read_store(&app).await?;
+        let cached = content_hash.as_deref()
+            .filter(|_| !force_reparse.unwrap_or(false) && sign_convention.is_none())
+            .and_then(|hash| store.cached_parse(hash, PARSER_VERSION))
+            .map(|cached| ParsedStatement {
+                transactions: cached.transactions.clone(),
+                meta: cached.meta.clone(),
+                sign_convention: cached.sign_convention.clone(),
+                warnings: Vec::new(),
+            });
+        (cached, store.bank_profiles.clone())
+    };
+    let from_cache = cached.is_some();
+    let parsed = match cached {
+        Some(parsed) => {
+            log!("Using cached parse of {}", file_path);
+            Ok(parsed)
+        }
+        // Parsing is CPU-bound, so it runs off the async runtime
+        None => {
+            let path = file_path.clone();
+            let password = password.clone();
+            let language = settings.statement_language;
+            tokio::task::spawn_blocking(move || parse_file(&path, bytes, password.as_deref(), sign_convention, &profiles, mode, language).map_err(ParseFailure::from))
+                .await
+                .unwrap_or_else(|e| Err(ParseFailure::Failed(e.to_string())))
+        }
+    };
+    
+    let analysis = finish_analysis(&app, &settings, &file_path, parsed, content_hash.clone(), from_cache, private).await?;
+    // Kept only once the import succeeds, so every original has a statement to re-import as
+    if let (Some(bytes), Some(hash)) = (original, content_hash.as_deref()) {
+        if let Err(e) = originals::keep(&app, &settings, &file_path, hash, bytes).await {
+            log!("Could not keep original: {}", e);
+        }
+    }
+    Ok(analysis)
+}
+
+// Everything after parsing, shared by files and pasted text
+async fn finish_analysis(app: &AppHandle, settings: &Settings, file_path: &str, parsed: Result<ParsedStatement, ParseFailure>, content_hash: Option<String>, from_cache: bool, private: bool) -> Result<AnalysisResult, AnalyzeError> {
+    let ParsedStatement { mut transactions, meta, sign_convention, warnings } = match parsed {
+        Ok(parsed) => parsed,
+        // Password problems need the user, so the frontend can prompt and retry
+        Err(ParseFailure::NeedsPassword) => {
+            return Err(AnalyzeError::NeedsPassword {
+                message: i18n::error(&settings.locale, "error.pdf_password_required"),
+            });
+        }
+        Err(ParseFailure::WrongPassword) => {
+            return Err(AnalyzeError::WrongPassword {
+                message: i18n::error(&settings.locale, "error.pdf_password_invalid"),
+            });
+        }
+        Err(ParseFailure::AmbiguousColumns(ambiguity)) => {
+            return Err(AnalyzeError::AmbiguousColumns {
+                message: ambiguity.message(&settings.locale),
+            });
+        }
+        Err(ParseFailure::Failed(e)) => {
+            log!("File parsing error: {}", e);
+            return Err(i18n::text(&settings.locale, "error.parse_failed", &[("reason", e)]).into());
+        }
+    };
+    
+    // Payments and refunds are recognised so they can be left out of spending
+    let parsed_count = transactions.len();
+    transactions.retain(|t| t.transaction_type == TransactionType::Debit);
+    if transactions.len() < parsed_count {
+        log!("Set aside {} payments and credits", parsed_count - transactions.len());
+    }
+    
+    if transactions.is_empty() {
+        return Err(i18n::error(&settings.locale, "error.no_transactions").into());
+    }
+    
+    let known = credit_analyzer_core::merchants::enrich_transactions(&mut transactions);
+    log!("Recognized the merchant on {} of {} transactions", known, transactions.len());
+    
+    // A private analysis only borrows the user's bank profiles, so someone else's statement
+    // stays out of their history
+    if private {
+        let mut analysis = analyze_transactions(transactions.clone(), file_path, &[], settings).await;
+        analysis.statement_meta = meta;
+        analysis.sign_convention = sign_convention;
+        analysis.parse_warnings = warnings;
+        if let Ok(definitions) = custom_insights::definitions_path(app).and_then(|path| credit_analyzer_core::custom_insights::load_definitions(&path)) {
+            analysis.insights.extend(credit_analyzer_core::custom_insights::evaluate_all(&definitions, &transactions));
+        }
+        insights::rank(&mut analysis.insights);
+        i18n::localize_insights(&mut analysis.insights, &settings.locale);
+        return Ok(analysis);
+    }
+    
+    // Earlier imports give the analysis something to compare against. The lock is held until
+    // the import is saved, so a concurrent import or edit can't be lost.
+    let mut store = state::write_store(app).await?;
+    if let (Some(hash), false, true) = (&content_hash, from_cache, warnings.is_empty()) {
+        store.cache_parse(hash, PARSER_VERSION, &transactions, meta.as_ref(), sign_convention.as_ref());
+    }
+    
+    let enriched = credit_analyzer_core::amazon::enrich_transactions(&mut transactions, &store.amazon_orders);
+    if enriched > 0 {
+        log!("Matched {} Amazon charges to orders", enriched);
+    }
+    let linked = credit_analyzer_core::wallets::link_card_charges(&mut transactions, &store.wallet_payments);
+    if linked > 0 {
+        log!("Linked {} card charges to PayPal/Venmo payments", linked);
+    }
+    
+    // Analyze real transactions
+    let history = store.history_excluding(file_path);
+    let mut analysis = analyze_transactions(transactions.clone(), file_path, &history, settings).await;
+    analysis.statement_meta = meta.clone();
+    analysis.sign_convention = sign_convention;
+    analysis.parse_warnings = warnings;
+    
+    let statement_id = store.record_statement(file_path, transactions, meta);
+    analysis.records = records::lifetime_records(&store.statements);
+    
+    // Goals are tracked per statement so progress builds up over imports
+    if let Some(statement) = store.statement(statement_id).cloned() {
+        analysis.goals = credit_analyzer_core::goals::evaluate_goals(&mut store.goals, statement_id, &statement.transactions);
+        analysis.insights.extend(credit_analyzer_core::goals::goal_insights(&analysis.goals));
+    }
+    
+    // User-defined insights from custom_insights.json in the config directory
+    match custom_insights::definitions_path(app).and_then(|path| credit_analyzer_core::custom_insights::load_definitions(&path)) {
+        Ok(definitions) => {
+            if let Some(statement) = store.statement(statement_id) {
+                analysis.insights.extend(credit_analyzer_core::custom_insights::evaluate_all(&definitions, &statement.transactions));
+            }
+        }
+        Err(e) => log!("Failed to load custom insights: {}", e),
+    }
+    insights::rank(&mut analysis.insights);
+    
+    match store.save() {
+        Ok(()) => {
+            events::transactions_changed(app, vec![statement_id]);
+            if !analysis.goals.is_empty() {
+                events::budgets_updated(app, analysis.goals.iter().map(|g| g.goal_id).collect());
+            }
+            events::alerts_triggered(app, anomalies::unreviewed(&store).into_iter().map(|a| a.id).collect(), Vec::new());
+        }
+        Err(e) => log!("Failed to save statement history: {}", e),
+    }
+    drop(store);
+    // A new closing date moves statement-cycle periods
+    if let Err(e) = periods::sync(app).await {
+        log!("Could not apply period boundaries: {}", e);
+    }
+    // A freshly imported statement may already be inside the reminder window
+    if let Err(e) = reminders::notify_due(app).await {
+        log!("Payment reminder check failed: {}", e);
+    }
+    
+    // Private analyses never go online, so only imported statements get logos
+    if settings.merchant_logos {
+        logos::attach_logos(app, &mut analysis.top_merchants).await;
+    }
+    
+    i18n::localize_insights(&mut analysis.insights, &settings.locale);
+    Ok(analysis)
+}
+
+// Statement text pasted in, e.g. a transaction table copied from the bank's website. It's saved
+// like an imported file, under a name derived from the text so pasting it again replaces it.
+#[command]
+#[specta::specta]
+async fn analyze_text(app: AppHandle, content: String, hint: Option<TextHint>, sign_convention: Option<SignConvention>, privacy_mode: Option<bool>, parse_mode: Option<ParseMode>) -> Result<AnalysisResult, AnalyzeError> {
+    let private = privacy_mode.unwrap_or(false);
+    let _private_scope = private.then(privacy::begin);
+    let settings = settings::load_settings(&app)?;
+    let profiles = if private {
+        state::private_bank_profiles(&app).await?
+    } else {
+        state::read_store(&app).await?.bank_profiles.clone()
+    };
+    let mode = parse_mode.unwrap_or(settings.parse_mode);
+    
+    let file_path = format!("pasted-{}.csv", credit_analyzer_store::content_hash(content.as_bytes()));
+    log!("Analyzing pasted text as {}", file_path);
+    
+    let language = settings.statement_language;
+    let parsed = tokio::task::spawn_blocking(move || parse_text(&content, hint, sign_convention, &profiles, mode, language).map_err(ParseFailure::from))
+        .await
+        .unwrap_or_else(|e| Err(ParseFailure::Failed(e.to_string())));
+    // Pasted text isn't re-read from disk, so there's nothing for the parse cache to save
+    finish_analysis(&app, &settings, &file_path, parsed, None, false, private).await
+}
+
+// Screenshot rows after the user has checked them against scan_screenshot's reading. The
+// statement is keyed by the image, so importing the same screenshot again replaces it.
+#[command]
+#[specta::specta]
+async fn import_screenshot(app: AppHandle, file_path: String, rows: Vec<OcrRow>, privacy_mode: Option<bool>) -> Result<AnalysisResult, AnalyzeError> {
+    let private = privacy_mode.unwrap_or(false);
+    let _private_scope = private.then(privacy::begin);
+    let settings = settings::load_settings(&app)?;
+    log!("Importing {} rows from screenshot {}", rows.len(), file_path);
+    
+    let parsed = Ok(ParsedStatement { transactions: ocr::to_transactions(&rows), meta: None, sign_convention: None, warnings: Vec::new() });
+    finish_analysis(&app, &settings, &file_path, parsed, None, false, private).await
+}
+
+// Command signatures and every type crossing IPC, so the frontend's copy can't drift
+fn specta_builder() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new()
+        .commands(tauri_specta::collect_commands![
+            analyze_statement,
+            analyze_text,
+            screenshots::scan_screenshot,
+            import_screenshot,
+            settings::get_settings,
+            settings::update_settings,
+            secrets::set_secret,
+            secrets::clear_secret,
+            secrets::list_secret_status,
+            simulation::simulate_cut,
+            simulation::simulate_discretionary,
+            compare::compare_statements,
+            annual::annual_summary,
+            merchant_detail::get_merchant_detail,
+            category_detail::get_category_detail,
+            digest::generate_digest,
+            saved_analysis::export_analysis,
+            saved_analysis::open_saved_analysis,
+            demo::load_demo_data,
+            demo::exit_demo_mode,
+            bank_profiles::suggest_bank_profile,
+            bank_profiles::save_bank_profile,
+            bank_profiles::list_bank_profiles,
+            bank_profiles::export_bank_profile,
+            bank_profiles::import_bank_profile,
+            goals::add_goal,
+            goals::list_goals,
+            goals::delete_goal,
+            cards::save_card,
+            cards::list_cards,
+            cards::delete_card,
+            cards::get_benefit_report,
+            cards::get_break_even,
+            custom_insights::get_custom_insights,
+            custom_insights::save_custom_insights,
+            amazon::import_amazon_orders,
+            wallets::import_wallet_activity,
+            migration::import_history,
+            reminders::list_payment_reminders,
+            dashboard::get_dashboard,
+            anomalies::review_anomaly,
+            gray_charges::list_gray_charges,
+            gray_charges::review_gray_charge,
+            rollups::rebuild_rollups,
+            recategorize::recategorize_all,
+            rollups::set_transaction_category,
+            bulk::bulk_edit,
+            bulk::undo_bulk_edit,
+            audit::get_audit_log,
+            manual::add_transaction,
+            manual::edit_transaction,
+            trash::delete_transaction,
+            trash::list_trash,
+            trash::restore_transaction,
+            notes::add_note,
+            notes::attach_receipt,
+            notes::set_tags,
+            notes::set_shared,
+            receipts::scan_receipt,
+            receipts::split_transaction,
+            giving::giving_report,
+            giving::export_giving_report,
+            business::export_business_expenses,
+            settlement::settle_shared_expenses,
+            settlement::export_settlement,
+            export::export_transactions,
+            originals::list_originals,
+            originals::reimport_originals,
+            snapshots::create_snapshot,
+            snapshots::list_snapshots,
+            snapshots::restore_snapshot,
+            snapshots::delete_snapshot,
+            health::verify_data,
+            opened_files::take_opened_files,
+            folder_import::scan_dropped,
+            folder_import::import_files,
+            tray::get_quick_stats,
+            ledger::get_ledger,
+            windows::open_window,
+            windows::list_windows,
+            windows::get_window_view,
+            windows::close_window,
+            updates::get_version_info,
+            updates::check_update_compatibility,
+            updates::prepare_update,
+            config_bundle::export_config,
+            config_bundle::import_config,
+            sync::create_sync_key,
+            sync::sync_now,
+        ])
+}
+
+// u64 ids and counts never get near 2^53
+fn typescript() -> Typescript {
+    Typescript::default().bigint(BigIntExportBehavior::Number)
+}
+
+pub fn run() {
+    let bindings = specta_builder();
+    // Regenerated on every debug run and committed; bindings_are_current catches a stale copy
+    #[cfg(debug_assertions)]
+    bindings
+        .export(typescript(), "../src/bindings.ts")
+        .expect("failed to export TypeScript bindings");
+    
+    let builder = tauri::Builder::default();
+    // Registered first, so a second launch, from a statement double-clicked while the app is
+    // open, hands its files to this one and exits before setting anything up
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+        opened_files::open_args(app, args, std::path::Path::new(&cwd));
+        opened_files::focus(app);
+    }));
+    #[cfg(desktop)]
+    let builder = builder.plugin(shortcut::plugin()).plugin(background::autostart_plugin());
+    builder
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(state::AppState::default())
+        .manage(opened_files::OpenedFiles::default())
+        .manage(windows::Windows::default())
+        .on_window_event(background::on_window_event)
+        .setup(|app| {
+            if let Err(e) = tauri::async_runtime::block_on(periods::sync(app.handle())) {
+                log!("Could not apply period boundaries: {}", e);
+            }
+            reminders::start(app.handle().clone());
+            background::start(app.handle().clone());
+            #[cfg(feature = "sync")]
+            sync::start(app.handle().clone());
+            let tray = tray::build(app.handle());
+            if let Err(e) = &tray {
+                log!("Could not add tray icon: {}", e);
+            }
+            // The main window starts hidden; a launch at login leaves it in the tray, unless
+            // there's no tray to bring it back from
+            if !background::launched_in_background() || tray.is_err() {
+                opened_files::focus(app.handle());
+            }
+            #[cfg(desktop)]
+            if let Err(e) = settings::load_settings(app.handle()).and_then(|settings| shortcut::apply(app.handle(), &settings)) {
+                log!("Could not register import shortcut: {}", e);
+            }
+            #[cfg(desktop)]
+            if let Err(e) = settings::load_settings(app.handle()).and_then(|settings| background::apply_autostart(app.handle(), &settings)) {
+                log!("Could not apply launch at login: {}", e);
+            }
+            let cwd = std::env::current_dir().unwrap_or_default();
+            opened_files::open_args(app.handle(), std::env::args().collect(), &cwd);
+            Ok(())
+        })
+        .invoke_handler(bindings.invoke_handler())
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(opened_files::on_run_event);
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use credit_analyzer_core::cards::Card;
+    use credit_analyzer_core::goals::Goal;
+    use credit_analyzer_core::settings::{Settings, UpdateChannel};
+    use credit_analyzer_core::synthetic;
+    use credit_analyzer_store::sync::SyncLedger;
+    use credit_analyzer_store::{Store, SCHEMA_VERSION};
+    use proptest::prelude::*;
+
+    use crate::config_bundle::{self, ConfigBundle, ConfigImportMode, StoreConfig};
+    use crate::{anomalies, state};
+    use crate::updates::{self, Compatibility, UpdateCandidate};
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 7, 15).unwrap()
+    }
+
+    // Every statement the profile generates, imported in order
+    fn profile_store(profile: &synthetic::SyntheticProfile) -> Store {
+        let mut store = Store::default();
+        for (source_path, transactions) in synthetic::generate(profile, today()) {
+            store.record_statement(&source_path, transactions, None);
+        }
+        store
+    }
+
+    fn synthetic_store(seed: u64) -> Store {
+        profile_store(&synthetic::SyntheticProfile { seed: Some(seed), ..Default::default() })
+    }
+
+    proptest! {
+        #[test]
+        fn synthetic_fraud_event_is_flagged(seed in 1u64..u64::MAX) {
+            let store = profile_store(&synthetic::SyntheticProfile { seed: Some(seed), fraud_event: true, ..Default::default() });
+            let anomalies = anomalies::detect_anomalies(&store);
+            prop_assert!(anomalies.iter().any(|a| a.description == "AMAZON MKTPLACE"), "{:?}", anomalies);
+        }
+
+        #[test]
+        fn recategorize_commits_exactly_its_preview(seed in 1u64..u64::MAX, stale_every in 2usize..7) {
+            let mut store = synthetic_store(seed);
+            // Stand-ins for an older rule set and a category the user picked by hand
+            let transactions = store.statements.iter_mut().flat_map(|s| s.transactions.iter_mut());
+            for (i, tx) in transactions.enumerate() {
+                if i == 0 {
+                    tx.category = Some("Travel".to_string());
+                    tx.rule_category = Some("Other".to_string());
+                } else if i % stale_every == 0 {
+                    tx.rule_category = Some("Stale".to_string());
+                }
+            }
+
+            let preview = store.category_changes();
+            prop_assert!(preview.iter().all(|c| c.category_from == "Stale"));
+            let committed = store.recategorize();
+            prop_assert_eq!(preview.len(), committed.len());
+            prop_assert!(store.category_changes().is_empty());
+            prop_assert_eq!(store.statements[0].transactions[0].category.as_deref(), Some("Travel"));
+        }
+
+        #[test]
+        fn repair_leaves_a_healthy_store(seed in 1u64..u64::MAX, damage_every in 2usize..7) {
+            let mut store = synthetic_store(seed);
+            prop_assert!(store.check_health().is_empty());
+
+            // Edits that skipped the rollups, and tags written around set_tags
+            let transactions = store.statements.iter_mut().flat_map(|s| s.transactions.iter_mut());
+            for tx in transactions.step_by(damage_every) {
+                tx.amount += 1.0;
+                tx.tags = vec![" Work".to_string(), "work".to_string()];
+            }
+            store.reminded_statements.push(u64::MAX);
+            prop_assert!(!store.check_health().is_empty());
+
+            let found = store.repair();
+            prop_assert!(found.iter().all(|i| i.repairable));
+            prop_assert!(store.check_health().is_empty());
+        }
+
+        #[test]
+        fn reimport_keeps_user_edits(seed in 1u64..u64::MAX) {
+            let profile = synthetic::SyntheticProfile { seed: Some(seed), ..Default::default() };
+            let (source_path, transactions) = synthetic::generate(&profile, today()).into_iter().next().unwrap();
+            prop_assume!(transactions.len() >= 2);
+            prop_assume!((&transactions[0].date, &transactions[0].description) != (&transactions[1].date, &transactions[1].description));
+            let mut store = Store::default();
+            store.record_statement(&source_path, transactions.clone(), None);
+
+            // A category picked by hand and a corrected description, as the commands leave them
+            let edited = &mut store.statements[0].transactions;
+            edited[0].category = Some("Travel".to_string());
+            edited[1].description = "CORRECTED MERCHANT".to_string();
+            store.record_statement(&source_path, transactions.clone(), None);
+
+            let reimported = &store.statements[0].transactions;
+            prop_assert_eq!(reimported.len(), transactions.len());
+            prop_assert_eq!(reimported[0].category.as_deref(), Some("Travel"));
+            prop_assert_eq!(reimported[1].description.as_str(), "CORRECTED MERCHANT");
+        }
+
+        #[test]
+        fn update_plan_runs_each_migration_once(data in 0u32..6, target in 0u32..6, oldest in 0u32..6) {
+            let candidate = UpdateCandidate {
+                version: "9.9.9".to_string(),
+                channel: UpdateChannel::Stable,
+                schema_version: target,
+                min_schema_version: oldest,
+                migrations: Vec::new(),
+            };
+            let report = updates::check(data, UpdateChannel::Stable, &candidate, "en");
+            if target < data || data < oldest {
+                prop_assert_eq!(report.compatibility, Compatibility::Blocked);
+                prop_assert!(report.reason.is_some());
+            } else {
+                let steps: Vec<(u32, u32)> = report.plan.iter().map(|step| (step.from, step.to)).collect();
+                prop_assert_eq!(steps, (data..target).map(|v| (v, v + 1)).collect::<Vec<_>>());
+                prop_assert_eq!(report.compatibility == Compatibility::Compatible, data == target);
+            }
+
+            let beta = UpdateCandidate { channel: UpdateChannel::Beta, ..candidate };
+            prop_assert_eq!(updates::check(data, UpdateChannel::Stable, &beta, "en").compatibility, Compatibility::Blocked);
+        }
+
+        #[test]
+        fn sync_converges_after_edits_on_both_devices(seed in 1u64..u64::MAX, edited in 0usize..6, deleted in 0usize..6) {
+            let mut desktop = synthetic_store(seed);
+            let mut laptop = Store::default();
+            let mut desktop_ledger = SyncLedger { device_id: "desktop".to_string(), ..Default::default() };
+            let mut laptop_ledger = SyncLedger { device_id: "laptop".to_string(), ..Default::default() };
+
+            // One round: each device notes its own changes, then takes the other's
+            let mut sync = |desktop: &mut Store, laptop: &mut Store, now: i64| {
+                desktop.note_local_changes(&mut desktop_ledger, now);
+                laptop.note_local_changes(&mut laptop_ledger, now);
+                let from_desktop = desktop.sync_records(&desktop_ledger);
+                laptop.apply_sync_records(&mut laptop_ledger, from_desktop);
+                let from_laptop = laptop.sync_records(&laptop_ledger);
+                desktop.apply_sync_records(&mut desktop_ledger, from_laptop);
+            };
+            sync(&mut desktop, &mut laptop, 1);
+            prop_assert_eq!(laptop.statements.len(), desktop.statements.len());
+
+            // The laptop recategorizes one statement while the desktop deletes another
+            let statements = desktop.statements.len();
+            let edited = edited % statements;
+            let deleted = (edited + 1 + deleted % (statements - 1).max(1)) % statements;
+            let edited_id = desktop.statements[edited].sync_id.clone();
+            let deleted_id = desktop.statements[deleted].sync_id.clone();
+            let on_laptop = laptop.statements.iter_mut().find(|s| s.sync_id == edited_id).unwrap();
+            for tx in &mut on_laptop.transactions {
+                tx.category = Some("Travel".to_string());
+            }
+            desktop.statements.remove(deleted);
+            sync(&mut desktop, &mut laptop, 2);
+
+            let contents = |store: &Store| {
+                let mut statements: Vec<String> = store.statements.iter()
+                    .map(|s| format!("{} {:?}", s.sync_id, s.transactions.iter().map(|t| t.category.clone()).collect::<Vec<_>>()))
+                    .collect();
+                statements.sort();
+                statements
+            };
+            prop_assert_eq!(contents(&desktop), contents(&laptop));
+            prop_assert!(laptop.statements.iter().all(|s| s.sync_id != deleted_id) || deleted == edited);
+            prop_assert!(desktop.check_health().iter().all(|i| i.kind != credit_analyzer_store::health::IssueKind::RollupMismatch));
+        }
+    }
+
+    #[test]
+    fn bindings_are_current() {
+        let exported = std::env::temp_dir().join(format!("credit-analyzer-bindings-{}.ts", std::process::id()));
+        crate::specta_builder().export(crate::typescript(), &exported).unwrap();
+        let current = std::fs::read_to_string(&exported).unwrap();
+        std::fs::remove_file(&exported).unwrap();
+        let committed = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../src/bindings.ts")).unwrap();
+        assert!(committed == current, "src/bindings.ts is out of date; a debug run of the app regenerates it");
+    }
+
+    #[test]
+    fn failed_config_import_leaves_the_store_as_it_was() {
+        let card: Card = serde_json::from_value(serde_json::json!({ "id": 0, "name": "Everyday", "annual_fee": 95.0 })).unwrap();
+        let goal: Goal = serde_json::from_value(serde_json::json!({
+            "id": 0,
+            "name": "Dining",
+            "kind": { "kind": "category_under", "category": "Dining", "limit": 300.0 },
+            "baseline": null,
+            "created_at": "2024-07-01",
+        })).unwrap();
+        let mut store = synthetic_store(11);
+        config_bundle::apply_to_store(&mut store, vec![goal.clone()], Vec::new(), vec![card.clone()], ConfigImportMode::Merge);
+        let configuration = |store: &Store| serde_json::json!({
+            "goals": store.goals,
+            "bank_profiles": store.bank_profiles,
+            "cards": store.cards,
+            "audit_entries": store.audit_log.len(),
+        });
+        let before = configuration(&store);
+
+        // One bad item anywhere in the bundle is caught before anything is written
+        let bundle = ConfigBundle {
+            format: "credit_analyzer.config".to_string(),
+            version: 1,
+            app_version: "0.0.0".to_string(),
+            exported_at: "2024-07-15T00:00:00Z".to_string(),
+            settings: Settings::default(),
+            custom_insights: Vec::new(),
+            goals: vec![goal],
+            bank_profiles: Vec::new(),
+            cards: vec![card.clone(), Card { name: " ".to_string(), ..card.clone() }],
+        };
+        assert!(config_bundle::validate(&bundle, "en").is_err());
+
+        // A replace that can't be saved is put back, audit entry and all
+        let saved = StoreConfig::of(&store);
+        config_bundle::apply_to_store(&mut store, Vec::new(), Vec::new(), vec![card], ConfigImportMode::Replace);
+        assert!(store.goals.is_empty());
+        assert_ne!(configuration(&store), before);
+        saved.restore(&mut store);
+        assert_eq!(configuration(&store), before);
+    }
+
+    #[test]
+    fn loaded_store_reports_the_current_schema() {
+        let path = std::env::temp_dir().join(format!("credit-analyzer-empty-{}", std::process::id())).join("store.json");
+        let mut empty = state::read_only(&path).unwrap();
+        let mut snapshotted = false;
+        empty.upgrade(|_| snapshotted = true);
+        assert_eq!(empty.schema_version, SCHEMA_VERSION);
+        assert!(!snapshotted);
+
+        // An update that needs the current format can be installed over a new install
+        let candidate = UpdateCandidate {
+            version: "9.9.9".to_string(),
+            channel: UpdateChannel::Stable,
+            schema_version: SCHEMA_VERSION,
+            min_schema_version: SCHEMA_VERSION,
+            migrations: Vec::new(),
+        };
+        let report = updates::check(empty.schema_version, UpdateChannel::Stable, &candidate, "en");
+        assert_eq!(report.compatibility, Compatibility::Compatible);
+
+        // Data from before versioning is handed over for its snapshot before it's converted
+        let mut old = synthetic_store(7);
+        old.schema_version = 0;
+        let mut snapshotted = false;
+        old.upgrade(|store| snapshotted = store.schema_version == 0);
+        assert_eq!(old.schema_version, SCHEMA_VERSION);
+        assert!(snapshotted);
+    }
+
+    #[test]
+    fn private_reads_leave_no_trace() {
+        let dir = std::env::temp_dir().join(format!("credit-analyzer-private-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("store.json");
+        assert!(state::read_only(&path).unwrap().statements.is_empty());
+        assert!(!dir.exists());
+
+        // A store from before schema versions, which opening it the usual way would upgrade
+        let mut old = synthetic_store(7);
+        old.schema_version = 0;
+        std::fs::create_dir_all(&dir).unwrap();
+        old.save(&path).unwrap();
+        let written = std::fs::read(&path).unwrap();
+
+        let store = state::read_only(&path).unwrap();
+        assert_eq!(store.schema_version, 0);
+        assert_eq!(std::fs::read(&path).unwrap(), written);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use credit_analyzer_core::MerchantTotal;
+
+const CACHE_DIR: &str = "merchant_logos";
+const INDEX_FILE: &str = "index.json";
+// Company name to domain, for merchants the knowledge base doesn't know
+const SUGGEST_URL: &str = "https://autocomplete.clearbit.com/v1/companies/suggest";
+const FAVICON_URL: &str = "https://www.google.com/s2/favicons";
+const LOGO_SIZE: &str = "64";
+// A slow lookup shouldn't hold up the analysis it decorates
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Keyed by merchant name. Merchants nothing was found for are kept too, so they aren't looked
+// up again; failed requests aren't, so they're retried next time.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LogoIndex {
+    merchants: BTreeMap<String, CachedLogo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedLogo {
+    domain: Option<String>,
+    logo_path: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Suggestion {
+    domain: String,
+}
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_cache_dir()
+        .map_err(|e| format!("Could not resolve cache directory: {}", e))?
+        .join(CACHE_DIR);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Could not create logo cache: {}", e))?;
+    Ok(dir)
+}
+
+// A damaged index only costs a fresh lookup, so it's read as empty
+fn load_index(dir: &Path) -> LogoIndex {
+    fs::read_to_string(dir.join(INDEX_FILE)).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(dir: &Path, index: &LogoIndex) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Could not serialize logo cache: {}", e))?;
+    fs::write(dir.join(INDEX_FILE), content)
+        .map_err(|e| format!("Could not write logo cache: {}", e))
+}
+
+// Domains become file names, so anything that isn't a plain hostname is turned away
+fn is_hostname(domain: &str) -> bool {
+    !domain.is_empty() && !domain.starts_with('.')
+        && domain.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+async fn find_domain(client: &reqwest::Client, merchant: &str) -> Result<Option<String>, String> {
+    let suggestions: Vec<Suggestion> = client.get(SUGGEST_URL)
+        .query(&[("query", merchant)])
+        .send().await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Domain lookup failed: {}", e))?
+        .json().await
+        .map_err(|e| format!("Domain lookup failed: {}", e))?;
+    Ok(suggestions.into_iter().map(|s| s.domain).find(|domain| is_hostname(domain)))
+}
+
+async fn fetch_logo(client: &reqwest::Client, domain: &str, dir: &Path) -> Result<String, String> {
+    let bytes = client.get(FAVICON_URL)
+        .query(&[("domain", domain), ("sz", LOGO_SIZE)])
+        .send().await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Logo download failed: {}", e))?
+        .bytes().await
+        .map_err(|e| format!("Logo download failed: {}", e))?;
+    let path = dir.join(format!("{}.png", domain));
+    tokio::fs::write(&path, &bytes).await
+        .map_err(|e| format!("Could not cache logo: {}", e))?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+async fn resolve(client: &reqwest::Client, merchant: &MerchantTotal, dir: &Path) -> Result<CachedLogo, String> {
+    let domain = match merchant.domain.clone().filter(|domain| is_hostname(domain)) {
+        Some(domain) => Some(domain),
+        None => find_domain(client, &merchant.merchant).await?,
+    };
+    let logo_path = match &domain {
+        Some(domain) => Some(fetch_logo(client, domain, dir).await?),
+        None => None,
+    };
+    Ok(CachedLogo { domain, logo_path })
+}
+
+// Fills in domains and logos from the on-disk cache, looking up whatever it doesn't have yet.
+// Only called when the merchant logos setting is on; any failure leaves the merchant as it was.
+pub async fn attach_logos(app: &AppHandle, merchants: &mut [MerchantTotal]) {
+    let dir = match cache_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log!("{}", e);
+            return;
+        }
+    };
+    let mut index = load_index(&dir);
+    let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            log!("Could not start logo lookups: {}", e);
+            return;
+        }
+    };
+
+    let mut fetched = false;
+    for merchant in merchants.iter_mut() {
+        let cached = match index.merchants.get(&merchant.merchant) {
+            // A logo deleted from the cache is downloaded again
+            Some(cached) if cached.logo_path.as_deref().is_none_or(|path| Path::new(path).exists()) => cached.clone(),
+            _ => match resolve(&client, merchant, &dir).await {
+                Ok(cached) => {
+                    index.merchants.insert(merchant.merchant.clone(), cached.clone());
+                    fetched = true;
+                    cached
+                }
+                Err(e) => {
+                    log!("Logo lookup for {} failed: {}", merchant.merchant, e);
+                    continue;
+                }
+            },
+        };
+        merchant.domain = merchant.domain.take().or(cached.domain);
+        merchant.logo_path = cached.logo_path;
+    }
+
+    if fetched {
+        if let Err(e) = save_index(&dir, &index) {
+            log!("{}", e);
+        }
+    }
+}
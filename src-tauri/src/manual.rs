@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::history::StoredStatement;
+use credit_analyzer_core::manual::{statement_source, ManualEntry};
+use credit_analyzer_core::{goals, i18n, Transaction};
+use credit_analyzer_store::audit::{transaction_target, AuditAction};
+use credit_analyzer_store::{rollups, Store};
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+// Where an added transaction ended up, so it can be edited later
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AddedTransaction {
+    pub statement_id: u64,
+    pub index: usize,
+    pub transaction: Transaction,
+}
+
+// Goals measured on the statement when it was imported are measured again; statements of
+// manual entries alone were never measured and stay that way
+fn refresh_goals(app: &AppHandle, store: &mut Store, statement_id: u64) {
+    let measured = store.goals.iter().any(|goal| goal.progress.iter().any(|p| p.statement_id == statement_id));
+    let Some(statement) = store.statement(statement_id).filter(|_| measured) else {
+        return;
+    };
+    let transactions = statement.transactions.clone();
+    let statuses = goals::evaluate_goals(&mut store.goals, statement_id, &transactions);
+    events::budgets_updated(app, statuses.iter().map(|s| s.goal_id).collect());
+}
+
+// Adds a transaction the statements missed, such as a cash purchase. With a statement id it
+// joins that statement; otherwise it goes with the other manual entries for its month.
+#[command]
+#[specta::specta]
+pub async fn add_transaction(app: AppHandle, entry: ManualEntry, statement_id: Option<u64>) -> Result<AddedTransaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    entry.validate(&locale)?;
+    let transaction = entry.to_transaction();
+    let mut open_store = state::write_store(&app).await?;
+    // A plain Store, so its fields can be borrowed separately
+    let store: &mut Store = &mut open_store;
+
+    let source = statement_source(&transaction);
+    let position = match statement_id {
+        Some(id) => store.statements.iter().position(|s| s.id == id)
+            .ok_or_else(|| i18n::text(&locale, "error.statement_not_found", &[("id", id.to_string())]))?,
+        None => match store.statements.iter().position(|s| s.source_path == source) {
+            Some(position) => position,
+            None => {
+                let id = store.allocate_id();
+                let imported_at = chrono::Utc::now().to_rfc3339();
+                let sync_id = store.new_sync_id(format!("{} {}", source, imported_at).as_bytes());
+                store.statements.push(StoredStatement {
+                    id,
+                    source_path: source,
+                    imported_at,
+                    transactions: Vec::new(),
+                    meta: None,
+                    sync_id,
+                });
+                store.statements.len() - 1
+            }
+        },
+    };
+    let statement = &mut store.statements[position];
+    statement.transactions.push(transaction.clone());
+    let added = AddedTransaction { statement_id: statement.id, index: statement.transactions.len() - 1, transaction };
+    rollups::add(&mut store.rollups, std::slice::from_ref(&added.transaction));
+    store.audit(AuditAction::Add, transaction_target(added.statement_id, added.index), None::<&Transaction>, Some(&added.transaction));
+    log!("Added a manual transaction to statement {}", added.statement_id);
+
+    refresh_goals(&app, store, added.statement_id);
+    open_store.save()?;
+    events::transactions_changed(&app, vec![added.statement_id]);
+    Ok(added)
+}
+
+// Corrects a transaction's date, description, amount, category and note. Imported rows can be
+// edited too; they keep their import flag, tags, receipt and the rest.
+#[command]
+#[specta::specta]
+pub async fn edit_transaction(app: AppHandle, statement_id: u64, index: usize, entry: ManualEntry) -> Result<Transaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    entry.validate(&locale)?;
+    let mut open_store = state::write_store(&app).await?;
+    let store: &mut Store = &mut open_store;
+
+    let transaction = store.statements.iter_mut()
+        .find(|s| s.id == statement_id)
+        .and_then(|s| s.transactions.get_mut(index))
+        .ok_or_else(|| i18n::text(&locale, "error.transaction_not_found", &[
+            ("id", statement_id.to_string()),
+            ("index", index.to_string()),
+        ]))?;
+    let before = transaction.clone();
+    rollups::subtract(&mut store.rollups, std::slice::from_ref(transaction));
+    entry.apply(transaction);
+    rollups::add(&mut store.rollups, std::slice::from_ref(transaction));
+    let updated = transaction.clone();
+    store.audit(AuditAction::Edit, transaction_target(statement_id, index), Some(&before), Some(&updated));
+
+    refresh_goals(&app, store, statement_id);
+    open_store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(updated)
+}
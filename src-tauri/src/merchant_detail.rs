@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::{extract_merchant_name, i18n, month_key, parse_date, transaction_category};
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantTransaction {
+    pub statement_id: u64,
+    pub index: usize,
+    pub date: String,
+    pub description: String,
+    pub category: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantMonthTotal {
+    // "2024-01", or "Unknown" for undated transactions
+    pub month: String,
+    pub total: f64,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantCategory {
+    pub category: String,
+    pub total: f64,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct MerchantDetail {
+    pub merchant: String,
+    pub total: f64,
+    pub count: usize,
+    pub average_ticket: f64,
+    // ISO dates
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    // Oldest first
+    pub transactions: Vec<MerchantTransaction>,
+    pub monthly: Vec<MerchantMonthTotal>,
+    // Most spent first; more than one when a merchant sells several kinds of thing
+    pub categories: Vec<MerchantCategory>,
+}
+
+// Everything the store holds for one merchant, matched on the normalized name
+pub fn merchant_detail(store: &Store, merchant: &str) -> Option<MerchantDetail> {
+    let wanted = extract_merchant_name(merchant);
+    let mut transactions: Vec<MerchantTransaction> = store.statements.iter()
+        .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+        .filter(|(_, _, t)| extract_merchant_name(&t.description).eq_ignore_ascii_case(&wanted))
+        .map(|(statement_id, index, t)| MerchantTransaction {
+            statement_id,
+            index,
+            date: t.date.clone(),
+            description: t.description.clone(),
+            category: transaction_category(t),
+            amount: t.amount,
+        })
+        .collect();
+    if transactions.is_empty() {
+        return None;
+    }
+    transactions.sort_by_key(|t| parse_date(&t.date));
+
+    let mut monthly: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+    let mut categories: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+    for tx in &transactions {
+        let month = month_key(&tx.date).unwrap_or_else(|| "Unknown".to_string());
+        for (map, key) in [(&mut monthly, month), (&mut categories, tx.category.clone())] {
+            let entry = map.entry(key).or_insert((0.0, 0));
+            entry.0 += tx.amount;
+            entry.1 += 1;
+        }
+    }
+
+    let mut categories: Vec<MerchantCategory> = categories.into_iter()
+        .map(|(category, (total, count))| MerchantCategory { category, total, count })
+        .collect();
+    categories.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+    let dates: Vec<_> = transactions.iter().filter_map(|t| parse_date(&t.date)).collect();
+    let total: f64 = transactions.iter().map(|t| t.amount).sum();
+    Some(MerchantDetail {
+        merchant: wanted,
+        total,
+        count: transactions.len(),
+        average_ticket: total / transactions.len() as f64,
+        first_seen: dates.iter().min().map(|d| d.format("%Y-%m-%d").to_string()),
+        last_seen: dates.iter().max().map(|d| d.format("%Y-%m-%d").to_string()),
+        monthly: monthly.into_iter()
+            .map(|(month, (total, count))| MerchantMonthTotal { month, total, count })
+            .collect(),
+        categories,
+        transactions,
+    })
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_merchant_detail(app: AppHandle, merchant: String) -> Result<MerchantDetail, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+    merchant_detail(&store, &merchant)
+        .ok_or_else(|| i18n::text(&locale, "error.merchant_not_found", &[("merchant", merchant)]))
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::migration::{detect_source, parse_export, MigrationSource};
+use credit_analyzer_core::{i18n, month_key, Transaction};
+
+use crate::events;
+use crate::settings;
+use crate::snapshots::{self, SnapshotReason};
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct HistoryImportSummary {
+    pub source: MigrationSource,
+    pub transactions_imported: usize,
+    pub statements_recorded: usize,
+}
+
+// Splits a multi-month export into one stored statement per month, so growth comparisons
+// and goal baselines have the migrated history to work with
+#[command]
+#[specta::specta]
+pub async fn import_history(app: AppHandle, file_path: String) -> Result<HistoryImportSummary, String> {
+    let locale = settings::load_settings(&app)?.locale;
+
+    let content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    let source = detect_source(&content)
+        .ok_or("File is not a Mint, YNAB or Monarch export")?;
+    let transactions = parse_export(&content, source)?;
+    let transactions_imported = transactions.len();
+
+    let mut months: BTreeMap<String, Vec<Transaction>> = BTreeMap::new();
+    for tx in transactions {
+        let month = month_key(&tx.date).unwrap_or_else(|| "Unknown".to_string());
+        months.entry(month).or_default().push(tx);
+    }
+
+    let mut store = state::write_store(&app).await?;
+    snapshots::take(&app, &store, SnapshotReason::HistoryImport)?;
+
+    // Oldest first, matching the order statements are normally imported in
+    let statements_recorded = months.len();
+    let statement_ids: Vec<u64> = months.into_iter()
+        .map(|(month, transactions)| store.record_statement(&format!("{}#{}", file_path, month), transactions, None))
+        .collect();
+    store.save()?;
+    events::transactions_changed(&app, statement_ids);
+
+    Ok(HistoryImportSummary {
+        source,
+        transactions_imported,
+        statements_recorded,
+    })
+}
@@ -0,0 +1,117 @@
+use std::path::Path;
+use tauri::{command, AppHandle, Manager};
+
+use credit_analyzer_core::shared::SharedExpense;
+use credit_analyzer_core::{i18n, Transaction};
+use credit_analyzer_store::audit::{transaction_target, AuditAction};
+use credit_analyzer_store::health::normalized_tags;
+use credit_analyzer_store::Store;
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+const RECEIPTS_DIR: &str = "receipts";
+
+fn find_transaction<'a>(store: &'a mut Store, statement_id: u64, index: usize, locale: &str) -> Result<&'a mut Transaction, String> {
+    store.statements.iter_mut()
+        .find(|s| s.id == statement_id)
+        .and_then(|s| s.transactions.get_mut(index))
+        .ok_or_else(|| i18n::text(locale, "error.transaction_not_found", &[
+            ("id", statement_id.to_string()),
+            ("index", index.to_string()),
+        ]))
+}
+
+// Sets or, with an empty note, clears the note on a transaction
+#[command]
+#[specta::specta]
+pub async fn add_note(app: AppHandle, statement_id: u64, index: usize, note: String) -> Result<Transaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+
+    let transaction = find_transaction(&mut store, statement_id, index, &locale)?;
+    let before = transaction.clone();
+    let note = note.trim();
+    transaction.note = (!note.is_empty()).then(|| note.to_string());
+    let updated = transaction.clone();
+    store.audit(AuditAction::Edit, transaction_target(statement_id, index), Some(&before), Some(&updated));
+
+    store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(updated)
+}
+
+// Copies the receipt into the app data directory, so it survives the original being moved
+#[command]
+#[specta::specta]
+pub async fn attach_receipt(app: AppHandle, statement_id: u64, index: usize, file_path: String) -> Result<Transaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+    // Fail on a bad transaction before copying anything
+    find_transaction(&mut store, statement_id, index, &locale)?;
+
+    let source = Path::new(&file_path);
+    let file_name = source.file_name()
+        .ok_or_else(|| i18n::error(&locale, "error.file_not_found"))?
+        .to_string_lossy();
+    let receipts_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Could not resolve app data directory: {}", e))?
+        .join(RECEIPTS_DIR);
+    tokio::fs::create_dir_all(&receipts_dir).await
+        .map_err(|e| format!("Could not create receipts directory: {}", e))?;
+
+    let stored = receipts_dir.join(format!("{}-{}-{}", statement_id, index, file_name));
+    tokio::fs::copy(source, &stored).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+
+    let transaction = find_transaction(&mut store, statement_id, index, &locale)?;
+    let before = transaction.clone();
+    transaction.receipt_path = Some(stored.to_string_lossy().to_string());
+    let updated = transaction.clone();
+    store.audit(AuditAction::Edit, transaction_target(statement_id, index), Some(&before), Some(&updated));
+
+    store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(updated)
+}
+
+// Replaces the transaction's tags. Tags are matched in lowercase, so "Donation" and "donation"
+// are the same tag.
+#[command]
+#[specta::specta]
+pub async fn set_tags(app: AppHandle, statement_id: u64, index: usize, tags: Vec<String>) -> Result<Transaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+
+    let transaction = find_transaction(&mut store, statement_id, index, &locale)?;
+    let before = transaction.clone();
+    transaction.tags = normalized_tags(&tags);
+    let updated = transaction.clone();
+    store.audit(AuditAction::Edit, transaction_target(statement_id, index), Some(&before), Some(&updated));
+
+    store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(updated)
+}
+
+// Marks the transaction as split with a partner, or with None as the user's alone
+#[command]
+#[specta::specta]
+pub async fn set_shared(app: AppHandle, statement_id: u64, index: usize, shared: Option<SharedExpense>) -> Result<Transaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if let Some(shared) = &shared {
+        shared.validate(&locale)?;
+    }
+    let mut store = state::write_store(&app).await?;
+
+    let transaction = find_transaction(&mut store, statement_id, index, &locale)?;
+    let before = transaction.clone();
+    transaction.shared = shared.map(|shared| SharedExpense { partner: shared.partner.trim().to_string(), ..shared });
+    let updated = transaction.clone();
+    store.audit(AuditAction::Edit, transaction_target(statement_id, index), Some(&before), Some(&updated));
+
+    store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(updated)
+}
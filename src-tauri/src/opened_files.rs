@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Manager, RunEvent};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::events;
+
+// What the app registers itself to open, see bundle.fileAssociations in tauri.conf.json
+const OPENABLE_EXTENSIONS: &[&str] = &["csv"];
+// What a quick import offers; the same as the window's file picker
+const PICKABLE_EXTENSIONS: &[&str] = &["csv", "pdf", "html", "htm"];
+
+// Statements the OS asked the app to open, waiting for the frontend to take them. They're
+// queued rather than sent with the event so a file opened at launch isn't lost before the
+// window is listening.
+#[derive(Default)]
+pub struct OpenedFiles(Mutex<Vec<String>>);
+
+fn is_openable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| OPENABLE_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(extension)))
+}
+
+// Queues files for the frontend to import, whatever their type; callers decide what's openable
+pub fn open(app: &AppHandle, paths: Vec<PathBuf>) {
+    let paths: Vec<String> = paths.into_iter()
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    if paths.is_empty() {
+        return;
+    }
+    let count = paths.len();
+    match app.state::<OpenedFiles>().0.lock() {
+        Ok(mut queued) => queued.extend(paths),
+        Err(e) => {
+            log!("Could not queue opened files: {}", e);
+            return;
+        }
+    }
+    log!("Queued {} files opened from the OS", count);
+    events::files_opened(app, count);
+}
+
+// Windows and Linux pass the file as a launch argument, to this launch or to a second one
+// the single-instance plugin forwards here. Relative paths are relative to that launch's
+// working directory. Flags and the program path are skipped as they aren't openable files.
+pub fn open_args(app: &AppHandle, args: Vec<String>, cwd: &Path) {
+    open(app, args.into_iter().skip(1).map(|arg| cwd.join(arg)).filter(|path| is_openable(path)).collect());
+}
+
+// A second launch brings the running window forward, where its files are about to show up
+pub fn focus(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+// Quick import from the tray or the global shortcut: picks a statement and hands it to the
+// window like a file opened from Finder or Explorer, so it goes through the same import,
+// privacy setting and all
+pub fn pick_file(app: &AppHandle) {
+    let handle = app.clone();
+    app.dialog()
+        .file()
+        .add_filter("Financial Files", PICKABLE_EXTENSIONS)
+        .pick_file(move |picked| {
+            let Some(path) = picked.and_then(|path| path.into_path().ok()) else {
+                return;
+            };
+            open(&handle, vec![path]);
+            focus(&handle);
+        });
+}
+
+// macOS hands files over as an event instead, at launch and while running
+pub fn on_run_event(app: &AppHandle, event: RunEvent) {
+    #[cfg(target_os = "macos")]
+    if let RunEvent::Opened { urls } = event {
+        open(app, urls.into_iter().filter_map(|url| url.to_file_path().ok()).collect());
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = (app, event);
+}
+
+// Oldest first; each file is handed out once
+#[command]
+#[specta::specta]
+pub async fn take_opened_files(app: AppHandle) -> Result<Vec<String>, String> {
+    let mut queued = app.state::<OpenedFiles>().inner().0.lock().map_err(|e| e.to_string())?;
+    Ok(std::mem::take(&mut *queued))
+}
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Manager};
+
+use credit_analyzer_core::settings::Settings;
+use credit_analyzer_core::{parse_file, ParseFailure, PARSER_VERSION};
+use credit_analyzer_store::originals::StoredOriginal;
+
+use crate::snapshots::{self, SnapshotReason};
+use crate::{finish_analysis, settings, state, AnalyzeError};
+
+const ORIGINALS_DIR: &str = "originals";
+// zstd's default; statements are small enough that higher levels buy little
+const COMPRESSION_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReimportFailure {
+    pub source_path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ReimportSummary {
+    // Source paths of the statements parsed again
+    pub reimported: Vec<String>,
+    // Password-protected PDFs land here, as their password isn't kept
+    pub failed: Vec<ReimportFailure>,
+}
+
+fn originals_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir()
+        .map_err(|e| format!("Could not resolve app data directory: {}", e))?
+        .join(ORIGINALS_DIR))
+}
+
+fn original_path(dir: &Path, content_hash: &str) -> PathBuf {
+    dir.join(format!("{}.zst", content_hash))
+}
+
+// Files are only ever removed once the store no longer points at them, so a failure here just
+// leaves a stray file behind
+async fn remove_files(dir: &Path, hashes: Vec<String>) {
+    for hash in hashes {
+        if let Err(e) = tokio::fs::remove_file(original_path(dir, &hash)).await {
+            log!("Could not remove kept original {}: {}", hash, e);
+        }
+    }
+}
+
+// Compresses and keeps an imported file, then lets go of any kept past their retention
+pub async fn keep(app: &AppHandle, settings: &Settings, source_path: &str, content_hash: &str, bytes: Vec<u8>) -> Result<(), String> {
+    let dir = originals_dir(app)?;
+    tokio::fs::create_dir_all(&dir).await
+        .map_err(|e| format!("Could not create originals directory: {}", e))?;
+    let path = original_path(&dir, content_hash);
+    let size = bytes.len() as u64;
+    let compressed = tokio::task::spawn_blocking(move || zstd::encode_all(bytes.as_slice(), COMPRESSION_LEVEL))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Could not compress original: {}", e))?;
+    let compressed_size = compressed.len() as u64;
+    tokio::fs::write(&path, compressed).await
+        .map_err(|e| format!("Could not keep original: {}", e))?;
+
+    let mut store = state::write_store(app).await?;
+    let mut unreferenced = store.keep_original(StoredOriginal {
+        source_path: source_path.to_string(),
+        content_hash: content_hash.to_string(),
+        stored_at: chrono::Utc::now().to_rfc3339(),
+        parser_version: PARSER_VERSION,
+        size,
+        compressed_size,
+    });
+    if let Some(days) = settings.originals_retention_days {
+        unreferenced.extend(store.expire_originals(days));
+    }
+    store.save()?;
+    drop(store);
+    remove_files(&dir, unreferenced).await;
+    Ok(())
+}
+
+async fn read_original(dir: &Path, content_hash: &str) -> Result<Vec<u8>, String> {
+    let compressed = tokio::fs::read(original_path(dir, content_hash)).await
+        .map_err(|e| format!("Kept original is missing: {}", e))?;
+    tokio::task::spawn_blocking(move || zstd::decode_all(compressed.as_slice()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Kept original is damaged: {}", e))
+}
+
+// Newest first
+#[command]
+#[specta::specta]
+pub async fn list_originals(app: AppHandle) -> Result<Vec<StoredOriginal>, String> {
+    let store = state::read_store(&app).await?;
+    let mut originals = store.originals.clone();
+    originals.sort_by(|a, b| b.stored_at.cmp(&a.stored_at));
+    Ok(originals)
+}
+
+// Runs the kept originals through the import again, as if each file had just been picked.
+// Notes, tags and manual rows carry over as they do on any re-import. Only originals last
+// parsed by an older parser are redone unless `all` is set.
+#[command]
+#[specta::specta]
+pub async fn reimport_originals(app: AppHandle, all: Option<bool>) -> Result<ReimportSummary, String> {
+    let settings = settings::load_settings(&app)?;
+    let dir = originals_dir(&app)?;
+    let (originals, profiles) = {
+        let mut store = state::write_store(&app).await?;
+        // Nothing past its retention is worth parsing again
+        if let Some(days) = settings.originals_retention_days {
+            let unreferenced = store.expire_originals(days);
+            if !unreferenced.is_empty() {
+                store.save()?;
+                remove_files(&dir, unreferenced).await;
+            }
+        }
+        let originals: Vec<StoredOriginal> = store.originals.iter()
+            .filter(|o| all.unwrap_or(false) || o.parser_version < PARSER_VERSION)
+            .cloned()
+            .collect();
+        if !originals.is_empty() {
+            snapshots::take(&app, &store, SnapshotReason::Reimport)?;
+        }
+        (originals, store.bank_profiles.clone())
+    };
+
+    let mut summary = ReimportSummary::default();
+    for original in originals {
+        let result = match read_original(&dir, &original.content_hash).await {
+            Ok(bytes) => {
+                let path = original.source_path.clone();
+                let profiles = profiles.clone();
+                let (mode, language) = (settings.parse_mode, settings.statement_language);
+                let parsed = tokio::task::spawn_blocking(move || parse_file(&path, Some(bytes), None, None, &profiles, mode, language).map_err(ParseFailure::from))
+                    .await
+                    .unwrap_or_else(|e| Err(ParseFailure::Failed(e.to_string())));
+                finish_analysis(&app, &settings, &original.source_path, parsed, Some(original.content_hash.clone()), false, false).await
+                    .map_err(AnalyzeError::into_message)
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(_) => {
+                let mut store = state::write_store(&app).await?;
+                if let Some(kept) = store.originals.iter_mut().find(|o| o.content_hash == original.content_hash && o.source_path == original.source_path) {
+                    kept.parser_version = PARSER_VERSION;
+                }
+                store.save()?;
+                summary.reimported.push(original.source_path);
+            }
+            Err(reason) => summary.failed.push(ReimportFailure { source_path: original.source_path, reason }),
+        }
+    }
+    log!("Re-imported {} kept originals, {} failed", summary.reimported.len(), summary.failed.len());
+    Ok(summary)
+}
@@ -0,0 +1,29 @@
+use tauri::AppHandle;
+
+use credit_analyzer_core::periods::{self, PeriodBoundary};
+use credit_analyzer_store::rollups;
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+// Applies the configured boundary, regrouping the stored rollups if it moved. Run at startup,
+// when settings change, and after an import, which can move a statement-cycle boundary.
+pub async fn sync(app: &AppHandle) -> Result<(), String> {
+    let boundary = settings::load_settings(app)?.period_boundary;
+    apply(app, &boundary).await
+}
+
+// Regroups the rollups by `boundary`, whether or not it's been saved yet
+pub async fn apply(app: &AppHandle, boundary: &PeriodBoundary) -> Result<(), String> {
+    let mut store = state::write_store(app).await?;
+
+    let day = periods::resolve_start_day(boundary, &store.statements);
+    periods::set_start_day(day);
+    if store.rollup_start_day.unwrap_or(1) != day {
+        rollups::rebuild(&mut store);
+        store.save()?;
+        events::all_transactions_changed(app, &store);
+    }
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_store::preview::ChangePreview;
+
+use crate::events;
+use crate::snapshots::{self, SnapshotReason};
+use crate::state;
+
+// Re-runs categorization over stored history after the rules or keyword packs change, and
+// rebuilds the rollups to match. Categories the user set by hand are never touched. A dry run
+// only reports what would move, for checking before historical reports shift.
+#[command]
+#[specta::specta]
+pub async fn recategorize_all(app: AppHandle, dry_run: Option<bool>) -> Result<ChangePreview, String> {
+    if dry_run.unwrap_or(false) {
+        let store = state::read_store(&app).await?;
+        return Ok(ChangePreview::new(store.category_changes()));
+    }
+
+    let mut store = state::write_store(&app).await?;
+    snapshots::take(&app, &store, SnapshotReason::Recategorize)?;
+    let changes = store.recategorize();
+    store.save()?;
+    log!("Recategorized {} transactions", changes.len());
+    events::all_transactions_changed(&app, &store);
+    Ok(ChangePreview::new(changes))
+}
@@ -0,0 +1,228 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::{categorize_description, i18n, parse_amount, parse_date, transaction_category, Transaction};
+use credit_analyzer_store::audit::{transaction_target, AuditAction};
+use credit_analyzer_store::rollups;
+use credit_analyzer_store::Store;
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+// Lines that carry an amount but aren't something that was bought
+const NON_ITEM_MARKERS: &[&str] = &[
+    "total", "subtotal", "tax", "change", "cash", "tender", "visa", "mastercard", "amex",
+    "debit", "credit", "balance", "tip", "discount", "savings",
+];
+// A receipt dated further from the charge than this belongs to another purchase
+const MATCH_WINDOW_DAYS: i64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReceiptItem {
+    pub description: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CategorySplit {
+    pub category: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ReceiptScan {
+    pub merchant: Option<String>,
+    // ISO date
+    pub date: Option<String>,
+    pub total: Option<f64>,
+    pub items: Vec<ReceiptItem>,
+    // The card charge the receipt belongs to, as (statement id, transaction index)
+    pub matched: Option<(u64, usize)>,
+    // Items grouped by category and scaled to the charge, ready for split_transaction
+    pub suggested_splits: Vec<CategorySplit>,
+}
+
+// Images go through the tesseract CLI; PDF receipts already carry their text
+fn receipt_text(path: &Path, locale: &str) -> Result<String, String> {
+    let is_pdf = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"));
+    if is_pdf {
+        let bytes = std::fs::read(path).map_err(|e| format!("Could not read receipt: {}", e))?;
+        return pdf_extract::extract_text_from_mem(&bytes).map_err(|e| format!("Could not read receipt: {}", e));
+    }
+
+    let output = Command::new("tesseract")
+        .arg(path)
+        .arg("stdout")
+        .output()
+        .map_err(|_| i18n::error(locale, "error.ocr_unavailable"))?;
+    if !output.status.success() {
+        return Err(format!("OCR failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Pulls the merchant (the first line of text), date, total and priced lines out of OCR text,
+// leaving the match to the caller
+pub fn parse_receipt(text: &str) -> Result<ReceiptScan, String> {
+    let invalid = |e: regex::Error| e.to_string();
+    let priced = Regex::new(r"^(.*?[A-Za-z].*?)\s+\$?(-?\d{1,5}[.,]\d{2})\s*[A-Z]?$").map_err(invalid)?;
+    let date_pattern = Regex::new(r"\b(\d{1,2}/\d{1,2}/\d{2,4}|\d{4}-\d{2}-\d{2})\b").map_err(invalid)?;
+
+    let lines: Vec<&str> = text.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let merchant = lines.first().map(|line| line.to_string());
+    let date = lines.iter()
+        .find_map(|line| date_pattern.captures(line))
+        .and_then(|captures| parse_date(&captures[1]))
+        .map(|date| date.format("%Y-%m-%d").to_string());
+
+    let mut total = None;
+    let mut items = Vec::new();
+    for line in &lines {
+        let Some(captures) = priced.captures(line) else {
+            continue;
+        };
+        let Ok(amount) = parse_amount(&captures[2].replace(',', ".")) else {
+            continue;
+        };
+        let label = captures[1].trim();
+        let lower = label.to_lowercase();
+
+        // The last "total" wins, since subtotals come before it
+        if lower.contains("total") && !lower.contains("sub") {
+            total = Some(amount);
+        } else if !NON_ITEM_MARKERS.iter().any(|marker| lower.contains(marker)) && amount > 0.0 {
+            items.push(ReceiptItem { description: label.to_string(), amount });
+        }
+    }
+
+    Ok(ReceiptScan { merchant, date, total, items, matched: None, suggested_splits: Vec::new() })
+}
+
+// Groups items by category and scales them so the parts add up to the charge, which spreads
+// tax across the categories in proportion
+fn suggest_splits(items: &[ReceiptItem], charge: &Transaction) -> Vec<CategorySplit> {
+    let fallback = transaction_category(charge);
+    let mut by_category: BTreeMap<String, f64> = BTreeMap::new();
+    for item in items {
+        let category = match categorize_description(&item.description) {
+            category if category == "Other" => fallback.clone(),
+            category => category,
+        };
+        *by_category.entry(category).or_insert(0.0) += item.amount;
+    }
+
+    let items_total: f64 = by_category.values().sum();
+    if by_category.len() < 2 || items_total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut splits: Vec<CategorySplit> = by_category.into_iter()
+        .map(|(category, amount)| CategorySplit {
+            category,
+            amount: (amount / items_total * charge.amount * 100.0).round() / 100.0,
+        })
+        .collect();
+    // Rounding leftovers go to the largest part so the split still sums to the charge
+    let drift = charge.amount - splits.iter().map(|s| s.amount).sum::<f64>();
+    if let Some(largest) = splits.iter_mut().max_by(|a, b| a.amount.total_cmp(&b.amount)) {
+        largest.amount = ((largest.amount + drift) * 100.0).round() / 100.0;
+    }
+    splits
+}
+
+// The attached transaction if there is one, else the charge for the receipt's total closest
+// to the receipt's date
+fn match_charge(store: &Store, receipt_path: &str, date: Option<&str>, total: Option<f64>) -> Option<(u64, usize)> {
+    let attached = store.statements.iter().find_map(|s| {
+        s.transactions.iter()
+            .position(|t| t.receipt_path.as_deref() == Some(receipt_path))
+            .map(|index| (s.id, index))
+    });
+    if attached.is_some() {
+        return attached;
+    }
+
+    let total = total?;
+    let receipt_date = date.and_then(parse_date);
+    store.statements.iter()
+        .flat_map(|s| s.transactions.iter().enumerate().map(move |(index, t)| (s.id, index, t)))
+        .filter(|(_, _, t)| (t.amount - total).abs() < 0.01)
+        .filter_map(|(id, index, t)| {
+            let days = match (receipt_date, parse_date(&t.date)) {
+                (Some(receipt), Some(charged)) => (charged - receipt).num_days().abs(),
+                _ => 0,
+            };
+            (days <= MATCH_WINDOW_DAYS).then_some((days, id, index))
+        })
+        .min_by_key(|(days, _, _)| *days)
+        .map(|(_, id, index)| (id, index))
+}
+
+#[command]
+#[specta::specta]
+pub async fn scan_receipt(app: AppHandle, file_path: String) -> Result<ReceiptScan, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if !tokio::fs::try_exists(&file_path).await.unwrap_or(false) {
+        return Err(i18n::error(&locale, "error.file_not_found"));
+    }
+
+    // OCR is slow, so it stays off the async runtime
+    let (path, ocr_locale) = (file_path.clone(), locale.clone());
+    let text = tokio::task::spawn_blocking(move || receipt_text(Path::new(&path), &ocr_locale))
+        .await
+        .map_err(|e| e.to_string())??;
+    let mut scan = parse_receipt(&text)?;
+
+    let store = state::read_store(&app).await?;
+    scan.matched = match_charge(&store, &file_path, scan.date.as_deref(), scan.total);
+    scan.suggested_splits = scan.matched
+        .and_then(|(id, index)| store.statement(id).and_then(|s| s.transactions.get(index)))
+        .map(|charge| suggest_splits(&scan.items, charge))
+        .unwrap_or_default();
+
+    Ok(scan)
+}
+
+// Replaces a transaction with one part per category. The parts keep the original's date,
+// note and receipt, and must add up to its amount.
+#[command]
+#[specta::specta]
+pub async fn split_transaction(app: AppHandle, statement_id: u64, index: usize, splits: Vec<CategorySplit>) -> Result<Vec<Transaction>, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut open_store = state::write_store(&app).await?;
+    // A plain Store, so its fields can be borrowed separately
+    let store: &mut Store = &mut open_store;
+
+    let not_found = || i18n::text(&locale, "error.transaction_not_found", &[
+        ("id", statement_id.to_string()),
+        ("index", index.to_string()),
+    ]);
+    let statement = store.statements.iter_mut().find(|s| s.id == statement_id).ok_or_else(not_found)?;
+    let original = statement.transactions.get(index).cloned().ok_or_else(not_found)?;
+
+    let split_total: f64 = splits.iter().map(|s| s.amount).sum();
+    if splits.len() < 2 || splits.iter().any(|s| s.amount <= 0.0) || (split_total - original.amount).abs() >= 0.01 {
+        return Err(i18n::text(&locale, "error.split_total_mismatch", &[("amount", format!("{:.2}", original.amount))]));
+    }
+
+    let parts: Vec<Transaction> = splits.into_iter().map(|split| Transaction {
+        amount: split.amount,
+        category: Some(split.category),
+        ..original.clone()
+    }).collect();
+
+    rollups::subtract(&mut store.rollups, std::slice::from_ref(&original));
+    rollups::add(&mut store.rollups, &parts);
+    statement.transactions.splice(index..=index, parts.iter().cloned());
+    store.audit(AuditAction::Edit, transaction_target(statement_id, index), Some(&original), Some(&parts));
+
+    open_store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(parts)
+}
@@ -0,0 +1,146 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{command, AppHandle};
+use tauri_plugin_notification::NotificationExt;
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_store::Store;
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+// The app may stay open for days, so due dates are rechecked periodically
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PaymentReminder {
+    pub statement_id: u64,
+    pub issuer: Option<String>,
+    pub card_last4: Option<String>,
+    // ISO date
+    pub due_date: String,
+    pub days_until: i64,
+    pub minimum_payment: Option<f64>,
+    pub new_balance: Option<f64>,
+}
+
+// The most recent due date for each card, if it hasn't passed yet, soonest first. Cards
+// are told apart by issuer and last four digits; a newer statement supersedes older ones.
+pub fn upcoming(store: &Store, today: NaiveDate) -> Vec<PaymentReminder> {
+    let mut latest: HashMap<(Option<String>, Option<String>), (NaiveDate, PaymentReminder)> = HashMap::new();
+
+    for statement in &store.statements {
+        let Some(meta) = &statement.meta else {
+            continue;
+        };
+        let Some(due) = meta.payment_due_date.as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()) else {
+            continue;
+        };
+
+        let card = (meta.issuer.clone(), meta.card_last4.clone());
+        if latest.get(&card).is_some_and(|(existing, _)| *existing >= due) {
+            continue;
+        }
+        latest.insert(card, (due, PaymentReminder {
+            statement_id: statement.id,
+            issuer: meta.issuer.clone(),
+            card_last4: meta.card_last4.clone(),
+            due_date: due.format("%Y-%m-%d").to_string(),
+            days_until: (due - today).num_days(),
+            minimum_payment: meta.minimum_payment,
+            new_balance: meta.new_balance,
+        }));
+    }
+
+    let mut reminders: Vec<PaymentReminder> = latest.into_values()
+        .map(|(_, reminder)| reminder)
+        .filter(|reminder| reminder.days_until >= 0)
+        .collect();
+    reminders.sort_by(|a, b| a.due_date.cmp(&b.due_date));
+    reminders
+}
+
+fn card_label(reminder: &PaymentReminder, locale: &str) -> String {
+    match (&reminder.issuer, &reminder.card_last4) {
+        (Some(issuer), Some(last4)) => format!("{} ••{}", issuer, last4),
+        (Some(issuer), None) => issuer.clone(),
+        (None, Some(last4)) => i18n::text(locale, "reminder.card_ending", &[("last4", last4.clone())]),
+        (None, None) => i18n::text(locale, "reminder.card_unknown", &[]),
+    }
+}
+
+fn notify(app: &AppHandle, reminder: &PaymentReminder, locale: &str) -> Result<(), String> {
+    let card = card_label(reminder, locale);
+    let mut params = vec![
+        ("card", card),
+        ("date", reminder.due_date.clone()),
+        ("days", reminder.days_until.to_string()),
+    ];
+    // The minimum is what avoids a late fee, so it's the amount worth calling out
+    let body = match reminder.minimum_payment.or(reminder.new_balance) {
+        Some(amount) => {
+            params.push(("amount", format!("{:.2}", amount)));
+            i18n::text(locale, "reminder.body", &params)
+        }
+        None => i18n::text(locale, "reminder.body_no_amount", &params),
+    };
+
+    app.notification()
+        .builder()
+        .title(i18n::text(locale, "reminder.title", &[]))
+        .body(body)
+        .show()
+        .map_err(|e| format!("Could not show notification: {}", e))
+}
+
+// Notifies once per statement when its due date is within the configured lead time
+pub async fn notify_due(app: &AppHandle) -> Result<(), String> {
+    let settings = settings::load_settings(app)?;
+    if !settings.payment_reminders {
+        return Ok(());
+    }
+
+    let mut store = state::write_store(app).await?;
+    let today = Local::now().date_naive();
+
+    let mut sent = Vec::new();
+    for reminder in upcoming(&store, today) {
+        if reminder.days_until > i64::from(settings.reminder_days_before) ||
+            store.reminded_statements.contains(&reminder.statement_id) {
+            continue;
+        }
+        notify(app, &reminder, &settings.locale)?;
+        store.reminded_statements.push(reminder.statement_id);
+        sent.push(reminder.statement_id);
+    }
+
+    if !sent.is_empty() {
+        store.save()?;
+        events::alerts_triggered(app, Vec::new(), sent);
+    }
+    Ok(())
+}
+
+// Checks on startup and then every CHECK_INTERVAL for as long as the app runs
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = notify_due(&app).await {
+                log!("Payment reminder check failed: {}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[command]
+#[specta::specta]
+pub async fn list_payment_reminders(app: AppHandle) -> Result<Vec<PaymentReminder>, String> {
+    let store = state::read_store(&app).await?;
+    Ok(upcoming(&store, Local::now().date_naive()))
+}
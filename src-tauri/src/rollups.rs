@@ -0,0 +1,51 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_store::audit::{transaction_target, AuditAction};
+use credit_analyzer_store::rollups::{add, rebuild, subtract, MonthlyRollup};
+use credit_analyzer_store::Store;
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+// Full recompute, for when the categorization rules themselves change
+#[command]
+#[specta::specta]
+pub async fn rebuild_rollups(app: AppHandle) -> Result<Vec<MonthlyRollup>, String> {
+    let mut store = state::write_store(&app).await?;
+    rebuild(&mut store);
+    store.save()?;
+    events::all_transactions_changed(&app, &store);
+    Ok(store.rollups.clone())
+}
+
+// Overrides one transaction's category (None returns it to the keyword rules), adjusting
+// only the rollup cells it moves between
+#[command]
+#[specta::specta]
+pub async fn set_transaction_category(app: AppHandle, statement_id: u64, index: usize, category: Option<String>) -> Result<(), String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut open_store = state::write_store(&app).await?;
+    // A plain Store, so its fields can be borrowed separately
+    let store: &mut Store = &mut open_store;
+
+    let transaction = store.statements.iter_mut()
+        .find(|s| s.id == statement_id)
+        .and_then(|s| s.transactions.get_mut(index))
+        .ok_or_else(|| i18n::text(&locale, "error.transaction_not_found", &[
+            ("id", statement_id.to_string()),
+            ("index", index.to_string()),
+        ]))?;
+
+    let before = transaction.category.clone();
+    subtract(&mut store.rollups, std::slice::from_ref(transaction));
+    transaction.category = category.filter(|c| !c.trim().is_empty());
+    add(&mut store.rollups, std::slice::from_ref(transaction));
+    let after = transaction.category.clone();
+    store.audit(AuditAction::CategoryOverride, transaction_target(statement_id, index), Some(&before), Some(&after));
+
+    open_store.save()?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(())
+}
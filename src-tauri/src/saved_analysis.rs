@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::{i18n, AnalysisResult};
+
+use crate::settings;
+
+const FORMAT: &str = "credit_analyzer.analysis";
+// Bump when a field is renamed or reshaped, and teach `upgrade` to read the old shape. Fields
+// that are only added need no bump: AnalysisResult fills in anything missing with its default.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SavedAnalysis {
+    pub format: String,
+    pub schema_version: u32,
+    // The app that wrote the file, for diagnosing odd imports
+    pub app_version: String,
+    pub exported_at: String,
+    pub analysis: AnalysisResult,
+}
+
+// The analysis payload of a saved file, in the current shape. Version 0 is a bare
+// AnalysisResult as the app returned it, saved before files had an envelope.
+fn upgrade(content: Value, locale: &str) -> Result<Value, String> {
+    let invalid = || i18n::error(locale, "error.analysis_invalid_file");
+    if content.get("format").is_none() {
+        return content.get("spending_categories").is_some().then_some(content).ok_or_else(invalid);
+    }
+    if content.get("format").and_then(Value::as_str) != Some(FORMAT) {
+        return Err(invalid());
+    }
+
+    let version = content.get("schema_version").and_then(Value::as_u64).ok_or_else(invalid)?;
+    if version > u64::from(SCHEMA_VERSION) {
+        return Err(i18n::text(locale, "error.analysis_newer_version", &[("version", version.to_string())]));
+    }
+    content.get("analysis").cloned().ok_or_else(invalid)
+}
+
+pub fn read_saved(content: &str, locale: &str) -> Result<AnalysisResult, String> {
+    let content: Value = serde_json::from_str(content)
+        .map_err(|_| i18n::error(locale, "error.analysis_invalid_file"))?;
+    serde_json::from_value(upgrade(content, locale)?)
+        .map_err(|_| i18n::error(locale, "error.analysis_invalid_file"))
+}
+
+// Saves an analysis the frontend already has, so it can be reopened without the statement
+#[command]
+#[specta::specta]
+pub async fn export_analysis(analysis: AnalysisResult, file_path: String) -> Result<(), String> {
+    let saved = SavedAnalysis {
+        format: FORMAT.to_string(),
+        schema_version: SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        analysis,
+    };
+    let content = serde_json::to_string_pretty(&saved)
+        .map_err(|e| format!("Could not serialize analysis: {}", e))?;
+    tokio::fs::write(&file_path, content).await
+        .map_err(|e| format!("Could not write analysis: {}", e))
+}
+
+#[command]
+#[specta::specta]
+pub async fn open_saved_analysis(app: AppHandle, file_path: String) -> Result<AnalysisResult, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    read_saved(&content, &locale)
+}
@@ -0,0 +1,45 @@
+use chrono::{Datelike, Local};
+use std::process::Command;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::ocr::{self, OcrRow};
+use credit_analyzer_core::statement_language::StatementLanguage;
+
+use crate::settings;
+
+// Word-level output, so each row can carry how sure OCR was of it
+fn ocr_tsv(file_path: &str, language: StatementLanguage, locale: &str) -> Result<String, String> {
+    let output = Command::new("tesseract")
+        .arg(file_path)
+        .arg("stdout")
+        .args(["-l", language.ocr_model()])
+        .arg("tsv")
+        .output()
+        .map_err(|_| i18n::error(locale, "error.ocr_unavailable"))?;
+    if !output.status.success() {
+        return Err(format!("OCR failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Reads the transaction list in a PNG/JPG screenshot. Nothing is imported: the rows go back
+// for the user to check, low-confidence ones flagged, and then to import_screenshot.
+#[command]
+#[specta::specta]
+pub async fn scan_screenshot(app: AppHandle, file_path: String) -> Result<Vec<OcrRow>, String> {
+    let settings = settings::load_settings(&app)?;
+    let (locale, language) = (settings.locale, settings.statement_language);
+    if !tokio::fs::try_exists(&file_path).await.unwrap_or(false) {
+        return Err(i18n::error(&locale, "error.file_not_found"));
+    }
+    // Apps leave the year off recent transactions
+    let year = Local::now().year();
+    let rows = tokio::task::spawn_blocking(move || {
+        ocr_tsv(&file_path, language, &locale).map(|tsv| ocr::rows_from_lines(&ocr::lines_from_tsv(&tsv), year, language))
+    })
+    .await
+    .map_err(|e| format!("OCR failed: {}", e))??;
+    log!("Read {} rows from screenshot, {} to review", rows.len(), rows.iter().filter(|r| r.needs_review).count());
+    Ok(rows)
+}
@@ -0,0 +1,108 @@
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::i18n;
+
+use crate::settings;
+
+// Keychain service the entries are filed under, matching the bundle identifier
+const SERVICE: &str = "com.creditanalyzer.app";
+
+// Credentials for integrations, kept in the OS keychain rather than settings.json
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretKey {
+    PlaidClientId,
+    PlaidSecret,
+    ImapPassword,
+    LlmApiKey,
+    // Encrypts what sync writes to the shared folder; every synced device holds the same one
+    SyncKey,
+}
+
+const ALL_KEYS: &[SecretKey] = &[SecretKey::PlaidClientId, SecretKey::PlaidSecret, SecretKey::ImapPassword, SecretKey::LlmApiKey, SecretKey::SyncKey];
+
+impl SecretKey {
+    fn account(self) -> &'static str {
+        match self {
+            SecretKey::PlaidClientId => "plaid_client_id",
+            SecretKey::PlaidSecret => "plaid_secret",
+            SecretKey::ImapPassword => "imap_password",
+            SecretKey::LlmApiKey => "llm_api_key",
+            SecretKey::SyncKey => "sync_key",
+        }
+    }
+}
+
+// What the frontend may know about a secret: never its value
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SecretStatus {
+    pub key: SecretKey,
+    pub stored: bool,
+    // Why the keychain couldn't be read, when it couldn't
+    pub error: Option<String>,
+}
+
+fn entry(key: SecretKey) -> Result<Entry, keyring::Error> {
+    Entry::new(SERVICE, key.account())
+}
+
+fn unavailable(locale: &str, e: keyring::Error) -> String {
+    i18n::text(locale, "error.keychain_unavailable", &[("reason", e.to_string())])
+}
+
+// For integrations to read their credentials. None when the secret was never set.
+pub fn get_secret(key: SecretKey) -> Result<Option<String>, String> {
+    match entry(key).and_then(|entry| entry.get_password()) {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(unavailable(i18n::DEFAULT_LOCALE, e)),
+    }
+}
+
+pub fn put_secret(key: SecretKey, value: &str, locale: &str) -> Result<(), String> {
+    entry(key)
+        .and_then(|entry| entry.set_password(value))
+        .map_err(|e| unavailable(locale, e))
+}
+
+fn status(key: SecretKey) -> SecretStatus {
+    match get_secret(key) {
+        Ok(value) => SecretStatus { key, stored: value.is_some_and(|value| !value.is_empty()), error: None },
+        Err(e) => SecretStatus { key, stored: false, error: Some(e) },
+    }
+}
+
+#[command]
+#[specta::specta]
+pub async fn set_secret(app: AppHandle, key: SecretKey, value: String) -> Result<SecretStatus, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    // Pasted keys often pick up a trailing newline
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(i18n::error(&locale, "error.secret_empty"));
+    }
+
+    put_secret(key, value, &locale)?;
+    Ok(status(key))
+}
+
+// Clearing a secret that was never set is not an error
+#[command]
+#[specta::specta]
+pub async fn clear_secret(app: AppHandle, key: SecretKey) -> Result<SecretStatus, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    match entry(key).and_then(|entry| entry.delete_credential()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(status(key)),
+        Err(e) => Err(unavailable(&locale, e)),
+    }
+}
+
+// Health check for the settings screen: whether each secret is stored and readable
+#[command]
+#[specta::specta]
+pub async fn list_secret_status() -> Vec<SecretStatus> {
+    ALL_KEYS.iter().map(|key| status(*key)).collect()
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Manager};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::settings::Settings;
+use credit_analyzer_store::audit::AuditAction;
+
+use crate::periods;
+#[cfg(desktop)]
+use crate::{background, shortcut};
+use crate::state;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// Only resolved; the directory is made when settings are first saved, so reading them, as a
+// private analysis does, leaves nothing behind
+pub fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir()
+        .map_err(|e| format!("Could not resolve config directory: {}", e))?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+// Read from disk once, then served from the shared state
+pub fn load_settings(app: &AppHandle) -> Result<Settings, String> {
+    if let Some(settings) = state::cached_settings(app) {
+        return Ok(settings);
+    }
+    let settings = Settings::load(&settings_path(app)?)?;
+    state::cache_settings(app, &settings);
+    Ok(settings)
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_settings(app: AppHandle) -> Result<Settings, String> {
+    load_settings(&app)
+}
+
+// Everything a change of settings does outside the settings file, then the file itself, so
+// nothing is saved unless all of it took. Keys another app holds are refused here.
+async fn apply(app: &AppHandle, settings: &Settings, previous: &Settings) -> Result<(), String> {
+    #[cfg(desktop)]
+    if settings.import_shortcut != previous.import_shortcut {
+        shortcut::apply(app, settings)?;
+    }
+    #[cfg(desktop)]
+    if settings.autostart != previous.autostart {
+        background::apply_autostart(app, settings)?;
+    }
+    if settings.period_boundary != previous.period_boundary {
+        periods::apply(app, &settings.period_boundary).await?;
+    }
+    let path = settings_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Could not create config directory: {}", e))?;
+    }
+    settings.save(&path)
+}
+
+// Undoes as much of apply as ran. Each step only moves things to match `previous`, so the ones
+// that never ran are left as they are.
+async fn revert(app: &AppHandle, previous: &Settings) {
+    #[cfg(desktop)]
+    {
+        let _ = shortcut::apply(app, previous);
+        let _ = background::apply_autostart(app, previous);
+    }
+    let _ = periods::apply(app, &previous.period_boundary).await;
+}
+
+#[command]
+#[specta::specta]
+pub async fn update_settings(app: AppHandle, settings: Settings) -> Result<Settings, String> {
+    settings.validate()?;
+    let previous = load_settings(&app)?;
+    if let Some(folder) = settings.statements_folder.as_deref().filter(|_| settings.background_import) {
+        if !Path::new(folder).is_dir() {
+            return Err(i18n::text(&settings.locale, "error.statements_folder_missing", &[("folder", folder.to_string())]));
+        }
+    }
+    if let Err(e) = apply(&app, &settings, &previous).await {
+        revert(&app, &previous).await;
+        return Err(e);
+    }
+    state::cache_settings(&app, &settings);
+
+    // The change has been made by now, so a failure here only delays the audit entry to the
+    // next save
+    let mut store = state::write_store(&app).await?;
+    store.audit(AuditAction::RuleChange, "settings", Some(&previous), Some(&settings));
+    // Cached parses read dates and amounts the old language's way
+    if settings.statement_language != previous.statement_language {
+        store.parse_cache.clear();
+    }
+    if let Err(e) = store.save() {
+        log!("Could not record the settings change: {}", e);
+    }
+    Ok(settings)
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::{i18n, parse_date};
+use credit_analyzer_store::Store;
+
+use crate::category_detail::{in_period, valid_period};
+use crate::settings;
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SharedLine {
+    pub statement_id: u64,
+    pub index: usize,
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    pub partner_share: f64,
+    pub paid_by_partner: bool,
+    // Positive when the partner owes the user for this charge
+    pub owed_by_partner: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct Settlement {
+    pub partner: String,
+    // Their part of what the user paid for
+    pub partner_owes: f64,
+    // The user's part of what the partner paid for
+    pub you_owe: f64,
+    // What changes hands: positive means the partner pays the user
+    pub net: f64,
+    // Oldest first
+    pub lines: Vec<SharedLine>,
+}
+
+// One settlement per partner; `period` is a month ("2024-01"), a year ("2024"), or None for
+// everything imported
+pub fn settle(store: &Store, period: Option<&str>) -> Vec<Settlement> {
+    let mut by_partner: BTreeMap<String, Vec<SharedLine>> = BTreeMap::new();
+    for statement in &store.statements {
+        for (index, tx) in statement.transactions.iter().enumerate() {
+            let Some(shared) = tx.shared.as_ref().filter(|_| in_period(&tx.date, period)) else {
+                continue;
+            };
+            by_partner.entry(shared.partner.clone()).or_default().push(SharedLine {
+                statement_id: statement.id,
+                index,
+                date: tx.date.clone(),
+                description: tx.description.clone(),
+                amount: tx.amount,
+                partner_share: shared.partner_share,
+                paid_by_partner: shared.paid_by_partner,
+                owed_by_partner: shared.owed_by_partner(tx.amount),
+            });
+        }
+    }
+
+    by_partner.into_iter()
+        .map(|(partner, mut lines)| {
+            lines.sort_by_key(|line| parse_date(&line.date));
+            let partner_owes: f64 = lines.iter().map(|l| l.owed_by_partner).filter(|owed| *owed > 0.0).sum();
+            let you_owe: f64 = -lines.iter().map(|l| l.owed_by_partner).filter(|owed| *owed < 0.0).sum::<f64>();
+            Settlement { partner, partner_owes, you_owe, net: partner_owes - you_owe, lines }
+        })
+        .collect()
+}
+
+// Every shared charge with its split, then who pays whom, per partner
+fn settlement_csv(settlements: &[Settlement]) -> Result<Vec<u8>, String> {
+    let write_error = |e: &dyn std::fmt::Display| format!("Could not write settlement: {}", e);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["Partner", "Date", "Description", "Amount", "Partner share %", "Paid by", "Owed by partner"])
+        .map_err(|e| write_error(&e))?;
+
+    for settlement in settlements {
+        for line in &settlement.lines {
+            writer.write_record([
+                settlement.partner.as_str(),
+                line.date.as_str(),
+                line.description.as_str(),
+                &format!("{:.2}", line.amount),
+                &format!("{:.0}", line.partner_share),
+                if line.paid_by_partner { settlement.partner.as_str() } else { "You" },
+                &format!("{:.2}", line.owed_by_partner),
+            ]).map_err(|e| write_error(&e))?;
+        }
+        let summary = if settlement.net >= 0.0 {
+            format!("{} owes you", settlement.partner)
+        } else {
+            format!("You owe {}", settlement.partner)
+        };
+        writer.write_record([settlement.partner.as_str(), "", &summary, "", "", "", &format!("{:.2}", settlement.net.abs())])
+            .map_err(|e| write_error(&e))?;
+    }
+    writer.into_inner().map_err(|e| write_error(&e))
+}
+
+async fn checked_settlements(app: &AppHandle, period: Option<&str>) -> Result<Vec<Settlement>, String> {
+    let locale = settings::load_settings(app)?.locale;
+    if let Some(period) = period.filter(|p| !valid_period(p)) {
+        return Err(i18n::text(&locale, "error.period_invalid", &[("period", period.to_string())]));
+    }
+    let store = state::read_store(app).await?;
+    let settlements = settle(&store, period);
+    if settlements.is_empty() {
+        return Err(i18n::error(&locale, "error.no_shared_expenses"));
+    }
+    Ok(settlements)
+}
+
+#[command]
+#[specta::specta]
+pub async fn settle_shared_expenses(app: AppHandle, period: Option<String>) -> Result<Vec<Settlement>, String> {
+    checked_settlements(&app, period.as_deref()).await
+}
+
+// Writes the settlement as CSV to send to the partner
+#[command]
+#[specta::specta]
+pub async fn export_settlement(app: AppHandle, period: Option<String>, file_path: String) -> Result<(), String> {
+    let settlements = checked_settlements(&app, period.as_deref()).await?;
+    let content = settlement_csv(&settlements)?;
+    tokio::fs::write(&file_path, content).await
+        .map_err(|e| format!("Could not write settlement: {}", e))
+}
@@ -0,0 +1,37 @@
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Wry};
+use tauri_plugin_global_shortcut::{Builder, GlobalShortcutExt, Shortcut, ShortcutState};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::settings::Settings;
+
+use crate::opened_files;
+
+// Only the import shortcut is ever registered, so any press is that one
+pub fn plugin() -> TauriPlugin<Wry> {
+    Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                opened_files::focus(app);
+                opened_files::pick_file(app);
+            }
+        })
+        .build()
+}
+
+// Registers the shortcut the settings name, replacing whatever was registered before. Fails
+// when the keys can't be read as a shortcut or another app already holds them.
+pub fn apply(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    shortcuts.unregister_all()
+        .map_err(|e| format!("Could not release the import shortcut: {}", e))?;
+    let Some(keys) = settings.import_shortcut.as_deref() else {
+        return Ok(());
+    };
+    let shortcut: Shortcut = keys.parse()
+        .map_err(|_| i18n::text(&settings.locale, "error.shortcut_invalid", &[("shortcut", keys.to_string())]))?;
+    shortcuts.register(shortcut)
+        .map_err(|_| i18n::text(&settings.locale, "error.shortcut_unavailable", &[("shortcut", keys.to_string())]))?;
+    log!("Registered import shortcut {}", keys);
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::categories::{self, CategoryClass};
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::{monthly_factor, transaction_category, Transaction};
+use credit_analyzer_store::Store;
+
+use crate::settings;
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CutSimulation {
+    pub category: String,
+    pub percent: f64,
+    pub monthly_category_total: f64,
+    pub monthly_category_after: f64,
+    pub monthly_total: f64,
+    pub monthly_after: f64,
+    pub monthly_savings: f64,
+    pub annual_total: f64,
+    pub annual_after: f64,
+    pub annual_savings: f64,
+}
+
+// Label for a cut across every discretionary ("want") category
+const DISCRETIONARY_LABEL: &str = "Wants";
+
+// Cuts `percent` off the spending `in_scope` selects; errors with `empty_error` when it selects none
+fn simulate_cut_where(
+    transactions: &[Transaction],
+    category: &str,
+    percent: f64,
+    locale: &str,
+    in_scope: impl Fn(&str) -> bool,
+    empty_error: impl FnOnce() -> String,
+) -> Result<CutSimulation, String> {
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(i18n::error(locale, "error.reduction_out_of_range"));
+    }
+
+    let total: f64 = transactions.iter().map(|t| t.amount).sum();
+    let category_total: f64 = transactions.iter()
+        .filter(|t| in_scope(&transaction_category(t)))
+        .map(|t| t.amount)
+        .sum();
+
+    if category_total == 0.0 {
+        return Err(empty_error());
+    }
+
+    // Normalize the statement to a month so different cycle lengths compare
+    let factor = monthly_factor(transactions);
+    let monthly_total = total * factor;
+    let monthly_category_total = category_total * factor;
+    let monthly_savings = monthly_category_total * percent / 100.0;
+
+    Ok(CutSimulation {
+        category: category.to_string(),
+        percent,
+        monthly_category_total,
+        monthly_category_after: monthly_category_total - monthly_savings,
+        monthly_total,
+        monthly_after: monthly_total - monthly_savings,
+        monthly_savings,
+        annual_total: monthly_total * 12.0,
+        annual_after: (monthly_total - monthly_savings) * 12.0,
+        annual_savings: monthly_savings * 12.0,
+    })
+}
+
+pub fn simulate_category_cut(transactions: &[Transaction], category: &str, percent: f64, locale: &str) -> Result<CutSimulation, String> {
+    simulate_cut_where(
+        transactions, category, percent, locale,
+        |name| name.eq_ignore_ascii_case(category),
+        || i18n::text(locale, "error.category_empty", &[("category", category.to_string())]),
+    )
+}
+
+// The same what-if across all spending classed as a want, however it's split into categories
+pub fn simulate_discretionary_cut(transactions: &[Transaction], percent: f64, classes: &BTreeMap<String, CategoryClass>, locale: &str) -> Result<CutSimulation, String> {
+    simulate_cut_where(
+        transactions, DISCRETIONARY_LABEL, percent, locale,
+        |name| categories::class(name, classes) == CategoryClass::Want,
+        || i18n::error(locale, "error.no_discretionary_spending"),
+    )
+}
+
+// The given statement's transactions, or the latest statement's
+fn statement_transactions<'a>(store: &'a Store, statement_id: Option<u64>, locale: &str) -> Result<&'a [Transaction], String> {
+    let statement = match statement_id {
+        Some(id) => store.statement(id)
+            .ok_or_else(|| i18n::text(locale, "error.statement_not_found", &[("id", id.to_string())]))?,
+        None => store.latest_statement()
+            .ok_or_else(|| i18n::error(locale, "error.no_statements"))?,
+    };
+    Ok(&statement.transactions)
+}
+
+#[command]
+#[specta::specta]
+pub async fn simulate_cut(app: AppHandle, category: String, percent: f64, statement_id: Option<u64>) -> Result<CutSimulation, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let store = state::read_store(&app).await?;
+    let transactions = statement_transactions(&store, statement_id, &locale)?;
+
+    simulate_category_cut(transactions, &category, percent, &locale)
+}
+
+#[command]
+#[specta::specta]
+pub async fn simulate_discretionary(app: AppHandle, percent: f64, statement_id: Option<u64>) -> Result<CutSimulation, String> {
+    let settings = settings::load_settings(&app)?;
+    let store = state::read_store(&app).await?;
+    let transactions = statement_transactions(&store, statement_id, &settings.locale)?;
+
+    simulate_discretionary_cut(transactions, percent, &settings.category_classes, &settings.locale)
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Manager};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_store::Store;
+
+use crate::{demo, events, settings, state};
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+// Demo data gets its own, so restoring can never move it into the real store
+const DEMO_SNAPSHOTS_DIR: &str = "demo_snapshots";
+const INDEX_FILE: &str = "index.json";
+const COMPRESSION_LEVEL: i32 = 3;
+// Automatic snapshots beyond this are dropped oldest first; named ones stay until deleted
+const MAX_AUTOMATIC_SNAPSHOTS: usize = 10;
+
+// What a snapshot was taken before. Named snapshots the user takes are Manual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum SnapshotReason {
+    Manual,
+    StoreUpgrade,
+    HistoryImport,
+    BulkEdit,
+    Recategorize,
+    Reimport,
+    Restore,
+    Repair,
+    AppUpdate,
+    ConfigImport,
+}
+
+impl SnapshotReason {
+    fn label(self) -> &'static str {
+        match self {
+            SnapshotReason::Manual => "Snapshot",
+            SnapshotReason::StoreUpgrade => "Before store upgrade",
+            SnapshotReason::HistoryImport => "Before history import",
+            SnapshotReason::BulkEdit => "Before bulk edit",
+            SnapshotReason::Recategorize => "Before recategorizing",
+            SnapshotReason::Reimport => "Before re-importing originals",
+            SnapshotReason::Restore => "Before restoring a snapshot",
+            SnapshotReason::Repair => "Before repairing data",
+            SnapshotReason::AppUpdate => "Before updating the app",
+            SnapshotReason::ConfigImport => "Before replacing the configuration",
+        }
+    }
+}
+
+// A copy of the whole store at one moment, kept compressed beside it. Unlike an export it
+// holds everything the app knows, and restoring one puts the store back exactly as it was.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Snapshot {
+    pub id: u64,
+    pub name: String,
+    pub reason: SnapshotReason,
+    pub taken_at: String,
+    pub statements: usize,
+    pub compressed_size: u64,
+}
+
+fn snapshots_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir()
+        .map_err(|e| format!("Could not resolve app data directory: {}", e))?
+        .join(if demo::is_active() { DEMO_SNAPSHOTS_DIR } else { SNAPSHOTS_DIR }))
+}
+
+fn snapshot_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{}.json.zst", id))
+}
+
+fn read_index(dir: &Path) -> Result<Vec<Snapshot>, String> {
+    let path = dir.join(INDEX_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Could not read snapshots: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Snapshot index is corrupted: {}", e))
+}
+
+fn write_index(dir: &Path, snapshots: &[Snapshot]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(snapshots)
+        .map_err(|e| format!("Could not serialize snapshots: {}", e))?;
+    let path = dir.join(INDEX_FILE);
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(|e| format!("Could not write snapshots: {}", e))?;
+    std::fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Could not write snapshots: {}", e))
+}
+
+fn remove_snapshot_file(dir: &Path, id: u64) {
+    if let Err(e) = std::fs::remove_file(snapshot_path(dir, id)) {
+        log!("Could not remove snapshot {}: {}", id, e);
+    }
+}
+
+fn write_snapshot(dir: &Path, store: &Store, name: String, reason: SnapshotReason) -> Result<Snapshot, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Could not create snapshots directory: {}", e))?;
+    let mut index = read_index(dir)?;
+
+    // Ids are the time taken, nudged past the newest so two in one millisecond don't collide
+    let now = chrono::Utc::now();
+    let newest = index.iter().map(|s| s.id).max().unwrap_or(0);
+    let id = (now.timestamp_millis().max(0) as u64).max(newest + 1);
+
+    let json = serde_json::to_vec(store)
+        .map_err(|e| format!("Could not serialize store: {}", e))?;
+    let compressed = zstd::encode_all(json.as_slice(), COMPRESSION_LEVEL)
+        .map_err(|e| format!("Could not compress snapshot: {}", e))?;
+    std::fs::write(snapshot_path(dir, id), &compressed)
+        .map_err(|e| format!("Could not write snapshot: {}", e))?;
+
+    let snapshot = Snapshot {
+        id,
+        name,
+        reason,
+        taken_at: now.to_rfc3339(),
+        statements: store.statements.len(),
+        compressed_size: compressed.len() as u64,
+    };
+    index.push(snapshot.clone());
+
+    let automatic: Vec<u64> = index.iter().filter(|s| s.reason != SnapshotReason::Manual).map(|s| s.id).collect();
+    let expired = &automatic[..automatic.len().saturating_sub(MAX_AUTOMATIC_SNAPSHOTS)];
+    index.retain(|s| !expired.contains(&s.id));
+    write_index(dir, &index)?;
+    for &id in expired {
+        remove_snapshot_file(dir, id);
+    }
+    Ok(snapshot)
+}
+
+// Takes a snapshot before a risky change. Callers hold the store's write lock, so what's
+// captured is exactly what the change starts from; a failure here should stop the change.
+pub fn take(app: &AppHandle, store: &Store, reason: SnapshotReason) -> Result<Snapshot, String> {
+    let dir = snapshots_dir(app)?;
+    // Compressing a large store blocks, and the store can't move to a blocking task without
+    // copying it
+    let snapshot = tokio::task::block_in_place(|| write_snapshot(&dir, store, reason.label().to_string(), reason))?;
+    log!("Took snapshot {} ({})", snapshot.id, snapshot.name);
+    Ok(snapshot)
+}
+
+#[command]
+#[specta::specta]
+pub async fn create_snapshot(app: AppHandle, name: String) -> Result<Snapshot, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(i18n::error(&locale, "error.snapshot_name_empty"));
+    }
+    let dir = snapshots_dir(&app)?;
+    // The write lock also keeps two snapshots from updating the index at once
+    let store = state::write_store(&app).await?;
+    let snapshot = tokio::task::block_in_place(|| write_snapshot(&dir, &store, name, SnapshotReason::Manual))?;
+    log!("Took snapshot {} ({})", snapshot.id, snapshot.name);
+    Ok(snapshot)
+}
+
+// Newest first
+#[command]
+#[specta::specta]
+pub async fn list_snapshots(app: AppHandle) -> Result<Vec<Snapshot>, String> {
+    let mut snapshots = read_index(&snapshots_dir(&app)?)?;
+    snapshots.reverse();
+    Ok(snapshots)
+}
+
+// Puts the store back as it was when the snapshot was taken. The store as it stands is
+// snapshotted first, so a restore can itself be undone.
+#[command]
+#[specta::specta]
+pub async fn restore_snapshot(app: AppHandle, id: u64) -> Result<Snapshot, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let dir = snapshots_dir(&app)?;
+    let mut store = state::write_store(&app).await?;
+    let snapshot = read_index(&dir)?.into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| i18n::error(&locale, "error.snapshot_not_found"))?;
+
+    let restored = tokio::task::block_in_place(|| -> Result<Store, String> {
+        let compressed = std::fs::read(snapshot_path(&dir, id))
+            .map_err(|e| format!("Snapshot is missing: {}", e))?;
+        let json = zstd::decode_all(compressed.as_slice())
+            .map_err(|e| format!("Snapshot is damaged: {}", e))?;
+        let mut restored: Store = serde_json::from_slice(&json)
+            .map_err(|e| format!("Snapshot is damaged: {}", e))?;
+        if restored.is_newer() {
+            return Err(i18n::text(&locale, "error.store_newer_version", &[("version", restored.schema_version.to_string())]));
+        }
+        // Snapshots taken by an older version need the same upgrade as its stores
+        restored.migrate();
+        Ok(restored)
+    })?;
+
+    take(&app, &store, SnapshotReason::Restore)?;
+    **store = restored;
+    store.save()?;
+    log!("Restored snapshot {} ({})", snapshot.id, snapshot.name);
+    events::all_transactions_changed(&app, &store);
+    Ok(snapshot)
+}
+
+#[command]
+#[specta::specta]
+pub async fn delete_snapshot(app: AppHandle, id: u64) -> Result<(), String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let dir = snapshots_dir(&app)?;
+    let _store = state::write_store(&app).await?;
+    let mut index = read_index(&dir)?;
+    if !index.iter().any(|s| s.id == id) {
+        return Err(i18n::error(&locale, "error.snapshot_not_found"));
+    }
+    index.retain(|s| s.id != id);
+    write_index(&dir, &index)?;
+    remove_snapshot_file(&dir, id);
+    Ok(())
+}
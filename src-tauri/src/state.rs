@@ -0,0 +1,149 @@
+use std::fs;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tokio::sync::{RwLock, RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard};
+
+use credit_analyzer_core::bank_profiles::BankProfile;
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::settings::Settings;
+use credit_analyzer_store::Store;
+
+use crate::{demo, settings};
+use crate::snapshots::{self, SnapshotReason};
+
+const STORE_FILE: &str = "store.json";
+
+// The store as loaded from `path`, which moves when demo mode is switched on or off
+pub struct OpenStore {
+    path: PathBuf,
+    store: Store,
+}
+
+impl OpenStore {
+    // Writes the store back to the file it was loaded from
+    pub fn save(&self) -> Result<(), String> {
+        self.store.save(&self.path)
+    }
+}
+
+impl Deref for OpenStore {
+    type Target = Store;
+
+    fn deref(&self) -> &Store {
+        &self.store
+    }
+}
+
+impl DerefMut for OpenStore {
+    fn deref_mut(&mut self) -> &mut Store {
+        &mut self.store
+    }
+}
+
+// Managed by Tauri so every command shares one copy of the store and settings. A command that
+// changes the store holds the write lock from reading it to saving it, so concurrent imports
+// and edits can't overwrite each other's changes.
+#[derive(Default)]
+pub struct AppState {
+    store: RwLock<Option<OpenStore>>,
+    // A std lock is enough: it's never held across an await
+    settings: std::sync::RwLock<Option<Settings>>,
+}
+
+// Where the store lives, without creating anything
+fn store_file(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir()
+        .map_err(|e| format!("Could not resolve app data directory: {}", e))?;
+    Ok(dir.join(if demo::is_active() { demo::DEMO_STORE_FILE } else { STORE_FILE }))
+}
+
+pub fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let path = store_file(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Could not create app data directory: {}", e))?;
+    }
+    Ok(path)
+}
+
+fn is_current(slot: &Option<OpenStore>, path: &Path) -> bool {
+    slot.as_ref().is_some_and(|open| open.path == path)
+}
+
+fn not_loaded() -> String {
+    "Store is not loaded".to_string()
+}
+
+// Loads the store on first use, and again after demo mode moves it. Every store is stamped with
+// this version, so a new one reports it too; one an older version wrote is snapshotted before
+// it's upgraded; the upgrade goes ahead even if that fails, as the
+// store on disk is only replaced at the next save. One a later version wrote isn't opened at
+// all, since saving it would drop whatever that version added.
+fn load_into(app: &AppHandle, slot: &mut Option<OpenStore>, path: PathBuf) -> Result<(), String> {
+    if !is_current(slot, &path) {
+        let mut store = Store::load_unmigrated(&path)?;
+        if store.is_newer() {
+            let locale = settings::load_settings(app)?.locale;
+            return Err(i18n::text(&locale, "error.store_newer_version", &[("version", store.schema_version.to_string())]));
+        }
+        store.upgrade(|old| {
+            if let Err(e) = snapshots::take(app, old, SnapshotReason::StoreUpgrade) {
+                log!("Could not snapshot store before upgrading it: {}", e);
+            }
+        });
+        *slot = Some(OpenStore { path, store });
+    }
+    Ok(())
+}
+
+pub async fn read_store(app: &AppHandle) -> Result<RwLockReadGuard<'_, Store>, String> {
+    let path = store_path(app)?;
+    let state = app.state::<AppState>().inner();
+
+    let slot = state.store.read().await;
+    let slot = if is_current(&slot, &path) {
+        slot
+    } else {
+        drop(slot);
+        let mut slot = state.store.write().await;
+        load_into(app, &mut slot, path)?;
+        slot.downgrade()
+    };
+    RwLockReadGuard::try_map(slot, |slot| slot.as_ref().map(|open| &open.store)).map_err(|_| not_loaded())
+}
+
+pub async fn write_store(app: &AppHandle) -> Result<RwLockMappedWriteGuard<'_, OpenStore>, String> {
+    let path = store_path(app)?;
+    let mut slot = app.state::<AppState>().inner().store.write().await;
+    load_into(app, &mut slot, path)?;
+    RwLockWriteGuard::try_map(slot, Option::as_mut).map_err(|_| not_loaded())
+}
+
+// The store as its file holds it: not upgraded, not snapshotted, and nothing created when
+// there's no file yet
+pub fn read_only(path: &Path) -> Result<Store, String> {
+    Store::load_unmigrated(path)
+}
+
+// The bank profiles a private analysis parses with, from the loaded store if there is one and
+// otherwise straight from the file, so a private analysis leaves nothing on disk
+pub async fn private_bank_profiles(app: &AppHandle) -> Result<Vec<BankProfile>, String> {
+    let path = store_file(app)?;
+    let slot = app.state::<AppState>().inner().store.read().await;
+    if let Some(open) = slot.as_ref().filter(|open| open.path == path) {
+        return Ok(open.store.bank_profiles.clone());
+    }
+    drop(slot);
+    Ok(read_only(&path)?.bank_profiles)
+}
+
+pub fn cached_settings(app: &AppHandle) -> Option<Settings> {
+    app.state::<AppState>().settings.read().ok()?.clone()
+}
+
+pub fn cache_settings(app: &AppHandle, settings: &Settings) {
+    if let Ok(mut cached) = app.state::<AppState>().settings.write() {
+        *cached = Some(settings.clone());
+    }
+}
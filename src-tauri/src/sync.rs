@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Manager};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_store::sync::{SyncLedger, SyncRecord};
+
+use crate::secrets::{self, SecretKey};
+use crate::{demo, events, settings, state};
+
+// Made inside the chosen folder, so it can sit among the user's other files
+const SYNC_DIR: &str = "Credit Analyzer Sync";
+const SYNC_EXTENSION: &str = "sync";
+const LEDGER_FILE: &str = "sync_ledger.json";
+// Starts every sync file; a new layout gets a new marker
+const MAGIC: &[u8] = b"CAS1";
+const COMPRESSION_LEVEL: i32 = 3;
+const KEY_LEN: usize = 32;
+#[cfg(feature = "sync")]
+const NONCE_LEN: usize = 24;
+#[cfg(feature = "sync")]
+const SYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+// What one device writes to the folder: every statement it knows of, and the deletions it's
+// seen. Each device writes only its own file, so the folder's service never has to merge two.
+#[derive(Debug, Serialize, Deserialize)]
+struct Published {
+    device_id: String,
+    written_at: i64,
+    records: Vec<SyncRecord>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct SyncSummary {
+    // Other devices whose files were read
+    pub devices: usize,
+    // Statements added, changed or removed here by their changes
+    pub received: usize,
+    // Statements this device published
+    pub published: usize,
+    // Files that couldn't be read, as from a device with a different key
+    pub unreadable: Vec<String>,
+}
+
+// The key is shown to the user as hex, to type or paste on their other devices
+fn encode_key(key: &[u8; KEY_LEN]) -> String {
+    key.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_key(hex: &str) -> Option<[u8; KEY_LEN]> {
+    let hex = hex.trim();
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+#[cfg(feature = "sync")]
+fn generate_key(_locale: &str) -> Result<[u8; KEY_LEN], String> {
+    use chacha20poly1305::aead::{KeyInit, OsRng};
+    Ok(chacha20poly1305::XChaCha20Poly1305::generate_key(&mut OsRng).into())
+}
+
+// XChaCha20-Poly1305 with a random nonce per write, stored ahead of the ciphertext
+#[cfg(feature = "sync")]
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8], _locale: &str) -> Result<Vec<u8>, String> {
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use chacha20poly1305::XChaCha20Poly1305;
+
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = XChaCha20Poly1305::new(key.into()).encrypt(&nonce, plaintext)
+        .map_err(|_| "Could not encrypt sync file".to_string())?;
+    Ok([nonce.as_slice(), &sealed].concat())
+}
+
+// None when the key is wrong or the file was tampered with
+#[cfg(feature = "sync")]
+fn open(key: &[u8; KEY_LEN], sealed: &[u8]) -> Option<Vec<u8>> {
+    use chacha20poly1305::aead::{Aead, KeyInit};
+    use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+    if sealed.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key.into()).decrypt(XNonce::from_slice(nonce), ciphertext).ok()
+}
+
+// Builds without the sync feature keep the commands, so the frontend's bindings don't change,
+// and refuse here as well as up front
+#[cfg(not(feature = "sync"))]
+fn generate_key(locale: &str) -> Result<[u8; KEY_LEN], String> {
+    Err(i18n::error(locale, "error.sync_not_built"))
+}
+
+#[cfg(not(feature = "sync"))]
+fn seal(_key: &[u8; KEY_LEN], _plaintext: &[u8], locale: &str) -> Result<Vec<u8>, String> {
+    Err(i18n::error(locale, "error.sync_not_built"))
+}
+
+#[cfg(not(feature = "sync"))]
+fn open(_key: &[u8; KEY_LEN], _sealed: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+
+fn ensure_available(locale: &str) -> Result<(), String> {
+    if !cfg!(feature = "sync") {
+        return Err(i18n::error(locale, "error.sync_not_built"));
+    }
+    // Demo data never leaves this device
+    if demo::is_active() {
+        return Err(i18n::error(locale, "error.sync_demo"));
+    }
+    Ok(())
+}
+
+fn load_key(locale: &str) -> Result<[u8; KEY_LEN], String> {
+    let hex = secrets::get_secret(SecretKey::SyncKey)?
+        .ok_or_else(|| i18n::error(locale, "error.sync_key_missing"))?;
+    decode_key(&hex).ok_or_else(|| i18n::error(locale, "error.sync_key_invalid"))
+}
+
+fn ledger_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir()
+        .map_err(|e| format!("Could not resolve app data directory: {}", e))?
+        .join(LEDGER_FILE))
+}
+
+// A new device names itself the first time it syncs
+fn load_ledger(path: &Path) -> Result<SyncLedger, String> {
+    if !path.exists() {
+        let now = chrono::Utc::now().timestamp_millis();
+        return Ok(SyncLedger { device_id: format!("{:x}-{:x}", now, std::process::id()), ..Default::default() });
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read sync ledger: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Sync ledger is corrupted: {}", e))
+}
+
+fn save_ledger(path: &Path, ledger: &SyncLedger) -> Result<(), String> {
+    let content = serde_json::to_string(ledger)
+        .map_err(|e| format!("Could not serialize sync ledger: {}", e))?;
+    // Like the store, so a crash mid-write can't leave a ledger that makes every statement new
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(|e| format!("Could not write sync ledger: {}", e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Could not replace sync ledger: {}", e))
+}
+
+fn read_published(path: &Path, key: &[u8; KEY_LEN]) -> Option<Published> {
+    let bytes = std::fs::read(path).ok()?;
+    let compressed = open(key, bytes.strip_prefix(MAGIC)?)?;
+    let json = zstd::decode_all(compressed.as_slice()).ok()?;
+    serde_json::from_slice(&json).ok()
+}
+
+// Every other device's file. A service's conflicted copy of a file reads as one more device,
+// which is harmless: its versions are older and lose.
+fn read_devices(dir: &Path, own_device: &str, key: &[u8; KEY_LEN]) -> (Vec<Published>, Vec<String>) {
+    let (mut devices, mut unreadable) = (Vec::new(), Vec::new());
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (devices, unreadable);
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let is_sync_file = path.extension().is_some_and(|extension| extension == SYNC_EXTENSION);
+        let is_own = path.file_stem().is_some_and(|stem| stem == own_device);
+        if !is_sync_file || is_own {
+            continue;
+        }
+        match read_published(&path, key) {
+            Some(published) => devices.push(published),
+            None => unreadable.push(path.to_string_lossy().into_owned()),
+        }
+    }
+    (devices, unreadable)
+}
+
+fn write_published(dir: &Path, published: &Published, key: &[u8; KEY_LEN], locale: &str) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Could not create sync directory: {}", e))?;
+    let json = serde_json::to_vec(published)
+        .map_err(|e| format!("Could not serialize sync file: {}", e))?;
+    let compressed = zstd::encode_all(json.as_slice(), COMPRESSION_LEVEL)
+        .map_err(|e| format!("Could not compress sync file: {}", e))?;
+    let content = [MAGIC, &seal(key, &compressed, locale)?].concat();
+
+    // Renamed into place, so other devices never read half a file
+    let path = dir.join(&published.device_id).with_extension(SYNC_EXTENSION);
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(|e| format!("Could not write sync file: {}", e))?;
+    std::fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Could not write sync file: {}", e))
+}
+
+// Merges in the other devices' changes, then publishes this device's view. The store is saved
+// before the ledger, so a failure in between only makes the next sync publish again.
+async fn run(app: &AppHandle) -> Result<SyncSummary, String> {
+    let settings = settings::load_settings(app)?;
+    let locale = settings.locale;
+    ensure_available(&locale)?;
+    let folder = settings.sync_folder.ok_or_else(|| i18n::error(&locale, "error.sync_folder_required"))?;
+    if !Path::new(&folder).is_dir() {
+        return Err(i18n::text(&locale, "error.sync_folder_missing", &[("folder", folder)]));
+    }
+    let key = load_key(&locale)?;
+    let dir = Path::new(&folder).join(SYNC_DIR);
+    let ledger_path = ledger_path(app)?;
+
+    // Held throughout, so two syncs can't interleave their ledgers
+    let mut store = state::write_store(app).await?;
+    let mut ledger = load_ledger(&ledger_path)?;
+    let (devices, unreadable) = tokio::task::block_in_place(|| read_devices(&dir, &ledger.device_id, &key));
+
+    let now = chrono::Utc::now().timestamp_millis();
+    store.assign_sync_ids();
+    store.note_local_changes(&mut ledger, now);
+    let mut summary = SyncSummary { devices: devices.len(), unreadable, ..Default::default() };
+    for device in devices {
+        summary.received += store.apply_sync_records(&mut ledger, device.records);
+    }
+    let published = Published { device_id: ledger.device_id.clone(), written_at: now, records: store.sync_records(&ledger) };
+    summary.published = published.records.iter().filter(|r| r.statement.is_some()).count();
+
+    store.save()?;
+    save_ledger(&ledger_path, &ledger)?;
+    tokio::task::block_in_place(|| write_published(&dir, &published, &key, &locale))?;
+    if summary.received > 0 {
+        events::all_transactions_changed(app, &store);
+    }
+    log!("Synced with {} devices: {} received, {} published", summary.devices, summary.received, summary.published);
+    Ok(summary)
+}
+
+// Makes a new key for a first device and stores it in the keychain. The returned key is entered
+// on the other devices with set_secret; a device with a different key can't read this one's file.
+#[command]
+#[specta::specta]
+pub async fn create_sync_key(app: AppHandle) -> Result<String, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    ensure_available(&locale)?;
+    let key = encode_key(&generate_key(&locale)?);
+    secrets::put_secret(SecretKey::SyncKey, &key, &locale)?;
+    Ok(key)
+}
+
+#[command]
+#[specta::specta]
+pub async fn sync_now(app: AppHandle) -> Result<SyncSummary, String> {
+    run(&app).await
+}
+
+// Syncs every SYNC_INTERVAL while a sync folder is set. Settings are read on every pass, so
+// setting or clearing the folder needs no restart.
+#[cfg(feature = "sync")]
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let enabled = settings::load_settings(&app).is_ok_and(|s| s.sync_folder.is_some()) && !demo::is_active();
+            if enabled {
+                if let Err(e) = run(&app).await {
+                    log!("Sync failed: {}", e);
+                }
+            }
+            tokio::time::sleep(SYNC_INTERVAL).await;
+        }
+    });
+}
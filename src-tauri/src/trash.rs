@@ -0,0 +1,65 @@
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_store::trash::TrashedTransaction;
+
+use crate::bulk::{save_or_revert, Checkpoint};
+use crate::events;
+use crate::manual::AddedTransaction;
+use crate::settings;
+use crate::state;
+
+// Moves a transaction to the trash, e.g. a disputed charge. It drops out of every analysis
+// but can be restored for 30 days.
+#[command]
+#[specta::specta]
+pub async fn delete_transaction(app: AppHandle, statement_id: u64, index: usize) -> Result<TrashedTransaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+
+    let checkpoint = Checkpoint::new(&store, store.statement(statement_id).cloned().into_iter().collect());
+    store.purge_trash();
+    let id = store.trash_transaction(statement_id, index)
+        .ok_or_else(|| i18n::text(&locale, "error.transaction_not_found", &[
+            ("id", statement_id.to_string()),
+            ("index", index.to_string()),
+        ]))?;
+    let trashed = store.trash.iter().find(|t| t.id == id).cloned().ok_or("Trashed transaction went missing")?;
+
+    save_or_revert(&mut store, checkpoint)?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(trashed)
+}
+
+// Newest first
+#[command]
+#[specta::specta]
+pub async fn list_trash(app: AppHandle) -> Result<Vec<TrashedTransaction>, String> {
+    let mut store = state::write_store(&app).await?;
+    let before = store.trash.len();
+    store.purge_trash();
+    if store.trash.len() < before {
+        store.save()?;
+    }
+    Ok(store.trash.iter().rev().cloned().collect())
+}
+
+#[command]
+#[specta::specta]
+pub async fn restore_transaction(app: AppHandle, id: u64) -> Result<AddedTransaction, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    let mut store = state::write_store(&app).await?;
+
+    let statement = store.trash.iter().find(|t| t.id == id).and_then(|t| store.statement(t.statement_id)).cloned();
+    let checkpoint = Checkpoint::new(&store, statement.into_iter().collect());
+    let (statement_id, index) = store.restore_transaction(id)
+        .ok_or_else(|| i18n::text(&locale, "error.trash_not_found", &[("id", id.to_string())]))?;
+    let transaction = store.statement(statement_id)
+        .and_then(|s| s.transactions.get(index))
+        .cloned()
+        .ok_or("Restored transaction went missing")?;
+
+    save_or_revert(&mut store, checkpoint)?;
+    events::transactions_changed(&app, vec![statement_id]);
+    Ok(AddedTransaction { statement_id, index, transaction })
+}
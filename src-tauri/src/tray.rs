@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{command, AppHandle, Listener, Manager};
+
+use credit_analyzer_core::{goals, i18n};
+use credit_analyzer_store::Store;
+
+use crate::{dashboard, events, opened_files, settings, state};
+
+const TRAY_ID: &str = "main";
+
+// The few numbers the tray shows, cheap enough to recompute after every change
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct QuickStats {
+    // "2024-01"; the latest month with imported spending, as on the dashboard
+    pub month: Option<String>,
+    pub spend: f64,
+    pub budgets_met: usize,
+    pub budgets_total: usize,
+}
+
+pub fn quick_stats(store: &Store) -> QuickStats {
+    // Rollup months sort as text, so the largest is the latest
+    let month = store.rollups.iter()
+        .map(|r| &r.month)
+        .filter(|m| m.as_str() != "Unknown")
+        .max()
+        .cloned();
+    let spend = month.as_deref().map_or(0.0, |m| dashboard::month_total(store, m));
+    let budgets = goals::latest_statuses(&store.goals);
+    QuickStats {
+        month,
+        spend,
+        budgets_met: budgets.iter().filter(|b| b.met).count(),
+        budgets_total: budgets.len(),
+    }
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_quick_stats(app: AppHandle) -> Result<QuickStats, String> {
+    let store = state::read_store(&app).await?;
+    Ok(quick_stats(&store))
+}
+
+// The menu rows whose text follows the stats
+struct TrayItems {
+    spend: MenuItem<tauri::Wry>,
+    budgets: MenuItem<tauri::Wry>,
+}
+
+fn locale(app: &AppHandle) -> String {
+    settings::load_settings(app).map(|s| s.locale).unwrap_or_else(|_| i18n::DEFAULT_LOCALE.to_string())
+}
+
+fn spend_text(stats: &QuickStats, locale: &str) -> String {
+    match &stats.month {
+        Some(month) => i18n::text(locale, "tray.spend", &[("month", month.clone()), ("amount", format!("{:.2}", stats.spend))]),
+        None => i18n::text(locale, "tray.no_spend", &[]),
+    }
+}
+
+fn budgets_text(stats: &QuickStats, locale: &str) -> String {
+    if stats.budgets_total == 0 {
+        return i18n::text(locale, "tray.no_budgets", &[]);
+    }
+    i18n::text(locale, "tray.budgets", &[
+        ("met", stats.budgets_met.to_string()),
+        ("total", stats.budgets_total.to_string()),
+    ])
+}
+
+async fn refresh(app: AppHandle) {
+    let stats = match state::read_store(&app).await {
+        Ok(store) => quick_stats(&store),
+        Err(e) => {
+            log!("Could not refresh tray stats: {}", e);
+            return;
+        }
+    };
+    let locale = locale(&app);
+    let (spend, budgets) = (spend_text(&stats, &locale), budgets_text(&stats, &locale));
+    let items = app.state::<TrayItems>();
+    if let Err(e) = items.spend.set_text(&spend).and_then(|_| items.budgets.set_text(&budgets)) {
+        log!("Could not update tray menu: {}", e);
+    }
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(format!("{}\n{}", spend, budgets)));
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        "quick_import" => opened_files::pick_file(app),
+        "open" => opened_files::focus(app),
+        "quit" => app.exit(0),
+        _ => {}
+    }
+}
+
+// Adds the tray icon and keeps its stats current as the store changes
+pub fn build(app: &AppHandle) -> tauri::Result<()> {
+    let locale = locale(app);
+    let spend = MenuItem::with_id(app, "stats_spend", i18n::text(&locale, "tray.no_spend", &[]), false, None::<&str>)?;
+    let budgets = MenuItem::with_id(app, "stats_budgets", i18n::text(&locale, "tray.no_budgets", &[]), false, None::<&str>)?;
+    let quick_import = MenuItem::with_id(app, "quick_import", i18n::text(&locale, "tray.quick_import", &[]), true, None::<&str>)?;
+    let open = MenuItem::with_id(app, "open", i18n::text(&locale, "tray.open", &[]), true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", i18n::text(&locale, "tray.quit", &[]), true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[
+        &spend,
+        &budgets,
+        &PredefinedMenuItem::separator(app)?,
+        &quick_import,
+        &open,
+        &PredefinedMenuItem::separator(app)?,
+        &quit,
+    ])?;
+
+    let mut tray = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(on_menu_event);
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+    app.manage(TrayItems { spend, budgets });
+
+    for event in [events::TRANSACTIONS_CHANGED, events::BUDGETS_UPDATED] {
+        let handle = app.clone();
+        app.listen(event, move |_| {
+            tauri::async_runtime::spawn(refresh(handle.clone()));
+        });
+    }
+    tauri::async_runtime::spawn(refresh(app.clone()));
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::settings::UpdateChannel;
+use credit_analyzer_store::{MIGRATIONS, SCHEMA_VERSION};
+
+use crate::snapshots::{self, SnapshotReason};
+use crate::{settings, state};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct VersionInfo {
+    pub app_version: String,
+    pub channel: UpdateChannel,
+    // The store format this build reads and writes
+    pub schema_version: u32,
+    // The format the open store is in
+    pub data_schema_version: u32,
+}
+
+// An available update, as its release manifest describes it
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateCandidate {
+    pub version: String,
+    pub channel: UpdateChannel,
+    // The store format the update writes
+    pub schema_version: u32,
+    // The oldest format it can still upgrade; older data needs an earlier release first
+    pub min_schema_version: u32,
+    // What each of its migrations does, from version 0 like MIGRATIONS
+    #[serde(default)]
+    pub migrations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    // Reads the data as it is
+    Compatible,
+    // Upgrades the data on its first launch; a snapshot is taken before installing
+    NeedsMigration,
+    // Would leave the data unreadable, so installing is refused
+    Blocked,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MigrationStep {
+    pub from: u32,
+    pub to: u32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CompatibilityReport {
+    pub compatibility: Compatibility,
+    // Why it's blocked
+    pub reason: Option<String>,
+    // The upgrades the update will run on the data, in order
+    pub plan: Vec<MigrationStep>,
+}
+
+fn step_description(candidate: &UpdateCandidate, from: u32, locale: &str) -> String {
+    let index = from as usize;
+    candidate.migrations.get(index).cloned()
+        .or_else(|| MIGRATIONS.get(index).map(|description| description.to_string()))
+        .unwrap_or_else(|| i18n::text(locale, "update.migration_step", &[("from", from.to_string()), ("to", (from + 1).to_string())]))
+}
+
+// Whether an update can open data in `data_schema`. Moving to a build that writes an older format,
+// as leaving the beta channel can, would strand the data, and so would one that dropped the
+// upgrade from it.
+pub fn check(data_schema: u32, channel: UpdateChannel, candidate: &UpdateCandidate, locale: &str) -> CompatibilityReport {
+    let blocked = |key: &str| CompatibilityReport {
+        compatibility: Compatibility::Blocked,
+        reason: Some(i18n::text(locale, key, &[("version", candidate.version.clone())])),
+        plan: Vec::new(),
+    };
+    if candidate.channel != channel {
+        return blocked("error.update_wrong_channel");
+    }
+    if candidate.schema_version < data_schema {
+        return blocked("error.update_older_schema");
+    }
+    if data_schema < candidate.min_schema_version {
+        return blocked("error.update_schema_unsupported");
+    }
+
+    let plan: Vec<MigrationStep> = (data_schema..candidate.schema_version)
+        .map(|from| MigrationStep { from, to: from + 1, description: step_description(candidate, from, locale) })
+        .collect();
+    let compatibility = if plan.is_empty() { Compatibility::Compatible } else { Compatibility::NeedsMigration };
+    CompatibilityReport { compatibility, reason: None, plan }
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_version_info(app: AppHandle) -> Result<VersionInfo, String> {
+    let settings = settings::load_settings(&app)?;
+    let store = state::read_store(&app).await?;
+    Ok(VersionInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        channel: settings.update_channel,
+        schema_version: SCHEMA_VERSION,
+        data_schema_version: store.schema_version,
+    })
+}
+
+#[command]
+#[specta::specta]
+pub async fn check_update_compatibility(app: AppHandle, candidate: UpdateCandidate) -> Result<CompatibilityReport, String> {
+    let settings = settings::load_settings(&app)?;
+    let store = state::read_store(&app).await?;
+    Ok(check(store.schema_version, settings.update_channel, &candidate, &settings.locale))
+}
+
+// The gate an update passes right before it's installed: refuses one that would strand the
+// data, and snapshots the store ahead of one that upgrades it, so an older build can be
+// reinstalled and the snapshot restored if the upgrade goes wrong
+#[command]
+#[specta::specta]
+pub async fn prepare_update(app: AppHandle, candidate: UpdateCandidate) -> Result<CompatibilityReport, String> {
+    let settings = settings::load_settings(&app)?;
+    let store = state::write_store(&app).await?;
+    let report = check(store.schema_version, settings.update_channel, &candidate, &settings.locale);
+    match report.compatibility {
+        Compatibility::Blocked => return Err(report.reason.unwrap_or_default()),
+        Compatibility::NeedsMigration => {
+            snapshots::take(&app, &store, SnapshotReason::AppUpdate)?;
+        }
+        Compatibility::Compatible => {}
+    }
+    log!("Update to {} cleared, {} migration steps", candidate.version, report.plan.len());
+    Ok(report)
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{command, AppHandle};
+
+use credit_analyzer_core::i18n;
+use credit_analyzer_core::wallets::{detect_wallet, link_card_charges, parse_activity, Wallet};
+use credit_analyzer_store::audit::AuditAction;
+use credit_analyzer_store::{rollups, Store};
+
+use crate::events;
+use crate::settings;
+use crate::state;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct WalletImportSummary {
+    pub wallet: Wallet,
+    pub payments_imported: usize,
+    // Against the latest statement
+    pub charges_linked: usize,
+}
+
+#[command]
+#[specta::specta]
+pub async fn import_wallet_activity(app: AppHandle, file_path: String) -> Result<WalletImportSummary, String> {
+    let locale = settings::load_settings(&app)?.locale;
+
+    let content = tokio::fs::read_to_string(&file_path).await
+        .map_err(|_| i18n::error(&locale, "error.file_not_found"))?;
+    let wallet = detect_wallet(&content)
+        .ok_or("File is not a PayPal or Venmo activity export")?;
+    let payments = parse_activity(&content, wallet)?;
+
+    let mut open_store = state::write_store(&app).await?;
+    // A plain Store, so its fields can be borrowed separately
+    let store: &mut Store = &mut open_store;
+
+    // Overlapping exports replace payments rather than duplicating them
+    let payments_imported = payments.len();
+    store.wallet_payments.retain(|existing| !payments.iter().any(|p| p.id == existing.id));
+    store.wallet_payments.extend(payments);
+    store.audit(AuditAction::Import, file_path.as_str(), None::<&()>, Some(&serde_json::json!({ "wallet_payments": payments_imported })));
+
+    let charges_linked = match store.statements.last_mut() {
+        Some(statement) => {
+            rollups::subtract(&mut store.rollups, &statement.transactions);
+            let linked = link_card_charges(&mut statement.transactions, &store.wallet_payments);
+            rollups::add(&mut store.rollups, &statement.transactions);
+            linked
+        }
+        None => 0,
+    };
+
+    open_store.save()?;
+    if charges_linked > 0 {
+        events::transactions_changed(&app, open_store.statements.last().map(|s| s.id).into_iter().collect());
+    }
+
+    Ok(WalletImportSummary {
+        wallet,
+        payments_imported,
+        charges_linked,
+    })
+}
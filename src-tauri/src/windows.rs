@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+
+use credit_analyzer_core::i18n;
+
+use crate::category_detail::valid_period;
+use crate::settings;
+
+// Secondary windows are labelled with this and a number; capabilities/default.json grants
+// them the same commands as the main window
+const LABEL_PREFIX: &str = "view-";
+
+// What a secondary window shows. The window asks for its own view when it loads, so the
+// frontend is the same page in every window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WindowView {
+    // Transactions in a month or year, or all of them; two of these put months side by side
+    Ledger { period: Option<String> },
+    // compare_statements between two statements
+    Comparison { id_a: u64, id_b: u64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct OpenWindow {
+    pub label: String,
+    pub view: WindowView,
+}
+
+// The views of the secondary windows that are open, by label. Closed windows drop out as they
+// close, so this is always what's on screen.
+#[derive(Default)]
+pub struct Windows {
+    views: Mutex<BTreeMap<String, WindowView>>,
+    next: Mutex<u64>,
+}
+
+fn lock_error(e: impl std::fmt::Display) -> String {
+    format!("Window state is unavailable: {}", e)
+}
+
+fn title(view: &WindowView, locale: &str) -> String {
+    match view {
+        WindowView::Ledger { period: Some(period) } => i18n::text(locale, "window.ledger", &[("period", period.clone())]),
+        WindowView::Ledger { period: None } => i18n::text(locale, "window.ledger_all", &[]),
+        WindowView::Comparison { id_a, id_b } => i18n::text(locale, "window.comparison", &[
+            ("a", id_a.to_string()),
+            ("b", id_b.to_string()),
+        ]),
+    }
+}
+
+// Opens a window showing `view`, or brings forward the one already showing it
+#[command]
+#[specta::specta]
+pub async fn open_window(app: AppHandle, view: WindowView) -> Result<OpenWindow, String> {
+    let locale = settings::load_settings(&app)?.locale;
+    if let WindowView::Ledger { period: Some(period) } = &view {
+        if !valid_period(period) {
+            return Err(i18n::text(&locale, "error.period_invalid", &[("period", period.clone())]));
+        }
+    }
+
+    let windows = app.state::<Windows>();
+    let existing = windows.views.lock().map_err(lock_error)?
+        .iter()
+        .find(|(_, open)| **open == view)
+        .map(|(label, _)| label.clone());
+    if let Some(window) = existing.as_deref().and_then(|label| app.get_webview_window(label)) {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        return Ok(OpenWindow { label: window.label().to_string(), view });
+    }
+
+    let label = {
+        let mut next = windows.next.lock().map_err(lock_error)?;
+        *next += 1;
+        format!("{}{}", LABEL_PREFIX, next)
+    };
+    // Registered first, as the page may ask for its view before build returns
+    windows.views.lock().map_err(lock_error)?.insert(label.clone(), view.clone());
+    let built = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App("index.html".into()))
+        .title(title(&view, &locale))
+        .inner_size(900.0, 700.0)
+        .min_inner_size(600.0, 500.0)
+        .build();
+    let window = match built {
+        Ok(window) => window,
+        Err(e) => {
+            windows.views.lock().map_err(lock_error)?.remove(&label);
+            return Err(format!("Could not open window: {}", e));
+        }
+    };
+
+    let handle = app.clone();
+    let closed = label.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            if let Ok(mut views) = handle.state::<Windows>().inner().views.lock() {
+                views.remove(&closed);
+            }
+        }
+    });
+    Ok(OpenWindow { label, view })
+}
+
+// Oldest first
+#[command]
+#[specta::specta]
+pub async fn list_windows(app: AppHandle) -> Result<Vec<OpenWindow>, String> {
+    let views = app.state::<Windows>().inner().views.lock().map_err(lock_error)?;
+    let mut windows: Vec<OpenWindow> = views.iter()
+        .map(|(label, view)| OpenWindow { label: label.clone(), view: view.clone() })
+        .collect();
+    // Labels sort as text, so "view-10" would come before "view-9"
+    windows.sort_by_key(|w| w.label[LABEL_PREFIX.len()..].parse::<u64>().unwrap_or(0));
+    Ok(windows)
+}
+
+// The view of the window asking; None in the main window
+#[command]
+#[specta::specta]
+pub async fn get_window_view(app: AppHandle, window: WebviewWindow) -> Result<Option<WindowView>, String> {
+    let views = app.state::<Windows>().inner().views.lock().map_err(lock_error)?;
+    Ok(views.get(window.label()).cloned())
+}
+
+// Closes a secondary window; the main window can't be closed this way
+#[command]
+#[specta::specta]
+pub async fn close_window(app: AppHandle, label: String) -> Result<(), String> {
+    if !label.starts_with(LABEL_PREFIX) {
+        return Ok(());
+    }
+    if let Some(window) = app.get_webview_window(&label) {
+        window.close().map_err(|e| format!("Could not close window: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,932 @@
+
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async analyzeStatement(filePath: string, password: string | null, forceReparse: boolean | null, signConvention: SignConvention | null, privacyMode: boolean | null, parseMode: ParseMode | null) : Promise<Result<AnalysisResult, AnalyzeError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("analyze_statement", { filePath, password, forceReparse, signConvention, privacyMode, parseMode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async analyzeText(content: string, hint: TextHint | null, signConvention: SignConvention | null, privacyMode: boolean | null, parseMode: ParseMode | null) : Promise<Result<AnalysisResult, AnalyzeError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("analyze_text", { content, hint, signConvention, privacyMode, parseMode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async scanScreenshot(filePath: string) : Promise<Result<OcrRow[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_screenshot", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importScreenshot(filePath: string, rows: OcrRow[], privacyMode: boolean | null) : Promise<Result<AnalysisResult, AnalyzeError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_screenshot", { filePath, rows, privacyMode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettings() : Promise<Result<Settings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSettings(settings: Settings) : Promise<Result<Settings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setSecret(key: SecretKey, value: string) : Promise<Result<SecretStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_secret", { key, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async clearSecret(key: SecretKey) : Promise<Result<SecretStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_secret", { key }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listSecretStatus() : Promise<SecretStatus[]> {
+    return await TAURI_INVOKE("list_secret_status");
+},
+async simulateCut(category: string, percent: number, statementId: number | null) : Promise<Result<CutSimulation, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("simulate_cut", { category, percent, statementId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async simulateDiscretionary(percent: number, statementId: number | null) : Promise<Result<CutSimulation, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("simulate_discretionary", { percent, statementId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async compareStatements(idA: number, idB: number) : Promise<Result<StatementDiff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("compare_statements", { idA, idB }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async annualSummary(year: number | null) : Promise<Result<AnnualSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("annual_summary", { year }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMerchantDetail(merchant: string) : Promise<Result<MerchantDetail, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_merchant_detail", { merchant }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCategoryDetail(category: string, period: string | null) : Promise<Result<CategoryDetail, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_category_detail", { category, period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async generateDigest(period: DigestPeriod) : Promise<Result<Digest, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_digest", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportAnalysis(analysis: AnalysisResult, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_analysis", { analysis, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openSavedAnalysis(filePath: string) : Promise<Result<AnalysisResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_saved_analysis", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loadDemoData(profile: SyntheticProfile | null) : Promise<Result<AnalysisResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_demo_data", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exitDemoMode() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("exit_demo_mode") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async suggestBankProfile(filePath: string) : Promise<Result<ProfileSuggestion, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("suggest_bank_profile", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveBankProfile(profile: BankProfile) : Promise<Result<BankProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_bank_profile", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listBankProfiles() : Promise<Result<BankProfile[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_bank_profiles") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportBankProfile(name: string, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_bank_profile", { name, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importBankProfile(filePath: string, samplePath: string) : Promise<Result<ProfileImport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_bank_profile", { filePath, samplePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addGoal(name: string, kind: GoalKind) : Promise<Result<Goal, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_goal", { name, kind }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listGoals() : Promise<Result<Goal[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_goals") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteGoal(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_goal", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveCard(card: Card) : Promise<Result<Card, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_card", { card }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listCards() : Promise<Result<Card[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_cards") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteCard(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_card", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBenefitReport(cardId: number, year: number | null) : Promise<Result<BenefitReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_benefit_report", { cardId, year }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBreakEven(year: number | null) : Promise<Result<PortfolioBreakEven, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_break_even", { year }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCustomInsights() : Promise<Result<CustomInsightDefinition[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_custom_insights") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveCustomInsights(definitions: CustomInsightDefinition[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_custom_insights", { definitions }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importAmazonOrders(filePath: string) : Promise<Result<AmazonImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_amazon_orders", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importWalletActivity(filePath: string) : Promise<Result<WalletImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_wallet_activity", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importHistory(filePath: string) : Promise<Result<HistoryImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_history", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listPaymentReminders() : Promise<Result<PaymentReminder[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_payment_reminders") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDashboard() : Promise<Result<Dashboard, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_dashboard") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reviewAnomaly(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("review_anomaly", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listGrayCharges() : Promise<Result<Subscription[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_gray_charges") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reviewGrayCharge(merchant: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("review_gray_charge", { merchant }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async rebuildRollups() : Promise<Result<MonthlyRollup[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rebuild_rollups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async recategorizeAll(dryRun: boolean | null) : Promise<Result<ChangePreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("recategorize_all", { dryRun }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setTransactionCategory(statementId: number, index: number, category: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_transaction_category", { statementId, index, category }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async bulkEdit(filter: TransactionFilter, action: BulkAction, dryRun: boolean | null) : Promise<Result<BulkSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("bulk_edit", { filter, action, dryRun }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async undoBulkEdit() : Promise<Result<BulkSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("undo_bulk_edit") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAuditLog(action: AuditAction | null, since: string | null, limit: number | null) : Promise<Result<AuditEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_audit_log", { action, since, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addTransaction(entry: ManualEntry, statementId: number | null) : Promise<Result<AddedTransaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_transaction", { entry, statementId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async editTransaction(statementId: number, index: number, entry: ManualEntry) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("edit_transaction", { statementId, index, entry }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteTransaction(statementId: number, index: number) : Promise<Result<TrashedTransaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_transaction", { statementId, index }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listTrash() : Promise<Result<TrashedTransaction[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_trash") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreTransaction(id: number) : Promise<Result<AddedTransaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_transaction", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addNote(statementId: number, index: number, note: string) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_note", { statementId, index, note }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async attachReceipt(statementId: number, index: number, filePath: string) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("attach_receipt", { statementId, index, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setTags(statementId: number, index: number, tags: string[]) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_tags", { statementId, index, tags }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setShared(statementId: number, index: number, shared: SharedExpense | null) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_shared", { statementId, index, shared }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async scanReceipt(filePath: string) : Promise<Result<ReceiptScan, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_receipt", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async splitTransaction(statementId: number, index: number, splits: CategorySplit[]) : Promise<Result<Transaction[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("split_transaction", { statementId, index, splits }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async givingReport(year: number) : Promise<Result<GivingReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("giving_report", { year }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportGivingReport(year: number, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_giving_report", { year, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportBusinessExpenses(period: string | null, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_business_expenses", { period, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async settleSharedExpenses(period: string | null) : Promise<Result<Settlement[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("settle_shared_expenses", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportSettlement(period: string | null, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_settlement", { period, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportTransactions(period: string | null, filePath: string) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_transactions", { period, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listOriginals() : Promise<Result<StoredOriginal[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_originals") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reimportOriginals(all: boolean | null) : Promise<Result<ReimportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reimport_originals", { all }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createSnapshot(name: string) : Promise<Result<Snapshot, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_snapshot", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listSnapshots() : Promise<Result<Snapshot[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_snapshots") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreSnapshot(id: number) : Promise<Result<Snapshot, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_snapshot", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteSnapshot(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_snapshot", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async verifyData(repair: boolean | null) : Promise<Result<HealthReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_data", { repair }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async takeOpenedFiles() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("take_opened_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async scanDropped(paths: string[]) : Promise<Result<ImportManifest, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_dropped", { paths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importFiles(paths: string[]) : Promise<Result<BatchImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_files", { paths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getQuickStats() : Promise<Result<QuickStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_quick_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLedger(period: string | null) : Promise<Result<Ledger, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_ledger", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openWindow(view: WindowView) : Promise<Result<OpenWindow, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_window", { view }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listWindows() : Promise<Result<OpenWindow[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_windows") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getWindowView() : Promise<Result<WindowView | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_window_view") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async closeWindow(label: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("close_window", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getVersionInfo() : Promise<Result<VersionInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_version_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkUpdateCompatibility(candidate: UpdateCandidate) : Promise<Result<CompatibilityReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_update_compatibility", { candidate }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async prepareUpdate(candidate: UpdateCandidate) : Promise<Result<CompatibilityReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prepare_update", { candidate }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportConfig(filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_config", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importConfig(filePath: string, mode: ConfigImportMode) : Promise<Result<ConfigImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_config", { filePath, mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createSyncKey() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_sync_key") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async syncNow() : Promise<Result<SyncSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_now") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type AddedTransaction = { statement_id: number; index: number; transaction: Transaction }
+export type Aggregation = "sum" | "count" | "average" | "max" | "min"
+export type AmazonImportSummary = { orders_imported: number; orders_total: number; charges_matched: number; charges_unmatched: Transaction[] }
+export type AmountChange = { name: string; before: number; after: number; change: number }
+export type AnalysisResult = { spending_categories: CategoryTotal[]; spending_classes: ClassTotal[]; top_merchants: MerchantTotal[]; largest_transactions: LargeTransaction[]; records: LifetimeRecords | null; monthly_total: number; insights: Insight[]; transaction_count: number; cash_advance: CashAdvanceWarning | null; travel: TravelSummary | null; trips: Trip[]; subscriptions: Subscription[]; trial_conversions: TrialConversion[]; service_overlaps: ServiceOverlap[]; food: FoodReport | null; vehicle: VehicleReport | null; merchant_growth: MerchantGrowthReport | null; heatmap: SpendingHeatmap | null; merchant_concentration: ParetoAnalysis | null; micro_spending: MicroSpendingReport | null; round_up: RoundUpSimulation | null; income: IncomeReport | null; business: BusinessSplit | null; goals: GoalStatus[]; rewards_earned: number | null; statement_meta: StatementMeta | null; sign_convention: SignDetection | null; parse_warnings: ParseWarning[]; sections: AnalyzerSection[] }
+export type AnalyzeError = { kind: "needs_password"; message: string } | { kind: "wrong_password"; message: string } | { kind: "ambiguous_columns"; message: string } | { kind: "failed"; message: string }
+export type AnalyzerSection = { analyzer: string; title: string; unit: string | null; rows: SectionRow[]; total: number | null; previous_total: number | null }
+export type AnnualCategory = { category: string; total: number; monthly: number[] }
+export type AnnualSummary = { year: number | null; months: string[]; total_spend: number; monthly_totals: number[]; categories: AnnualCategory[]; largest_purchases: LargePurchase[]; fees_paid: number; interest_paid: number }
+export type Anomaly = { id: string; date: string; description: string; merchant: string; amount: number; typical_amount: number }
+export type AuditAction = "import" | "category_override" | "edit" | "add" | "delete" | "restore" | "bulk_edit" | "undo" | "recategorize" | "repair" | "rule_change"
+export type AuditEntry = { id: number; at: string; action: AuditAction; target: string; before: string | null; after: string | null }
+export type BankProfile = { name: string; skip_lines: number; header_signature: string[]; date_column: string; description_column: string; amount_column: string | null; debit_column: string | null; credit_column: string | null; date_format: string | null; sign_convention: SignConvention | null }
+export type BatchImportSummary = { imported: string[]; failed: ImportFailure[] }
+export type BenefitCadence = "monthly" | "quarterly" | "semi_annual" | "annual"
+export type BenefitPeriod = { period: string; available: number; used: number }
+export type BenefitReport = { card_id: number; card_name: string; year: number; benefits: BenefitUsage[]; available: number; captured: number; annual_fee: number; net_of_fee: number }
+export type BenefitUsage = { name: string; cadence: BenefitCadence; periods: BenefitPeriod[]; available: number; captured: number; unused_periods: string[] }
+export type BudgetShare = { amount: number; percent_of_income: number; target_percent: number }
+export type BulkAction = { kind: "recategorize"; category: string | null } | { kind: "retag"; add: string[]; remove: string[] } | { kind: "delete" }
+export type BulkSummary = { edit_id: number | null; affected: number; statement_ids: number[]; preview: ChangePreview }
+export type BusinessSplit = { business_total: number; personal_total: number; business_count: number; categories: CategoryTotal[] }
+export type Card = { id: number; name: string; issuer: string | null; last4: string | null; annual_fee: number; benefits: CardBenefit[]; reward_rate: number | null }
+export type CardBenefit = { name: string; amount: number; cadence: BenefitCadence; merchants: string[]; category: string | null }
+export type CardBreakEven = { card_id: number; card_name: string; rewards: number; rewards_estimated: boolean; benefits: number; annual_fee: number; other_fees: number; interest: number; net: number }
+export type CashAdvanceWarning = { severity: Severity; message: string; advances: Transaction[]; total_advanced: number; total_fees: number; fees_estimated: boolean; estimated_interest: number; effective_cost: number; effective_apr: number }
+export type CategoryClass = "need" | "want" | "savings_debt"
+export type CategoryDetail = { category: string; period: string | null; total: number; count: number; transactions: CategoryTransaction[]; top_merchants: MerchantTotal[]; monthly: MonthlyRollup[]; budgets: GoalStatus[] }
+export type CategoryShift = { category: string; before: number; after: number }
+export type CategorySplit = { category: string; amount: number }
+export type CategoryTotal = { category: string; id: string; color: string; icon: string; total: number; percentage: number }
+export type CategoryTransaction = { statement_id: number; index: number; date: string; description: string; amount: number }
+export type ChangePreview = { affected: number; samples: RowChange[]; shifts: CategoryShift[] }
+export type ClassTotal = { class: CategoryClass; total: number; percentage: number }
+export type ColumnRole = "date" | "description" | "amount" | "debit" | "credit" | "ignore"
+export type Compatibility = "compatible" | "needs_migration" | "blocked"
+export type CompatibilityReport = { compatibility: Compatibility; reason: string | null; plan: MigrationStep[] }
+export type ConfigImportMode = "merge" | "replace"
+export type ConfigImportSummary = { goals: number; bank_profiles: number; cards: number; custom_insights: number; replaced: string[] }
+export type CountryTotal = { country: string; total: number; count: number }
+export type CustomInsightDefinition = { name: string; filter: string; aggregation: Aggregation; threshold: number; trigger: Trigger; severity: Severity; message: string }
+export type CutSimulation = { category: string; percent: number; monthly_category_total: number; monthly_category_after: number; monthly_total: number; monthly_after: number; monthly_savings: number; annual_total: number; annual_after: number; annual_savings: number }
+export type Dashboard = { current_month: string | null; current_month_spend: number; previous_month_spend: number; change_percent: number | null; top_categories: CategoryTotal[]; subscriptions: Subscription[]; upcoming_payments: PaymentReminder[]; anomalies: Anomaly[]; gray_charges: Subscription[]; budgets: GoalStatus[] }
+export type DetectedColumn = { index: number; header: string; samples: string[]; role: ColumnRole }
+export type DetectionBasis = "override" | "payment_rows" | "summary_rows" | "majority"
+export type Digest = { period: DigestPeriod; start: string; end: string; total_spend: number; previous_spend: number; markdown: string; html: string }
+export type DigestPeriod = "week" | "month"
+export type Donation = { statement_id: number; index: number; date: string; organization: string; description: string; amount: number; receipt_path: string | null; note: string | null }
+export type FoodMonth = { month: string; groceries: number; dining: number; dining_share: number }
+export type FoodReport = { groceries: number; dining: number; dining_share: number; previous_dining_share: number | null; months: FoodMonth[] }
+export type FuelMonth = { month: string; total: number; fill_ups: number; average_fill_up: number }
+export type GivingRecipient = { organization: string; total: number; count: number }
+export type GivingReport = { year: number; total: number; monthly_totals: number[]; recipients: GivingRecipient[]; donations: Donation[] }
+export type Goal = { id: number; name: string; kind: GoalKind; baseline: number | null; created_at: string; progress: GoalProgress[] }
+export type GoalKind = { kind: "reduce_category"; category: string; amount: number } | { kind: "category_under"; category: string; limit: number } | { kind: "total_under"; limit: number }
+export type GoalProgress = { statement_id: number; evaluated_at: string; actual: number; target: number; met: boolean }
+export type GoalStatus = { goal_id: number; name: string; target: number; actual: number; met: boolean; remaining: number; trend: string }
+export type HealthIssue = { kind: IssueKind; detail: string; repairable: boolean }
+export type HealthReport = { statements: number; transactions: number; issues: HealthIssue[]; repaired: boolean }
+export type HistoryImportSummary = { source: MigrationSource; transactions_imported: number; statements_recorded: number }
+export type HourlyMatrix = { counts: number[][]; amounts: number[][]; timed_transactions: number }
+export type ImportFailure = { path: string; reason: string }
+export type ImportManifest = { entries: ManifestEntry[]; truncated: boolean }
+export type IncomeReport = { monthly_income: number; monthly_spend: number; spend_percent: number; savings_rate: number; needs: BudgetShare; wants: BudgetShare; savings: BudgetShare }
+export type Insight = { kind: InsightKind; severity: Severity; message_key: string; params: { [key in string]: string }; message: string; amount: number | null; merchants: string[]; categories: string[] }
+export type InsightKind = "cash_advance" | "summary" | "top_category" | "micro_spending" | "travel" | "foreign_fees" | "merchant_growth" | "new_merchants" | "concentration" | "round_up" | "income" | "carbon" | "fuel" | "dining_out" | "price_hike" | "trial_conversion" | "overlapping_services" | "goal" | "tip" | "sample_data" | "custom"
+export type IssueKind = "RollupMismatch" | "DuplicateStatementId" | "IdCounterBehind" | "OrphanTrash" | "OrphanReminder" | "UnnormalizedTags" | "InvalidAmount" | "InvalidSharedExpense"
+export type LargePurchase = { statement_id: number; date: string; description: string; category: string; amount: number }
+export type LargeTransaction = { date: string; description: string; category: string; amount: number }
+export type Ledger = { period: string | null; total: number; rows: LedgerRow[] }
+export type LedgerRow = { statement_id: number; index: number; date: string; description: string; amount: number; category: string; tags: string[]; note: string | null }
+export type LifetimeRecords = { largest_purchase: LargeTransaction | null; biggest_merchant_month: MerchantMonth | null }
+export type ManifestEntry = { path: string; source: string | null; first_date: string | null; last_date: string | null; rows: number; already_imported: boolean; error: string | null }
+export type ManualEntry = { date: string; description: string; amount: number; category: string | null; note: string | null }
+export type MerchantCategory = { category: string; total: number; count: number }
+export type MerchantDetail = { merchant: string; total: number; count: number; average_ticket: number; first_seen: string | null; last_seen: string | null; transactions: MerchantTransaction[]; monthly: MerchantMonthTotal[]; categories: MerchantCategory[] }
+export type MerchantGrowth = { merchant: string; previous_average: number; current: number; change: number; change_percent: number }
+export type MerchantGrowthReport = { statements_compared: number; by_amount: MerchantGrowth[]; by_percent: MerchantGrowth[]; new_merchants: MerchantTotal[] }
+export type MerchantInfo = { name: string; domain: string; category: string | null; service: ServiceClass | null }
+export type MerchantMonth = { merchant: string; month: string; total: number; count: number }
+export type MerchantMonthTotal = { month: string; total: number; count: number }
+export type MerchantTotal = { merchant: string; total: number; count: number; domain: string | null; logo_path: string | null }
+export type MerchantTransaction = { statement_id: number; index: number; date: string; description: string; category: string; amount: number }
+export type MicroSpendingReport = { threshold: number; count: number; total: number; top_merchants: MerchantTotal[]; annualized: number }
+export type MigrationSource = "mint" | "ynab" | "monarch"
+export type MigrationStep = { from: number; to: number; description: string }
+export type MonthlyRollup = { month: string; category: string; total: number; count: number }
+export type MonthlyRoundUp = { month: string; amount: number }
+export type OcrRow = { date: string; description: string; amount: number; confidence: number; needs_review: boolean; source: string }
+export type OpenWindow = { label: string; view: WindowView }
+export type ParetoAnalysis = { merchant_count: number; merchants_for_80_percent: number; merchant_share_for_80_percent: number; cumulative: ParetoPoint[] }
+export type ParetoPoint = { merchant: string; total: number; cumulative_percent: number }
+export type ParseMode = "strict" | "lenient"
+export type ParseWarning = { row: number; reason: string }
+export type PaymentReminder = { statement_id: number; issuer: string | null; card_last4: string | null; due_date: string; days_until: number; minimum_payment: number | null; new_balance: number | null }
+export type PeriodBoundary = { kind: "calendar" } | { kind: "start_day"; day: number } | { kind: "statement_cycle" }
+export type PortfolioBreakEven = { year: number; cards: CardBreakEven[]; rewards: number; benefits: number; fees: number; interest: number; net: number }
+export type PriceChange = { date: string; from: number; to: number }
+export type ProfileImport = { profile: BankProfile; transaction_count: number; preview: Transaction[] }
+export type ProfileSuggestion = { columns: DetectedColumn[]; template: BankProfile }
+export type QuickStats = { month: string | null; spend: number; budgets_met: number; budgets_total: number }
+export type ReceiptItem = { description: string; amount: number }
+export type ReceiptScan = { merchant: string | null; date: string | null; total: number | null; items: ReceiptItem[]; matched: [number, number] | null; suggested_splits: CategorySplit[] }
+export type ReimportFailure = { source_path: string; reason: string }
+export type ReimportSummary = { reimported: string[]; failed: ReimportFailure[] }
+export type RoundUpSimulation = { increment: number; total: number; monthly: MonthlyRoundUp[]; monthly_average: number; annualized: number }
+export type RowChange = { statement_id: number; index: number; date: string; description: string; amount: number; category_from: string; category_to: string | null; tags_from: string[]; tags_to: string[] }
+export type SecretKey = "plaid_client_id" | "plaid_secret" | "imap_password" | "llm_api_key" | "sync_key"
+export type SecretStatus = { key: SecretKey; stored: boolean; error: string | null }
+export type SectionKind = "purchases" | "payments" | "fees" | "interest" | "cash_advances" | "other"
+export type SectionRow = { label: string; value: number; previous: number | null }
+export type ServiceClass = "music_streaming" | "video_streaming" | "cloud_storage" | "news" | "fitness" | "vpn" | "password_manager" | "gaming"
+export type ServiceOverlap = { class: ServiceClass; merchants: string[]; monthly_total: number; keep: string; annual_savings: number }
+export type Settings = { micro_spend_threshold: number; round_up_increment: number; locale: string; payment_reminders: boolean; reminder_days_before: number; monthly_income: number | null; category_classes: { [key in string]: CategoryClass }; period_boundary: PeriodBoundary; carbon_footprint: boolean; emission_factors: { [key in string]: number }; business_rules: string[]; home_state: string | null; merchant_logos: boolean; parse_mode: ParseMode; statement_language: StatementLanguage; keep_originals: boolean; originals_retention_days: number | null; import_shortcut: string | null; autostart: boolean; background_import: boolean; statements_folder: string | null; update_channel: UpdateChannel; sync_folder: string | null }
+export type Settlement = { partner: string; partner_owes: number; you_owe: number; net: number; lines: SharedLine[] }
+export type Severity = "info" | "notice" | "warning" | "critical"
+export type SharedExpense = { partner: string; partner_share: number; paid_by_partner: boolean }
+export type SharedLine = { statement_id: number; index: number; date: string; description: string; amount: number; partner_share: number; paid_by_partner: boolean; owed_by_partner: number }
+export type SignConvention = "positive_purchases" | "negative_purchases"
+export type SignDetection = { convention: SignConvention; basis: DetectionBasis }
+export type Snapshot = { id: number; name: string; reason: SnapshotReason; taken_at: string; statements: number; compressed_size: number }
+export type SnapshotReason = "Manual" | "StoreUpgrade" | "HistoryImport" | "BulkEdit" | "Recategorize" | "Reimport" | "Restore" | "Repair" | "AppUpdate" | "ConfigImport"
+export type SpendingHeatmap = { days: string[]; day_counts: number[]; day_amounts: number[]; hourly: HourlyMatrix | null }
+export type StatementDiff = { statement_a: number; statement_b: number; total_before: number; total_after: number; categories_up: AmountChange[]; categories_down: AmountChange[]; merchants_added: AmountChange[]; merchants_removed: AmountChange[]; subscriptions_added: AmountChange[]; subscriptions_cancelled: AmountChange[]; subscription_price_changes: AmountChange[]; fees: AmountChange[]; fees_before: number; fees_after: number }
+export type StatementLanguage = "english" | "french" | "german" | "spanish"
+export type StatementMeta = { issuer: string | null; card_last4: string | null; statement_date: string | null; payment_due_date: string | null; minimum_payment: number | null; new_balance: number | null; purchase_apr: number | null }
+export type StoredOriginal = { source_path: string; content_hash: string; stored_at: string; parser_version: number; size: number; compressed_size: number }
+export type Subscription = { merchant: string; amount: number; charges: number; first_charged: string; last_charged: string; price_changes: PriceChange[]; extra_annual_cost: number }
+export type SyncSummary = { devices: number; received: number; published: number; unreadable: string[] }
+export type SyntheticProfile = { months: number | null; seed: number | null; heavy_dining: boolean; many_subscriptions: boolean; fraud_event: boolean }
+export type TextHint = "csv" | "tsv"
+export type Transaction = { date: string; description: string; amount: number; category: string | null; currency: string | null; country: string | null; time: string | null; rewards: number | null; order_id: string | null; payment_id: string | null; section: SectionKind | null; transaction_type: TransactionType; note: string | null; mcc: string | null; receipt_path: string | null; tags: string[]; shared: SharedExpense | null; merchant_info: MerchantInfo | null; manual: boolean; rule_category: string | null; import_key: string | null }
+export type TransactionFilter = { merchant: string | null; category: string | null; tag: string | null; period: string | null }
+export type TransactionType = "debit" | "credit"
+export type TrashedTransaction = { id: number; statement_id: number; index: number; deleted_at: string; transaction: Transaction }
+export type TravelSummary = { total_spent_abroad: number; transaction_count: number; countries: CountryTotal[]; total_fx_fees: number; fx_fee_rate: number }
+export type TrialConversion = { merchant: string; trial_charged: string | null; trial_amount: number | null; first_charged: string; amount: number }
+export type Trigger = "above" | "below"
+export type Trip = { start: string; end: string; days: number; destination: string | null; total: number; transaction_count: number; flights: number; lodging: number; dining: number; local_transport: number; other: number }
+export type UpdateCandidate = { version: string; channel: UpdateChannel; schema_version: number; min_schema_version: number; migrations: string[] }
+export type UpdateChannel = "stable" | "beta"
+export type VehicleReport = { fill_ups: number; total: number; average_fill_up: number; monthly_fuel: number; fill_ups_per_month: number; baseline_monthly_fuel: number | null; baseline_fill_ups_per_month: number | null; baseline_average_fill_up: number | null; budget_drift: number | null; months: FuelMonth[] }
+export type VersionInfo = { app_version: string; channel: UpdateChannel; schema_version: number; data_schema_version: number }
+export type Wallet = "pay_pal" | "venmo"
+export type WalletImportSummary = { wallet: Wallet; payments_imported: number; charges_linked: number }
+export type WindowView = { kind: "ledger"; period: string | null } | { kind: "comparison"; id_a: number; id_b: number }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
@@ -1,10 +1,23 @@
-import { invoke } from '@tauri-apps/api/core';
+import { commands } from './bindings';
+import { listen } from '@tauri-apps/api/event';
+import { getCurrentWebview } from '@tauri-apps/api/webview';
+
+// The generated commands hand back { status, data } or { status, error } rather than throwing;
+// this throws the error again, so callers keep their try/catch
+function unwrap(result) {
+    if (result.status === 'error') {
+        throw result.error;
+    }
+    return result.data;
+}
 
 // Try to import dialog, but handle errors gracefully
 let openDialog = null;
+let saveDialog = null;
 try {
     const dialogModule = await import('@tauri-apps/plugin-dialog');
     openDialog = dialogModule.open;
+    saveDialog = dialogModule.save;
     console.log('Dialog module loaded successfully');
 } catch (error) {
     console.error('Failed to load dialog module:', error);
@@ -26,11 +39,13 @@ async function analyzeStatement() {
             multiple: false,
             filters: [{
                 name: 'Financial Files',
-                extensions: ['csv', 'pdf', 'xlsx', 'xls']
+                extensions: ['csv', 'pdf', 'xlsx', 'xls', 'html', 'htm']
             }]
         });
         
-        console.log('Dialog result:', selected);
+        if (!document.getElementById('privacyMode').checked) {
+            console.log('Dialog result:', selected);
+        }
         
         if (!selected) {
             console.log('No file selected (user cancelled)');
@@ -60,13 +75,17 @@ async function analyzeFromInput() {
     await analyzeWithPath(fileName);
 }
 
-async function analyzeWithPath(filePath) {
+async function analyzeWithPath(filePath, password = null) {
     const loadingDiv = document.getElementById('loading');
     const resultsDiv = document.getElementById('results');
     const analyzeBtn = document.getElementById('analyzeBtn');
+    // Private analyses keep the file and its results out of the console too
+    const privacyMode = document.getElementById('privacyMode').checked;
     
     try {
-        console.log('Starting analysis for:', filePath);
+        if (!privacyMode) {
+            console.log('Starting analysis for:', filePath);
+        }
         
         // Show loading state
         loadingDiv.classList.remove('hidden');
@@ -76,16 +95,24 @@ async function analyzeWithPath(filePath) {
         console.log('Calling backend...');
         
         // Call Rust backend
-        const analysis = await invoke('analyze_statement', { 
-            filePath: filePath 
-        });
+        const analysis = unwrap(await commands.analyzeStatement(filePath, password, null, null, privacyMode, null));
         
-        console.log('Analysis completed:', analysis);
+        if (!privacyMode) {
+            console.log('Analysis completed:', analysis);
+        }
         
         // Display results
         displayResults(analysis);
         
     } catch (error) {
+        // Protected PDFs: ask for the password and try again
+        if (error.kind === 'needs_password' || error.kind === 'wrong_password') {
+            const entered = prompt(error.message);
+            if (entered) {
+                return analyzeWithPath(filePath, entered);
+            }
+            return;
+        }
         console.error('Analysis failed:', error);
         alert('Analysis failed: ' + error.message);
     } finally {
@@ -94,48 +121,305 @@ async function analyzeWithPath(filePath) {
     }
 }
 
+// Generated statements in a separate store, for trying the app without real data
+async function loadDemoData() {
+    const loadingDiv = document.getElementById('loading');
+    try {
+        loadingDiv.classList.remove('hidden');
+        // Each option names the profile flag it turns on
+        const flag = document.getElementById('demoProfile').value;
+        const profile = flag ? { [flag]: true } : null;
+        const analysis = unwrap(await commands.loadDemoData(profile));
+        document.getElementById('exitDemoBtn').classList.remove('hidden');
+        displayResults(analysis);
+    } catch (error) {
+        console.error('Loading demo data failed:', error);
+        alert('Loading demo data failed: ' + error);
+    } finally {
+        loadingDiv.classList.add('hidden');
+    }
+}
+
+async function exitDemoMode() {
+    try {
+        unwrap(await commands.exitDemoMode());
+        document.getElementById('exitDemoBtn').classList.add('hidden');
+        document.getElementById('results').classList.add('hidden');
+    } catch (error) {
+        console.error('Leaving demo mode failed:', error);
+        alert('Leaving demo mode failed: ' + error);
+    }
+}
+
+// The analysis on screen, kept so it can be saved
+let currentAnalysis = null;
+
+async function saveAnalysis() {
+    if (!saveDialog || !currentAnalysis) {
+        return;
+    }
+    const filePath = await saveDialog({ filters: [{ name: 'Saved Analysis', extensions: ['json'] }] });
+    if (!filePath) {
+        return;
+    }
+    try {
+        unwrap(await commands.exportAnalysis(currentAnalysis, filePath));
+    } catch (error) {
+        alert('Saving the analysis failed: ' + error);
+    }
+}
+
+async function openSavedAnalysis() {
+    if (!openDialog) {
+        return;
+    }
+    const filePath = await openDialog({ multiple: false, filters: [{ name: 'Saved Analysis', extensions: ['json'] }] });
+    if (!filePath) {
+        return;
+    }
+    try {
+        displayResults(unwrap(await commands.openSavedAnalysis(filePath)));
+    } catch (error) {
+        alert('Opening the analysis failed: ' + error);
+    }
+}
+
 function displayResults(analysis) {
+    currentAnalysis = analysis;
     const resultsDiv = document.getElementById('results');
     const categoriesDiv = document.getElementById('categories');
     const merchantsDiv = document.getElementById('merchants');
+    const largestDiv = document.getElementById('largest');
     const insightsDiv = document.getElementById('insights');
+    const warningsDiv = document.getElementById('warnings');
+    
+    // Critical insights are shown as banners above the results
+    warningsDiv.innerHTML = '';
+    analysis.insights
+        .filter(insight => insight.severity === 'critical')
+        .forEach(insight => {
+            const warning = document.createElement('div');
+            warning.className = `warning warning-${insight.severity}`;
+            warning.textContent = insight.message;
+            warningsDiv.appendChild(warning);
+        });
     
     // Display categories
     categoriesDiv.innerHTML = '<h3>Spending Categories</h3>';
     analysis.spending_categories.forEach(cat => {
         const item = document.createElement('div');
         item.className = 'category-item';
+        item.dataset.category = cat.id;
+        item.style.borderLeftColor = cat.color;
         item.innerHTML = `
-            <span>${cat.category}</span>
+            <span>${cat.icon} ${cat.category}</span>
             <span>$${cat.total.toFixed(2)} (${cat.percentage.toFixed(1)}%)</span>
         `;
         categoriesDiv.appendChild(item);
     });
     
-    // Display top merchants
+    // Display top merchants. Names are isolated so an Arabic or Hebrew one doesn't reorder
+    // the text around it.
     merchantsDiv.innerHTML = '<h3>Top Merchants</h3>';
     analysis.top_merchants.forEach(merchant => {
         const item = document.createElement('div');
         item.className = 'merchant-item';
         item.innerHTML = `
-            <span>${merchant.merchant}</span>
+            <span><bdi>${merchant.merchant}</bdi></span>
             <span>$${merchant.total.toFixed(2)} (${merchant.count} transactions)</span>
         `;
         merchantsDiv.appendChild(item);
     });
     
+    // Display largest purchases and all-time records
+    largestDiv.innerHTML = '<h3>Largest Purchases</h3>';
+    analysis.largest_transactions.forEach(tx => {
+        const item = document.createElement('div');
+        item.className = 'merchant-item';
+        item.innerHTML = `
+            <span>${tx.date} · <bdi>${tx.description}</bdi> (${tx.category})</span>
+            <span>$${tx.amount.toFixed(2)}</span>
+        `;
+        largestDiv.appendChild(item);
+    });
+    const records = analysis.records;
+    if (records?.largest_purchase) {
+        const item = document.createElement('div');
+        item.className = 'merchant-item';
+        item.innerHTML = `
+            <span>Largest purchase ever: <bdi>${records.largest_purchase.description}</bdi> (${records.largest_purchase.date})</span>
+            <span>$${records.largest_purchase.amount.toFixed(2)}</span>
+        `;
+        largestDiv.appendChild(item);
+    }
+    if (records?.biggest_merchant_month) {
+        const record = records.biggest_merchant_month;
+        const item = document.createElement('div');
+        item.className = 'merchant-item';
+        item.innerHTML = `
+            <span>Most at one merchant in a month: <bdi>${record.merchant}</bdi> (${record.month})</span>
+            <span>$${record.total.toFixed(2)}</span>
+        `;
+        largestDiv.appendChild(item);
+    }
+    
     // Display insights
     insightsDiv.innerHTML = '<div class="insights"><h3>Insights & Recommendations</h3><ul></ul></div>';
     const insightsList = insightsDiv.querySelector('ul');
-    analysis.insights.forEach(insight => {
-        const li = document.createElement('li');
-        li.textContent = insight;
-        insightsList.appendChild(li);
-    });
+    analysis.insights
+        .filter(insight => insight.severity !== 'critical')
+        .forEach(insight => {
+            const li = document.createElement('li');
+            li.className = `severity-${insight.severity}`;
+            li.textContent = insight.message;
+            insightsList.appendChild(li);
+        });
     
     resultsDiv.classList.remove('hidden');
 }
 
+// Statements opened from Finder or Explorer wait in a queue until taken here, one at a time
+async function importOpenedFiles() {
+    const paths = unwrap(await commands.takeOpenedFiles());
+    for (const path of paths) {
+        await analyzeWithPath(path);
+    }
+}
+
+// Only the main window imports; secondary windows show views
+if (getCurrentWebview().label === 'main') {
+    listen('files://opened', importOpenedFiles);
+    importOpenedFiles();
+}
+
+// Dropped files and folders are scanned first, so the user can see what each file holds and
+// pick which to import
+getCurrentWebview().onDragDropEvent(async (event) => {
+    if (event.payload.type !== 'drop' || event.payload.paths.length === 0) {
+        return;
+    }
+    const loadingDiv = document.getElementById('loading');
+    try {
+        loadingDiv.classList.remove('hidden');
+        showManifest(unwrap(await commands.scanDropped(event.payload.paths)));
+    } catch (error) {
+        console.error('Scanning dropped files failed:', error);
+        alert('Scanning dropped files failed: ' + error);
+    } finally {
+        loadingDiv.classList.add('hidden');
+    }
+});
+
+function showManifest(manifest) {
+    const entriesDiv = document.getElementById('manifestEntries');
+    entriesDiv.innerHTML = '';
+    manifest.entries.forEach(entry => {
+        const item = document.createElement('label');
+        item.className = 'merchant-item';
+        const checkbox = document.createElement('input');
+        checkbox.type = 'checkbox';
+        checkbox.value = entry.path;
+        checkbox.checked = !entry.error && !entry.already_imported;
+        checkbox.disabled = !!entry.error;
+        const period = entry.first_date ? `${entry.first_date} – ${entry.last_date}` : '';
+        const details = entry.error ?? [entry.source ?? 'Unknown format', period, `${entry.rows} rows`, entry.already_imported ? 'already imported' : '']
+            .filter(part => part)
+            .join(' · ');
+        const name = document.createElement('bdi');
+        name.textContent = entry.path;
+        const detailSpan = document.createElement('span');
+        detailSpan.textContent = details;
+        item.append(checkbox, name, detailSpan);
+        entriesDiv.appendChild(item);
+    });
+    if (manifest.truncated) {
+        const note = document.createElement('p');
+        note.textContent = `Only the first ${manifest.entries.length} files are listed.`;
+        entriesDiv.appendChild(note);
+    }
+    document.getElementById('dropManifest').classList.remove('hidden');
+}
+
+function closeManifest() {
+    document.getElementById('dropManifest').classList.add('hidden');
+}
+
+async function importSelectedFiles() {
+    const paths = [...document.querySelectorAll('#manifestEntries input:checked')].map(box => box.value);
+    if (paths.length === 0) {
+        return;
+    }
+    closeManifest();
+    const loadingDiv = document.getElementById('loading');
+    try {
+        loadingDiv.classList.remove('hidden');
+        const summary = unwrap(await commands.importFiles(paths));
+        const failures = summary.failed.map(failure => `${failure.path}: ${failure.reason}`);
+        alert(`Imported ${summary.imported.length} files.` + (failures.length ? '\n\nNot imported:\n' + failures.join('\n') : ''));
+    } catch (error) {
+        console.error('Importing dropped files failed:', error);
+        alert('Importing dropped files failed: ' + error);
+    } finally {
+        loadingDiv.classList.add('hidden');
+    }
+}
+
+// Ledgers and comparisons open in their own windows, each a copy of this page that asks which
+// view it shows. Two ledgers for different months sit side by side.
+async function openLedgerWindow() {
+    const period = document.getElementById('ledgerPeriod').value.trim() || null;
+    try {
+        unwrap(await commands.openWindow({ kind: 'ledger', period }));
+    } catch (error) {
+        alert('Could not open window: ' + error);
+    }
+}
+
+function addRow(container, label, value) {
+    const item = document.createElement('div');
+    item.className = 'merchant-item';
+    const name = document.createElement('bdi');
+    name.textContent = label;
+    const amount = document.createElement('span');
+    amount.textContent = value;
+    item.append(name, amount);
+    container.appendChild(item);
+}
+
+async function renderWindowView(view) {
+    const viewDiv = document.getElementById('windowView');
+    viewDiv.innerHTML = '';
+    const heading = document.createElement('h2');
+    viewDiv.appendChild(heading);
+    if (view.kind === 'ledger') {
+        const ledger = unwrap(await commands.getLedger(view.period));
+        heading.textContent = `${view.period ?? 'All transactions'}: $${ledger.total.toFixed(2)}`;
+        ledger.rows.forEach(row => addRow(viewDiv, `${row.date}  ${row.description}  (${row.category})`, `$${row.amount.toFixed(2)}`));
+    } else if (view.kind === 'comparison') {
+        const diff = unwrap(await commands.compareStatements(view.id_a, view.id_b));
+        heading.textContent = `$${diff.total_before.toFixed(2)} → $${diff.total_after.toFixed(2)}`;
+        [...diff.categories_up, ...diff.categories_down].forEach(change =>
+            addRow(viewDiv, change.name, `${change.change >= 0 ? '+' : ''}$${change.change.toFixed(2)}`));
+    }
+}
+
+// A secondary window swaps the import controls for its view, and keeps it current as
+// imports and edits land in any window
+const windowView = unwrap(await commands.getWindowView());
+if (windowView) {
+    document.querySelector('.upload-section').classList.add('hidden');
+    document.getElementById('windowView').classList.remove('hidden');
+    renderWindowView(windowView);
+    listen('transactions://changed', () => renderWindowView(windowView));
+}
+
 // Make functions available globally
 window.analyzeStatement = analyzeStatement;
-window.analyzeFromInput = analyzeFromInput;
\ No newline at end of file
+window.analyzeFromInput = analyzeFromInput;
+window.loadDemoData = loadDemoData;
+window.saveAnalysis = saveAnalysis;
+window.openSavedAnalysis = openSavedAnalysis;
+window.importSelectedFiles = importSelectedFiles;
+window.closeManifest = closeManifest;
+window.openLedgerWindow = openLedgerWindow;
+window.exitDemoMode = exitDemoMode;
\ No newline at end of file